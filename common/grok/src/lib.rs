@@ -0,0 +1,274 @@
+//! The `tornado_common_grok` crate expands grok expressions into regular expressions.
+//!
+//! A grok expression is a regex that can reference named patterns with the `%{PATTERN}` and
+//! `%{PATTERN:field}` syntaxes, e.g. `%{IP:src} %{NUMBER:port}`.
+//! The first form is expanded into a non-capturing group, the second one into a group
+//! named after the field.
+
+use std::collections::HashMap;
+use thiserror::Error;
+
+const REFERENCE_START_DELIMITER: &str = "%{";
+const REFERENCE_END_DELIMITER: char = '}';
+const REFERENCE_SEPARATOR: char = ':';
+
+/// The definitions of the built-in patterns, in the pattern file format.
+pub const BUILT_IN_PATTERNS: &str = include_str!("../patterns/grok-patterns");
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GrokError {
+    #[error("UnknownPatternError: [{name}]")]
+    UnknownPatternError { name: String },
+    #[error("RecursivePatternError: the pattern [{name}] references itself")]
+    RecursivePatternError { name: String },
+    #[error("InvalidReferenceError: [{reference}]")]
+    InvalidReferenceError { reference: String },
+    #[error("UnclosedReferenceError: the reference at position [{position}] is not closed")]
+    UnclosedReferenceError { position: usize },
+    #[error("InvalidPatternDefinitionError: [{definition}]")]
+    InvalidPatternDefinitionError { definition: String },
+}
+
+/// A library of named patterns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrokPatterns {
+    patterns: HashMap<String, String>,
+}
+
+impl Default for GrokPatterns {
+    /// Returns a library that contains the built-in patterns.
+    fn default() -> Self {
+        let mut patterns = GrokPatterns::empty();
+        patterns.add_patterns(BUILT_IN_PATTERNS).expect("The built-in grok patterns must be valid");
+        patterns
+    }
+}
+
+impl GrokPatterns {
+    /// Returns a library without patterns.
+    pub fn empty() -> Self {
+        GrokPatterns { patterns: HashMap::new() }
+    }
+
+    /// Adds the patterns defined in the pattern file format: one `NAME regex` definition
+    /// per line, where empty lines and lines starting with `#` are ignored.
+    /// A pattern with the same name of an existing one replaces it.
+    pub fn add_patterns(&mut self, definitions: &str) -> Result<(), GrokError> {
+        for line in definitions.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_once(char::is_whitespace) {
+                Some((name, pattern)) if is_valid_name(name) && !pattern.trim().is_empty() => {
+                    self.add_pattern(name, pattern.trim());
+                }
+                _ => {
+                    return Err(GrokError::InvalidPatternDefinitionError {
+                        definition: line.to_owned(),
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn add_pattern<N: Into<String>, P: Into<String>>(&mut self, name: N, pattern: P) {
+        self.patterns.insert(name.into(), pattern.into());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.patterns.get(name).map(String::as_str)
+    }
+
+    /// Returns the regex obtained by recursively replacing all the pattern references
+    /// of the grok expression.
+    pub fn expand(&self, expression: &str) -> Result<String, GrokError> {
+        let mut regex = String::with_capacity(expression.len());
+        self.expand_into(expression, &mut regex, &mut vec![])?;
+        Ok(regex)
+    }
+
+    fn expand_into<'a>(
+        &'a self,
+        expression: &str,
+        regex: &mut String,
+        expanding: &mut Vec<&'a str>,
+    ) -> Result<(), GrokError> {
+        let mut rest = expression;
+        while let Some(start) = rest.find(REFERENCE_START_DELIMITER) {
+            regex.push_str(&rest[..start]);
+            let reference_text = &rest[start + REFERENCE_START_DELIMITER.len()..];
+            let end = reference_text.find(REFERENCE_END_DELIMITER).ok_or_else(|| {
+                GrokError::UnclosedReferenceError {
+                    position: expression.len() - rest.len() + start,
+                }
+            })?;
+            let reference = &reference_text[..end];
+            rest = &reference_text[end + 1..];
+
+            let (name, field) = match reference.split_once(REFERENCE_SEPARATOR) {
+                Some((name, field)) => (name, Some(field)),
+                None => (reference, None),
+            };
+            if !is_valid_name(name) || field.is_some_and(|field| !is_valid_field(field)) {
+                return Err(GrokError::InvalidReferenceError {
+                    reference: format!(
+                        "{}{}{}",
+                        REFERENCE_START_DELIMITER, reference, REFERENCE_END_DELIMITER
+                    ),
+                });
+            }
+
+            let (name, pattern) = self
+                .patterns
+                .get_key_value(name)
+                .ok_or_else(|| GrokError::UnknownPatternError { name: name.to_owned() })?;
+            if expanding.contains(&name.as_str()) {
+                return Err(GrokError::RecursivePatternError { name: name.to_owned() });
+            }
+
+            match field {
+                Some(field) => {
+                    regex.push_str("(?P<");
+                    regex.push_str(field);
+                    regex.push('>');
+                }
+                None => regex.push_str("(?:"),
+            }
+            expanding.push(name);
+            self.expand_into(pattern, regex, expanding)?;
+            expanding.pop();
+            regex.push(')');
+        }
+        regex.push_str(rest);
+        Ok(())
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_valid_field(field: &str) -> bool {
+    field.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use regex::Regex;
+
+    #[test]
+    fn should_expand_references_to_the_patterns() {
+        // Arrange
+        let mut patterns = GrokPatterns::empty();
+        patterns.add_pattern("DIGITS", "[0-9]+");
+        patterns.add_pattern("PORT", "%{DIGITS}");
+
+        // Act
+        let regex = patterns.expand("^port %{PORT:port} of %{DIGITS}$").unwrap();
+
+        // Assert
+        assert_eq!("^port (?P<port>(?:[0-9]+)) of (?:[0-9]+)$", regex);
+    }
+
+    #[test]
+    fn should_leave_expressions_without_references_unchanged() {
+        let patterns = GrokPatterns::default();
+        assert_eq!("^[a-z]{2,3}%$", patterns.expand("^[a-z]{2,3}%$").unwrap());
+    }
+
+    #[test]
+    fn expand_should_fail_with_invalid_references() {
+        // Arrange
+        let mut patterns = GrokPatterns::empty();
+        patterns.add_pattern("DIGITS", "[0-9]+");
+        patterns.add_pattern("LOOP", "a%{LOOP_AGAIN}");
+        patterns.add_pattern("LOOP_AGAIN", "b%{LOOP}");
+
+        // Act & Assert
+        assert_eq!(
+            Err(GrokError::UnknownPatternError { name: "NUMBER".to_owned() }),
+            patterns.expand("%{DIGITS} %{NUMBER}")
+        );
+        assert_eq!(
+            Err(GrokError::RecursivePatternError { name: "LOOP".to_owned() }),
+            patterns.expand("%{LOOP}")
+        );
+        assert_eq!(
+            Err(GrokError::UnclosedReferenceError { position: 10 }),
+            patterns.expand("%{DIGITS} %{DIGITS")
+        );
+        assert!(patterns.expand("%{DIGITS:port:int}").is_err());
+        assert!(patterns.expand("%{DIGITS:}").is_err());
+        assert!(patterns.expand("%{}").is_err());
+    }
+
+    #[test]
+    fn should_add_patterns_from_definitions() {
+        // Arrange
+        let mut patterns = GrokPatterns::default();
+
+        // Act
+        patterns
+            .add_patterns(
+                r#"
+                # Firewall patterns
+                FW_ACTION ACCEPT|DROP
+
+                FW_LINE %{FW_ACTION:action} %{IP:src}
+                IP [0-9.]+
+            "#,
+            )
+            .unwrap();
+
+        // Assert
+        assert_eq!(Some("ACCEPT|DROP"), patterns.get("FW_ACTION"));
+        assert_eq!(Some("[0-9.]+"), patterns.get("IP"));
+        assert_eq!(
+            "(?:(?P<action>ACCEPT|DROP) (?P<src>[0-9.]+))",
+            patterns.expand("%{FW_LINE}").unwrap()
+        );
+        assert!(patterns.add_patterns("FW_EMPTY").is_err());
+        assert!(patterns.add_patterns("FW-NAME .*").is_err());
+    }
+
+    #[test]
+    fn built_in_patterns_should_be_valid_regexes() {
+        let patterns = GrokPatterns::default();
+        for name in patterns.patterns.keys() {
+            let regex = patterns.expand(&format!("%{{{}}}", name)).unwrap();
+            assert!(Regex::new(&regex).is_ok(), "Invalid built-in pattern [{}]", name);
+        }
+    }
+
+    #[test]
+    fn built_in_patterns_should_match_common_formats() {
+        // Arrange
+        let patterns = GrokPatterns::default();
+        let regex = Regex::new(
+            &patterns
+                .expand(
+                    r"^%{SYSLOGBASE} %{IP:src}:%{POSINT:port} -> %{IPV6:dst} %{GREEDYDATA:msg}$",
+                )
+                .unwrap(),
+        )
+        .unwrap();
+
+        // Act
+        let captures = regex
+            .captures("Oct 11 22:14:15 fw01 kernel[42]: 10.0.0.1:5353 -> fe80::1%eth0 dropped")
+            .unwrap();
+
+        // Assert
+        assert_eq!("Oct 11 22:14:15", &captures["timestamp"]);
+        assert_eq!("fw01", &captures["logsource"]);
+        assert_eq!("kernel", &captures["program"]);
+        assert_eq!("42", &captures["pid"]);
+        assert_eq!("10.0.0.1", &captures["src"]);
+        assert_eq!("5353", &captures["port"]);
+        assert_eq!("fe80::1%eth0", &captures["dst"]);
+        assert_eq!("dropped", &captures["msg"]);
+    }
+}
@@ -0,0 +1,824 @@
+//! The condition module contains the parser of the textual form of the Rule conditions,
+//! e.g. `event.type == "syslog" && (payload.severity <= 3 || payload.msg =~ /panic/)`.
+//!
+//! The grammar is:
+//! ```text
+//! condition  := and ( "||" and )*
+//! and        := unary ( "&&" unary )*
+//! unary      := "!" unary | "(" condition ")" | function | comparison
+//! function   := name "(" [ operand ( "," operand )* ] ")"
+//! comparison := operand ( ("==" | "!=" | "<" | "<=" | ">" | ">=") operand
+//!                       | ("=~" | "!~") regex
+//!                       | "in" array )
+//! operand    := path | string | number | "true" | "false" | "null" | array
+//! array      := "[" [ operand ( "," operand )* ] "]"
+//! regex      := "/" chars "/"
+//! ```
+//! A path is an accessor expression without delimiters, e.g. `event.payload.hosts[0]`; if its root
+//! is not `event`, `_variables` or `item`, it refers to a field of the event, e.g. `payload.host`.
+
+use crate::{ParserBuilder, EVENT_KEY, EXTRACTED_VARIABLES_KEY, FOREACH_ITEM_KEY};
+use serde_json::Value;
+use thiserror::Error;
+
+const VALUE_TYPES: [&str; 6] = ["string", "number", "bool", "array", "object", "null"];
+
+/// The maximum number of nested parentheses, negations and arrays in a condition.
+/// The parser is recursive, so the limit prevents a condition from overflowing the stack.
+const MAX_NESTING_DEPTH: usize = 64;
+
+/// A Rule condition; each variant corresponds to an operator of the matcher.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    And { conditions: Vec<Condition> },
+    Or { conditions: Vec<Condition> },
+    Not { condition: Box<Condition> },
+    Contains { first: Value, second: Value },
+    ContainsIgnoreCase { first: Value, second: Value },
+    Equals { first: Value, second: Value },
+    EqualsIgnoreCase { first: Value, second: Value },
+    GreaterEqualThan { first: Value, second: Value },
+    GreaterThan { first: Value, second: Value },
+    LessEqualThan { first: Value, second: Value },
+    LessThan { first: Value, second: Value },
+    NotEquals { first: Value, second: Value },
+    Regex { regex: String, target: String, grok: bool },
+    In { first: Value, values: Vec<Value> },
+    StartsWith { first: Value, second: Value },
+    EndsWith { first: Value, second: Value },
+    Exists { target: String },
+    NotExists { target: String },
+    IsType { target: String, value_type: String },
+    IpInCidr { target: String, networks: Vec<String> },
+    IpEquals { first: Value, second: Value },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionErrorKind {
+    UnexpectedCharacter,
+    UnterminatedLiteral,
+    InvalidLiteral,
+    UnexpectedToken,
+    UnexpectedEnd,
+    UnknownFunction,
+    InvalidArguments,
+    InvalidAccessor,
+    TooDeeplyNested,
+}
+
+/// An error in the text of a condition.
+/// The line and the column, both starting from 1, are the position of the error in the text.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{kind:?} at line {line}, column {column}: {message}")]
+pub struct ConditionError {
+    pub kind: ConditionErrorKind,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Condition {
+    /// Parses the textual form of a condition.
+    pub fn parse(text: &str) -> Result<Condition, ConditionError> {
+        let tokens = tokenize(text)?;
+        let mut parser = ConditionParser { text, tokens, position: 0, depth: 0 };
+        let condition = parser.parse_or()?;
+        match parser.peek() {
+            None => Ok(condition),
+            Some(token) => Err(parser.unexpected(token)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    Comma,
+    And,
+    Or,
+    Not,
+    Equals,
+    NotEquals,
+    LessThan,
+    LessEqualThan,
+    GreaterThan,
+    GreaterEqualThan,
+    Matches,
+    NotMatches,
+    In,
+    Literal(Value),
+    Regex(String),
+    Path(String),
+    Function(String),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    start: usize,
+}
+
+fn error_at(
+    text: &str,
+    offset: usize,
+    kind: ConditionErrorKind,
+    message: String,
+) -> ConditionError {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+    ConditionError { kind, message, line, column }
+}
+
+fn is_path_char(character: char) -> bool {
+    character.is_alphanumeric() || character == '_' || character == '-'
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, ConditionError> {
+    let mut tokens = vec![];
+    let mut chars = text.char_indices().peekable();
+
+    while let Some(&(start, character)) = chars.peek() {
+        let mut push = |kind: TokenKind, length: usize| {
+            tokens.push(Token { kind, start });
+            length
+        };
+        let next = text[start + character.len_utf8()..].chars().next();
+        let length = match (character, next) {
+            (c, _) if c.is_whitespace() => 1,
+            ('(', _) => push(TokenKind::LeftParen, 1),
+            (')', _) => push(TokenKind::RightParen, 1),
+            ('[', _) => push(TokenKind::LeftBracket, 1),
+            (']', _) => push(TokenKind::RightBracket, 1),
+            (',', _) => push(TokenKind::Comma, 1),
+            ('&', Some('&')) => push(TokenKind::And, 2),
+            ('|', Some('|')) => push(TokenKind::Or, 2),
+            ('=', Some('=')) => push(TokenKind::Equals, 2),
+            ('=', Some('~')) => push(TokenKind::Matches, 2),
+            ('!', Some('=')) => push(TokenKind::NotEquals, 2),
+            ('!', Some('~')) => push(TokenKind::NotMatches, 2),
+            ('!', _) => push(TokenKind::Not, 1),
+            ('<', Some('=')) => push(TokenKind::LessEqualThan, 2),
+            ('<', _) => push(TokenKind::LessThan, 1),
+            ('>', Some('=')) => push(TokenKind::GreaterEqualThan, 2),
+            ('>', _) => push(TokenKind::GreaterThan, 1),
+            ('"', _) => {
+                let end = find_closing(text, start, '"').ok_or_else(|| {
+                    error_at(
+                        text,
+                        start,
+                        ConditionErrorKind::UnterminatedLiteral,
+                        "The string is not terminated".to_owned(),
+                    )
+                })?;
+                let literal = serde_json::from_str(&text[start..end]).map_err(|err| {
+                    error_at(
+                        text,
+                        start,
+                        ConditionErrorKind::InvalidLiteral,
+                        format!("Invalid string: {}", err),
+                    )
+                })?;
+                push(TokenKind::Literal(literal), end - start)
+            }
+            ('/', _) => {
+                let end = find_closing(text, start, '/').ok_or_else(|| {
+                    error_at(
+                        text,
+                        start,
+                        ConditionErrorKind::UnterminatedLiteral,
+                        "The regex is not terminated".to_owned(),
+                    )
+                })?;
+                let regex = text[start + 1..end - 1].replace("\\/", "/");
+                push(TokenKind::Regex(regex), end - start)
+            }
+            (c, _)
+                if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) =>
+            {
+                let end = text[start + 1..]
+                    .find(|c: char| {
+                        !(c.is_ascii_alphanumeric() || c == '.' || c == '+' || c == '-')
+                    })
+                    .map_or(text.len(), |index| start + 1 + index);
+                match serde_json::from_str::<Value>(&text[start..end]) {
+                    Ok(number) if number.is_number() => {
+                        push(TokenKind::Literal(number), end - start)
+                    }
+                    _ => {
+                        return Err(error_at(
+                            text,
+                            start,
+                            ConditionErrorKind::InvalidLiteral,
+                            format!("Invalid number [{}]", &text[start..end]),
+                        ))
+                    }
+                }
+            }
+            (c, _) if c.is_alphabetic() || c == '_' => {
+                let (kind, end) = tokenize_word(text, start)?;
+                push(kind, end - start)
+            }
+            (c, _) => {
+                return Err(error_at(
+                    text,
+                    start,
+                    ConditionErrorKind::UnexpectedCharacter,
+                    format!("Unexpected character [{}]", c),
+                ))
+            }
+        };
+
+        while let Some(&(index, _)) = chars.peek() {
+            if index >= start + length {
+                break;
+            }
+            chars.next();
+        }
+    }
+    Ok(tokens)
+}
+
+/// Returns the offset after the delimiter that closes the literal starting at `start`;
+/// the delimiters preceded by a backslash are ignored.
+fn find_closing(text: &str, start: usize, delimiter: char) -> Option<usize> {
+    let mut escaped = false;
+    for (index, character) in text[start + 1..].char_indices() {
+        match character {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            c if c == delimiter => return Some(start + 1 + index + c.len_utf8()),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Reads a keyword, a function name or a path, e.g. `event.payload."key.with.dots"[0]`.
+fn tokenize_word(text: &str, start: usize) -> Result<(TokenKind, usize), ConditionError> {
+    let word_end = |from: usize| {
+        text[from..].find(|c: char| !is_path_char(c)).map_or(text.len(), |index| from + index)
+    };
+
+    let mut end = word_end(start);
+    let word = &text[start..end];
+    if text[end..].starts_with('(') {
+        return Ok((TokenKind::Function(word.to_owned()), end));
+    }
+    match word {
+        "in" => return Ok((TokenKind::In, end)),
+        "true" => return Ok((TokenKind::Literal(Value::Bool(true)), end)),
+        "false" => return Ok((TokenKind::Literal(Value::Bool(false)), end)),
+        "null" => return Ok((TokenKind::Literal(Value::Null), end)),
+        _ => {}
+    }
+
+    loop {
+        let rest = &text[end..];
+        let closing = if let Some(quoted) = rest.strip_prefix(".\"") {
+            quoted.find('"').map(|index| end + 2 + index + 1)
+        } else if rest.starts_with('.') && rest[1..].starts_with(is_path_char) {
+            let key_end = word_end(end + 1);
+            Some(if text[key_end..].starts_with("()") { key_end + 2 } else { key_end })
+        } else if rest.starts_with('[') {
+            rest.find(']').map(|index| end + index + 1)
+        } else {
+            break;
+        };
+        end = closing.ok_or_else(|| {
+            error_at(
+                text,
+                end,
+                ConditionErrorKind::UnterminatedLiteral,
+                "The key of the path is not terminated".to_owned(),
+            )
+        })?;
+    }
+    Ok((TokenKind::Path(text[start..end].to_owned()), end))
+}
+
+struct ConditionParser<'a> {
+    text: &'a str,
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+}
+
+impl ConditionParser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<Token, ConditionError> {
+        let token = self.tokens.get(self.position).cloned().ok_or_else(|| {
+            error_at(
+                self.text,
+                self.text.len(),
+                ConditionErrorKind::UnexpectedEnd,
+                "Unexpected end of the condition".to_owned(),
+            )
+        })?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn next_if(&mut self, kind: &TokenKind) -> bool {
+        if self.peek().is_some_and(|token| &token.kind == kind) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, kind: TokenKind, expected: &str) -> Result<Token, ConditionError> {
+        let token = self.next()?;
+        if token.kind == kind {
+            Ok(token)
+        } else {
+            Err(self.error(
+                &token,
+                ConditionErrorKind::UnexpectedToken,
+                format!("Expected {}", expected),
+            ))
+        }
+    }
+
+    fn error(&self, token: &Token, kind: ConditionErrorKind, message: String) -> ConditionError {
+        error_at(self.text, token.start, kind, message)
+    }
+
+    /// Enters the nested condition or array opened by the last token, failing if the maximum
+    /// nesting depth is exceeded.
+    fn enter(&mut self) -> Result<(), ConditionError> {
+        if self.depth == MAX_NESTING_DEPTH {
+            return Err(self.error(
+                &self.tokens[self.position - 1],
+                ConditionErrorKind::TooDeeplyNested,
+                format!("The condition is nested more than {} levels deep", MAX_NESTING_DEPTH),
+            ));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn unexpected(&self, token: &Token) -> ConditionError {
+        self.error(
+            token,
+            ConditionErrorKind::UnexpectedToken,
+            format!("Unexpected token [{}]", self.token_text(token)),
+        )
+    }
+
+    fn token_text(&self, token: &Token) -> &str {
+        let end = self.tokens.get(self.position).map_or(self.text.len(), |next| next.start);
+        let end = if end > token.start { end } else { self.text.len() };
+        self.text[token.start..end].trim()
+    }
+
+    fn parse_or(&mut self) -> Result<Condition, ConditionError> {
+        let mut conditions = vec![self.parse_and()?];
+        while self.next_if(&TokenKind::Or) {
+            conditions.push(self.parse_and()?);
+        }
+        Ok(if conditions.len() == 1 { conditions.remove(0) } else { Condition::Or { conditions } })
+    }
+
+    fn parse_and(&mut self) -> Result<Condition, ConditionError> {
+        let mut conditions = vec![self.parse_unary()?];
+        while self.next_if(&TokenKind::And) {
+            conditions.push(self.parse_unary()?);
+        }
+        Ok(if conditions.len() == 1 { conditions.remove(0) } else { Condition::And { conditions } })
+    }
+
+    fn parse_unary(&mut self) -> Result<Condition, ConditionError> {
+        if self.next_if(&TokenKind::Not) {
+            self.enter()?;
+            let condition = self.parse_unary()?;
+            self.depth -= 1;
+            return Ok(Condition::Not { condition: Box::new(condition) });
+        }
+        if self.next_if(&TokenKind::LeftParen) {
+            self.enter()?;
+            let condition = self.parse_or()?;
+            self.expect(TokenKind::RightParen, "[)]")?;
+            self.depth -= 1;
+            return Ok(condition);
+        }
+        if let Some(Token { kind: TokenKind::Function(name), .. }) = self.peek().cloned() {
+            let token = self.next()?;
+            return self.parse_function(&token, &name);
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_function(&mut self, token: &Token, name: &str) -> Result<Condition, ConditionError> {
+        self.expect(TokenKind::LeftParen, "[(]")?;
+        let mut arguments = vec![];
+        if !self.next_if(&TokenKind::RightParen) {
+            loop {
+                arguments.push(self.parse_operand()?);
+                if self.next_if(&TokenKind::RightParen) {
+                    break;
+                }
+                self.expect(TokenKind::Comma, "[,] or [)]")?;
+            }
+        }
+
+        let invalid_arguments = |expected: &str| {
+            self.error(
+                token,
+                ConditionErrorKind::InvalidArguments,
+                format!("The function [{}] expects {}", name, expected),
+            )
+        };
+        let target = |value: &Value| match value {
+            Value::String(target) => Ok(target.to_owned()),
+            _ => Err(invalid_arguments("a path or a string as first argument")),
+        };
+
+        match (name, arguments.as_slice()) {
+            ("contains", [first, second]) => {
+                Ok(Condition::Contains { first: first.clone(), second: second.clone() })
+            }
+            ("containsIgnoreCase", [first, second]) => {
+                Ok(Condition::ContainsIgnoreCase { first: first.clone(), second: second.clone() })
+            }
+            ("equalsIgnoreCase", [first, second]) => {
+                Ok(Condition::EqualsIgnoreCase { first: first.clone(), second: second.clone() })
+            }
+            ("startsWith", [first, second]) => {
+                Ok(Condition::StartsWith { first: first.clone(), second: second.clone() })
+            }
+            ("endsWith", [first, second]) => {
+                Ok(Condition::EndsWith { first: first.clone(), second: second.clone() })
+            }
+            ("ipEquals", [first, second]) => {
+                Ok(Condition::IpEquals { first: first.clone(), second: second.clone() })
+            }
+            ("grok", [first, Value::String(regex)]) => {
+                Ok(Condition::Regex { regex: regex.to_owned(), target: target(first)?, grok: true })
+            }
+            ("isType", [first, Value::String(value_type)])
+                if VALUE_TYPES.contains(&value_type.as_str()) =>
+            {
+                Ok(Condition::IsType { target: target(first)?, value_type: value_type.to_owned() })
+            }
+            ("ipInCidr", [first, Value::Array(networks)])
+                if networks.iter().all(Value::is_string) =>
+            {
+                let networks =
+                    networks.iter().filter_map(Value::as_str).map(str::to_owned).collect();
+                Ok(Condition::IpInCidr { target: target(first)?, networks })
+            }
+            ("exists", [first]) => Ok(Condition::Exists { target: target(first)? }),
+            ("notExists", [first]) => Ok(Condition::NotExists { target: target(first)? }),
+            (
+                "contains" | "containsIgnoreCase" | "equalsIgnoreCase" | "startsWith" | "endsWith"
+                | "ipEquals",
+                _,
+            ) => Err(invalid_arguments("two arguments")),
+            ("grok", _) => Err(invalid_arguments("a path and a string")),
+            ("isType", _) => {
+                Err(invalid_arguments(&format!("a path and one of the types {:?}", VALUE_TYPES)))
+            }
+            ("ipInCidr", _) => Err(invalid_arguments("a path and an array of strings")),
+            ("exists" | "notExists", _) => Err(invalid_arguments("one argument")),
+            _ => Err(self.error(
+                token,
+                ConditionErrorKind::UnknownFunction,
+                format!("Unknown function [{}]", name),
+            )),
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<Condition, ConditionError> {
+        let first_token = self.peek().cloned();
+        let first = self.parse_operand()?;
+        let token = self.next()?;
+        let second = |parser: &mut Self| parser.parse_operand();
+        match token.kind {
+            TokenKind::Equals => Ok(Condition::Equals { first, second: second(self)? }),
+            TokenKind::NotEquals => Ok(Condition::NotEquals { first, second: second(self)? }),
+            TokenKind::LessThan => Ok(Condition::LessThan { first, second: second(self)? }),
+            TokenKind::LessEqualThan => {
+                Ok(Condition::LessEqualThan { first, second: second(self)? })
+            }
+            TokenKind::GreaterThan => Ok(Condition::GreaterThan { first, second: second(self)? }),
+            TokenKind::GreaterEqualThan => {
+                Ok(Condition::GreaterEqualThan { first, second: second(self)? })
+            }
+            TokenKind::Matches | TokenKind::NotMatches => {
+                let target = match first {
+                    Value::String(target) => target,
+                    _ => {
+                        let first_token = first_token.unwrap_or(token);
+                        return Err(self.error(
+                            &first_token,
+                            ConditionErrorKind::InvalidArguments,
+                            "The target of a regex must be a path or a string".to_owned(),
+                        ));
+                    }
+                };
+                let regex = match self.next()? {
+                    Token { kind: TokenKind::Regex(regex), .. } => regex,
+                    other => {
+                        return Err(self.error(
+                            &other,
+                            ConditionErrorKind::UnexpectedToken,
+                            "Expected a regex, e.g. /^[a-z]+$/".to_owned(),
+                        ))
+                    }
+                };
+                let condition = Condition::Regex { regex, target, grok: false };
+                if token.kind == TokenKind::NotMatches {
+                    Ok(Condition::Not { condition: Box::new(condition) })
+                } else {
+                    Ok(condition)
+                }
+            }
+            TokenKind::In => {
+                let values_token = self.peek().cloned().unwrap_or(token);
+                match self.parse_operand()? {
+                    Value::Array(values) => Ok(Condition::In { first, values }),
+                    _ => Err(self.error(
+                        &values_token,
+                        ConditionErrorKind::UnexpectedToken,
+                        "Expected an array".to_owned(),
+                    )),
+                }
+            }
+            _ => Err(self.error(
+                &token,
+                ConditionErrorKind::UnexpectedToken,
+                format!("Expected a comparison operator, found [{}]", self.token_text(&token)),
+            )),
+        }
+    }
+
+    fn parse_operand(&mut self) -> Result<Value, ConditionError> {
+        let token = self.next()?;
+        match token.kind {
+            TokenKind::Literal(value) => Ok(value),
+            TokenKind::Path(ref path) => {
+                let accessor = to_accessor(path);
+                ParserBuilder::engine_matcher(&accessor).map_err(|err| {
+                    self.error(
+                        &token,
+                        ConditionErrorKind::InvalidAccessor,
+                        format!("Invalid path [{}]: {}", path, err),
+                    )
+                })?;
+                Ok(Value::String(accessor))
+            }
+            TokenKind::LeftBracket => {
+                self.enter()?;
+                let mut values = vec![];
+                if !self.next_if(&TokenKind::RightBracket) {
+                    loop {
+                        values.push(self.parse_operand()?);
+                        if self.next_if(&TokenKind::RightBracket) {
+                            break;
+                        }
+                        self.expect(TokenKind::Comma, "[,] or []]")?;
+                    }
+                }
+                self.depth -= 1;
+                Ok(Value::Array(values))
+            }
+            _ => Err(self.error(
+                &token,
+                ConditionErrorKind::UnexpectedToken,
+                format!("Expected a path or a value, found [{}]", self.token_text(&token)),
+            )),
+        }
+    }
+}
+
+/// Converts a path into an accessor expression, e.g. `payload.host` into `${event.payload.host}`.
+fn to_accessor(path: &str) -> String {
+    let root = path.split(['.', '[']).next().unwrap_or_default();
+    if root == EVENT_KEY || root == EXTRACTED_VARIABLES_KEY || root == FOREACH_ITEM_KEY {
+        format!("${{{}}}", path)
+    } else {
+        format!("${{{}.{}}}", EVENT_KEY, path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn error_of(text: &str) -> (ConditionErrorKind, usize, usize) {
+        let error = Condition::parse(text).unwrap_err();
+        (error.kind, error.line, error.column)
+    }
+
+    #[test]
+    fn should_parse_comparisons() {
+        assert_eq!(
+            Condition::Equals { first: json!("${event.type}"), second: json!("syslog") },
+            Condition::parse(r#"event.type == "syslog""#).unwrap()
+        );
+        assert_eq!(
+            Condition::LessEqualThan {
+                first: json!("${event.payload.severity}"),
+                second: json!(3)
+            },
+            Condition::parse("payload.severity <= 3").unwrap()
+        );
+        assert_eq!(
+            Condition::NotEquals { first: json!("${_variables.host}"), second: json!(-1.5) },
+            Condition::parse("_variables.host != -1.5").unwrap()
+        );
+        assert_eq!(
+            Condition::GreaterThan { first: json!("${item[0]}"), second: Value::Null },
+            Condition::parse("item[0] > null").unwrap()
+        );
+        assert_eq!(
+            Condition::In {
+                first: json!("${event.payload.oids.\"key.with.dots\"}"),
+                values: vec![json!("a"), json!(true), json!("${event.type}")]
+            },
+            Condition::parse(r#"payload.oids."key.with.dots" in ["a", true, event.type]"#).unwrap()
+        );
+        assert_eq!(
+            Condition::GreaterEqualThan {
+                first: json!("${event.payload.items[*].tags.length()}"),
+                second: json!([1, 2])
+            },
+            Condition::parse("payload.items[*].tags.length() >= [1, 2]").unwrap()
+        );
+    }
+
+    #[test]
+    fn should_parse_regexes() {
+        assert_eq!(
+            Condition::Regex {
+                regex: "^/var/log/[a-z]+$".to_owned(),
+                target: "${event.payload.path}".to_owned(),
+                grok: false
+            },
+            Condition::parse(r"payload.path =~ /^\/var\/log\/[a-z]+$/").unwrap()
+        );
+        assert_eq!(
+            Condition::Not {
+                condition: Box::new(Condition::Regex {
+                    regex: r"\d+".to_owned(),
+                    target: "${event.payload.msg}".to_owned(),
+                    grok: false
+                })
+            },
+            Condition::parse(r"payload.msg !~ /\d+/").unwrap()
+        );
+    }
+
+    #[test]
+    fn should_parse_functions() {
+        assert_eq!(
+            Condition::StartsWith { first: json!("${event.type}"), second: json!("sys") },
+            Condition::parse(r#"startsWith(event.type, "sys")"#).unwrap()
+        );
+        assert_eq!(
+            Condition::Exists { target: "${event.payload.host}".to_owned() },
+            Condition::parse("exists(payload.host)").unwrap()
+        );
+        assert_eq!(
+            Condition::IsType {
+                target: "${event.payload.host}".to_owned(),
+                value_type: "string".to_owned()
+            },
+            Condition::parse(r#"isType(payload.host, "string")"#).unwrap()
+        );
+        assert_eq!(
+            Condition::IpInCidr {
+                target: "${event.payload.ip}".to_owned(),
+                networks: vec!["10.0.0.0/8".to_owned(), "fd00::/8".to_owned()]
+            },
+            Condition::parse(r#"ipInCidr(payload.ip, ["10.0.0.0/8", "fd00::/8"])"#).unwrap()
+        );
+        assert_eq!(
+            Condition::Regex {
+                regex: "%{IP:ip}".to_owned(),
+                target: "${event.payload.msg}".to_owned(),
+                grok: true
+            },
+            Condition::parse(r#"grok(payload.msg, "%{IP:ip}")"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_parse_boolean_operators_with_precedence() {
+        // Act
+        let condition = Condition::parse(
+            r#"event.type == "syslog" && (payload.severity <= 3 || payload.msg =~ /panic/)
+                || !exists(payload.host) && true == true"#,
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(
+            Condition::Or {
+                conditions: vec![
+                    Condition::And {
+                        conditions: vec![
+                            Condition::Equals {
+                                first: json!("${event.type}"),
+                                second: json!("syslog")
+                            },
+                            Condition::Or {
+                                conditions: vec![
+                                    Condition::LessEqualThan {
+                                        first: json!("${event.payload.severity}"),
+                                        second: json!(3)
+                                    },
+                                    Condition::Regex {
+                                        regex: "panic".to_owned(),
+                                        target: "${event.payload.msg}".to_owned(),
+                                        grok: false
+                                    },
+                                ]
+                            },
+                        ]
+                    },
+                    Condition::And {
+                        conditions: vec![
+                            Condition::Not {
+                                condition: Box::new(Condition::Exists {
+                                    target: "${event.payload.host}".to_owned()
+                                })
+                            },
+                            Condition::Equals { first: json!(true), second: json!(true) },
+                        ]
+                    },
+                ]
+            },
+            condition
+        );
+    }
+
+    #[test]
+    fn should_return_the_position_of_the_errors() {
+        assert_eq!((ConditionErrorKind::UnexpectedCharacter, 1, 12), error_of("event.type = 1"));
+        assert_eq!(
+            (ConditionErrorKind::UnexpectedToken, 2, 5),
+            error_of("event.type == 1 &&\n    && true == true")
+        );
+        assert_eq!((ConditionErrorKind::UnexpectedEnd, 1, 14), error_of("event.type =="));
+        assert_eq!(
+            (ConditionErrorKind::UnterminatedLiteral, 1, 15),
+            error_of(r#"event.type == "sys"#)
+        );
+        assert_eq!(
+            (ConditionErrorKind::UnterminatedLiteral, 1, 15),
+            error_of("event.type =~ /sys")
+        );
+        assert_eq!((ConditionErrorKind::UnknownFunction, 1, 1), error_of("capitalize(event.type)"));
+        assert_eq!((ConditionErrorKind::InvalidArguments, 1, 3), error_of("!(exists(a, b))"));
+        assert_eq!(
+            (ConditionErrorKind::InvalidArguments, 1, 1),
+            error_of(r#"isType(event.type, "date")"#)
+        );
+        assert_eq!((ConditionErrorKind::InvalidAccessor, 1, 1), error_of("event[a] == 1"));
+        assert_eq!((ConditionErrorKind::UnexpectedToken, 1, 12), error_of("event.type 1"));
+        assert_eq!((ConditionErrorKind::UnexpectedToken, 1, 18), error_of("(event.type == 1))"));
+        assert_eq!((ConditionErrorKind::InvalidLiteral, 1, 15), error_of("event.type == 1.2.3"));
+        assert_eq!((ConditionErrorKind::UnexpectedToken, 1, 15), error_of("event.type in 1"));
+        assert_eq!((ConditionErrorKind::InvalidArguments, 1, 1), error_of("1 =~ /a/"));
+    }
+
+    #[test]
+    fn should_limit_the_nesting_depth() {
+        let nested = |open: &str, close: &str, depth: usize, inner: &str| {
+            format!("{}{}{}", open.repeat(depth), inner, close.repeat(depth))
+        };
+
+        assert!(Condition::parse(&nested("(", ")", MAX_NESTING_DEPTH, "a == 1")).is_ok());
+        assert!(Condition::parse(&nested("!", "", MAX_NESTING_DEPTH, "exists(a)")).is_ok());
+        assert!(
+            Condition::parse(&format!("a in {}", nested("[", "]", MAX_NESTING_DEPTH, ""))).is_ok()
+        );
+
+        assert_eq!(
+            (ConditionErrorKind::TooDeeplyNested, 1, 65),
+            error_of(&nested("(", ")", MAX_NESTING_DEPTH + 1, "a == 1"))
+        );
+        assert_eq!(
+            (ConditionErrorKind::TooDeeplyNested, 1, 65),
+            error_of(&nested("!", "", MAX_NESTING_DEPTH + 1, "exists(a)"))
+        );
+        assert_eq!(
+            (ConditionErrorKind::TooDeeplyNested, 1, 70),
+            error_of(&format!("a in {}", nested("[", "]", MAX_NESTING_DEPTH + 1, "")))
+        );
+        assert_eq!(
+            (ConditionErrorKind::TooDeeplyNested, 1, 65),
+            error_of(&nested("(", ")", 100_000, "a == 1"))
+        );
+    }
+}
@@ -0,0 +1,287 @@
+//! The filter module contains the filters that transform the value of an expression,
+//! e.g. `${event.payload.host | upper}`.
+//!
+//! Filters are appended to the expression separated by a pipe and they are applied from left
+//! to right. Their arguments are JSON literals separated by commas.
+
+use crate::parser::ParserError;
+use chrono::format::{Item, StrftimeItems};
+use chrono::TimeZone;
+use chrono_tz::Tz;
+use serde_json::Value;
+use std::borrow::Cow;
+
+const FILTER_DELIMITER: char = '|';
+const FILTER_ARGUMENTS_START_DELIMITER: char = '(';
+const FILTER_ARGUMENTS_END_DELIMITER: char = ')';
+const FILTER_ARGUMENTS_DELIMITER: char = ',';
+const QUOTE: char = '"';
+const ESCAPE: char = '\\';
+
+#[derive(Debug, PartialEq)]
+pub enum ValueFilter {
+    /// Converts the value to uppercase
+    Upper,
+    /// Converts the value to lowercase
+    Lower,
+    /// Removes the leading and trailing whitespaces
+    Trim,
+    /// Keeps only the first `length` characters
+    Truncate { length: usize },
+    /// Replaces a missing or null value
+    Default { value: Value },
+    /// Formats an epoch timestamp in milliseconds with a strftime pattern
+    Date { format: String, timezone: Tz },
+}
+
+impl ValueFilter {
+    /// Builds a filter from its expression, e.g. `upper` or `truncate(200)`.
+    pub fn build(expression: &str) -> Result<ValueFilter, ParserError> {
+        let expression = expression.trim();
+        let (name, arguments) = match expression.split_once(FILTER_ARGUMENTS_START_DELIMITER) {
+            Some((name, arguments)) => {
+                let arguments =
+                    arguments.strip_suffix(FILTER_ARGUMENTS_END_DELIMITER).ok_or_else(|| {
+                        filter_error(expression, "the arguments must be enclosed in parentheses")
+                    })?;
+                (name.trim(), parse_arguments(expression, arguments)?)
+            }
+            None => (expression, vec![]),
+        };
+
+        match (name, arguments.as_slice()) {
+            ("upper", []) => Ok(ValueFilter::Upper),
+            ("lower", []) => Ok(ValueFilter::Lower),
+            ("trim", []) => Ok(ValueFilter::Trim),
+            ("truncate", [length]) => match length.as_u64() {
+                Some(length) => Ok(ValueFilter::Truncate { length: length as usize }),
+                None => Err(filter_error(expression, "the length must be a positive integer")),
+            },
+            ("default", [value]) => Ok(ValueFilter::Default { value: value.clone() }),
+            ("date", [Value::String(format)]) => build_date(expression, format, None),
+            ("date", [Value::String(format), Value::String(timezone)]) => {
+                build_date(expression, format, Some(timezone))
+            }
+            ("upper" | "lower" | "trim" | "truncate" | "default" | "date", _) => {
+                Err(filter_error(expression, "invalid arguments"))
+            }
+            _ => Err(filter_error(expression, "unknown filter")),
+        }
+    }
+
+    /// Applies the filter to the value of an expression; the value is `None` if the
+    /// expression cannot be resolved.
+    pub fn apply<'o>(&'o self, value: Option<Cow<'o, Value>>) -> Option<Cow<'o, Value>> {
+        match self {
+            ValueFilter::Default { value: default } => match value {
+                None => Some(Cow::Borrowed(default)),
+                Some(value) if value.is_null() => Some(Cow::Borrowed(default)),
+                value => value,
+            },
+            ValueFilter::Upper => map_text(value?, |text| text.to_uppercase()),
+            ValueFilter::Lower => map_text(value?, |text| text.to_lowercase()),
+            ValueFilter::Trim => map_text(value?, |text| text.trim().to_owned()),
+            ValueFilter::Truncate { length } => {
+                map_text(value?, |text| text.chars().take(*length).collect())
+            }
+            ValueFilter::Date { format, timezone } => {
+                let timestamp = value?.as_i64()?;
+                let date = timezone.timestamp_millis_opt(timestamp).single()?;
+                Some(Cow::Owned(Value::String(date.format(format).to_string())))
+            }
+        }
+    }
+}
+
+/// Parses an expression followed by its filters, e.g. `event.type | upper`, and returns the
+/// accessor part of the expression and the filters.
+pub fn parse_filtered_expression(
+    expression: &str,
+) -> Result<(&str, Vec<ValueFilter>), ParserError> {
+    let (accessor, filters) = split_filters(expression);
+    Ok((accessor, filters.into_iter().map(ValueFilter::build).collect::<Result<_, _>>()?))
+}
+
+/// Applies the filters, from left to right, to the value of an expression.
+pub fn apply_filters<'o>(
+    filters: &'o [ValueFilter],
+    value: Option<Cow<'o, Value>>,
+) -> Option<Cow<'o, Value>> {
+    filters.iter().fold(value, |value, filter| filter.apply(value))
+}
+
+/// Splits an expression into its accessor and its filters.
+/// If there are filters, the accessor is trimmed.
+pub(crate) fn split_filters(expression: &str) -> (&str, Vec<&str>) {
+    let mut parts = split_unquoted(expression, FILTER_DELIMITER);
+    let accessor = parts.remove(0);
+    if parts.is_empty() {
+        (accessor, parts)
+    } else {
+        (accessor.trim(), parts)
+    }
+}
+
+/// Splits the text at each delimiter that is not enclosed in double quotes.
+fn split_unquoted(text: &str, delimiter: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (index, character) in text.char_indices() {
+        match character {
+            _ if escaped => escaped = false,
+            ESCAPE if quoted => escaped = true,
+            QUOTE => quoted = !quoted,
+            _ if character == delimiter && !quoted => {
+                parts.push(&text[start..index]);
+                start = index + character.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+fn parse_arguments(expression: &str, arguments: &str) -> Result<Vec<Value>, ParserError> {
+    if arguments.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    split_unquoted(arguments, FILTER_ARGUMENTS_DELIMITER)
+        .into_iter()
+        .map(|argument| {
+            serde_json::from_str(argument.trim()).map_err(|_| {
+                filter_error(
+                    expression,
+                    &format!("the argument [{}] is not a valid JSON literal", argument.trim()),
+                )
+            })
+        })
+        .collect()
+}
+
+fn build_date(
+    expression: &str,
+    format: &str,
+    timezone: Option<&str>,
+) -> Result<ValueFilter, ParserError> {
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Err(filter_error(expression, "the date format is not a valid strftime pattern"));
+    }
+    let timezone = match timezone {
+        Some(timezone) => {
+            timezone.trim().parse().map_err(|_| filter_error(expression, "unknown timezone"))?
+        }
+        None => Tz::UTC,
+    };
+    Ok(ValueFilter::Date { format: format.to_owned(), timezone })
+}
+
+fn map_text<'o, F: FnOnce(&str) -> String>(
+    value: Cow<'o, Value>,
+    map: F,
+) -> Option<Cow<'o, Value>> {
+    let text = match value.as_ref() {
+        Value::String(text) => map(text),
+        Value::Number(number) => map(&number.to_string()),
+        Value::Bool(boolean) => map(&boolean.to_string()),
+        Value::Null | Value::Array(..) | Value::Object(..) => return None,
+    };
+    Some(Cow::Owned(Value::String(text)))
+}
+
+fn filter_error(filter: &str, message: &str) -> ParserError {
+    ParserError::InvalidFilterError { filter: filter.to_owned(), message: message.to_owned() }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn apply(filter: &str, value: Option<Value>) -> Option<Value> {
+        ValueFilter::build(filter)
+            .unwrap()
+            .apply(value.as_ref().map(Cow::Borrowed))
+            .map(Cow::into_owned)
+    }
+
+    #[test]
+    fn should_build_filters() {
+        assert_eq!(ValueFilter::Upper, ValueFilter::build(" upper ").unwrap());
+        assert_eq!(ValueFilter::Trim, ValueFilter::build("trim()").unwrap());
+        assert_eq!(
+            ValueFilter::Truncate { length: 200 },
+            ValueFilter::build("truncate( 200 )").unwrap()
+        );
+        assert_eq!(
+            ValueFilter::Default { value: json!("n/a, none") },
+            ValueFilter::build(r#"default("n/a, none")"#).unwrap()
+        );
+        assert_eq!(
+            ValueFilter::Date { format: "%F %T".to_owned(), timezone: Tz::Europe__Rome },
+            ValueFilter::build(r#"date("%F %T", "Europe/Rome")"#).unwrap()
+        );
+        assert_eq!(
+            ValueFilter::Date { format: "%F".to_owned(), timezone: Tz::UTC },
+            ValueFilter::build(r#"date("%F")"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn build_should_fail_with_invalid_filters() {
+        assert!(ValueFilter::build("capitalize").is_err());
+        assert!(ValueFilter::build("upper(1)").is_err());
+        assert!(ValueFilter::build("truncate").is_err());
+        assert!(ValueFilter::build("truncate(-1)").is_err());
+        assert!(ValueFilter::build("truncate(10").is_err());
+        assert!(ValueFilter::build("default(n/a)").is_err());
+        assert!(ValueFilter::build(r#"date("%Q")"#).is_err());
+        assert!(ValueFilter::build(r#"date("%F", "Mars/Olympus")"#).is_err());
+    }
+
+    #[test]
+    fn should_apply_text_filters() {
+        assert_eq!(Some(json!("WEB01")), apply("upper", Some(json!("web01"))));
+        assert_eq!(Some(json!("web01")), apply("lower", Some(json!("WEB01"))));
+        assert_eq!(Some(json!("web01")), apply("trim", Some(json!("  web01 \n"))));
+        assert_eq!(Some(json!("TRUE")), apply("upper", Some(json!(true))));
+        assert_eq!(Some(json!("àè")), apply("truncate(2)", Some(json!("àèìòù"))));
+        assert_eq!(Some(json!("12")), apply("truncate(2)", Some(json!(1234))));
+        assert_eq!(Some(json!("web")), apply("truncate(10)", Some(json!("web"))));
+        assert_eq!(None, apply("upper", Some(json!(["web01"]))));
+        assert_eq!(None, apply("upper", Some(Value::Null)));
+        assert_eq!(None, apply("upper", None));
+    }
+
+    #[test]
+    fn should_apply_default_filter() {
+        assert_eq!(Some(json!("n/a")), apply(r#"default("n/a")"#, None));
+        assert_eq!(Some(json!(0)), apply("default(0)", Some(Value::Null)));
+        assert_eq!(Some(json!("")), apply(r#"default("n/a")"#, Some(json!(""))));
+        assert_eq!(Some(json!({ "a": 1 })), apply("default(0)", Some(json!({ "a": 1 }))));
+    }
+
+    #[test]
+    fn should_apply_date_filter() {
+        assert_eq!(
+            Some(json!("2019-02-18 13:33:20")),
+            apply(r#"date("%F %T")"#, Some(json!(1550496800000_i64)))
+        );
+        assert_eq!(
+            Some(json!("2019-02-18 14:33:20")),
+            apply(r#"date("%F %T", "Europe/Rome")"#, Some(json!(1550496800000_i64)))
+        );
+        assert_eq!(None, apply(r#"date("%F")"#, Some(json!("1550496800000"))));
+        assert_eq!(None, apply(r#"date("%F")"#, None));
+    }
+
+    #[test]
+    fn should_split_outside_quotes() {
+        assert_eq!(vec!["a ", " b", ""], split_unquoted("a | b|", '|'));
+        assert_eq!(vec![r#""a|b""#, " c"], split_unquoted(r#""a|b"| c"#, '|'));
+        assert_eq!(vec![r#""a\"|b""#, "c"], split_unquoted(r#""a\"|b"|c"#, '|'));
+        assert_eq!(vec!["abc"], split_unquoted("abc", '|'));
+    }
+}
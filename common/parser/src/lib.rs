@@ -1,3 +1,5 @@
+mod condition;
+mod filter;
 mod interpolator;
 mod parser;
 
@@ -5,9 +7,11 @@ use lazy_static::lazy_static;
 use regex::{Match, Regex};
 use serde_json::Value;
 use std::borrow::Cow;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display, Formatter};
 use tornado_common_types::ValueGet;
 
+pub use crate::condition::{Condition, ConditionError, ConditionErrorKind};
+pub use crate::filter::{apply_filters, parse_filtered_expression, ValueFilter};
 pub use crate::parser::{
     AccessorExpression, Parser, ParserBuilder, ParserError, EXTRACTED_VARIABLES_KEY,
 };
@@ -63,17 +67,121 @@ pub trait CustomParser: Sync + Send + Debug {
 
 #[derive(PartialEq, Debug, Clone)]
 pub enum ValueGetter {
+    /// The entry of a map, e.g. `.key`
     Map { key: String },
+    /// The element of an array, e.g. `[0]`
     Array { index: usize },
+    /// The element of an array counting from its end, e.g. `[-1]` is the last one
+    ArrayFromEnd { offset: usize },
+    /// The elements of an array between `start` (included) and `end` (excluded), e.g. `[0:3]`.
+    /// Negative bounds count from the end of the array.
+    Slice { start: Option<i64>, end: Option<i64> },
+    /// All the elements of an array, i.e. `[*]`
+    Wildcard,
+    /// The number of elements of an array or a map, or the number of chars of a string,
+    /// i.e. `length()`
+    Length,
+    /// The keys of a map, i.e. `keys()`
+    Keys,
 }
 
 impl ValueGetter {
+    /// Returns the value selected by this getter.
+    /// Only the `Map` and `Array` getters can be used with a generic ValueGet; the other getters
+    /// return `None`.
     pub fn get<'o, I: ValueGet>(&self, value: &'o I) -> Option<&'o Value> {
         match self {
             ValueGetter::Map { key } => value.get_from_map(key),
             ValueGetter::Array { index } => value.get_from_array(*index),
+            ValueGetter::ArrayFromEnd { .. }
+            | ValueGetter::Slice { .. }
+            | ValueGetter::Wildcard
+            | ValueGetter::Length
+            | ValueGetter::Keys => None,
         }
     }
+
+    /// Applies a chain of getters to a value.
+    /// `Wildcard` and `Slice` are projections: the getters that follow them are applied to each
+    /// selected element and the elements for which they return no value are skipped.
+    pub fn get_all<'o>(getters: &[ValueGetter], value: Cow<'o, Value>) -> Option<Cow<'o, Value>> {
+        let Some((getter, tail)) = getters.split_first() else {
+            return Some(value);
+        };
+        let value = match value {
+            Cow::Borrowed(value) => value,
+            Cow::Owned(value) => {
+                return ValueGetter::get_all(getters, Cow::Borrowed(&value))
+                    .map(|value| Cow::Owned(value.into_owned()))
+            }
+        };
+
+        let project = |elements: &'o [Value]| {
+            let projection = elements
+                .iter()
+                .filter_map(|element| ValueGetter::get_all(tail, Cow::Borrowed(element)))
+                .map(Cow::into_owned)
+                .collect();
+            Some(Cow::Owned(Value::Array(projection)))
+        };
+
+        match getter {
+            ValueGetter::Map { .. } | ValueGetter::Array { .. } => {
+                ValueGetter::get_all(tail, Cow::Borrowed(getter.get(value)?))
+            }
+            ValueGetter::ArrayFromEnd { offset } => {
+                let array = value.as_array()?;
+                let index = array.len().checked_sub(*offset)?;
+                ValueGetter::get_all(tail, Cow::Borrowed(array.get(index)?))
+            }
+            ValueGetter::Slice { start, end } => {
+                let array = value.as_array()?;
+                let start = slice_bound(*start, array.len()).unwrap_or(0);
+                let end = slice_bound(*end, array.len()).unwrap_or(array.len());
+                project(if start < end { &array[start..end] } else { &[] })
+            }
+            ValueGetter::Wildcard => project(value.as_array()?),
+            ValueGetter::Length => {
+                let length = match value {
+                    Value::Array(array) => array.len(),
+                    Value::Object(map) => map.len(),
+                    Value::String(text) => text.chars().count(),
+                    _ => return None,
+                };
+                ValueGetter::get_all(tail, Cow::Owned(Value::from(length)))
+            }
+            ValueGetter::Keys => {
+                let keys = value.as_object()?.keys().cloned().map(Value::String).collect();
+                ValueGetter::get_all(tail, Cow::Owned(Value::Array(keys)))
+            }
+        }
+    }
+}
+
+impl Display for ValueGetter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let bound = |bound: &Option<i64>| bound.map(|bound| bound.to_string()).unwrap_or_default();
+        match self {
+            ValueGetter::Map { key } => write!(f, "{}", key),
+            ValueGetter::Array { index } => write!(f, "[{}]", index),
+            ValueGetter::ArrayFromEnd { offset } => write!(f, "[-{}]", offset),
+            ValueGetter::Slice { start, end } => write!(f, "[{}:{}]", bound(start), bound(end)),
+            ValueGetter::Wildcard => write!(f, "[*]"),
+            ValueGetter::Length => write!(f, "length()"),
+            ValueGetter::Keys => write!(f, "keys()"),
+        }
+    }
+}
+
+/// Converts a slice bound, which is negative if it counts from the end, into an index of an
+/// array with the given length.
+fn slice_bound(bound: Option<i64>, length: usize) -> Option<usize> {
+    let bound = bound?;
+    if bound < 0 {
+        Some(length.saturating_sub(bound.unsigned_abs() as usize))
+    } else {
+        Some((bound as usize).min(length))
+    }
 }
 
 impl From<&str> for ValueGetter {
@@ -1,3 +1,4 @@
+use crate::filter::{apply_filters, parse_filtered_expression, split_filters, ValueFilter};
 use crate::interpolator::StringInterpolator;
 use crate::{is_valid_matcher_root, CustomParser, Template, ValueGetter, FOREACH_ITEM_KEY};
 use lazy_static::*;
@@ -15,6 +16,10 @@ const PAYLOAD_KEY_PARSE_REGEX: &str = r#"("[^"]+"|[^\.^\[]+|\[[^\]]+\])"#;
 const PAYLOAD_MAP_KEY_PARSE_TRAILING_DELIMITER: &str = "\"";
 const PAYLOAD_ARRAY_KEY_START_DELIMITER: &str = "[";
 const PAYLOAD_ARRAY_KEY_END_DELIMITER: &str = "]";
+const PAYLOAD_ARRAY_WILDCARD: &str = "*";
+const PAYLOAD_ARRAY_SLICE_DELIMITER: char = ':';
+const LENGTH_PSEUDO_KEY: &str = "length()";
+const KEYS_PSEUDO_KEY: &str = "keys()";
 pub const EXTRACTED_VARIABLES_KEY: &str = "_variables";
 
 lazy_static! {
@@ -27,10 +32,14 @@ pub enum ParserError {
     UnknownKeyError { key: String },
     #[error("NotANumberError: [{key}]")]
     NotANumberError { key: String },
+    #[error("InvalidSliceError: [{key}]")]
+    InvalidSliceError { key: String },
     #[error("InvalidCharacterError: [{character}] in [{key}]")]
     InvalidCharacterError { key: String, character: String },
     #[error("EmptyAccessorError")]
     EmptyAccessorError,
+    #[error("InvalidFilterError: [{filter}]: {message}")]
+    InvalidFilterError { filter: String, message: String },
 }
 
 pub trait ParserFactory {
@@ -65,6 +74,7 @@ impl ParserBuilder {
             .strip_prefix("${")
             .and_then(|rest| rest.strip_suffix('}'))
             .map(|rest| {
+                let rest = split_filters(rest).0;
                 self.ignored_expressions
                     .iter()
                     .map(|expr| key_is_root_entry_of_expression(expr, rest))
@@ -81,21 +91,7 @@ impl ParserBuilder {
             )
             .add_ignored_expression(FOREACH_ITEM_KEY.to_owned());
 
-        match parser_builder.build_parser(input) {
-            Ok(Parser::Exp(AccessorExpression { keys })) if is_valid_matcher_root(&keys) => {
-                Ok(Parser::Exp(AccessorExpression { keys }))
-            }
-            Ok(Parser::Exp(AccessorExpression { mut keys })) => match keys.first_mut() {
-                Some(ValueGetter::Array { index }) => {
-                    Err(ParserError::UnknownKeyError { key: format!("{}", index) })
-                }
-                Some(ValueGetter::Map { key }) => {
-                    Err(ParserError::UnknownKeyError { key: std::mem::take(key) })
-                }
-                None => Err(ParserError::EmptyAccessorError),
-            },
-            res => res,
-        }
+        parser_builder.build_parser(input).and_then(validate_matcher_root)
     }
 
     pub fn build_parser(&self, template_string: &str) -> Result<Parser, ParserError> {
@@ -111,17 +107,23 @@ impl ParserBuilder {
     }
 
     fn parse_expression(&self, keys: &str) -> Result<Parser, ParserError> {
-        let expression = &keys[2..keys.len() - 1];
+        let (expression, filters) = parse_filtered_expression(&keys[2..keys.len() - 1])?;
+        if filters.is_empty() {
+            return self.parse_accessor(expression);
+        }
+
+        Ok(Parser::Filtered { parser: Box::new(self.parse_accessor(expression)?), filters })
+    }
 
+    fn parse_accessor(&self, expression: &str) -> Result<Parser, ParserError> {
         let getters = Parser::parse_keys(expression)?;
         let (head, tail) = match getters.as_slice() {
             [] => return Err(ParserError::EmptyAccessorError), // "${}"
-            [ValueGetter::Map { .. }] // "${event}"
-            | [ValueGetter::Array { .. }, ..] // "${[123]event}"
-            | [ValueGetter::Map { .. }, ValueGetter::Array { .. }, ..] => { // "${event[123]}"
+            [ValueGetter::Map { key }, tail @ ..] // "${event.timestamp}"
+                if matches!(tail.first(), Some(ValueGetter::Map { .. })) => (key, tail),
+            _ => { // "${event}", "${[123]event}", "${event[123]}"
                 return Ok(Parser::Exp(AccessorExpression { keys: getters }))
             }
-            [ValueGetter::Map { key }, tail @ ..] => (key, tail), // "${event.timestamp}"
         };
 
         for (key, factory) in &self.custom_parser_factories {
@@ -143,6 +145,11 @@ pub enum Parser {
     Interpolator { interpolator: StringInterpolator },
     Val(Value),
     Custom { key: ValueGetter, parser: Box<dyn CustomParser> },
+    /// An expression whose value is transformed by a chain of filters, e.g. `${event.type | upper}`
+    Filtered {
+        parser: Box<Parser>,
+        filters: Vec<ValueFilter>,
+    },
 }
 
 #[derive(Debug)]
@@ -152,16 +159,27 @@ pub struct AccessorExpression {
 
 impl AccessorExpression {
     pub fn parse_value<'o, I: ValueGet>(&'o self, value: &'o I) -> Option<Cow<'o, Value>> {
-        let mut iter = self.keys.iter();
-        let mut tmp = iter.next()?.get(value)?;
-        for key in iter {
-            tmp = key.get(tmp)?;
-        }
-        Some(Cow::Borrowed(tmp))
+        let (head, tail) = self.keys.split_first()?;
+        ValueGetter::get_all(tail, Cow::Borrowed(head.get(value)?))
     }
 }
 
 impl Parser {
+    /// Returns the keys of each expression of a template, e.g. `[[event, type], [_variables, host]]`
+    /// for `${event.type} on ${_variables.host | upper}`.
+    /// The expressions that cannot be parsed are skipped.
+    pub fn accessor_keys(template: &str) -> Vec<Vec<ValueGetter>> {
+        Template::from(template)
+            .matches()
+            .iter()
+            .filter_map(|expression| {
+                let expression = expression.as_str();
+                let (accessor, _) = split_filters(&expression[2..expression.len() - 1]);
+                Parser::parse_keys(accessor.trim()).ok()
+            })
+            .collect()
+    }
+
     fn parse_keys(expression: &str) -> Result<Vec<ValueGetter>, ParserError> {
         RE.find_iter(expression)
             .map(|next_match| {
@@ -175,16 +193,19 @@ impl Parser {
                         PAYLOAD_MAP_KEY_PARSE_TRAILING_DELIMITER,
                     ) {
                         result = key.to_string();
+                    } else {
+                        match result.as_str() {
+                            LENGTH_PSEUDO_KEY => return Ok(ValueGetter::Length),
+                            KEYS_PSEUDO_KEY => return Ok(ValueGetter::Keys),
+                            _ => {}
+                        }
                     };
                     if let Some(key) = get_key_between_delimiters(
                         result.as_str(),
                         PAYLOAD_ARRAY_KEY_START_DELIMITER,
                         PAYLOAD_ARRAY_KEY_END_DELIMITER,
                     ) {
-                        return match key.parse() {
-                            Ok(index) => Ok(ValueGetter::Array { index }),
-                            Err(_) => Err(ParserError::NotANumberError { key: key.to_owned() }),
-                        };
+                        return Parser::parse_array_key(key);
                     }
                     if result.contains(PAYLOAD_MAP_KEY_PARSE_TRAILING_DELIMITER) {
                         return Err(ParserError::InvalidCharacterError {
@@ -198,6 +219,39 @@ impl Parser {
             .collect()
     }
 
+    /// Parses the content of the square brackets, e.g. `0`, `-1`, `*` or `1:3`.
+    fn parse_array_key(key: &str) -> Result<ValueGetter, ParserError> {
+        let parse_number = |number: &str| {
+            number
+                .trim()
+                .parse::<i64>()
+                .map_err(|_| ParserError::NotANumberError { key: key.to_owned() })
+        };
+        let parse_bound = |bound: &str| {
+            if bound.trim().is_empty() {
+                Ok(None)
+            } else {
+                parse_number(bound).map(Some)
+            }
+        };
+
+        if key.trim() == PAYLOAD_ARRAY_WILDCARD {
+            return Ok(ValueGetter::Wildcard);
+        }
+        match key.split(PAYLOAD_ARRAY_SLICE_DELIMITER).collect::<Vec<_>>().as_slice() {
+            [index] => match parse_number(index)? {
+                index if index < 0 => {
+                    Ok(ValueGetter::ArrayFromEnd { offset: index.unsigned_abs() as usize })
+                }
+                index => Ok(ValueGetter::Array { index: index as usize }),
+            },
+            [start, end] => {
+                Ok(ValueGetter::Slice { start: parse_bound(start)?, end: parse_bound(end)? })
+            }
+            _ => Err(ParserError::InvalidSliceError { key: key.to_owned() }),
+        }
+    }
+
     pub fn parse_value<'o, I: ValueGet>(
         &'o self,
         value: &'o I,
@@ -212,7 +266,33 @@ impl Parser {
             Parser::Custom { key, parser } => {
                 key.get(value).and_then(|val| parser.parse_value(val, context))
             }
+            Parser::Filtered { parser, filters } => {
+                apply_filters(filters, parser.parse_value(value, context))
+            }
+        }
+    }
+}
+
+/// Checks that the root of the accessor expressions is valid for the matcher.
+fn validate_matcher_root(parser: Parser) -> Result<Parser, ParserError> {
+    match parser {
+        Parser::Exp(AccessorExpression { keys }) if is_valid_matcher_root(&keys) => {
+            Ok(Parser::Exp(AccessorExpression { keys }))
+        }
+        Parser::Exp(AccessorExpression { mut keys }) => match keys.first_mut() {
+            Some(ValueGetter::Array { index }) => {
+                Err(ParserError::UnknownKeyError { key: format!("{}", index) })
+            }
+            Some(ValueGetter::Map { key }) => {
+                Err(ParserError::UnknownKeyError { key: std::mem::take(key) })
+            }
+            Some(getter) => Err(ParserError::UnknownKeyError { key: getter.to_string() }),
+            None => Err(ParserError::EmptyAccessorError),
+        },
+        Parser::Filtered { parser, filters } => {
+            Ok(Parser::Filtered { parser: Box::new(validate_matcher_root(*parser)?), filters })
         }
+        parser => Ok(parser),
     }
 }
 
@@ -578,6 +658,106 @@ mod test {
         assert_eq!(expected, Parser::parse_keys("hello.world[11].inner[0]").unwrap())
     }
 
+    #[test]
+    fn builder_parser_should_return_array_projections_and_pseudo_keys() {
+        let expected: Vec<ValueGetter> = vec![
+            "items".into(),
+            ValueGetter::Wildcard,
+            "tags".into(),
+            ValueGetter::ArrayFromEnd { offset: 1 },
+            ValueGetter::Slice { start: Some(0), end: Some(3) },
+            ValueGetter::Slice { start: Some(-2), end: None },
+            ValueGetter::Slice { start: None, end: Some(-1) },
+            ValueGetter::Length,
+            ValueGetter::Keys,
+            "length()".into(),
+        ];
+        assert_eq!(
+            expected,
+            Parser::parse_keys(r#"items[*].tags[-1][0:3][-2:][:-1].length().keys()."length()""#)
+                .unwrap()
+        )
+    }
+
+    #[test]
+    fn builder_parser_should_fail_with_invalid_array_keys() {
+        assert!(matches!(
+            Parser::parse_keys("items[1:2:3]"),
+            Err(ParserError::InvalidSliceError { .. })
+        ));
+        assert!(matches!(
+            Parser::parse_keys("items[a:2]"),
+            Err(ParserError::NotANumberError { .. })
+        ));
+        assert!(matches!(
+            Parser::parse_keys("items[-a]"),
+            Err(ParserError::NotANumberError { .. })
+        ));
+        assert!(matches!(
+            Parser::parse_keys("items[**]"),
+            Err(ParserError::NotANumberError { .. })
+        ));
+    }
+
+    #[test]
+    fn parser_expression_should_project_arrays() {
+        // Arrange
+        let builder = ParserBuilder::default();
+        let value = json!({
+            "items": [
+                { "name": "one", "tags": ["a", "b"] },
+                { "name": "two", "tags": [] },
+                { "tags": ["c"] },
+                { "name": "four", "tags": ["d", "e", "f"] }
+            ],
+            "map": { "first": 1, "second": 2 },
+            "text": "àèì"
+        });
+
+        // Act & Assert
+        let parse = |template: &str| {
+            builder.build_parser(template).unwrap().parse_value(&value, "").map(Cow::into_owned)
+        };
+        assert_eq!(Some(json!(["one", "two", "four"])), parse("${items[*].name}"));
+        assert_eq!(
+            Some(json!([["a", "b"], [], ["c"], ["d", "e", "f"]])),
+            parse("${items[*].tags}")
+        );
+        assert_eq!(Some(json!(["b", "c", "f"])), parse("${items[*].tags[-1]}"));
+        assert_eq!(Some(json!("four")), parse("${items[-1].name}"));
+        assert_eq!(None, parse("${items[-5].name}"));
+        assert_eq!(Some(json!(["one", "two"])), parse("${items[0:2].name}"));
+        assert_eq!(Some(json!(["two", "four"])), parse("${items[-3:].name}"));
+        assert_eq!(Some(json!(["one", "two"])), parse("${items[:-2].name}"));
+        assert_eq!(Some(json!([])), parse("${items[3:1]}"));
+        assert_eq!(Some(json!(["f"])), parse("${items[3].tags[2:10]}"));
+        assert_eq!(Some(json!(4)), parse("${items.length()}"));
+        assert_eq!(Some(json!([2, 0, 1, 3])), parse("${items[*].tags.length()}"));
+        assert_eq!(Some(json!(2)), parse("${map.length()}"));
+        assert_eq!(Some(json!(3)), parse("${text.length()}"));
+        assert_eq!(Some(json!(["first", "second"])), parse("${map.keys()}"));
+        assert_eq!(Some(json!("second")), parse("${map.keys()[-1]}"));
+        assert_eq!(None, parse("${map[*]}"));
+        assert_eq!(None, parse("${items.keys()}"));
+        assert_eq!(Some(json!("items: 4")), parse("items: ${items.length()}"));
+    }
+
+    #[test]
+    fn engine_matcher_should_accept_projections_of_the_event() {
+        assert!(matches!(
+            ParserBuilder::engine_matcher("${event.payload.items[*].name}"),
+            Ok(Parser::Exp(..))
+        ));
+        assert!(matches!(
+            ParserBuilder::engine_matcher("${event.payload.items.length()}"),
+            Ok(Parser::Exp(..))
+        ));
+        assert!(matches!(
+            ParserBuilder::engine_matcher("${[*].name}"),
+            Err(ParserError::UnknownKeyError { key }) if key == "[*]"
+        ));
+    }
+
     #[test]
     fn parser_expression_should_work_with_hashmaps() {
         // Arrange
@@ -722,6 +902,68 @@ mod test {
         assert_eq!(&json!(1), result.as_ref());
     }
 
+    #[test]
+    fn parser_expression_should_apply_the_filters() {
+        // Arrange
+        let builder = ParserBuilder::default();
+        let map = json!({
+            "host": "  web01 ",
+            "created_ms": 1550496800000_i64,
+            "a|b": "piped",
+        });
+
+        // Act & Assert
+        let parse = |template: &str| {
+            builder.build_parser(template).unwrap().parse_value(&map, "").map(Cow::into_owned)
+        };
+        assert_eq!(Some(json!("WEB01")), parse("${host | trim | upper}"));
+        assert_eq!(Some(json!("web")), parse("${host|trim|truncate(3)}"));
+        assert_eq!(Some(json!("n/a")), parse(r#"${missing | default("n/a")}"#));
+        assert_eq!(Some(json!("N|A")), parse(r#"${missing | default("n|a") | upper}"#));
+        assert_eq!(Some(json!("PIPED")), parse(r#"${"a|b" | upper}"#));
+        assert_eq!(None, parse("${missing | upper}"));
+        assert_eq!(
+            Some(json!("host WEB01 at 2019-02-18 14:33:20")),
+            parse(r#"host ${host | trim | upper} at ${created_ms | date("%F %T", "Europe/Rome")}"#)
+        );
+    }
+
+    #[test]
+    fn builder_should_fail_with_invalid_filters() {
+        // Arrange
+        let builder = ParserBuilder::default();
+
+        // Act & Assert
+        assert!(matches!(
+            builder.build_parser("${host | capitalize}"),
+            Err(ParserError::InvalidFilterError { .. })
+        ));
+        assert!(matches!(
+            builder.build_parser("The host is ${host | truncate(a)}"),
+            Err(ParserError::InvalidFilterError { .. })
+        ));
+        assert!(matches!(
+            builder.build_parser("${host | }"),
+            Err(ParserError::InvalidFilterError { .. })
+        ));
+    }
+
+    #[test]
+    fn engine_matcher_should_validate_the_root_of_the_filtered_expressions() {
+        assert!(matches!(
+            ParserBuilder::engine_matcher("${event.payload.host | upper}"),
+            Ok(Parser::Filtered { .. })
+        ));
+        assert!(matches!(
+            ParserBuilder::engine_matcher("${payload.host | upper}"),
+            Err(ParserError::UnknownKeyError { .. })
+        ));
+        assert!(matches!(
+            ParserBuilder::engine_matcher("${item.host | upper}"),
+            Ok(Parser::Val(_))
+        ));
+    }
+
     #[derive(Debug)]
     pub struct MyParser {
         pub expression: Vec<ValueGetter>,
@@ -738,4 +980,28 @@ mod test {
         println!("build custom parser with expression: [{:?}]", expression);
         Ok(Box::new(MyParser { expression: expression.to_vec() }))
     }
+
+    #[test]
+    fn accessor_keys_should_return_the_keys_of_each_expression() {
+        // Act
+        let keys = Parser::accessor_keys(
+            "${event.type} on ${_variables.rule.hosts[0] | upper} of ${} and ${event.\"a.b\"}",
+        );
+
+        // Assert
+        assert_eq!(
+            vec![
+                vec![ValueGetter::from("event"), ValueGetter::from("type")],
+                vec![
+                    ValueGetter::from("_variables"),
+                    ValueGetter::from("rule"),
+                    ValueGetter::from("hosts"),
+                    ValueGetter::from(0),
+                ],
+                vec![ValueGetter::from("event"), ValueGetter::from("a.b")],
+            ],
+            keys
+        );
+        assert!(Parser::accessor_keys("no expressions").is_empty());
+    }
 }
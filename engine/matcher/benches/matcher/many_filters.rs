@@ -0,0 +1,84 @@
+use criterion::Criterion;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tornado_common_api::Event;
+use tornado_engine_matcher::config::nodes::Filter;
+use tornado_engine_matcher::config::rule::*;
+use tornado_engine_matcher::config::{Defaultable, MatcherConfig};
+use tornado_engine_matcher::matcher::Matcher;
+
+const FILTERS_COUNT: usize = 1000;
+
+// Use case with a thousand sibling filters, each one passing the events of a single type to its
+// ruleset, and an event matching only one of them.
+// The filters with an 'equals' condition are indexed by the event type, so only the matching one
+// is evaluated; the same filters wrapped in an 'AND' operator are evaluated one by one.
+pub fn bench(c: &mut Criterion) {
+    let event = json!(Event::new(format!("type_{}", FILTERS_COUNT / 2)));
+
+    let indexed_matcher = Matcher::build(&new_config(true)).unwrap();
+    let event_clone = event.clone();
+    c.bench_function("Many filters - indexed", move |b| {
+        b.iter(|| execute_test(&indexed_matcher, event_clone.clone()))
+    });
+
+    let matcher = Matcher::build(&new_config(false)).unwrap();
+    c.bench_function("Many filters - evaluated", move |b| {
+        b.iter(|| execute_test(&matcher, event.clone()))
+    });
+}
+
+fn execute_test(matcher: &Matcher, event: Value) {
+    matcher.process(event, false);
+}
+
+fn new_config(indexed: bool) -> MatcherConfig {
+    let nodes = (0..FILTERS_COUNT)
+        .map(|index| {
+            let operator = Operator::Equals {
+                first: Value::String("${event.type}".to_owned()),
+                second: Value::String(format!("type_{}", index)),
+            };
+            let operator =
+                if indexed { operator } else { Operator::And { operators: vec![operator] } };
+            MatcherConfig::Filter {
+                name: format!("filter_{}", index),
+                filter: Filter {
+                    description: "".to_owned(),
+                    active: true,
+                    filter: Defaultable::Value(operator),
+                    schedule: None,
+                },
+                nodes: vec![MatcherConfig::Ruleset {
+                    name: "ruleset".to_owned(),
+                    rules: vec![new_rule("rule_name")],
+                }],
+            }
+        })
+        .collect();
+
+    MatcherConfig::Filter {
+        name: "root".to_owned(),
+        filter: Filter {
+            description: "".to_owned(),
+            active: true,
+            filter: Defaultable::Default {},
+            schedule: None,
+        },
+        nodes,
+    }
+}
+
+fn new_rule(name: &str) -> Rule {
+    Rule {
+        name: name.to_owned(),
+        do_continue: true,
+        active: true,
+        actions: vec![],
+        description: "".to_owned(),
+        constraint: Constraint { where_operator: None, with: HashMap::new() },
+        threshold: None,
+        suppression: None,
+        schedule: None,
+    }
+}
@@ -1,3 +1,4 @@
 pub mod full_match;
+pub mod many_filters;
 pub mod no_match;
 pub mod one_simple_rule;
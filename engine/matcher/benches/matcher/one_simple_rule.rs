@@ -69,5 +69,8 @@ fn new_rule(name: &str, operator: Operator) -> Rule {
         actions: vec![],
         description: "".to_owned(),
         constraint,
+        threshold: None,
+        suppression: None,
+        schedule: None,
     }
 }
@@ -8,6 +8,7 @@ use criterion::*;
 criterion_group!(
     benches,
     full_match::bench,
+    many_filters::bench,
     no_match::bench,
     one_simple_rule::bench,
     spike::start_with::bench,
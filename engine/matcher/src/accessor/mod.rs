@@ -59,6 +59,14 @@ impl Accessor {
         !matches!(&self.parser, Parser::Val(_))
     }
 
+    /// Returns the value of this Accessor if it is a constant
+    pub fn constant_value(&self) -> Option<&Value> {
+        match &self.parser {
+            Parser::Val(value) => Some(value),
+            _ => None,
+        }
+    }
+
     pub fn try_as_expression(self) -> Result<AccessorExpression, MatcherError> {
         match self.parser {
             Parser::Exp(exp) => Ok(exp),
@@ -0,0 +1,622 @@
+//! The diff module compares two processing trees, e.g. a draft and the deployed configuration.
+//!
+//! Nodes and Rules are identified by their path. A node or a Rule that is removed from a path
+//! and added, unchanged, to another one is reported as moved; a Rule whose position changes
+//! with respect to the other Rules of its Ruleset is reported as moved too.
+
+use crate::config::rule::Rule;
+use crate::config::MatcherConfig;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashSet};
+
+/// The key of the JSON representation of the Operators that contains their type
+const TYPE_KEY: &str = "type";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigChangeTarget {
+    Node,
+    Rule,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigChangeKind {
+    Added,
+    Removed,
+    /// The node or the Rule was moved from another path. For a Rule reordered in its Ruleset,
+    /// `from` is equal to the path of the change.
+    Moved {
+        from: Vec<String>,
+    },
+    /// The definition of the node or of the Rule changed; the changes of its children are
+    /// reported separately.
+    Modified {
+        fields: Vec<FieldChange>,
+    },
+}
+
+/// A change of a node or of a Rule between two processing trees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChange {
+    /// The names of the nodes from the root to the changed node or Rule
+    pub path: Vec<String>,
+    pub target: ConfigChangeTarget,
+    pub kind: ConfigChangeKind,
+}
+
+/// A changed field of the JSON representation of a node or of a Rule.
+/// The `field` contains the keys from the node, or the Rule, to the changed value; for example,
+/// `["constraint", "WITH", "sender", "regex", "match"]` is the regex of the extracted variable `sender`.
+/// A value is `None` if the field is not present in that version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: Vec<String>,
+    pub old_value: Option<Value>,
+    pub new_value: Option<Value>,
+}
+
+/// Returns the changes required to turn the `old` processing tree into the `new` one
+pub fn diff_configs(old: &MatcherConfig, new: &MatcherConfig) -> Vec<ConfigChange> {
+    let mut diff = ConfigDiff::default();
+    diff.diff_nodes(&[], old, new);
+    diff.into_changes()
+}
+
+#[derive(Default)]
+struct ConfigDiff {
+    changes: Vec<ConfigChange>,
+    /// The JSON representation of the added and removed nodes and Rules, by index of the change
+    contents: Vec<Option<Value>>,
+}
+
+impl ConfigDiff {
+    fn diff_nodes(&mut self, parent_path: &[String], old: &MatcherConfig, new: &MatcherConfig) {
+        let path = child_path(parent_path, new.get_name());
+        let (old_type, old_definition) = node_definition(old);
+        let (new_type, new_definition) = node_definition(new);
+        if old.get_name() != new.get_name() || old_type != new_type {
+            self.push_with_content(
+                parent_path,
+                ConfigChangeTarget::Node,
+                ConfigChangeKind::Removed,
+                old.get_name(),
+                old,
+            );
+            self.push_with_content(
+                parent_path,
+                ConfigChangeTarget::Node,
+                ConfigChangeKind::Added,
+                new.get_name(),
+                new,
+            );
+            return;
+        }
+
+        let fields = diff_values(&old_definition, &new_definition);
+        if !fields.is_empty() {
+            self.push(
+                path.clone(),
+                ConfigChangeTarget::Node,
+                ConfigChangeKind::Modified { fields },
+            );
+        }
+
+        match (old, new) {
+            (
+                MatcherConfig::Filter { nodes: old_nodes, .. }
+                | MatcherConfig::Iterator { nodes: old_nodes, .. }
+                | MatcherConfig::Enricher { nodes: old_nodes, .. },
+                MatcherConfig::Filter { nodes: new_nodes, .. }
+                | MatcherConfig::Iterator { nodes: new_nodes, .. }
+                | MatcherConfig::Enricher { nodes: new_nodes, .. },
+            ) => self.diff_children(&path, old_nodes, new_nodes),
+            (
+                MatcherConfig::Ruleset { rules: old_rules, .. },
+                MatcherConfig::Ruleset { rules: new_rules, .. },
+            ) => self.diff_rules(&path, old_rules, new_rules),
+            _ => {}
+        }
+    }
+
+    fn diff_children(
+        &mut self,
+        path: &[String],
+        old_nodes: &[MatcherConfig],
+        new_nodes: &[MatcherConfig],
+    ) {
+        for old_node in old_nodes {
+            if !new_nodes.iter().any(|new_node| new_node.get_name() == old_node.get_name()) {
+                self.push_with_content(
+                    path,
+                    ConfigChangeTarget::Node,
+                    ConfigChangeKind::Removed,
+                    old_node.get_name(),
+                    old_node,
+                );
+            }
+        }
+        for new_node in new_nodes {
+            match old_nodes.iter().find(|old_node| old_node.get_name() == new_node.get_name()) {
+                Some(old_node) => self.diff_nodes(path, old_node, new_node),
+                None => self.push_with_content(
+                    path,
+                    ConfigChangeTarget::Node,
+                    ConfigChangeKind::Added,
+                    new_node.get_name(),
+                    new_node,
+                ),
+            }
+        }
+    }
+
+    fn diff_rules(&mut self, path: &[String], old_rules: &[Rule], new_rules: &[Rule]) {
+        let old_names: Vec<&str> = old_rules.iter().map(|rule| rule.name.as_str()).collect();
+        let new_names: Vec<&str> = new_rules.iter().map(|rule| rule.name.as_str()).collect();
+        let common_old_names: Vec<&str> =
+            old_names.iter().copied().filter(|name| new_names.contains(name)).collect();
+        let common_new_names: Vec<&str> =
+            new_names.iter().copied().filter(|name| old_names.contains(name)).collect();
+        // The Rules that keep their relative order are the longest common subsequence of the
+        // Rules in both versions; the other ones are moved.
+        let unmoved_names = longest_common_subsequence(&common_old_names, &common_new_names);
+
+        for old_rule in old_rules {
+            if !new_names.contains(&old_rule.name.as_str()) {
+                self.push_with_content(
+                    path,
+                    ConfigChangeTarget::Rule,
+                    ConfigChangeKind::Removed,
+                    &old_rule.name,
+                    old_rule,
+                );
+            }
+        }
+        for new_rule in new_rules {
+            let rule_path = child_path(path, &new_rule.name);
+            match old_rules.iter().find(|old_rule| old_rule.name == new_rule.name) {
+                Some(old_rule) => {
+                    if !unmoved_names.contains(new_rule.name.as_str()) {
+                        let from = rule_path.clone();
+                        self.push(
+                            rule_path.clone(),
+                            ConfigChangeTarget::Rule,
+                            ConfigChangeKind::Moved { from },
+                        );
+                    }
+                    let fields =
+                        diff_values(&rule_definition(old_rule), &rule_definition(new_rule));
+                    if !fields.is_empty() {
+                        self.push(
+                            rule_path,
+                            ConfigChangeTarget::Rule,
+                            ConfigChangeKind::Modified { fields },
+                        );
+                    }
+                }
+                None => self.push_with_content(
+                    path,
+                    ConfigChangeTarget::Rule,
+                    ConfigChangeKind::Added,
+                    &new_rule.name,
+                    new_rule,
+                ),
+            }
+        }
+    }
+
+    fn push(&mut self, path: Vec<String>, target: ConfigChangeTarget, kind: ConfigChangeKind) {
+        self.changes.push(ConfigChange { path, target, kind });
+        self.contents.push(None);
+    }
+
+    /// Pushes an added or removed node or Rule with its JSON representation
+    fn push_with_content<T: Serialize>(
+        &mut self,
+        parent_path: &[String],
+        target: ConfigChangeTarget,
+        kind: ConfigChangeKind,
+        name: &str,
+        content: &T,
+    ) {
+        self.changes.push(ConfigChange { path: child_path(parent_path, name), target, kind });
+        self.contents.push(Some(to_value(content)));
+    }
+
+    /// Returns the changes, after replacing each unchanged node or Rule that is removed from
+    /// a path and added to another one with a single moved change.
+    fn into_changes(mut self) -> Vec<ConfigChange> {
+        let mut moved_indexes = HashSet::new();
+        for added_index in 0..self.changes.len() {
+            if self.changes[added_index].kind != ConfigChangeKind::Added {
+                continue;
+            }
+            let removed_index = (0..self.changes.len()).find(|&index| {
+                let (added, removed) = (&self.changes[added_index], &self.changes[index]);
+                removed.kind == ConfigChangeKind::Removed
+                    && !moved_indexes.contains(&index)
+                    && removed.target == added.target
+                    && removed.path.last() == added.path.last()
+                    && self.contents[index] == self.contents[added_index]
+            });
+            if let Some(removed_index) = removed_index {
+                moved_indexes.insert(removed_index);
+                let from = self.changes[removed_index].path.clone();
+                self.changes[added_index].kind = ConfigChangeKind::Moved { from };
+            }
+        }
+        self.changes
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| !moved_indexes.contains(index))
+            .map(|(_, change)| change)
+            .collect()
+    }
+}
+
+fn child_path(parent_path: &[String], name: &str) -> Vec<String> {
+    let mut path = parent_path.to_vec();
+    path.push(name.to_owned());
+    path
+}
+
+fn to_value<T: Serialize>(content: &T) -> Value {
+    serde_json::to_value(content).unwrap_or(Value::Null)
+}
+
+/// Returns the type of a node and the JSON representation of its definition,
+/// without the name and the children.
+fn node_definition(node: &MatcherConfig) -> (&'static str, Value) {
+    match node {
+        MatcherConfig::Filter { filter, .. } => ("filter", to_value(filter)),
+        MatcherConfig::Iterator { iterator, .. } => ("iterator", to_value(iterator)),
+        MatcherConfig::Ruleset { .. } => ("ruleset", Value::Null),
+        MatcherConfig::Correlation { correlation, .. } => ("correlation", to_value(correlation)),
+        MatcherConfig::Heartbeat { heartbeat, .. } => ("heartbeat", to_value(heartbeat)),
+        MatcherConfig::Enricher { enricher, .. } => ("enricher", to_value(enricher)),
+    }
+}
+
+/// Returns the JSON representation of a Rule without its name
+fn rule_definition(rule: &Rule) -> Value {
+    let mut definition = to_value(rule);
+    if let Value::Object(map) = &mut definition {
+        map.remove("name");
+    }
+    definition
+}
+
+/// Returns the changed fields between two JSON values.
+/// Objects are compared key by key and arrays of the same length item by item;
+/// an Operator whose type changes is reported as a single changed value.
+fn diff_values(old: &Value, new: &Value) -> Vec<FieldChange> {
+    let mut changes = vec![];
+    diff_values_in_field(&mut vec![], old, new, &mut changes);
+    changes
+}
+
+fn diff_values_in_field(
+    field: &mut Vec<String>,
+    old: &Value,
+    new: &Value,
+    changes: &mut Vec<FieldChange>,
+) {
+    if old == new {
+        return;
+    }
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map))
+            if old_map.get(TYPE_KEY) == new_map.get(TYPE_KEY) =>
+        {
+            let keys: BTreeSet<&String> = old_map.keys().chain(new_map.keys()).collect();
+            for key in keys {
+                field.push(key.to_owned());
+                match (old_map.get(key), new_map.get(key)) {
+                    (Some(old_value), Some(new_value)) => {
+                        diff_values_in_field(field, old_value, new_value, changes)
+                    }
+                    (old_value, new_value) => changes.push(FieldChange {
+                        field: field.clone(),
+                        old_value: old_value.cloned(),
+                        new_value: new_value.cloned(),
+                    }),
+                }
+                field.pop();
+            }
+        }
+        (Value::Array(old_items), Value::Array(new_items))
+            if old_items.len() == new_items.len() =>
+        {
+            for (index, (old_item, new_item)) in old_items.iter().zip(new_items).enumerate() {
+                field.push(index.to_string());
+                diff_values_in_field(field, old_item, new_item, changes);
+                field.pop();
+            }
+        }
+        _ => changes.push(FieldChange {
+            field: field.clone(),
+            old_value: Some(old.clone()),
+            new_value: Some(new.clone()),
+        }),
+    }
+}
+
+/// Returns the items of the longest common subsequence of two lists without duplicates
+fn longest_common_subsequence<'a>(first: &[&'a str], second: &[&'a str]) -> HashSet<&'a str> {
+    // common[i][j] is the length of the longest common subsequence of first[i..] and second[j..]
+    let mut common = vec![vec![0_usize; second.len() + 1]; first.len() + 1];
+    for i in (0..first.len()).rev() {
+        for j in (0..second.len()).rev() {
+            common[i][j] = if first[i] == second[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut items = HashSet::new();
+    let (mut i, mut j) = (0, 0);
+    while i < first.len() && j < second.len() {
+        if first[i] == second[j] {
+            items.insert(first[i]);
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    items
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::nodes::Filter;
+    use serde_json::json;
+
+    fn rule(name: &str, rule: Value) -> Rule {
+        let mut rule = rule;
+        rule["name"] = json!(name);
+        rule["description"] = json!("");
+        rule["continue"] = json!(true);
+        rule["active"] = json!(true);
+        if rule.get("actions").is_none() {
+            rule["actions"] = json!([]);
+        }
+        if rule.get("constraint").is_none() {
+            rule["constraint"] = json!({});
+        }
+        if rule["constraint"].get("WITH").is_none() {
+            rule["constraint"]["WITH"] = json!({});
+        }
+        serde_json::from_value(rule).unwrap()
+    }
+
+    fn filter(name: &str, nodes: Vec<MatcherConfig>) -> MatcherConfig {
+        MatcherConfig::Filter {
+            name: name.to_owned(),
+            filter: Filter { active: true, ..Default::default() },
+            nodes,
+        }
+    }
+
+    fn ruleset(name: &str, rules: Vec<Rule>) -> MatcherConfig {
+        MatcherConfig::Ruleset { name: name.to_owned(), rules }
+    }
+
+    fn summary(changes: &[ConfigChange]) -> Vec<(String, ConfigChangeTarget, String)> {
+        changes
+            .iter()
+            .map(|change| {
+                let kind = match &change.kind {
+                    ConfigChangeKind::Added => "added".to_owned(),
+                    ConfigChangeKind::Removed => "removed".to_owned(),
+                    ConfigChangeKind::Moved { from } => format!("moved from {}", from.join(".")),
+                    ConfigChangeKind::Modified { .. } => "modified".to_owned(),
+                };
+                (change.path.join("."), change.target, kind)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_return_no_changes_for_equal_configs() {
+        // Arrange
+        let config = filter("root", vec![ruleset("emails", vec![rule("archive", json!({}))])]);
+
+        // Act
+        let changes = diff_configs(&config, &config.clone());
+
+        // Assert
+        assert!(changes.is_empty(), "{:?}", changes);
+    }
+
+    #[test]
+    fn should_detect_added_and_removed_nodes_and_rules() {
+        // Arrange
+        let old = filter(
+            "root",
+            vec![
+                ruleset("emails", vec![rule("archive", json!({})), rule("forward", json!({}))]),
+                ruleset("traps", vec![rule("log", json!({}))]),
+            ],
+        );
+        let new = filter(
+            "root",
+            vec![
+                ruleset("emails", vec![rule("archive", json!({})), rule("notify", json!({}))]),
+                filter("syslog", vec![ruleset("errors", vec![])]),
+            ],
+        );
+
+        // Act
+        let changes = diff_configs(&old, &new);
+
+        // Assert
+        assert_eq!(
+            vec![
+                ("root.traps".to_owned(), ConfigChangeTarget::Node, "removed".to_owned()),
+                ("root.emails.forward".to_owned(), ConfigChangeTarget::Rule, "removed".to_owned()),
+                ("root.emails.notify".to_owned(), ConfigChangeTarget::Rule, "added".to_owned()),
+                ("root.syslog".to_owned(), ConfigChangeTarget::Node, "added".to_owned()),
+            ],
+            summary(&changes)
+        );
+    }
+
+    #[test]
+    fn should_detect_the_changed_fields_of_operators_extractors_and_actions() {
+        // Arrange
+        let old_rule = rule(
+            "archive",
+            json!({
+                "constraint": {
+                    "WHERE": { "type": "AND", "operators": [
+                        { "type": "equals", "first": "${event.type}", "second": "email" },
+                        { "type": "equals", "first": "${event.payload.level}", "second": "error" }
+                    ]},
+                    "WITH": {
+                        "sender": { "from": "${event.payload.from}", "regex": { "match": "[a-z]+", "group_match_idx": 0 } }
+                    }
+                },
+                "actions": [{ "id": "archive", "payload": { "event": "${event}", "archive_type": "one" } }]
+            }),
+        );
+        let new_rule = rule(
+            "archive",
+            json!({
+                "constraint": {
+                    "WHERE": { "type": "AND", "operators": [
+                        { "type": "equals", "first": "${event.type}", "second": "email" },
+                        { "type": "contains", "first": "${event.payload.level}", "second": "err" }
+                    ]},
+                    "WITH": {
+                        "sender": { "from": "${event.payload.from}", "regex": { "match": "[a-z0-9]+", "group_match_idx": 0 } }
+                    }
+                },
+                "actions": [{ "id": "archive", "payload": { "event": "${event}" } }]
+            }),
+        );
+        let old = filter("root", vec![ruleset("emails", vec![old_rule])]);
+        let new = filter("root", vec![ruleset("emails", vec![new_rule])]);
+
+        // Act
+        let changes = diff_configs(&old, &new);
+
+        // Assert
+        assert_eq!(1, changes.len());
+        assert_eq!(vec!["root", "emails", "archive"], changes[0].path);
+        assert_eq!(ConfigChangeTarget::Rule, changes[0].target);
+        let fields = match &changes[0].kind {
+            ConfigChangeKind::Modified { fields } => fields,
+            kind => panic!("Unexpected change {:?}", kind),
+        };
+        let fields: Vec<(String, Option<Value>, Option<Value>)> = fields
+            .iter()
+            .map(|field| (field.field.join("."), field.old_value.clone(), field.new_value.clone()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("actions.0.payload.archive_type".to_owned(), Some(json!("one")), None),
+                (
+                    "constraint.WHERE.operators.1".to_owned(),
+                    Some(
+                        json!({ "type": "equals", "first": "${event.payload.level}", "second": "error" })
+                    ),
+                    Some(
+                        json!({ "type": "contains", "first": "${event.payload.level}", "second": "err" })
+                    ),
+                ),
+                (
+                    "constraint.WITH.sender.regex.match".to_owned(),
+                    Some(json!("[a-z]+")),
+                    Some(json!("[a-z0-9]+")),
+                ),
+            ],
+            fields
+        );
+    }
+
+    #[test]
+    fn should_detect_moved_nodes_and_rules() {
+        // Arrange
+        let old = filter(
+            "root",
+            vec![
+                ruleset(
+                    "emails",
+                    vec![
+                        rule("first", json!({})),
+                        rule("second", json!({})),
+                        rule("third", json!({})),
+                        rule("archive", json!({ "description": "archive" })),
+                    ],
+                ),
+                ruleset("traps", vec![]),
+                filter("syslog", vec![ruleset("errors", vec![rule("log", json!({}))])]),
+            ],
+        );
+        let new = filter(
+            "root",
+            vec![
+                ruleset(
+                    "emails",
+                    vec![
+                        rule("second", json!({})),
+                        rule("third", json!({})),
+                        rule("first", json!({})),
+                    ],
+                ),
+                ruleset("traps", vec![rule("archive", json!({ "description": "archive" }))]),
+                filter("syslog", vec![]),
+                ruleset("errors", vec![rule("log", json!({}))]),
+            ],
+        );
+
+        // Act
+        let changes = diff_configs(&old, &new);
+
+        // Assert
+        assert_eq!(
+            vec![
+                (
+                    "root.emails.first".to_owned(),
+                    ConfigChangeTarget::Rule,
+                    "moved from root.emails.first".to_owned()
+                ),
+                (
+                    "root.traps.archive".to_owned(),
+                    ConfigChangeTarget::Rule,
+                    "moved from root.emails.archive".to_owned()
+                ),
+                (
+                    "root.errors".to_owned(),
+                    ConfigChangeTarget::Node,
+                    "moved from root.syslog.errors".to_owned()
+                ),
+            ],
+            summary(&changes)
+        );
+    }
+
+    #[test]
+    fn should_report_a_node_whose_type_changes_as_removed_and_added() {
+        // Arrange
+        let old = filter("root", vec![ruleset("emails", vec![])]);
+        let new = filter("root", vec![filter("emails", vec![])]);
+
+        // Act
+        let changes = diff_configs(&old, &new);
+
+        // Assert
+        assert_eq!(
+            vec![
+                ("root.emails".to_owned(), ConfigChangeTarget::Node, "removed".to_owned()),
+                ("root.emails".to_owned(), ConfigChangeTarget::Node, "added".to_owned()),
+            ],
+            summary(&changes)
+        );
+    }
+}
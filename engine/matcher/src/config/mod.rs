@@ -1,12 +1,15 @@
-use crate::config::nodes::{Filter, MatcherIterator};
+use crate::config::nodes::{Correlation, Enricher, Filter, Heartbeat, MatcherIterator};
 use crate::config::rule::Rule;
 use crate::config::v2::{ConfigNodeDir, ConfigType};
 use crate::error::MatcherError;
 use crate::matcher;
 use crate::matcher::Matcher;
+use crate::resources::MatcherResources;
+use crate::suite::RuleTestSuite;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 
+pub mod diff;
 pub mod nodes;
 pub mod operation;
 pub mod rule;
@@ -35,12 +38,40 @@ impl ConfigNodeDir for MatcherConfigDraftData {
     }
 }
 
+/// A copy of the configuration saved when it was deployed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MatcherConfigSnapshot {
+    pub data: MatcherConfigSnapshotData,
+    pub config: MatcherConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MatcherConfigSnapshotData {
+    pub snapshot_id: String,
+    pub created_ts_ms: i64,
+    /// The user that deployed the configuration
+    pub user: String,
+    /// The comment of the deploy
+    pub comment: String,
+}
+
+impl ConfigNodeDir for MatcherConfigSnapshotData {
+    fn config_type() -> ConfigType {
+        ConfigType::Snapshot
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub enum MatcherConfig {
     Filter { name: String, filter: Filter, nodes: Vec<MatcherConfig> },
     Iterator { name: String, iterator: MatcherIterator, nodes: Vec<MatcherConfig> },
     Ruleset { name: String, rules: Vec<Rule> },
+    Correlation { name: String, correlation: Correlation },
+    Heartbeat { name: String, heartbeat: Heartbeat },
+    Enricher { name: String, enricher: Enricher, nodes: Vec<MatcherConfig> },
 }
 
 impl MatcherConfig {
@@ -48,7 +79,10 @@ impl MatcherConfig {
         match self {
             MatcherConfig::Filter { name, .. }
             | MatcherConfig::Iterator { name, .. }
-            | MatcherConfig::Ruleset { name, .. } => name,
+            | MatcherConfig::Ruleset { name, .. }
+            | MatcherConfig::Correlation { name, .. }
+            | MatcherConfig::Heartbeat { name, .. }
+            | MatcherConfig::Enricher { name, .. } => name,
         }
     }
 
@@ -56,25 +90,36 @@ impl MatcherConfig {
         match self {
             MatcherConfig::Filter { name, .. }
             | MatcherConfig::Iterator { name, .. }
-            | MatcherConfig::Ruleset { name, .. } => name,
+            | MatcherConfig::Ruleset { name, .. }
+            | MatcherConfig::Correlation { name, .. }
+            | MatcherConfig::Heartbeat { name, .. }
+            | MatcherConfig::Enricher { name, .. } => name,
         }
     }
 
     fn get_child_node_by_name(&self, child_name: &str) -> Option<&MatcherConfig> {
         match self {
-            MatcherConfig::Filter { nodes, .. } | MatcherConfig::Iterator { nodes, .. } => {
+            MatcherConfig::Filter { nodes, .. }
+            | MatcherConfig::Iterator { nodes, .. }
+            | MatcherConfig::Enricher { nodes, .. } => {
                 nodes.iter().find(|child| child.get_name() == child_name)
             }
-            MatcherConfig::Ruleset { .. } => None,
+            MatcherConfig::Ruleset { .. }
+            | MatcherConfig::Correlation { .. }
+            | MatcherConfig::Heartbeat { .. } => None,
         }
     }
 
     fn get_mut_child_node_by_name(&mut self, child_name: &str) -> Option<&mut MatcherConfig> {
         match self {
-            MatcherConfig::Filter { nodes, .. } | MatcherConfig::Iterator { nodes, .. } => {
+            MatcherConfig::Filter { nodes, .. }
+            | MatcherConfig::Iterator { nodes, .. }
+            | MatcherConfig::Enricher { nodes, .. } => {
                 nodes.iter_mut().find(|child| child.get_name() == child_name)
             }
-            MatcherConfig::Ruleset { .. } => None,
+            MatcherConfig::Ruleset { .. }
+            | MatcherConfig::Correlation { .. }
+            | MatcherConfig::Heartbeat { .. } => None,
         }
     }
 
@@ -140,6 +185,15 @@ impl MatcherConfig {
             MatcherConfig::Iterator { .. } => Err(MatcherError::ConfigurationError {
                 message: "Cannot access rules in iterator nodes".to_string(),
             }),
+            MatcherConfig::Correlation { .. } => Err(MatcherError::ConfigurationError {
+                message: "Cannot access rules in correlation nodes".to_string(),
+            }),
+            MatcherConfig::Heartbeat { .. } => Err(MatcherError::ConfigurationError {
+                message: "Cannot access rules in heartbeat nodes".to_string(),
+            }),
+            MatcherConfig::Enricher { .. } => Err(MatcherError::ConfigurationError {
+                message: "Cannot access rules in enricher nodes".to_string(),
+            }),
             MatcherConfig::Ruleset { rules, .. } => Ok(rules),
         }
     }
@@ -152,8 +206,12 @@ impl MatcherConfig {
         }
         match self.get_node_by_path(path) {
             Some(MatcherConfig::Filter { nodes, .. })
-            | Some(MatcherConfig::Iterator { nodes, .. }) => Some(Cow::Borrowed(nodes)),
-            Some(MatcherConfig::Ruleset { .. }) | None => None,
+            | Some(MatcherConfig::Iterator { nodes, .. })
+            | Some(MatcherConfig::Enricher { nodes, .. }) => Some(Cow::Borrowed(nodes)),
+            Some(MatcherConfig::Ruleset { .. })
+            | Some(MatcherConfig::Correlation { .. })
+            | Some(MatcherConfig::Heartbeat { .. })
+            | None => None,
         }
     }
 
@@ -178,31 +236,38 @@ impl MatcherConfig {
 
     pub fn contains_iterator(&self) -> bool {
         match self {
-            MatcherConfig::Filter { nodes, .. } => {
+            MatcherConfig::Filter { nodes, .. } | MatcherConfig::Enricher { nodes, .. } => {
                 nodes.iter().any(MatcherConfig::contains_iterator)
             }
             MatcherConfig::Iterator { .. } => true,
-            MatcherConfig::Ruleset { .. } => false,
+            MatcherConfig::Ruleset { .. }
+            | MatcherConfig::Correlation { .. }
+            | MatcherConfig::Heartbeat { .. } => false,
         }
     }
 
     // Returns the total amount of direct children of a node
     pub fn get_direct_child_nodes_count(&self) -> usize {
         match self {
-            MatcherConfig::Filter { nodes, .. } | MatcherConfig::Iterator { nodes, .. } => {
-                nodes.len()
-            }
-            MatcherConfig::Ruleset { .. } => 0,
+            MatcherConfig::Filter { nodes, .. }
+            | MatcherConfig::Iterator { nodes, .. }
+            | MatcherConfig::Enricher { nodes, .. } => nodes.len(),
+            MatcherConfig::Ruleset { .. }
+            | MatcherConfig::Correlation { .. }
+            | MatcherConfig::Heartbeat { .. } => 0,
         }
     }
 
     // Returns the total amount of rules of the node and its children
     pub fn get_all_rules_count(&self) -> usize {
         match self {
-            MatcherConfig::Filter { nodes, .. } | MatcherConfig::Iterator { nodes, .. } => {
+            MatcherConfig::Filter { nodes, .. }
+            | MatcherConfig::Iterator { nodes, .. }
+            | MatcherConfig::Enricher { nodes, .. } => {
                 nodes.iter().map(MatcherConfig::get_all_rules_count).sum()
             }
             MatcherConfig::Ruleset { rules, .. } => rules.len(),
+            MatcherConfig::Correlation { .. } | MatcherConfig::Heartbeat { .. } => 0,
         }
     }
 
@@ -211,6 +276,7 @@ impl MatcherConfig {
         &mut self,
         path: &[&str],
         node: MatcherConfig,
+        resources: &MatcherResources,
     ) -> Result<(), MatcherError> {
         if path.is_empty() {
             return Err(MatcherError::ConfigurationError {
@@ -228,13 +294,21 @@ impl MatcherConfig {
         }
 
         // Validate input before saving it to the draft.
-        let _ = Matcher::build(&node)?;
+        let _ = Matcher::build_with_resources(&node, resources)?;
 
         match current_node {
             MatcherConfig::Ruleset { .. } => Err(MatcherError::ConfigurationError {
                 message: "A ruleset cannot have children nodes".to_string(),
             }),
-            MatcherConfig::Filter { nodes, .. } | MatcherConfig::Iterator { nodes, .. } => {
+            MatcherConfig::Correlation { .. } => Err(MatcherError::ConfigurationError {
+                message: "A correlation cannot have children nodes".to_string(),
+            }),
+            MatcherConfig::Heartbeat { .. } => Err(MatcherError::ConfigurationError {
+                message: "A heartbeat cannot have children nodes".to_string(),
+            }),
+            MatcherConfig::Filter { nodes, .. }
+            | MatcherConfig::Iterator { nodes, .. }
+            | MatcherConfig::Enricher { nodes, .. } => {
                 nodes.push(node.clone());
                 Ok(())
             }
@@ -246,6 +320,7 @@ impl MatcherConfig {
         &mut self,
         path: &[&str],
         new_node: MatcherConfig,
+        resources: &MatcherResources,
     ) -> Result<(), MatcherError> {
         if path.is_empty() {
             return Err(MatcherError::ConfigurationError {
@@ -262,7 +337,7 @@ impl MatcherConfig {
         }
 
         // Validate input before saving it to the draft.
-        let _ = Matcher::build(&new_node)?;
+        let _ = Matcher::build_with_resources(&new_node, resources)?;
 
         let old_node = self.get_mut_node_by_path_or_err(path)?;
         match (old_node, new_node) {
@@ -286,6 +361,27 @@ impl MatcherConfig {
                 *name = new_name;
                 *iterator = new_iterator;
             }
+            (
+                MatcherConfig::Correlation { name, correlation },
+                MatcherConfig::Correlation { name: new_name, correlation: new_correlation },
+            ) => {
+                *name = new_name;
+                *correlation = new_correlation;
+            }
+            (
+                MatcherConfig::Heartbeat { name, heartbeat },
+                MatcherConfig::Heartbeat { name: new_name, heartbeat: new_heartbeat },
+            ) => {
+                *name = new_name;
+                *heartbeat = new_heartbeat;
+            }
+            (
+                MatcherConfig::Enricher { name, enricher, .. },
+                MatcherConfig::Enricher { name: new_name, enricher: new_enricher, .. },
+            ) => {
+                *name = new_name;
+                *enricher = new_enricher;
+            }
             _ => {
                 return Err(MatcherError::ConfigurationError {
                     message: "Node to edit is not of same type of the new one passed".to_string(),
@@ -336,7 +432,9 @@ impl MatcherConfig {
         let parent_node = self.get_mut_node_by_path_or_err(path_to_parent)?;
 
         match parent_node {
-            MatcherConfig::Filter { nodes, .. } | MatcherConfig::Iterator { nodes, .. } => {
+            MatcherConfig::Filter { nodes, .. }
+            | MatcherConfig::Iterator { nodes, .. }
+            | MatcherConfig::Enricher { nodes, .. } => {
                 let num_nodes_before = nodes.len();
                 nodes.retain(|n| n.get_name() != *node_to_delete);
                 if nodes.len() == num_nodes_before {
@@ -352,13 +450,24 @@ impl MatcherConfig {
             MatcherConfig::Ruleset { .. } => Err(MatcherError::ConfigurationError {
                 message: "Can't delete a node in a ruleset.".to_string(),
             }),
+            MatcherConfig::Correlation { .. } => Err(MatcherError::ConfigurationError {
+                message: "Can't delete a node in a correlation.".to_string(),
+            }),
+            MatcherConfig::Heartbeat { .. } => Err(MatcherError::ConfigurationError {
+                message: "Can't delete a node in a heartbeat.".to_string(),
+            }),
         }
     }
 
     // Create a node at a specific path
-    pub fn create_rule(&mut self, ruleset_path: &[&str], rule: Rule) -> Result<(), MatcherError> {
+    pub fn create_rule(
+        &mut self,
+        ruleset_path: &[&str],
+        rule: Rule,
+        resources: &MatcherResources,
+    ) -> Result<(), MatcherError> {
         // validate rule before saving to the ruleset
-        matcher::validate_rule(&rule)?;
+        matcher::validate_rule(&rule, resources)?;
         let rules = self.get_mut_rules_by_path_or_err(ruleset_path)?;
 
         if rules.iter().any(|Rule { name, .. }| name == &rule.name) {
@@ -379,9 +488,10 @@ impl MatcherConfig {
         ruleset_path: &[&str],
         rule_name: &str,
         new_rule: Rule,
+        resources: &MatcherResources,
     ) -> Result<(), MatcherError> {
         // validate rule before saving to the ruleset
-        matcher::validate_rule(&new_rule)?;
+        matcher::validate_rule(&new_rule, resources)?;
         let rules = self.get_mut_rules_by_path_or_err(ruleset_path)?;
 
         match rules.iter_mut().find(|rule| rule.name == rule_name) {
@@ -496,6 +606,18 @@ impl<T: Serialize + Clone> From<Option<T>> for Defaultable<T> {
 #[async_trait::async_trait(? Send)]
 pub trait MatcherConfigReader: Sync + Send {
     async fn get_config(&self) -> Result<MatcherConfig, MatcherError>;
+
+    /// Returns the test suites of the Rulesets of the configuration.
+    /// By default, a configuration source has no test suites.
+    async fn get_test_suites(&self) -> Result<Vec<RuleTestSuite>, MatcherError> {
+        Ok(vec![])
+    }
+
+    /// Returns the resources used to build the Rules of the configuration.
+    /// By default, the Rules can use only the built-in grok patterns.
+    fn get_resources(&self) -> MatcherResources {
+        MatcherResources::default()
+    }
 }
 
 /// A MatcherConfigEditor permits to edit Tornado Configuration drafts
@@ -518,8 +640,22 @@ pub trait MatcherConfigEditor: MatcherConfigReader + Sync + Send {
         config: &MatcherConfig,
     ) -> Result<(), MatcherError>;
 
-    /// Deploy a draft by id replacing the current tornado configuration
-    async fn deploy_draft(&self, draft_id: &str) -> Result<MatcherConfig, MatcherError>;
+    /// Returns the test suites of the Rulesets of a draft.
+    /// By default, a configuration source has no test suites.
+    async fn get_draft_test_suites(
+        &self,
+        _draft_id: &str,
+    ) -> Result<Vec<RuleTestSuite>, MatcherError> {
+        Ok(vec![])
+    }
+
+    /// Deploy a draft by id replacing the current tornado configuration.
+    /// The comment describes the changes and is saved in the configuration history, if any.
+    async fn deploy_draft(
+        &self,
+        draft_id: &str,
+        comment: &str,
+    ) -> Result<MatcherConfig, MatcherError>;
 
     /// Deletes a draft by id
     async fn delete_draft(&self, draft_id: &str) -> Result<(), MatcherError>;
@@ -529,6 +665,39 @@ pub trait MatcherConfigEditor: MatcherConfigReader + Sync + Send {
 
     /// Deploys a new configuration overriding the current one
     async fn deploy_config(&self, config: &MatcherConfig) -> Result<MatcherConfig, MatcherError>;
+
+    /// Replaces the resources used to validate and build the Rules of the configuration.
+    /// By default, a configuration source does not keep the resources.
+    fn set_resources(&self, _resources: MatcherResources) {}
+
+    /// Returns the snapshots of the deployed configurations, from the most recent one.
+    /// By default, a configuration source has no history.
+    async fn get_snapshots(&self) -> Result<Vec<MatcherConfigSnapshotData>, MatcherError> {
+        Ok(vec![])
+    }
+
+    /// Returns a snapshot by id
+    async fn get_snapshot(&self, snapshot_id: &str) -> Result<MatcherConfigSnapshot, MatcherError> {
+        Err(MatcherError::SnapshotNotFoundError { snapshot_id: snapshot_id.to_owned() })
+    }
+
+    /// Returns the test suites of the Rulesets of a snapshot.
+    /// By default, a configuration source has no test suites.
+    async fn get_snapshot_test_suites(
+        &self,
+        _snapshot_id: &str,
+    ) -> Result<Vec<RuleTestSuite>, MatcherError> {
+        Ok(vec![])
+    }
+
+    /// Deploys the configuration of a snapshot replacing the current tornado configuration
+    async fn rollback_to_snapshot(
+        &self,
+        snapshot_id: &str,
+        _user: String,
+    ) -> Result<MatcherConfig, MatcherError> {
+        Err(MatcherError::SnapshotNotFoundError { snapshot_id: snapshot_id.to_owned() })
+    }
 }
 
 #[cfg(test)]
@@ -618,6 +787,9 @@ mod tests {
                     active: false,
                     constraint: Constraint { where_operator: None, with: Default::default() },
                     actions: vec![],
+                    threshold: None,
+                    suppression: None,
+                    schedule: None,
                 }],
             }],
         };
@@ -742,6 +914,7 @@ mod tests {
                                     description: "Filter at last level".to_string(),
                                     active: false,
                                     filter: Defaultable::Default {},
+                                    schedule: None,
                                 },
                                 nodes: vec![],
                             },
@@ -813,12 +986,21 @@ mod tests {
         };
 
         // Act
-        let result_not_existing =
-            config.create_node_in_path(&["root", "filter3"], new_filter.clone());
-        let result_ruleset = config
-            .create_node_in_path(&["root", "filter2", "filter3", "ruleset1"], new_filter.clone());
-        let result_already_existing_node =
-            config.create_node_in_path(&["root", "filter1"], new_filter);
+        let result_not_existing = config.create_node_in_path(
+            &["root", "filter3"],
+            new_filter.clone(),
+            &MatcherResources::default(),
+        );
+        let result_ruleset = config.create_node_in_path(
+            &["root", "filter2", "filter3", "ruleset1"],
+            new_filter.clone(),
+            &MatcherResources::default(),
+        );
+        let result_already_existing_node = config.create_node_in_path(
+            &["root", "filter1"],
+            new_filter,
+            &MatcherResources::default(),
+        );
 
         // Assert
         assert!(result_not_existing.is_err());
@@ -902,7 +1084,11 @@ mod tests {
         };
 
         // Act
-        let result = config.create_node_in_path(&["root", "filter2", "filter3"], new_filter);
+        let result = config.create_node_in_path(
+            &["root", "filter2", "filter3"],
+            new_filter,
+            &MatcherResources::default(),
+        );
 
         // Assert
         assert!(result.is_ok());
@@ -948,10 +1134,16 @@ mod tests {
             MatcherConfig::Ruleset { name: "edited_ruleset".to_string(), rules: vec![] };
 
         // Act
-        let result_not_existing =
-            config.edit_node_in_path(&["root", "filter3", "new_filter"], new_filter.clone());
-        let result_node_different_type =
-            config.edit_node_in_path(&["root", "filter2", "filter3"], new_ruleset);
+        let result_not_existing = config.edit_node_in_path(
+            &["root", "filter3", "new_filter"],
+            new_filter.clone(),
+            &MatcherResources::default(),
+        );
+        let result_node_different_type = config.edit_node_in_path(
+            &["root", "filter2", "filter3"],
+            new_ruleset,
+            &MatcherResources::default(),
+        );
 
         // Assert
         assert!(result_not_existing.is_err());
@@ -1052,10 +1244,16 @@ mod tests {
             MatcherConfig::Ruleset { name: "edited_ruleset".to_string(), rules: vec![] };
 
         // Act
-        let result_ruleset = config_ruleset
-            .edit_node_in_path(&["root", "filter2", "filter3", "ruleset1"], edited_ruleset.clone());
-        let result_filter =
-            config_filter.edit_node_in_path(&["root", "filter2", "filter3"], edited_filter);
+        let result_ruleset = config_ruleset.edit_node_in_path(
+            &["root", "filter2", "filter3", "ruleset1"],
+            edited_ruleset.clone(),
+            &MatcherResources::default(),
+        );
+        let result_filter = config_filter.edit_node_in_path(
+            &["root", "filter2", "filter3"],
+            edited_filter,
+            &MatcherResources::default(),
+        );
 
         // Assert
         assert!(result_ruleset.is_ok());
@@ -1153,6 +1351,7 @@ mod tests {
                     description: "".to_string(),
                     active: false,
                     filter: Defaultable::Default {},
+                    schedule: None,
                 },
                 nodes: vec![],
             }],
@@ -1182,10 +1381,14 @@ mod tests {
             active: true,
             constraint: Constraint { where_operator: None, with: Default::default() },
             actions: vec![],
+            threshold: None,
+            suppression: None,
+            schedule: None,
         };
 
         // Act
-        let result = config.create_rule(&["root", "ruleset2"], new_rule);
+        let result =
+            config.create_rule(&["root", "ruleset2"], new_rule, &MatcherResources::default());
 
         // Assert
         assert!(result.is_err());
@@ -1217,10 +1420,14 @@ mod tests {
             active: true,
             constraint: Constraint { where_operator: None, with: Default::default() },
             actions: vec![],
+            threshold: None,
+            suppression: None,
+            schedule: None,
         };
 
         // Act
-        let result = config.create_rule(&["root", "filter1"], new_rule);
+        let result =
+            config.create_rule(&["root", "filter1"], new_rule, &MatcherResources::default());
 
         // Assert
         assert!(result.is_err());
@@ -1251,7 +1458,8 @@ mod tests {
         };
 
         // Act
-        let result = config.create_rule(&["root", "ruleset1"], new_rule);
+        let result =
+            config.create_rule(&["root", "ruleset1"], new_rule, &MatcherResources::default());
 
         // Assert
         assert!(result.is_err());
@@ -1290,7 +1498,8 @@ mod tests {
         };
 
         // Act
-        let result = config.create_rule(&["root", "ruleset1"], new_rule);
+        let result =
+            config.create_rule(&["root", "ruleset1"], new_rule, &MatcherResources::default());
 
         // Assert
         assert!(result.is_ok());
@@ -1312,6 +1521,9 @@ mod tests {
                     active: true,
                     constraint: Constraint { where_operator: None, with: Default::default() },
                     actions: vec![],
+                    threshold: None,
+                    suppression: None,
+                    schedule: None,
                 }],
             }],
         };
@@ -1338,6 +1550,7 @@ mod tests {
                 description: "My Second Rule Description".to_string(),
                 ..Default::default()
             },
+            &MatcherResources::default(),
         );
 
         // Assert
@@ -1369,6 +1582,7 @@ mod tests {
                 description: "My Second Rule Description".to_string(),
                 ..Default::default()
             },
+            &MatcherResources::default(),
         );
 
         // Assert
@@ -1395,6 +1609,7 @@ mod tests {
                 description: "My Second Rule Description".to_string(),
                 ..Default::default()
             },
+            &MatcherResources::default(),
         );
 
         // Assert
@@ -1418,6 +1633,9 @@ mod tests {
                         active: true,
                         constraint: Constraint { where_operator: None, with: Default::default() },
                         actions: vec![],
+                        threshold: None,
+                        suppression: None,
+                        schedule: None,
                     },
                     Rule {
                         name: "my-rule2".to_string(),
@@ -1512,6 +1730,9 @@ mod tests {
             active: true,
             constraint: Constraint { where_operator: None, with: Default::default() },
             actions: vec![],
+            threshold: None,
+            suppression: None,
+            schedule: None,
         };
 
         MatcherConfig::Ruleset {
@@ -1615,8 +1836,12 @@ mod tests {
 
     #[test]
     fn should_refuse_missformated_names() {
-        let filter =
-            Filter { description: "".to_string(), active: false, filter: Defaultable::Default {} };
+        let filter = Filter {
+            description: "".to_string(),
+            active: false,
+            filter: Defaultable::Default {},
+            schedule: None,
+        };
 
         let old_config = MatcherConfig::Filter {
             name: "root".to_string(),
@@ -1632,6 +1857,7 @@ mod tests {
                 filter: filter.clone(),
                 nodes: vec![],
             },
+            &MatcherResources::default(),
         );
         assert!(result.is_err());
         assert_eq!(old_config, config);
@@ -1639,6 +1865,7 @@ mod tests {
         let result = config.create_node_in_path(
             &["root"],
             MatcherConfig::Ruleset { name: "test/name".to_string(), rules: vec![] },
+            &MatcherResources::default(),
         );
         assert!(result.is_err());
         assert_eq!(old_config, config);
@@ -1646,8 +1873,12 @@ mod tests {
 
     #[test]
     fn should_refuse_missformated_names_on_edit() {
-        let filter =
-            Filter { description: "".to_string(), active: false, filter: Defaultable::Default {} };
+        let filter = Filter {
+            description: "".to_string(),
+            active: false,
+            filter: Defaultable::Default {},
+            schedule: None,
+        };
 
         let old_config = MatcherConfig::Filter {
             name: "root".to_string(),
@@ -1663,6 +1894,7 @@ mod tests {
                 filter: filter.clone(),
                 nodes: vec![],
             },
+            &MatcherResources::default(),
         );
         assert!(result.is_err());
         assert_eq!(old_config, config);
@@ -1670,6 +1902,7 @@ mod tests {
         let result = config.create_node_in_path(
             &["root"],
             MatcherConfig::Ruleset { name: "test/name".to_string(), rules: vec![] },
+            &MatcherResources::default(),
         );
         assert!(result.is_err());
         assert_eq!(old_config, config);
@@ -1688,11 +1921,13 @@ mod tests {
                     where_operator: Some(Operator::Regex {
                         regex: "^(.*$".to_string(),
                         target: "".to_string(),
+                        grok: false,
                     }),
                     with: Default::default(),
                 },
                 ..Default::default()
             },
+            &MatcherResources::default(),
         );
 
         assert!(result.is_err());
@@ -1716,6 +1951,7 @@ mod tests {
                 },
                 ..Default::default()
             },
+            &MatcherResources::default(),
         );
 
         assert!(result.is_err());
@@ -1743,6 +1979,7 @@ mod tests {
                 },
                 ..Default::default()
             },
+            &MatcherResources::default(),
         );
 
         assert!(result.is_err());
@@ -1788,6 +2025,7 @@ mod tests {
                         first: json!("${event.metadata.tenant}"),
                         second: json!("master"),
                     }),
+                    schedule: None,
                 },
                 nodes: vec![
                     MatcherConfig::Filter {
@@ -1810,6 +2048,7 @@ mod tests {
                 description: "imported root filter".to_string(),
                 active: false,
                 filter: Defaultable::Default {},
+                schedule: None,
             },
             nodes: vec![],
         };
@@ -1891,6 +2130,7 @@ mod tests {
                 filter: Default::default(),
                 nodes: vec![],
             },
+            &MatcherResources::default(),
         );
 
         // Assert
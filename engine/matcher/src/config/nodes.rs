@@ -1,7 +1,8 @@
-use crate::config::rule::Operator;
+use crate::config::rule::{ConfigAction, Extractor, Operator, Schedule};
 use crate::config::Defaultable;
 use crate::error::MatcherError;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(deny_unknown_fields)]
@@ -9,6 +10,8 @@ pub struct Filter {
     pub description: String,
     pub active: bool,
     pub filter: Defaultable<Operator>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Schedule>,
 }
 
 impl Filter {
@@ -45,6 +48,78 @@ impl MatcherIterator {
     }
 }
 
+/// A Correlation detects an ordered sequence of events sharing the same correlation key.
+/// A sequence is started by an event matching the first step and it is completed when
+/// the following steps are matched, in order, within `timeout_seconds` from its start.
+/// The `on_complete` actions are produced when the sequence is completed, the `on_timeout`
+/// actions when it expires; for example, "problem not followed by recovery within 10 minutes"
+/// is a two-step correlation with only `on_timeout` actions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Correlation {
+    pub description: String,
+    pub active: bool,
+    pub correlate_by: String,
+    pub steps: Vec<Operator>,
+    pub timeout_seconds: u64,
+    #[serde(default)]
+    pub on_complete: Vec<ConfigAction>,
+    #[serde(default)]
+    pub on_timeout: Vec<ConfigAction>,
+}
+
+impl Correlation {
+    pub fn from_json(json: &str) -> Result<Correlation, MatcherError> {
+        serde_json::from_str(json).map_err(|e| MatcherError::JsonDeserializationError {
+            message: format!("Cannot deserialize Correlation. Error [{}]", e),
+        })
+    }
+}
+
+/// A Heartbeat watches the Events matching its filter, grouped by the value of `key`.
+/// When no Event is received for a key for more than `max_interval_seconds`, a synthetic
+/// `tornado.heartbeat_missing` Event is sent to the matcher; when the key reports again,
+/// a `tornado.heartbeat_recovered` Event is sent.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Heartbeat {
+    pub description: String,
+    pub active: bool,
+    pub filter: Defaultable<Operator>,
+    pub key: String,
+    pub max_interval_seconds: u64,
+}
+
+impl Heartbeat {
+    pub fn from_json(json: &str) -> Result<Heartbeat, MatcherError> {
+        serde_json::from_str(json).map_err(|e| MatcherError::JsonDeserializationError {
+            message: format!("Cannot deserialize Heartbeat. Error [{}]", e),
+        })
+    }
+}
+
+/// An Enricher extracts variables from the Events, with the same extractors and modifiers of
+/// the `WITH` clause of the Rules, and adds them to the Event payload before passing the Event
+/// to its children. The variables are added to the `payload_key` entry of the payload or,
+/// if it is not set, directly to the payload, replacing the entries with the same name.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Enricher {
+    pub description: String,
+    pub active: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_key: Option<String>,
+    pub with: HashMap<String, Extractor>,
+}
+
+impl Enricher {
+    pub fn from_json(json: &str) -> Result<Enricher, MatcherError> {
+        serde_json::from_str(json).map_err(|e| MatcherError::JsonDeserializationError {
+            message: format!("Cannot deserialize Enricher. Error [{}]", e),
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -102,4 +177,67 @@ mod test {
 
         assert!(Filter::from_json(json).is_err());
     }
+
+    #[test]
+    fn should_deserialize_correlation_from_json() {
+        let json = r##"{
+          "description": "Link down followed by BGP neighbor down on the same device",
+          "active": true,
+          "correlate_by": "${event.payload.host}",
+          "steps": [
+            { "type": "equals", "first": "${event.type}", "second": "link_down" },
+            { "type": "equals", "first": "${event.type}", "second": "bgp_down" }
+          ],
+          "timeout_seconds": 30,
+          "on_complete": [
+            { "id": "logger", "payload": { "host": "${event.payload.host}" } }
+          ]
+        }"##;
+
+        let correlation = Correlation::from_json(json).unwrap();
+
+        assert_eq!(2, correlation.steps.len());
+        assert_eq!(30, correlation.timeout_seconds);
+        assert_eq!(1, correlation.on_complete.len());
+        assert!(correlation.on_timeout.is_empty());
+    }
+
+    #[test]
+    fn should_deserialize_heartbeat_from_json() {
+        let json = r##"{
+          "description": "Every host sends a check result at least every 5 minutes",
+          "active": true,
+          "filter": { "type": "equals", "first": "${event.type}", "second": "check_result" },
+          "key": "${event.payload.host}",
+          "max_interval_seconds": 300
+        }"##;
+
+        let heartbeat = Heartbeat::from_json(json).unwrap();
+
+        assert_eq!("${event.payload.host}", heartbeat.key);
+        assert_eq!(300, heartbeat.max_interval_seconds);
+        assert!(matches!(heartbeat.filter, Defaultable::Value(Operator::Equals { .. })));
+    }
+
+    #[test]
+    fn should_deserialize_enricher_from_json() {
+        let json = r##"{
+          "description": "Adds the team in charge of the host",
+          "active": true,
+          "payload_key": "enrichment",
+          "with": {
+            "team": {
+              "from": "${event.payload.host}",
+              "regex": { "match": ".*", "group_match_idx": 0 },
+              "modifiers_post": [ { "type": "Lookup", "table": "teams", "field": "team" } ]
+            }
+          }
+        }"##;
+
+        let enricher = Enricher::from_json(json).unwrap();
+
+        assert_eq!(Some("enrichment".to_owned()), enricher.payload_key);
+        assert_eq!(1, enricher.with.len());
+        assert_eq!(1, enricher.with["team"].modifiers_post.len());
+    }
 }
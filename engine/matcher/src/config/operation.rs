@@ -55,7 +55,9 @@ pub fn matcher_config_filter(
 
     let node_filter = filter.get(node_name)?;
     match (node_filter, matcher_config) {
-        (_, MatcherConfig::Ruleset { .. }) => Some(matcher_config.clone()),
+        (_, MatcherConfig::Ruleset { .. })
+        | (_, MatcherConfig::Correlation { .. })
+        | (_, MatcherConfig::Heartbeat { .. }) => Some(matcher_config.clone()),
         (NodeFilter::AllChildren, node) => Some(node.clone()),
         (
             NodeFilter::SelectedChildren(selected_children),
@@ -93,6 +95,24 @@ pub fn matcher_config_filter(
                 nodes: children,
             })
         }
+        (
+            NodeFilter::SelectedChildren(selected_children),
+            MatcherConfig::Enricher { name, enricher, nodes },
+        ) => {
+            let children: Vec<_> = nodes
+                .iter()
+                .flat_map(|node| matcher_config_filter(node, selected_children))
+                .collect();
+
+            if children.is_empty() {
+                return None;
+            }
+            Some(MatcherConfig::Enricher {
+                name: name.to_owned(),
+                enricher: enricher.to_owned(),
+                nodes: children,
+            })
+        }
     }
 }
 
@@ -553,6 +573,11 @@ mod test {
     }
 
     fn filter_definition() -> Filter {
-        Filter { description: "desc".to_owned(), active: true, filter: Defaultable::Default {} }
+        Filter {
+            description: "desc".to_owned(),
+            active: true,
+            filter: Defaultable::Default {},
+            schedule: None,
+        }
     }
 }
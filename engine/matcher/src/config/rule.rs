@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use tornado_common_api::Payload;
+use tornado_common_parser::Condition;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(deny_unknown_fields)]
@@ -19,12 +20,73 @@ pub struct Rule {
     pub active: bool,
     pub constraint: Constraint,
     pub actions: Vec<ConfigAction>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<Threshold>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppression: Option<Suppression>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Schedule>,
+}
+
+/// A sliding-window threshold on a Rule.
+/// When present, the rule produces its actions only when at least `count` matching events
+/// with the same `group_by` value are received within `window_seconds`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Threshold {
+    pub count: usize,
+    pub window_seconds: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_by: Option<String>,
+}
+
+/// A deduplication window on a Rule.
+/// When present, once the rule produces its actions for an event, the following events with
+/// the same fingerprint are marked as duplicates for `ttl_seconds` and produce no actions.
+/// The fingerprint is computed from the values returned by the `fingerprint` accessors.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Suppression {
+    pub fingerprint: Vec<String>,
+    pub ttl_seconds: u64,
+}
+
+/// An activation schedule on a Filter or a Rule.
+/// When present, the node is active only if the creation time of the event falls within at least
+/// one of the `active_windows`, when there are any, and outside all the `inactive_windows`.
+/// The windows are evaluated in the `timezone`, e.g. "Europe/Rome"; when missing, UTC is used.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Schedule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub active_windows: Vec<ScheduleWindow>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inactive_windows: Vec<ScheduleWindow>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+#[serde(deny_unknown_fields)]
+pub enum ScheduleWindow {
+    /// A window that opens at each occurrence of the `cron` expression and lasts
+    /// `duration_seconds`. The expression has the fields
+    /// `sec min hour day_of_month month day_of_week [year]`, e.g. "0 0 2 * * Sun".
+    #[serde(rename = "recurring")]
+    Recurring { cron: String, duration_seconds: u64 },
+    /// A window between two local date-times in the format "2024-03-01T02:00:00";
+    /// `from` is included and `to` is excluded.
+    #[serde(rename = "range")]
+    Range { from: String, to: String },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(deny_unknown_fields)]
 pub struct Constraint {
-    #[serde(rename = "WHERE")]
+    /// The condition of the Rule; it is either an Operator or its textual form,
+    /// e.g. `"event.type == \"syslog\" && payload.severity <= 3"`.
+    #[serde(rename = "WHERE", default, with = "where_operator")]
     pub where_operator: Option<Operator>,
     #[serde(rename = "WITH")]
     pub with: HashMap<String, Extractor>,
@@ -59,6 +121,50 @@ pub enum Modifier {
     DateAndTime {
         timezone: String,
     },
+    Uppercase {},
+    Substring {
+        #[serde(default)]
+        start: i64,
+        end: Option<i64>,
+    },
+    Split {
+        separator: String,
+        #[serde(default)]
+        is_regex: bool,
+    },
+    Join {
+        separator: String,
+    },
+    PadLeft {
+        length: usize,
+        padding: Option<String>,
+    },
+    PadRight {
+        length: usize,
+        padding: Option<String>,
+    },
+    Default {
+        value: Value,
+    },
+    ParseJson {},
+    Base64Decode {},
+    Base64Encode {},
+    UrlDecode {},
+    HexDecode {},
+    ParseDate {
+        #[serde(default)]
+        formats: Vec<String>,
+        timezone: Option<String>,
+    },
+    FormatDate {
+        format: String,
+        timezone: Option<String>,
+    },
+    Lookup {
+        table: String,
+        field: Option<String>,
+        default_value: Option<Value>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -76,10 +182,35 @@ pub enum ExtractorRegex {
         regex: String,
         all_matches: Option<bool>,
     },
+    /// A grok expression, e.g. `%{IP:src} %{NUMBER:port}`, whose fields are
+    /// extracted as named groups.
+    Grok {
+        #[serde(rename = "grok_match")]
+        expression: String,
+        all_matches: Option<bool>,
+    },
     SingleKeyRegex {
         #[serde(rename = "single_key_match")]
         regex: String,
     },
+    KeyValue {
+        #[serde(rename = "key_value_match")]
+        format: KeyValueFormat,
+    },
+    /// A JMESPath expression, e.g. `alerts[?status=='firing'].labels.host`, evaluated
+    /// against the `from` value.
+    JMESPath {
+        #[serde(rename = "jmespath_match")]
+        expression: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct KeyValueFormat {
+    pub pair_separator: Option<String>,
+    pub key_value_separator: Option<String>,
+    pub quote: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -117,7 +248,50 @@ pub enum Operator {
     #[serde(alias = "notEqual")]
     NotEquals { first: Value, second: Value },
     #[serde(rename = "regex")]
-    Regex { regex: String, target: String },
+    Regex {
+        regex: String,
+        target: String,
+        /// Whether the regex is a grok expression
+        #[serde(default, skip_serializing_if = "is_false")]
+        grok: bool,
+    },
+    #[serde(rename = "in")]
+    In { first: Value, values: Vec<Value> },
+    #[serde(rename = "startsWith")]
+    StartsWith { first: Value, second: Value },
+    #[serde(rename = "endsWith")]
+    EndsWith { first: Value, second: Value },
+    #[serde(rename = "exists")]
+    Exists { target: String },
+    #[serde(rename = "notExists")]
+    NotExists { target: String },
+    #[serde(rename = "isType")]
+    IsType { target: String, value_type: ValueType },
+    #[serde(rename = "ipInCidr")]
+    IpInCidr { target: String, networks: Vec<String> },
+    #[serde(rename = "ipEquals")]
+    IpEquals { first: Value, second: Value },
+    /// The textual form of an operator,
+    /// e.g. `event.type == "syslog" && (payload.severity <= 3 || payload.msg =~ /panic/)`.
+    #[serde(rename = "expression")]
+    Expression { expression: String },
+}
+
+/// The type of value expected by the `isType` operator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ValueType {
+    #[serde(rename = "string")]
+    String,
+    #[serde(rename = "number")]
+    Number,
+    #[serde(rename = "bool")]
+    Bool,
+    #[serde(rename = "array")]
+    Array,
+    #[serde(rename = "object")]
+    Object,
+    #[serde(rename = "null")]
+    Null,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -134,12 +308,137 @@ impl Rule {
     }
 }
 
+impl Operator {
+    /// Parses the textual form of an operator.
+    pub fn parse_expression(expression: &str) -> Result<Operator, MatcherError> {
+        let condition =
+            Condition::parse(expression).map_err(|err| MatcherError::ExpressionSyntaxError {
+                expression: expression.to_owned(),
+                message: err.message,
+                line: err.line,
+                column: err.column,
+            })?;
+        Operator::from_condition(condition)
+    }
+
+    fn from_condition(condition: Condition) -> Result<Operator, MatcherError> {
+        let into_operators = |conditions: Vec<Condition>| {
+            conditions.into_iter().map(Operator::from_condition).collect::<Result<_, _>>()
+        };
+        Ok(match condition {
+            Condition::And { conditions } => {
+                Operator::And { operators: into_operators(conditions)? }
+            }
+            Condition::Or { conditions } => Operator::Or { operators: into_operators(conditions)? },
+            Condition::Not { condition } => {
+                Operator::Not { operator: Box::new(Operator::from_condition(*condition)?) }
+            }
+            Condition::Contains { first, second } => Operator::Contains { first, second },
+            Condition::ContainsIgnoreCase { first, second } => {
+                Operator::ContainsIgnoreCase { first, second }
+            }
+            Condition::Equals { first, second } => Operator::Equals { first, second },
+            Condition::EqualsIgnoreCase { first, second } => {
+                Operator::EqualsIgnoreCase { first, second }
+            }
+            Condition::GreaterEqualThan { first, second } => {
+                Operator::GreaterEqualThan { first, second }
+            }
+            Condition::GreaterThan { first, second } => Operator::GreaterThan { first, second },
+            Condition::LessEqualThan { first, second } => Operator::LessEqualThan { first, second },
+            Condition::LessThan { first, second } => Operator::LessThan { first, second },
+            Condition::NotEquals { first, second } => Operator::NotEquals { first, second },
+            Condition::Regex { regex, target, grok } => Operator::Regex { regex, target, grok },
+            Condition::In { first, values } => Operator::In { first, values },
+            Condition::StartsWith { first, second } => Operator::StartsWith { first, second },
+            Condition::EndsWith { first, second } => Operator::EndsWith { first, second },
+            Condition::Exists { target } => Operator::Exists { target },
+            Condition::NotExists { target } => Operator::NotExists { target },
+            Condition::IsType { target, value_type } => Operator::IsType {
+                target,
+                value_type: ValueType::from_name(&value_type).ok_or_else(|| {
+                    MatcherError::ConfigurationError {
+                        message: format!("Unknown type [{}] of the isType operator", value_type),
+                    }
+                })?,
+            },
+            Condition::IpInCidr { target, networks } => Operator::IpInCidr { target, networks },
+            Condition::IpEquals { first, second } => Operator::IpEquals { first, second },
+        })
+    }
+}
+
+impl ValueType {
+    /// Returns the ValueType with the given name, e.g. `string`, if any.
+    fn from_name(name: &str) -> Option<ValueType> {
+        match name {
+            "string" => Some(ValueType::String),
+            "number" => Some(ValueType::Number),
+            "bool" => Some(ValueType::Bool),
+            "array" => Some(ValueType::Array),
+            "object" => Some(ValueType::Object),
+            "null" => Some(ValueType::Null),
+            _ => None,
+        }
+    }
+}
+
+/// Serializes and deserializes the WHERE of a Constraint, whose value can be an Operator or
+/// its textual form.
+mod where_operator {
+    use super::Operator;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value;
+
+    pub fn serialize<S: Serializer>(
+        operator: &Option<Operator>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match operator {
+            Some(Operator::Expression { expression }) => serializer.serialize_str(expression),
+            operator => operator.serialize(serializer),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Operator>, D::Error> {
+        match Option::<Value>::deserialize(deserializer)? {
+            None => Ok(None),
+            Some(Value::String(expression)) => Ok(Some(Operator::Expression { expression })),
+            Some(value) => Operator::deserialize(value).map(Some).map_err(D::Error::custom),
+        }
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
 #[cfg(test)]
 mod test {
 
     use super::*;
     use std::fs;
 
+    #[test]
+    fn value_type_from_name_should_accept_the_serialized_names() {
+        let value_types = vec![
+            ValueType::String,
+            ValueType::Number,
+            ValueType::Bool,
+            ValueType::Array,
+            ValueType::Object,
+            ValueType::Null,
+        ];
+        for value_type in value_types {
+            let name = serde_json::to_value(&value_type).unwrap();
+            assert_eq!(Some(value_type), ValueType::from_name(name.as_str().unwrap()));
+        }
+        assert_eq!(None, ValueType::from_name("date"));
+    }
+
     #[test]
     fn should_return_error_if_invalid_json() {
         let json = r#"{"hello":"world"}"#;
@@ -252,10 +551,288 @@ mod test {
         assert!(rule.is_ok());
     }
 
+    #[test]
+    fn should_deserialize_rule_from_json_with_key_value_match() {
+        // Arrange
+        let json = r#"{
+          "description": "",
+          "continue": true,
+          "active": true,
+          "constraint": {
+            "WITH": {
+              "fields": {
+                "from": "${event.payload.line}",
+                "regex": { "key_value_match": { "pair_separator": ";" } }
+              },
+              "default_fields": {
+                "from": "${event.payload.line}",
+                "regex": { "key_value_match": {} }
+              }
+            }
+          },
+          "actions": []
+        }"#;
+
+        // Act
+        let rule = Rule::from_json(json).unwrap();
+
+        // Assert
+        assert_eq!(
+            ExtractorRegex::KeyValue {
+                format: KeyValueFormat {
+                    pair_separator: Some(";".to_owned()),
+                    key_value_separator: None,
+                    quote: None
+                }
+            },
+            rule.constraint.with["fields"].regex
+        );
+        assert_eq!(
+            ExtractorRegex::KeyValue {
+                format: KeyValueFormat {
+                    pair_separator: None,
+                    key_value_separator: None,
+                    quote: None
+                }
+            },
+            rule.constraint.with["default_fields"].regex
+        );
+    }
+
+    #[test]
+    fn should_deserialize_rule_from_json_with_grok_expressions() {
+        // Arrange
+        let json = r#"{
+          "description": "",
+          "continue": true,
+          "active": true,
+          "constraint": {
+            "WHERE": {
+              "type": "regex",
+              "regex": "^%{SYSLOGBASE}",
+              "target": "${event.payload.line}",
+              "grok": true
+            },
+            "WITH": {
+              "connection": {
+                "from": "${event.payload.line}",
+                "regex": { "grok_match": "%{IP:src}:%{POSINT:port}" }
+              }
+            }
+          },
+          "actions": []
+        }"#;
+
+        // Act
+        let rule = Rule::from_json(json).unwrap();
+
+        // Assert
+        assert_eq!(
+            Some(Operator::Regex {
+                regex: "^%{SYSLOGBASE}".to_owned(),
+                target: "${event.payload.line}".to_owned(),
+                grok: true
+            }),
+            rule.constraint.where_operator
+        );
+        assert_eq!(
+            ExtractorRegex::Grok {
+                expression: "%{IP:src}:%{POSINT:port}".to_owned(),
+                all_matches: None
+            },
+            rule.constraint.with["connection"].regex
+        );
+    }
+
+    #[test]
+    fn should_not_serialize_the_grok_flag_of_plain_regex_operators() {
+        let operator = Operator::Regex {
+            regex: "^[a-z]+$".to_owned(),
+            target: "${event.type}".to_owned(),
+            grok: false,
+        };
+        let json = serde_json::to_value(&operator).unwrap();
+        assert_eq!(
+            serde_json::json!({ "type": "regex", "regex": "^[a-z]+$", "target": "${event.type}" }),
+            json
+        );
+        assert_eq!(operator, serde_json::from_value(json).unwrap());
+    }
+
     #[test]
     fn should_deserialize_rule_from_json_with_modifiers_post() {
         let json = file_to_string("./test_resources/v1/rules/007_with_modifiers_post.json");
         let rule = Rule::from_json(&json);
         assert!(rule.is_ok());
     }
+
+    #[test]
+    fn should_deserialize_rule_from_json_with_schedule() {
+        // Arrange
+        let json = r#"{
+          "description": "",
+          "continue": true,
+          "active": true,
+          "constraint": { "WITH": {} },
+          "actions": [],
+          "schedule": {
+            "timezone": "Europe/Rome",
+            "inactive_windows": [
+              { "type": "recurring", "cron": "0 0 2 * * Sun", "duration_seconds": 7200 },
+              { "type": "range", "from": "2024-03-01T22:00:00", "to": "2024-03-02T06:00:00" }
+            ]
+          }
+        }"#;
+
+        // Act
+        let rule = Rule::from_json(json).unwrap();
+
+        // Assert
+        let schedule = rule.schedule.unwrap();
+        assert_eq!(Some("Europe/Rome".to_owned()), schedule.timezone);
+        assert!(schedule.active_windows.is_empty());
+        assert_eq!(
+            vec![
+                ScheduleWindow::Recurring {
+                    cron: "0 0 2 * * Sun".to_owned(),
+                    duration_seconds: 7200
+                },
+                ScheduleWindow::Range {
+                    from: "2024-03-01T22:00:00".to_owned(),
+                    to: "2024-03-02T06:00:00".to_owned()
+                },
+            ],
+            schedule.inactive_windows
+        );
+    }
+
+    #[test]
+    fn should_deserialize_the_membership_and_existence_operators() {
+        // Arrange
+        let json = r#"{
+          "type": "AND",
+          "operators": [
+            { "type": "in", "first": "${event.type}", "values": ["email", "syslog"] },
+            { "type": "startsWith", "first": "${event.payload.hostname}", "second": "web" },
+            { "type": "endsWith", "first": "${event.payload.hostname}", "second": ".com" },
+            { "type": "exists", "target": "${event.payload.severity}" },
+            { "type": "notExists", "target": "${event.payload.acknowledged}" },
+            { "type": "isType", "target": "${event.payload.severity}", "value_type": "number" }
+          ]
+        }"#;
+
+        // Act
+        let operator: Operator = serde_json::from_str(json).unwrap();
+
+        // Assert
+        assert_eq!(
+            Operator::And {
+                operators: vec![
+                    Operator::In {
+                        first: Value::String("${event.type}".to_owned()),
+                        values: vec![
+                            Value::String("email".to_owned()),
+                            Value::String("syslog".to_owned())
+                        ],
+                    },
+                    Operator::StartsWith {
+                        first: Value::String("${event.payload.hostname}".to_owned()),
+                        second: Value::String("web".to_owned()),
+                    },
+                    Operator::EndsWith {
+                        first: Value::String("${event.payload.hostname}".to_owned()),
+                        second: Value::String(".com".to_owned()),
+                    },
+                    Operator::Exists { target: "${event.payload.severity}".to_owned() },
+                    Operator::NotExists { target: "${event.payload.acknowledged}".to_owned() },
+                    Operator::IsType {
+                        target: "${event.payload.severity}".to_owned(),
+                        value_type: ValueType::Number,
+                    },
+                ]
+            },
+            operator
+        );
+    }
+
+    #[test]
+    fn should_deserialize_and_serialize_the_textual_where() {
+        // Arrange
+        let json = r#"{
+          "WHERE": "event.type == \"syslog\" && payload.severity <= 3",
+          "WITH": {}
+        }"#;
+
+        // Act
+        let constraint: Constraint = serde_json::from_str(json).unwrap();
+        let serialized = serde_json::to_value(&constraint).unwrap();
+
+        // Assert
+        assert_eq!(
+            Some(Operator::Expression {
+                expression: r#"event.type == "syslog" && payload.severity <= 3"#.to_owned()
+            }),
+            constraint.where_operator
+        );
+        assert_eq!(
+            Value::String(r#"event.type == "syslog" && payload.severity <= 3"#.to_owned()),
+            serialized["WHERE"]
+        );
+        assert_eq!(constraint, serde_json::from_value(serialized).unwrap());
+    }
+
+    #[test]
+    fn should_deserialize_the_where_operator_or_its_absence() {
+        let constraint: Constraint = serde_json::from_str(
+            r#"{ "WHERE": { "type": "exists", "target": "${event.type}" }, "WITH": {} }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Some(Operator::Exists { target: "${event.type}".to_owned() }),
+            constraint.where_operator
+        );
+
+        let constraint: Constraint = serde_json::from_str(r#"{ "WITH": {} }"#).unwrap();
+        assert_eq!(None, constraint.where_operator);
+
+        let constraint: Constraint =
+            serde_json::from_str(r#"{ "WHERE": null, "WITH": {} }"#).unwrap();
+        assert_eq!(None, constraint.where_operator);
+
+        assert!(serde_json::from_str::<Constraint>(
+            r#"{ "WHERE": { "type": "unknown" }, "WITH": {} }"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn should_parse_the_expression_into_operators() {
+        // Act
+        let operator = Operator::parse_expression(
+            r#"!(type in ["email", "syslog"]) || isType(payload.severity, "number")"#,
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(
+            Operator::Or {
+                operators: vec![
+                    Operator::Not {
+                        operator: Box::new(Operator::In {
+                            first: Value::String("${event.type}".to_owned()),
+                            values: vec![
+                                Value::String("email".to_owned()),
+                                Value::String("syslog".to_owned())
+                            ],
+                        })
+                    },
+                    Operator::IsType {
+                        target: "${event.payload.severity}".to_owned(),
+                        value_type: ValueType::Number,
+                    },
+                ]
+            },
+            operator
+        );
+    }
 }
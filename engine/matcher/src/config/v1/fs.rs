@@ -228,6 +228,7 @@ impl FsMatcherConfigManager {
                 active: true,
                 description: "An implicit filter that allows all events".to_owned(),
                 filter: Defaultable::Default {},
+                schedule: None,
             };
             return Ok(MatcherConfig::Filter { name, filter, nodes });
         }
@@ -1,16 +1,20 @@
-use crate::config::nodes::{Filter, MatcherIterator};
+use crate::config::nodes::{Correlation, Enricher, Filter, Heartbeat, MatcherIterator};
 use crate::config::rule::Rule;
 use crate::config::v2::error::DeploymentError;
 use crate::config::v2::{
-    gather_dir_entries, parse_node_config_from_file, read_config_from_root_dir, ConfigNodeDir,
-    FsMatcherConfigManagerV2, MatcherConfigError, MatcherConfigFilter, MatcherConfigIterator,
-    MatcherConfigRuleset, Version,
+    gather_dir_entries, parse_node_config_from_file, read_config_from_root_dir,
+    read_test_suites_from_root_dir, ruleset_tests_dir, ConfigNodeDir, FsMatcherConfigManagerV2,
+    MatcherConfigCorrelation, MatcherConfigEnricher, MatcherConfigError, MatcherConfigFilter,
+    MatcherConfigHeartbeat, MatcherConfigIterator, MatcherConfigRuleset, Version,
 };
 use crate::config::{
     v1, MatcherConfig, MatcherConfigDraft, MatcherConfigDraftData, MatcherConfigEditor,
+    MatcherConfigReader, MatcherConfigSnapshot, MatcherConfigSnapshotData,
 };
 use crate::error::MatcherError;
 use crate::matcher::Matcher;
+use crate::resources::MatcherResources;
+use crate::suite::{ruleset_paths, RuleTestSuite};
 use chrono::Local;
 use futures::stream::FuturesUnordered;
 use futures::StreamExt;
@@ -20,6 +24,10 @@ use std::path::{Path, PathBuf};
 use tokio::io::AsyncWriteExt;
 
 const DRAFT_ID: &str = "draft_001";
+/// The user of the snapshots of the configurations deployed without a draft
+const SYSTEM_USER: &str = "system";
+/// The comment of the snapshot of the configuration found when the history is empty
+const INITIAL_SNAPSHOT_COMMENT: &str = "Configuration deployed before the history was enabled";
 
 #[async_trait::async_trait(?Send)]
 impl MatcherConfigEditor for FsMatcherConfigManagerV2 {
@@ -91,18 +99,49 @@ impl MatcherConfigEditor for FsMatcherConfigManagerV2 {
             path.push("config");
             path
         };
-        atomic_deploy_config(&draft_config_dir, config).await?;
+        atomic_deploy_config(&draft_config_dir, config, &draft_config_dir, &self.get_resources())
+            .await?;
         Ok(())
     }
 
-    async fn deploy_draft(&self, draft_id: &str) -> Result<MatcherConfig, MatcherError> {
+    async fn get_draft_test_suites(
+        &self,
+        draft_id: &str,
+    ) -> Result<Vec<RuleTestSuite>, MatcherError> {
+        // ToDo: Do proper sanitation of the draft_id when multitenancy is added to avoid path-traversal vulnerabilities.
+        if draft_id != DRAFT_ID {
+            return Err(MatcherError::DraftNotFoundError { draft_id: draft_id.to_string() });
+        }
+
+        let draft_config_dir = {
+            let mut path = self.drafts_path.to_path_buf();
+            path.push(draft_id);
+            path.push("config");
+            path
+        };
+        let config = read_config_from_root_dir(&draft_config_dir).await?;
+        Ok(read_test_suites_from_root_dir(&draft_config_dir, &config).await?)
+    }
+
+    async fn deploy_draft(
+        &self,
+        draft_id: &str,
+        comment: &str,
+    ) -> Result<MatcherConfig, MatcherError> {
         // ToDo: Do proper sanitation of the draft_id when multitenancy is added to avoid path-traversal vulnerabilities.
         if draft_id != DRAFT_ID {
             return Err(MatcherError::DraftNotFoundError { draft_id: draft_id.to_string() });
         }
 
         let draft = self.get_draft(draft_id).await?;
-        atomic_deploy_config(&self.root_path, &draft.config).await?;
+        let draft_config_dir = {
+            let mut path = self.drafts_path.to_path_buf();
+            path.push(draft_id);
+            path.push("config");
+            path
+        };
+        self.deploy_with_snapshot(&draft.config, &draft_config_dir, &draft.data.user, comment)
+            .await?;
         Ok(draft.config)
     }
 
@@ -149,14 +188,139 @@ impl MatcherConfigEditor for FsMatcherConfigManagerV2 {
     }
 
     async fn deploy_config(&self, config: &MatcherConfig) -> Result<MatcherConfig, MatcherError> {
-        atomic_deploy_config(&self.root_path, config).await?;
+        self.deploy_with_snapshot(
+            config,
+            &self.root_path,
+            SYSTEM_USER,
+            "Configuration deployed without a draft",
+        )
+        .await?;
         Ok(config.clone())
     }
+
+    fn set_resources(&self, resources: MatcherResources) {
+        *self.resources.write().expect("The resources lock should not be poisoned") = resources;
+    }
+
+    async fn get_snapshots(&self) -> Result<Vec<MatcherConfigSnapshotData>, MatcherError> {
+        match &self.history {
+            Some(history) => history.get_snapshots().await,
+            None => Ok(vec![]),
+        }
+    }
+
+    async fn get_snapshot(&self, snapshot_id: &str) -> Result<MatcherConfigSnapshot, MatcherError> {
+        match &self.history {
+            Some(history) => history.get_snapshot(snapshot_id).await,
+            None => {
+                Err(MatcherError::SnapshotNotFoundError { snapshot_id: snapshot_id.to_owned() })
+            }
+        }
+    }
+
+    async fn get_snapshot_test_suites(
+        &self,
+        snapshot_id: &str,
+    ) -> Result<Vec<RuleTestSuite>, MatcherError> {
+        let Some(history) = &self.history else {
+            return Err(MatcherError::SnapshotNotFoundError {
+                snapshot_id: snapshot_id.to_owned(),
+            });
+        };
+
+        let snapshot_config_dir = history.get_snapshot_config_dir(snapshot_id).await?;
+        let config = read_config_from_root_dir(&snapshot_config_dir).await?;
+        Ok(read_test_suites_from_root_dir(&snapshot_config_dir, &config).await?)
+    }
+
+    async fn rollback_to_snapshot(
+        &self,
+        snapshot_id: &str,
+        user: String,
+    ) -> Result<MatcherConfig, MatcherError> {
+        let Some(history) = &self.history else {
+            return Err(MatcherError::SnapshotNotFoundError {
+                snapshot_id: snapshot_id.to_owned(),
+            });
+        };
+
+        info!("User {} is rolling back the configuration to snapshot {}", user, snapshot_id);
+        let snapshot = history.get_snapshot(snapshot_id).await?;
+        let snapshot_config_dir = history.get_snapshot_config_dir(snapshot_id).await?;
+        self.deploy_with_snapshot(
+            &snapshot.config,
+            &snapshot_config_dir,
+            &user,
+            &format!("Rollback to snapshot [{}]", snapshot_id),
+        )
+        .await?;
+        Ok(snapshot.config)
+    }
+}
+
+impl FsMatcherConfigManagerV2 {
+    /// Deploys the configuration and saves it in the history, if any, with the user and the
+    /// comment of the deploy. A rejected configuration does not change the history.
+    /// If the history is empty, the configuration replaced by the deploy is saved first, so
+    /// that it is possible to roll back to it; the deploy is aborted if it cannot be saved.
+    async fn deploy_with_snapshot(
+        &self,
+        config: &MatcherConfig,
+        tests_source_dir: &Path,
+        user: &str,
+        comment: &str,
+    ) -> Result<(), MatcherError> {
+        validate_config(config, &self.get_resources())?;
+        if let Some(history) = &self.history {
+            if history.is_empty().await?
+                && tokio::fs::try_exists(&self.root_path).await.unwrap_or(false)
+            {
+                history
+                    .create_snapshot(&self.root_path, SYSTEM_USER, INITIAL_SNAPSHOT_COMMENT)
+                    .await?;
+            }
+        }
+
+        atomic_write_config(&self.root_path, config, tests_source_dir).await?;
+
+        if let Some(history) = &self.history {
+            // The configuration is already deployed, so the deploy does not fail
+            if let Err(error) = history.create_snapshot(&self.root_path, user, comment).await {
+                error!("Cannot save the deployed configuration in the history: {:?}", error);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Validates the configuration and replaces the configuration in `dir` with it.
+/// The test cases of the Rulesets are not part of the configuration, so they are copied from
+/// the Rulesets with the same path in `tests_source_dir`.
+async fn atomic_deploy_config(
+    dir: &Path,
+    config: &MatcherConfig,
+    tests_source_dir: &Path,
+    resources: &MatcherResources,
+) -> Result<(), MatcherError> {
+    validate_config(config, resources)?;
+    atomic_write_config(dir, config, tests_source_dir).await
 }
 
-async fn atomic_deploy_config(dir: &Path, config: &MatcherConfig) -> Result<(), MatcherError> {
+fn validate_config(
+    config: &MatcherConfig,
+    resources: &MatcherResources,
+) -> Result<(), MatcherError> {
     // Validate also regex and accessor, which the MatcherConfigValidator does not do.
-    let _ = Matcher::build(config)?;
+    let _ = Matcher::build_with_resources(config, resources)?;
+    Ok(())
+}
+
+/// Replaces the configuration in `dir`, copying the test cases from `tests_source_dir`.
+async fn atomic_write_config(
+    dir: &Path,
+    config: &MatcherConfig,
+    tests_source_dir: &Path,
+) -> Result<(), MatcherError> {
     let dir_canonical = match dir.canonicalize() {
         Ok(parent) => parent,
         Err(error) => {
@@ -180,6 +344,7 @@ async fn atomic_deploy_config(dir: &Path, config: &MatcherConfig) -> Result<(),
             deploy_child_nodes_to_dir(tempdir.path(), &[config.clone()]).await?;
         }
     };
+    copy_test_suites(tests_source_dir, tempdir.path(), config).await?;
     sync_dir_to_disk(tempdir.path()).await?;
 
     if let Err(error) = tokio::fs::remove_dir_all(&dir_canonical).await {
@@ -203,6 +368,22 @@ async fn atomic_deploy_config(dir: &Path, config: &MatcherConfig) -> Result<(),
     Ok(())
 }
 
+async fn copy_test_suites(
+    source_dir: &Path,
+    dest_dir: &Path,
+    config: &MatcherConfig,
+) -> Result<(), MatcherError> {
+    for ruleset_path in ruleset_paths(config) {
+        let source_tests_dir = ruleset_tests_dir(source_dir, &ruleset_path);
+        if tokio::fs::try_exists(&source_tests_dir).await.unwrap_or(false) {
+            debug!("Copying the test cases in {}", source_tests_dir.display());
+            v1::fs::copy_recursive(source_tests_dir, ruleset_tests_dir(dest_dir, &ruleset_path))
+                .await?;
+        }
+    }
+    Ok(())
+}
+
 #[async_recursion::async_recursion]
 async fn deploy_child_nodes_to_dir(
     path: &Path,
@@ -233,6 +414,15 @@ async fn deploy_child_node(path: &Path, node: &MatcherConfig) -> Result<(), Depl
         MatcherConfig::Iterator { name, iterator, nodes } => {
             deploy_iterator_node(&parent, name, iterator, nodes).await?
         }
+        MatcherConfig::Correlation { name, correlation } => {
+            deploy_correlation_node(&parent, name, correlation).await?
+        }
+        MatcherConfig::Heartbeat { name, heartbeat } => {
+            deploy_heartbeat_node(&parent, name, heartbeat).await?
+        }
+        MatcherConfig::Enricher { name, enricher, nodes } => {
+            deploy_enricher_node(&parent, name, enricher, nodes).await?
+        }
     }
 
     Ok(())
@@ -276,6 +466,59 @@ async fn deploy_iterator_node(
     Ok(())
 }
 
+async fn deploy_correlation_node(
+    dir: &Path,
+    name: &str,
+    correlation: &Correlation,
+) -> Result<(), DeploymentError> {
+    let config = MatcherConfigCorrelation {
+        node_type: Default::default(),
+        name: name.to_string(),
+        correlation: correlation.to_owned(),
+    };
+
+    serialize_config_node_to_file(dir, &config).await?;
+    sync_dir_to_disk(dir).await?;
+
+    Ok(())
+}
+
+async fn deploy_heartbeat_node(
+    dir: &Path,
+    name: &str,
+    heartbeat: &Heartbeat,
+) -> Result<(), DeploymentError> {
+    let config = MatcherConfigHeartbeat {
+        node_type: Default::default(),
+        name: name.to_string(),
+        heartbeat: heartbeat.to_owned(),
+    };
+
+    serialize_config_node_to_file(dir, &config).await?;
+    sync_dir_to_disk(dir).await?;
+
+    Ok(())
+}
+
+async fn deploy_enricher_node(
+    dir: &Path,
+    name: &str,
+    enricher: &Enricher,
+    nodes: &[MatcherConfig],
+) -> Result<(), DeploymentError> {
+    let config = MatcherConfigEnricher {
+        node_type: Default::default(),
+        name: name.to_string(),
+        enricher: enricher.to_owned(),
+    };
+
+    serialize_config_node_to_file(dir, &config).await?;
+    deploy_child_nodes_to_dir(dir, nodes).await?;
+    sync_dir_to_disk(dir).await?;
+
+    Ok(())
+}
+
 async fn deploy_ruleset_node(
     dir: &Path,
     name: &str,
@@ -490,15 +733,22 @@ async fn sync_dir_to_disk(dir: &Path) -> Result<(), DeploymentError> {
 
 #[cfg(test)]
 mod tests {
-    use crate::config::nodes::MatcherIterator;
+    use crate::config::nodes::{Correlation, Enricher, Heartbeat, MatcherIterator};
+    use crate::config::rule::{Extractor, ExtractorRegex};
     use crate::config::v1::fs::copy_recursive;
-    use crate::config::v2::editor::{deploy_iterator_node, get_draft_from_dir, DRAFT_ID};
+    use crate::config::v2::editor::{
+        deploy_correlation_node, deploy_enricher_node, deploy_heartbeat_node, deploy_iterator_node,
+        get_draft_from_dir, DRAFT_ID, INITIAL_SNAPSHOT_COMMENT, SYSTEM_USER,
+    };
     use crate::config::v2::{
-        parse_node_config_from_file, ConfigType, FsMatcherConfigManagerV2, MatcherConfigIterator,
+        parse_node_config_from_file, read_enricher_from_dir, ConfigType, FsMatcherConfigManagerV2,
+        MatcherConfigCorrelation, MatcherConfigHeartbeat, MatcherConfigIterator,
     };
     use crate::config::{
         MatcherConfig, MatcherConfigDraftData, MatcherConfigEditor, MatcherConfigReader,
     };
+    use crate::suite::run_test_suites;
+    use std::collections::HashMap;
     use std::path::{Path, PathBuf};
     use tempfile::TempDir;
 
@@ -664,7 +914,7 @@ mod tests {
         }
 
         // Act
-        config_manager.deploy_draft(DRAFT_ID).await.unwrap();
+        config_manager.deploy_draft(DRAFT_ID, "").await.unwrap();
 
         // Assert
         let config = config_manager.get_config().await.unwrap();
@@ -677,6 +927,165 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn matcher_config_editor_should_save_a_snapshot_on_deploy_and_rollback() {
+        // Arrange
+        let temp_dir = TempDir::new().unwrap();
+        let draft_temp_dir = temp_dir.path().join("drafts");
+        let config_temp_dir = temp_dir.path().join("rules.d");
+        let history_temp_dir = temp_dir.path().join("history");
+
+        let config_manager =
+            FsMatcherConfigManagerV2::new(config_temp_dir.as_path(), draft_temp_dir.as_path())
+                .with_history(history_temp_dir.as_path(), 10);
+        copy_recursive(PathBuf::from(TEST_CONFIG_DIR), config_temp_dir.clone()).await.unwrap();
+        copy_recursive(PathBuf::from(TEST_DRAFT_DIR), draft_temp_dir.clone()).await.unwrap();
+        let old_config = config_manager.get_config().await.unwrap();
+
+        // Act
+        let deployed_config = config_manager.deploy_draft(DRAFT_ID, "One node only").await.unwrap();
+        let snapshots_after_deploy = config_manager.get_snapshots().await.unwrap();
+        let rollback_config =
+            config_manager.rollback_to_snapshot("1", "pippo".to_owned()).await.unwrap();
+
+        // Assert
+        assert_eq!(2, snapshots_after_deploy.len());
+        assert_eq!("2", snapshots_after_deploy[0].snapshot_id);
+        assert_eq!("One node only", snapshots_after_deploy[0].comment);
+        assert_eq!(deployed_config, config_manager.get_snapshot("2").await.unwrap().config);
+        assert_eq!("1", snapshots_after_deploy[1].snapshot_id);
+        assert_eq!(SYSTEM_USER, snapshots_after_deploy[1].user);
+        assert_eq!(INITIAL_SNAPSHOT_COMMENT, snapshots_after_deploy[1].comment);
+        assert_eq!(old_config, config_manager.get_snapshot("1").await.unwrap().config);
+
+        assert_eq!(old_config, rollback_config);
+        assert_eq!(old_config, config_manager.get_config().await.unwrap());
+        let snapshots_after_rollback = config_manager.get_snapshots().await.unwrap();
+        assert_eq!(3, snapshots_after_rollback.len());
+        assert_eq!("3", snapshots_after_rollback[0].snapshot_id);
+        assert_eq!("pippo", snapshots_after_rollback[0].user);
+        assert_eq!("Rollback to snapshot [1]", snapshots_after_rollback[0].comment);
+        assert_eq!(old_config, config_manager.get_snapshot("3").await.unwrap().config);
+        assert!(config_manager.get_snapshot_test_suites("1").await.unwrap().is_empty());
+
+        assert!(config_manager.get_snapshot("../rules.d").await.is_err());
+        assert!(config_manager.rollback_to_snapshot("100", "pippo".to_owned()).await.is_err());
+        assert!(config_manager.get_snapshot_test_suites("100").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn matcher_config_editor_should_keep_only_the_most_recent_snapshots() {
+        // Arrange
+        let temp_dir = TempDir::new().unwrap();
+        let config_temp_dir = temp_dir.path().join("rules.d");
+        let history_temp_dir = temp_dir.path().join("history");
+
+        let config_manager =
+            FsMatcherConfigManagerV2::new(config_temp_dir.as_path(), temp_dir.path())
+                .with_history(history_temp_dir.as_path(), 2);
+        copy_recursive(PathBuf::from(TEST_CONFIG_DIR), config_temp_dir.clone()).await.unwrap();
+        let config = config_manager.get_config().await.unwrap();
+
+        // Act
+        for _ in 0..4 {
+            config_manager.deploy_config(&config).await.unwrap();
+        }
+
+        // Assert
+        let snapshot_ids: Vec<_> = config_manager
+            .get_snapshots()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|snapshot| snapshot.snapshot_id)
+            .collect();
+        assert_eq!(vec!["5".to_owned(), "4".to_owned()], snapshot_ids);
+        assert!(!history_temp_dir.join("1").exists());
+    }
+
+    #[tokio::test]
+    async fn matcher_config_editor_should_not_change_the_history_if_the_deploy_is_rejected() {
+        // Arrange
+        let temp_dir = TempDir::new().unwrap();
+        let config_temp_dir = temp_dir.path().join("rules.d");
+        let history_temp_dir = temp_dir.path().join("history");
+
+        let config_manager =
+            FsMatcherConfigManagerV2::new(config_temp_dir.as_path(), temp_dir.path())
+                .with_history(history_temp_dir.as_path(), 10);
+        copy_recursive(PathBuf::from(TEST_CONFIG_DIR), config_temp_dir.clone()).await.unwrap();
+        let old_config = config_manager.get_config().await.unwrap();
+        let invalid_config = MatcherConfig::Filter {
+            name: "root".to_owned(),
+            filter: Default::default(),
+            nodes: vec![MatcherConfig::Ruleset { name: "invalid name".to_owned(), rules: vec![] }],
+        };
+
+        // Act
+        let result = config_manager.deploy_config(&invalid_config).await;
+
+        // Assert
+        assert!(result.is_err());
+        assert!(config_manager.get_snapshots().await.unwrap().is_empty());
+        assert_eq!(old_config, config_manager.get_config().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn matcher_config_editor_should_have_no_snapshots_without_history() {
+        // Arrange
+        let temp_dir = TempDir::new().unwrap();
+        let config_temp_dir = temp_dir.path().join("rules.d");
+        let config_manager =
+            FsMatcherConfigManagerV2::new(config_temp_dir.as_path(), temp_dir.path());
+        copy_recursive(PathBuf::from(TEST_CONFIG_DIR), config_temp_dir.clone()).await.unwrap();
+        let config = config_manager.get_config().await.unwrap();
+
+        // Act
+        config_manager.deploy_config(&config).await.unwrap();
+
+        // Assert
+        assert!(config_manager.get_snapshots().await.unwrap().is_empty());
+        assert!(config_manager.get_snapshot("1").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn matcher_config_editor_should_keep_the_test_suites_of_the_draft() {
+        // Arrange
+        let temp_dir = TempDir::new().unwrap();
+        let draft_temp_dir = {
+            let mut path = temp_dir.path().to_path_buf();
+            path.push("drafts");
+            path
+        };
+        let config_temp_dir = {
+            let mut path = temp_dir.path().to_path_buf();
+            path.push("rules.d");
+            path
+        };
+
+        let config_manager =
+            FsMatcherConfigManagerV2::new(config_temp_dir.as_path(), draft_temp_dir.as_path());
+        copy_recursive(PathBuf::from(TEST_CONFIG_DIR), config_temp_dir.clone()).await.unwrap();
+        copy_recursive(PathBuf::from(TEST_DRAFT_DIR), draft_temp_dir.clone()).await.unwrap();
+        assert!(config_manager.get_test_suites().await.unwrap().is_empty());
+
+        // Act
+        let draft = config_manager.get_draft(DRAFT_ID).await.unwrap();
+        config_manager.update_draft(DRAFT_ID, draft.data.user, &draft.config).await.unwrap();
+        let draft_suites = config_manager.get_draft_test_suites(DRAFT_ID).await.unwrap();
+        config_manager.deploy_draft(DRAFT_ID, "").await.unwrap();
+        let suites = config_manager.get_test_suites().await.unwrap();
+
+        // Assert
+        assert_eq!(1, suites.len());
+        assert_eq!(vec!["master".to_owned(), "archive_emails".to_owned()], suites[0].ruleset_path);
+        assert_eq!(vec!["master_email"], suites[0].cases.keys().collect::<Vec<_>>());
+        assert_eq!(draft_suites, suites);
+
+        let results = run_test_suites(&draft.config, &suites, &Default::default()).unwrap();
+        assert!(results[0].is_success(), "{}", results[0]);
+    }
+
     #[tokio::test]
     async fn matcher_config_editor_should_take_over_draft() {
         // Arrange
@@ -721,4 +1130,86 @@ mod tests {
 
         assert_eq!("master_iterator", loaded.name);
     }
+
+    #[tokio::test]
+    async fn should_deploy_and_load_correlation_node() {
+        let temp_dir = TempDir::new().unwrap();
+        let correlation = Correlation {
+            description: "".to_string(),
+            active: true,
+            correlate_by: "${event.payload.host}".to_string(),
+            steps: vec![],
+            timeout_seconds: 600,
+            on_complete: vec![],
+            on_timeout: vec![],
+        };
+
+        deploy_correlation_node(temp_dir.path(), "problem_not_recovered", &correlation)
+            .await
+            .unwrap();
+
+        let loaded: MatcherConfigCorrelation =
+            parse_node_config_from_file(temp_dir.path()).await.unwrap();
+
+        assert_eq!("problem_not_recovered", loaded.name);
+        assert_eq!(correlation, loaded.correlation);
+    }
+
+    #[tokio::test]
+    async fn should_deploy_and_load_heartbeat_node() {
+        let temp_dir = TempDir::new().unwrap();
+        let heartbeat = Heartbeat {
+            description: "".to_string(),
+            active: true,
+            filter: Default::default(),
+            key: "${event.payload.host}".to_string(),
+            max_interval_seconds: 300,
+        };
+
+        deploy_heartbeat_node(temp_dir.path(), "host_heartbeat", &heartbeat).await.unwrap();
+
+        let loaded: MatcherConfigHeartbeat =
+            parse_node_config_from_file(temp_dir.path()).await.unwrap();
+
+        assert_eq!("host_heartbeat", loaded.name);
+        assert_eq!(heartbeat, loaded.heartbeat);
+    }
+
+    #[tokio::test]
+    async fn should_deploy_and_load_enricher_node() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = MatcherConfig::Ruleset { name: "ruleset".to_string(), rules: vec![] };
+        let mut with = HashMap::new();
+        with.insert(
+            "host".to_owned(),
+            Extractor {
+                from: "${event.payload.hostname}".to_owned(),
+                regex: ExtractorRegex::Regex {
+                    regex: ".*".to_owned(),
+                    group_match_idx: Some(0),
+                    all_matches: None,
+                },
+                modifiers_post: vec![],
+            },
+        );
+        let enricher = Enricher {
+            description: "".to_string(),
+            active: true,
+            payload_key: Some("enrichment".to_owned()),
+            with,
+        };
+
+        deploy_enricher_node(temp_dir.path(), "host_enricher", &enricher, &[config]).await.unwrap();
+
+        let loaded = read_enricher_from_dir(temp_dir.path()).await.unwrap();
+
+        match loaded {
+            MatcherConfig::Enricher { name, enricher: loaded_enricher, nodes } => {
+                assert_eq!("host_enricher", name);
+                assert_eq!(enricher, loaded_enricher);
+                assert_eq!(1, nodes.len());
+            }
+            _ => unreachable!(),
+        }
+    }
 }
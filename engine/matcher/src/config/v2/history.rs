@@ -0,0 +1,206 @@
+use crate::config::v1;
+use crate::config::v2::editor::serialize_config_node_to_file;
+use crate::config::v2::{
+    gather_dir_entries, parse_node_config_from_file, read_config_from_root_dir, MatcherConfigError,
+};
+use crate::config::{MatcherConfigSnapshot, MatcherConfigSnapshotData};
+use crate::error::MatcherError;
+use chrono::Local;
+use log::{debug, info, trace, warn};
+use std::path::{Path, PathBuf};
+
+/// The prefix of the directories where the snapshots are written before being renamed to their id
+const TEMP_SNAPSHOT_DIR_PREFIX: &str = ".snapshot";
+
+/// The history of the deployed configurations.
+/// Each snapshot is saved in a directory, named after its numeric id, that contains the
+/// snapshot data and a copy of the deployed configuration in the `config` directory.
+pub struct ConfigHistory {
+    pub path: PathBuf,
+    pub max_snapshots: usize,
+}
+
+impl ConfigHistory {
+    /// Returns the snapshots from the most recent one
+    pub async fn get_snapshots(&self) -> Result<Vec<MatcherConfigSnapshotData>, MatcherError> {
+        let mut snapshots = vec![];
+        for (_, snapshot_dir) in self.get_snapshot_dirs().await?.into_iter().rev() {
+            snapshots.push(parse_node_config_from_file(&snapshot_dir).await?);
+        }
+        Ok(snapshots)
+    }
+
+    pub async fn get_snapshot(
+        &self,
+        snapshot_id: &str,
+    ) -> Result<MatcherConfigSnapshot, MatcherError> {
+        let snapshot_dir = self.snapshot_dir(snapshot_id).await?;
+        debug!("Trying to load a snapshot from the directory {}", snapshot_dir.display());
+        let data = parse_node_config_from_file(&snapshot_dir).await?;
+        let config = read_config_from_root_dir(&snapshot_config_dir(&snapshot_dir)).await?;
+        Ok(MatcherConfigSnapshot { data, config })
+    }
+
+    /// Returns whether there are no snapshots
+    pub async fn is_empty(&self) -> Result<bool, MatcherError> {
+        Ok(self.get_snapshot_dirs().await?.is_empty())
+    }
+
+    /// Returns the directory of the configuration of a snapshot
+    pub async fn get_snapshot_config_dir(
+        &self,
+        snapshot_id: &str,
+    ) -> Result<PathBuf, MatcherError> {
+        Ok(snapshot_config_dir(&self.snapshot_dir(snapshot_id).await?))
+    }
+
+    /// Saves a copy of the configuration in `config_dir` as a new snapshot and deletes the
+    /// oldest snapshots exceeding `max_snapshots`.
+    /// The snapshot is written in a temporary directory that is then renamed to its id, so a
+    /// snapshot is either complete or missing. If another snapshot takes the same id in the
+    /// meantime, the rename fails and the next id is tried.
+    pub async fn create_snapshot(
+        &self,
+        config_dir: &Path,
+        user: &str,
+        comment: &str,
+    ) -> Result<MatcherConfigSnapshotData, MatcherError> {
+        if let Err(error) = tokio::fs::create_dir_all(&self.path).await {
+            return Err(MatcherError::InternalSystemError {
+                message: format!("Cannot create history directory: {:?}", error),
+            });
+        };
+        let temp_dir = tempfile::Builder::new()
+            .prefix(TEMP_SNAPSHOT_DIR_PREFIX)
+            .tempdir_in(&self.path)
+            .map_err(|error| MatcherError::InternalSystemError {
+                message: format!("Cannot create temporary snapshot directory: {:?}", error),
+            })?;
+        v1::fs::copy_and_override(config_dir, snapshot_config_dir(temp_dir.path())).await?;
+
+        let mut snapshot_id =
+            self.get_snapshot_dirs().await?.last().map(|(id, _)| id + 1).unwrap_or(1);
+        let data = loop {
+            let data = MatcherConfigSnapshotData {
+                snapshot_id: snapshot_id.to_string(),
+                created_ts_ms: Local::now().timestamp_millis(),
+                user: user.to_owned(),
+                comment: comment.to_owned(),
+            };
+            serialize_config_node_to_file(temp_dir.path(), &data).await?;
+            let snapshot_dir = self.path.join(&data.snapshot_id);
+            // The rename fails if the target is a directory that is not empty
+            match tokio::fs::rename(temp_dir.path(), &snapshot_dir).await {
+                Ok(()) => break data,
+                Err(_) if tokio::fs::try_exists(&snapshot_dir).await.unwrap_or(false) => {
+                    debug!("The snapshot {} already exists, trying the next id", snapshot_id);
+                    snapshot_id += 1;
+                }
+                Err(error) => {
+                    return Err(MatcherError::InternalSystemError {
+                        message: format!("Cannot save snapshot directory: {:?}", error),
+                    })
+                }
+            }
+        };
+        info!("Created the configuration snapshot {} for user {}", data.snapshot_id, user);
+
+        let snapshot_dirs = self.get_snapshot_dirs().await?;
+        // The most recent snapshots, including the new one, are kept
+        let expired_count = snapshot_dirs.len().saturating_sub(self.max_snapshots.max(1));
+        for (_, old_snapshot_dir) in &snapshot_dirs[..expired_count] {
+            info!("Deleting the configuration snapshot {}", old_snapshot_dir.display());
+            if let Err(error) = tokio::fs::remove_dir_all(old_snapshot_dir).await {
+                return Err(MatcherError::InternalSystemError {
+                    message: format!(
+                        "Cannot delete snapshot [{}]: {:?}",
+                        old_snapshot_dir.display(),
+                        error
+                    ),
+                });
+            }
+        }
+        Ok(data)
+    }
+
+    /// Returns the directory of a snapshot. Only numeric ids are accepted, so the id cannot
+    /// refer to a directory outside of the history.
+    async fn snapshot_dir(&self, snapshot_id: &str) -> Result<PathBuf, MatcherError> {
+        let snapshot_dir = self.path.join(snapshot_id);
+        if snapshot_id.parse::<u64>().is_err()
+            || !tokio::fs::try_exists(&snapshot_dir).await.unwrap_or(false)
+        {
+            return Err(MatcherError::SnapshotNotFoundError {
+                snapshot_id: snapshot_id.to_owned(),
+            });
+        }
+        Ok(snapshot_dir)
+    }
+
+    /// Returns the snapshot directories sorted by id, from the oldest one
+    async fn get_snapshot_dirs(&self) -> Result<Vec<(u64, PathBuf)>, MatcherConfigError> {
+        if !tokio::fs::try_exists(&self.path).await.unwrap_or(false) {
+            return Ok(vec![]);
+        }
+
+        let mut snapshot_dirs = vec![];
+        for entry in gather_dir_entries(&self.path).await? {
+            let entry_path = entry.path();
+            match entry.file_name().to_str().and_then(|name| name.parse::<u64>().ok()) {
+                Some(snapshot_id) if entry_path.is_dir() => {
+                    snapshot_dirs.push((snapshot_id, entry_path))
+                }
+                _ if entry.file_name().to_string_lossy().starts_with(TEMP_SNAPSHOT_DIR_PREFIX) => {
+                    trace!("Ignoring the snapshot being written in {}", entry_path.display())
+                }
+                _ => warn!(
+                    "Ignoring the entry {} in the history directory, as it is not a snapshot.",
+                    entry_path.display()
+                ),
+            }
+        }
+        snapshot_dirs.sort();
+        Ok(snapshot_dirs)
+    }
+}
+
+fn snapshot_config_dir(snapshot_dir: &Path) -> PathBuf {
+    let mut path = snapshot_dir.to_path_buf();
+    path.push("config");
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::v2::history::ConfigHistory;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    const TEST_CONFIG_DIR: &str = "./test_resources/v2/test_config/";
+
+    #[tokio::test]
+    async fn create_snapshot_should_assign_a_different_id_to_concurrent_snapshots() {
+        // Arrange
+        let temp_dir = TempDir::new().unwrap();
+        let history = ConfigHistory { path: temp_dir.path().join("history"), max_snapshots: 10 };
+
+        // Act
+        let results = futures::future::join_all(
+            (0..5).map(|_| history.create_snapshot(Path::new(TEST_CONFIG_DIR), "user", "")),
+        )
+        .await;
+
+        // Assert
+        let mut snapshot_ids: Vec<u64> = results
+            .into_iter()
+            .map(|result| result.unwrap().snapshot_id.parse().unwrap())
+            .collect();
+        snapshot_ids.sort();
+        assert_eq!(vec![1, 2, 3, 4, 5], snapshot_ids);
+        for snapshot in history.get_snapshots().await.unwrap() {
+            let config = history.get_snapshot(&snapshot.snapshot_id).await.unwrap().config;
+            assert_eq!("root", config.get_name());
+        }
+        assert_eq!(5, std::fs::read_dir(&history.path).unwrap().count());
+    }
+}
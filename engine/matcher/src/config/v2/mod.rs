@@ -1,11 +1,15 @@
 mod editor;
 mod error;
+mod history;
 
-use crate::config::nodes::{Filter, MatcherIterator};
+use crate::config::nodes::{Correlation, Enricher, Filter, Heartbeat, MatcherIterator};
 use crate::config::rule::Rule;
 pub use crate::config::v2::error::MatcherConfigError;
+use crate::config::v2::history::ConfigHistory;
 use crate::config::{Defaultable, MatcherConfig, MatcherConfigReader};
 use crate::error::MatcherError;
+use crate::resources::MatcherResources;
+use crate::suite::{ruleset_paths, RuleTestSuite, TESTS_DIR};
 use futures::stream::FuturesOrdered;
 use futures::StreamExt;
 use log::{debug, error, info, trace, warn};
@@ -13,15 +17,19 @@ use monostate::MustBe;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::error::Category;
+use std::collections::BTreeMap;
 use std::ffi::OsStr;
 use std::fmt::{Display, Formatter};
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 use tokio::fs::DirEntry;
 
 pub struct FsMatcherConfigManagerV2 {
     root_path: PathBuf,
     drafts_path: PathBuf,
+    history: Option<ConfigHistory>,
+    resources: RwLock<MatcherResources>,
 }
 
 impl FsMatcherConfigManagerV2 {
@@ -29,27 +37,53 @@ impl FsMatcherConfigManagerV2 {
         root_path: P1,
         drafts_path: P2,
     ) -> FsMatcherConfigManagerV2 {
-        FsMatcherConfigManagerV2 { root_path: root_path.into(), drafts_path: drafts_path.into() }
+        FsMatcherConfigManagerV2 {
+            root_path: root_path.into(),
+            drafts_path: drafts_path.into(),
+            history: None,
+            resources: RwLock::new(MatcherResources::default()),
+        }
+    }
+
+    /// Sets the resources, e.g. the grok patterns, used to validate and build the Rules.
+    pub fn with_resources(mut self, resources: MatcherResources) -> Self {
+        self.resources = RwLock::new(resources);
+        self
+    }
+
+    /// Saves a snapshot of the configuration in `history_path` after each deploy.
+    /// Only the most recent `max_snapshots` snapshots are kept.
+    pub fn with_history<P: Into<PathBuf>>(mut self, history_path: P, max_snapshots: usize) -> Self {
+        self.history = Some(ConfigHistory { path: history_path.into(), max_snapshots });
+        self
     }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum ConfigType {
     Draft,
+    Snapshot,
     Root,
     Filter,
     Iterator,
     Ruleset,
+    Correlation,
+    Heartbeat,
+    Enricher,
 }
 
 impl Display for ConfigType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             ConfigType::Draft => f.write_str("draft"),
+            ConfigType::Snapshot => f.write_str("snapshot"),
             ConfigType::Root => f.write_str("root"),
             ConfigType::Filter => f.write_str("filter"),
             ConfigType::Ruleset => f.write_str("ruleset"),
             ConfigType::Iterator => f.write_str("iterator"),
+            ConfigType::Correlation => f.write_str("correlation"),
+            ConfigType::Heartbeat => f.write_str("heartbeat"),
+            ConfigType::Enricher => f.write_str("enricher"),
         }
     }
 }
@@ -65,7 +99,11 @@ impl ConfigType {
             ConfigType::Filter => "filter.json",
             ConfigType::Ruleset => "ruleset.json",
             ConfigType::Draft => "data.json",
+            ConfigType::Snapshot => "snapshot.json",
             ConfigType::Iterator => "iterator.json",
+            ConfigType::Correlation => "correlation.json",
+            ConfigType::Heartbeat => "heartbeat.json",
+            ConfigType::Enricher => "enricher.json",
         }
     }
 }
@@ -119,6 +157,57 @@ impl ConfigNodeDir for MatcherConfigIterator {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MatcherConfigCorrelation {
+    #[serde(rename = "type")]
+    #[allow(dead_code)]
+    node_type: MustBe!("correlation"),
+    name: String,
+    #[serde(flatten)]
+    correlation: Correlation,
+}
+
+impl ConfigNodeDir for MatcherConfigCorrelation {
+    fn config_type() -> ConfigType {
+        ConfigType::Correlation
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MatcherConfigHeartbeat {
+    #[serde(rename = "type")]
+    #[allow(dead_code)]
+    node_type: MustBe!("heartbeat"),
+    name: String,
+    #[serde(flatten)]
+    heartbeat: Heartbeat,
+}
+
+impl ConfigNodeDir for MatcherConfigHeartbeat {
+    fn config_type() -> ConfigType {
+        ConfigType::Heartbeat
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MatcherConfigEnricher {
+    #[serde(rename = "type")]
+    #[allow(dead_code)]
+    node_type: MustBe!("enricher"),
+    name: String,
+    #[serde(flatten)]
+    enricher: Enricher,
+}
+
+impl ConfigNodeDir for MatcherConfigEnricher {
+    fn config_type() -> ConfigType {
+        ConfigType::Enricher
+    }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, tag = "version")]
 pub enum Version {
@@ -153,6 +242,15 @@ impl MatcherConfigReader for FsMatcherConfigManagerV2 {
     async fn get_config(&self) -> Result<MatcherConfig, MatcherError> {
         Ok(read_config_from_root_dir(&self.root_path).await?)
     }
+
+    async fn get_test_suites(&self) -> Result<Vec<RuleTestSuite>, MatcherError> {
+        let config = read_config_from_root_dir(&self.root_path).await?;
+        Ok(read_test_suites_from_root_dir(&self.root_path, &config).await?)
+    }
+
+    fn get_resources(&self) -> MatcherResources {
+        self.resources.read().expect("The resources lock should not be poisoned").clone()
+    }
 }
 
 pub async fn get_config_version(path: &Path) -> Result<Version, MatcherConfigError> {
@@ -189,11 +287,53 @@ async fn read_config_from_root_dir(root_dir: &Path) -> Result<MatcherConfig, Mat
             active: true,
             description: "An implicit filter that allows all events".to_owned(),
             filter: Defaultable::Default {},
+            schedule: None,
         },
         nodes,
     })
 }
 
+/// Reads the test cases stored in the `tests` directories of the Rulesets of the configuration.
+/// The Rulesets without test cases are skipped.
+async fn read_test_suites_from_root_dir(
+    root_dir: &Path,
+    config: &MatcherConfig,
+) -> Result<Vec<RuleTestSuite>, MatcherConfigError> {
+    let mut suites = vec![];
+    for ruleset_path in ruleset_paths(config) {
+        let tests_dir = ruleset_tests_dir(root_dir, &ruleset_path);
+        if !tokio::fs::try_exists(&tests_dir).await.unwrap_or(false) {
+            continue;
+        }
+
+        let mut cases = BTreeMap::new();
+        for dir_entry in gather_dir_entries(&tests_dir).await? {
+            let path = dir_entry.path();
+            if path.extension() != Some(OsStr::new("json")) {
+                info!("Ignoring file [{}] as it is not a test case.", path.display());
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(OsStr::to_str).map(str::to_owned) else {
+                return Err(MatcherConfigError::FileNameError { path });
+            };
+            cases.insert(name, parse_from_file(&path).await?);
+        }
+
+        if !cases.is_empty() {
+            suites.push(RuleTestSuite { ruleset_path, cases });
+        }
+    }
+    Ok(suites)
+}
+
+/// Returns the directory that contains the test cases of a Ruleset.
+fn ruleset_tests_dir(root_dir: &Path, ruleset_path: &[String]) -> PathBuf {
+    let mut path = root_dir.to_path_buf();
+    path.extend(ruleset_path);
+    path.push(TESTS_DIR);
+    path
+}
+
 #[async_recursion::async_recursion]
 async fn read_child_nodes_from_dir(
     dir: &Path,
@@ -286,6 +426,30 @@ async fn read_node_from_dir<T: AsRef<Path>>(
         Err(error) => return Err(error),
     }
 
+    match read_correlation_from_dir(dir).await {
+        Ok(config) => return Ok(FileEntry { path: dir.to_path_buf(), content: config }),
+        Err(MatcherConfigError::FileNotFound { .. }) => {
+            trace!("Directory {} seems not to be a correlation node.", dir.display())
+        }
+        Err(error) => return Err(error),
+    }
+
+    match read_heartbeat_from_dir(dir).await {
+        Ok(config) => return Ok(FileEntry { path: dir.to_path_buf(), content: config }),
+        Err(MatcherConfigError::FileNotFound { .. }) => {
+            trace!("Directory {} seems not to be a heartbeat node.", dir.display())
+        }
+        Err(error) => return Err(error),
+    }
+
+    match read_enricher_from_dir(dir).await {
+        Ok(config) => return Ok(FileEntry { path: dir.to_path_buf(), content: config }),
+        Err(MatcherConfigError::FileNotFound { .. }) => {
+            trace!("Directory {} seems not to be an enricher node.", dir.display())
+        }
+        Err(error) => return Err(error),
+    }
+
     match read_ruleset_from_dir(dir).await {
         Ok(config) => Ok(FileEntry { path: dir.to_path_buf(), content: config }),
         Err(MatcherConfigError::FileNotFound { .. }) => {
@@ -314,6 +478,29 @@ async fn read_iterator_from_dir(dir: &Path) -> Result<MatcherConfig, MatcherConf
     Ok(MatcherConfig::Iterator { name: node.name, iterator: node.iterator, nodes: child_nodes })
 }
 
+async fn read_correlation_from_dir(dir: &Path) -> Result<MatcherConfig, MatcherConfigError> {
+    trace!("Reading correlation node config file from disk.");
+    let node: MatcherConfigCorrelation = parse_node_config_from_file(dir).await?;
+
+    Ok(MatcherConfig::Correlation { name: node.name, correlation: node.correlation })
+}
+
+async fn read_heartbeat_from_dir(dir: &Path) -> Result<MatcherConfig, MatcherConfigError> {
+    trace!("Reading heartbeat node config file from disk.");
+    let node: MatcherConfigHeartbeat = parse_node_config_from_file(dir).await?;
+
+    Ok(MatcherConfig::Heartbeat { name: node.name, heartbeat: node.heartbeat })
+}
+
+async fn read_enricher_from_dir(dir: &Path) -> Result<MatcherConfig, MatcherConfigError> {
+    trace!("Reading enricher node config file from disk.");
+    let node: MatcherConfigEnricher = parse_node_config_from_file(dir).await?;
+    trace!("Reading enricher node child nodes from disk.");
+    let child_nodes = read_child_nodes_from_dir(dir, MatcherConfigEnricher::config_type()).await?;
+
+    Ok(MatcherConfig::Enricher { name: node.name, enricher: node.enricher, nodes: child_nodes })
+}
+
 async fn read_ruleset_from_dir(dir: &Path) -> Result<MatcherConfig, MatcherConfigError> {
     let rules_dir_path = {
         let mut path = PathBuf::from(dir);
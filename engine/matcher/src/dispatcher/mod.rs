@@ -1,5 +1,5 @@
 use crate::error::MatcherError;
-use crate::model::{ProcessedNode, ProcessedRuleStatus};
+use crate::model::{ProcessedCorrelationStatus, ProcessedNode, ProcessedRuleStatus};
 use log::*;
 use std::sync::Arc;
 use tornado_common::actors::message::ActionMessage;
@@ -56,6 +56,17 @@ impl Dispatcher {
                     self.dispatch_actions(node)?;
                 }
             }
+            ProcessedNode::Enricher { nodes, name, .. } => {
+                let _span = tracing::error_span!(
+                    "dispatch_enricher",
+                    name = name.as_str(),
+                    otel.name = format!("Emit Actions of Enricher: {}", name).as_str()
+                )
+                .entered();
+                for node in nodes {
+                    self.dispatch_actions(node)?;
+                }
+            }
             ProcessedNode::Iterator { name, events, .. } => {
                 let _span = tracing::error_span!(
                     "dispatch_iterator",
@@ -69,6 +80,29 @@ impl Dispatcher {
                     }
                 }
             }
+            ProcessedNode::Correlation { name, correlation } => {
+                let _span = tracing::error_span!(
+                    "dispatch_correlation",
+                    name = name.as_str(),
+                    otel.name = format!("Emit Actions of Correlation: {}", name).as_str()
+                )
+                .entered();
+                match correlation.status {
+                    ProcessedCorrelationStatus::Completed | ProcessedCorrelationStatus::Expired => {
+                        debug!(
+                            "Correlation [{}] {:?}, dispatching actions",
+                            name, correlation.status
+                        );
+                        self.dispatch(correlation.actions)?
+                    }
+                    _ => {
+                        trace!("Correlation [{}] not completed, ignoring actions", name);
+                    }
+                }
+            }
+            ProcessedNode::Heartbeat { name, .. } => {
+                trace!("Heartbeat [{}] has no actions to dispatch", name);
+            }
         };
         Ok(())
     }
@@ -96,7 +130,9 @@ impl Dispatcher {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::model::{ProcessedFilter, ProcessedFilterStatus, ProcessedRule, ProcessedRules};
+    use crate::model::{
+        ProcessedCorrelation, ProcessedFilter, ProcessedFilterStatus, ProcessedRule, ProcessedRules,
+    };
     use std::sync::{Arc, Mutex};
     use tornado_common_api::{Action, Map, Value};
     use tornado_network_simple::SimpleEventBus;
@@ -230,4 +266,38 @@ mod test {
         // Assert
         assert_eq!(2, received.lock().unwrap().len());
     }
+
+    #[test]
+    fn should_publish_actions_of_completed_and_expired_correlations() {
+        // Arrange
+        let mut bus = SimpleEventBus::new();
+        let received = Arc::new(Mutex::new(vec![]));
+
+        {
+            let clone = received.clone();
+            bus.subscribe_to_action(
+                "action1",
+                Box::new(move |message: ActionMessage| {
+                    let mut value = clone.lock().unwrap();
+                    value.push(message.0.action)
+                }),
+            );
+        }
+
+        let dispatcher = Dispatcher::build(Arc::new(bus)).unwrap();
+
+        let new_node = |status: ProcessedCorrelationStatus| {
+            let mut correlation = ProcessedCorrelation::new(status);
+            correlation.actions.push(Action::new("action1"));
+            ProcessedNode::Correlation { name: "correlation".to_owned(), correlation }
+        };
+
+        // Act
+        dispatcher.dispatch_actions(new_node(ProcessedCorrelationStatus::Started)).unwrap();
+        dispatcher.dispatch_actions(new_node(ProcessedCorrelationStatus::Completed)).unwrap();
+        dispatcher.dispatch_actions(new_node(ProcessedCorrelationStatus::Expired)).unwrap();
+
+        // Assert
+        assert_eq!(2, received.lock().unwrap().len());
+    }
 }
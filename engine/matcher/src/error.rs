@@ -18,6 +18,9 @@ pub enum MatcherError {
     #[error("ParseOperatorError: [{message}]")]
     ParseOperatorError { message: String },
 
+    #[error("ExpressionSyntaxError: Cannot parse the expression [{expression}] at line [{line}], column [{column}]: [{message}]")]
+    ExpressionSyntaxError { expression: String, message: String, line: usize, column: usize },
+
     #[error("UnknownOperatorError: Operator [{operator}] is unknown")]
     UnknownOperatorError { operator: String },
 
@@ -59,6 +62,15 @@ pub enum MatcherError {
     #[error("DraftNotFoundError: Could not find draft with id [{draft_id}]")]
     DraftNotFoundError { draft_id: String },
 
+    #[error("SnapshotNotFoundError: Could not find snapshot with id [{snapshot_id}]")]
+    SnapshotNotFoundError { snapshot_id: String },
+
+    #[error("LookupTableLoadError: Cannot load lookup table [{table}] from [{source_file}]\n cause: [{cause}]")]
+    LookupTableLoadError { table: String, source_file: String, cause: String },
+
+    #[error("UnresolvedKeyError: Cannot resolve the [{key}] of rule [{rule_name}] for the event")]
+    UnresolvedKeyError { key: &'static str, rule_name: String },
+
     #[error("NestedIteratorError")]
     NestedIteratorError,
 }
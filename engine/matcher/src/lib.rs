@@ -4,9 +4,12 @@ pub mod accessor;
 pub mod config;
 pub mod dispatcher;
 pub mod error;
+pub mod lookup;
 pub mod matcher;
 pub mod model;
 pub mod regex;
+pub mod resources;
+pub mod suite;
 pub mod validator;
 
 #[cfg(test)]
@@ -0,0 +1,328 @@
+//! The lookup module contains the static lookup tables used by the Rules to enrich the
+//! extracted values, e.g. to find the team in charge of a host.
+//!
+//! A lookup table maps a key to a value and it is loaded from a file:
+//! - a CSV file with a header: the first column is the key and each row is mapped to
+//!   an object with one entry per column;
+//! - a JSON file containing an object: each entry maps a key to its value.
+//!
+//! The name of the table is the name of the file without the extension.
+
+use crate::error::MatcherError;
+use log::*;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+const CSV_EXTENSION: &str = "csv";
+const JSON_EXTENSION: &str = "json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LookupTableFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LookupTable {
+    name: String,
+    format: LookupTableFormat,
+    source: String,
+    rows: HashMap<String, Value>,
+}
+
+impl LookupTable {
+    /// Builds a table from the content of a CSV file with a header.
+    /// Every row is mapped to an object that contains all its columns; the value of the
+    /// first column is the key of the row.
+    pub fn from_csv(name: &str, source: &str, content: &str) -> Result<LookupTable, MatcherError> {
+        let mut reader =
+            csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(content.as_bytes());
+        let headers = reader
+            .headers()
+            .map_err(|err| LookupTable::load_error(name, source, err))?
+            .iter()
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        if headers.is_empty() {
+            return Err(LookupTable::load_error(name, source, "the header is missing"));
+        }
+
+        let mut rows = HashMap::new();
+        for record in reader.records() {
+            let record = record.map_err(|err| LookupTable::load_error(name, source, err))?;
+            let key = record.get(0).unwrap_or_default().to_owned();
+            let row = headers
+                .iter()
+                .zip(record.iter())
+                .map(|(header, field)| (header.to_owned(), Value::String(field.to_owned())))
+                .collect::<Map<_, _>>();
+            LookupTable::insert_row(name, source, &mut rows, key, Value::Object(row))?;
+        }
+
+        Ok(LookupTable {
+            name: name.to_owned(),
+            format: LookupTableFormat::Csv,
+            source: source.to_owned(),
+            rows,
+        })
+    }
+
+    /// Builds a table from the content of a JSON file that contains an object.
+    pub fn from_json(name: &str, source: &str, content: &str) -> Result<LookupTable, MatcherError> {
+        let rows = match serde_json::from_str(content)
+            .map_err(|err| LookupTable::load_error(name, source, err))?
+        {
+            Value::Object(entries) => entries.into_iter().collect(),
+            _ => {
+                return Err(LookupTable::load_error(
+                    name,
+                    source,
+                    "the file must contain a JSON object",
+                ))
+            }
+        };
+
+        Ok(LookupTable {
+            name: name.to_owned(),
+            format: LookupTableFormat::Json,
+            source: source.to_owned(),
+            rows,
+        })
+    }
+
+    /// Builds a table from a CSV or JSON file, according to its extension.
+    pub fn from_file(path: &Path) -> Result<LookupTable, MatcherError> {
+        let source = path.display().to_string();
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| LookupTable::load_error("", &source, "invalid file name"))?;
+        let content =
+            fs::read_to_string(path).map_err(|err| LookupTable::load_error(name, &source, err))?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(CSV_EXTENSION) => LookupTable::from_csv(name, &source, &content),
+            Some(JSON_EXTENSION) => LookupTable::from_json(name, &source, &content),
+            _ => Err(LookupTable::load_error(
+                name,
+                &source,
+                "unsupported file format, expected a .csv or .json file",
+            )),
+        }
+    }
+
+    fn insert_row(
+        name: &str,
+        source: &str,
+        rows: &mut HashMap<String, Value>,
+        key: String,
+        row: Value,
+    ) -> Result<(), MatcherError> {
+        if rows.contains_key(&key) {
+            return Err(LookupTable::load_error(
+                name,
+                source,
+                format!("the key [{}] is not unique", key),
+            ));
+        }
+        rows.insert(key, row);
+        Ok(())
+    }
+
+    fn load_error<E: ToString>(name: &str, source: &str, cause: E) -> MatcherError {
+        MatcherError::LookupTableLoadError {
+            table: name.to_owned(),
+            source_file: source.to_owned(),
+            cause: cause.to_string(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn format(&self) -> LookupTableFormat {
+        self.format
+    }
+
+    /// Returns the file from which the table was loaded.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.rows.get(key)
+    }
+}
+
+/// A set of lookup tables with unique names.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LookupTables {
+    tables: BTreeMap<String, Arc<LookupTable>>,
+}
+
+impl LookupTables {
+    /// Loads a table from each file of the folder. It returns no tables if the folder does
+    /// not exist.
+    pub fn from_dir(dir: &Path) -> Result<LookupTables, MatcherError> {
+        let mut tables = LookupTables::default();
+        if !dir.is_dir() {
+            debug!("The lookup tables folder [{}] does not exist", dir.display());
+            return Ok(tables);
+        }
+
+        let read_dir_error = |err: std::io::Error| MatcherError::ConfigurationError {
+            message: format!("Cannot read the lookup tables folder [{}]: {}", dir.display(), err),
+        };
+        let mut paths = fs::read_dir(dir)
+            .map_err(read_dir_error)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(read_dir_error)?;
+        paths.retain(|path| path.is_file());
+        paths.sort();
+
+        for path in paths {
+            let table = LookupTable::from_file(&path)?;
+            info!(
+                "Loaded lookup table [{}] with {} rows from [{}]",
+                table.name(),
+                table.len(),
+                table.source()
+            );
+            tables.add(table)?;
+        }
+        Ok(tables)
+    }
+
+    /// Adds a table. It fails if a table with the same name already exists.
+    pub fn add(&mut self, table: LookupTable) -> Result<(), MatcherError> {
+        if let Some(existing) = self.tables.get(table.name()) {
+            return Err(MatcherError::LookupTableLoadError {
+                table: table.name().to_owned(),
+                source_file: table.source().to_owned(),
+                cause: format!("a table with the same name is loaded from [{}]", existing.source()),
+            });
+        }
+        self.tables.insert(table.name().to_owned(), Arc::new(table));
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&LookupTable> {
+        self.tables.get(name).map(Arc::as_ref)
+    }
+
+    /// Returns a shared handle to the table with the given name.
+    pub fn get_shared(&self, name: &str) -> Option<Arc<LookupTable>> {
+        self.tables.get(name).cloned()
+    }
+
+    /// Returns the tables ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = &LookupTable> {
+        self.tables.values().map(Arc::as_ref)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn should_build_table_from_csv() {
+        // Act
+        let table = LookupTable::from_csv(
+            "teams",
+            "teams.csv",
+            "host,team , email\nweb01,ops,ops@example.com\ndb01,\"dba, core\",dba@example.com\n",
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!("teams", table.name());
+        assert_eq!(LookupTableFormat::Csv, table.format());
+        assert_eq!(2, table.len());
+        assert_eq!(
+            Some(&json!({ "host": "web01", "team": "ops", "email": "ops@example.com" })),
+            table.get("web01")
+        );
+        assert_eq!(Some(&json!("dba, core")), table.get("db01").and_then(|row| row.get("team")));
+        assert_eq!(None, table.get("host"));
+    }
+
+    #[test]
+    fn should_build_table_from_json() {
+        // Act
+        let table = LookupTable::from_json(
+            "severities",
+            "severities.json",
+            r#"{ "0": "ok", "2": { "name": "critical", "page": true } }"#,
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(LookupTableFormat::Json, table.format());
+        assert_eq!(2, table.len());
+        assert_eq!(Some(&json!("ok")), table.get("0"));
+        assert_eq!(Some(&json!({ "name": "critical", "page": true })), table.get("2"));
+    }
+
+    #[test]
+    fn build_should_fail_with_invalid_content() {
+        assert!(LookupTable::from_csv("t", "t.csv", "host,team\nweb01,ops,extra\n").is_err());
+        assert!(LookupTable::from_csv("t", "t.csv", "host,team\nweb01,ops\nweb01,dev\n").is_err());
+        assert!(LookupTable::from_json("t", "t.json", r#"["web01"]"#).is_err());
+        assert!(LookupTable::from_json("t", "t.json", r#"{ "web01": "#).is_err());
+    }
+
+    #[test]
+    fn should_load_tables_from_dir() {
+        // Arrange
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("teams.csv"), "host,team\nweb01,ops\n").unwrap();
+        fs::write(dir.path().join("codes.json"), r#"{ "404": "not found" }"#).unwrap();
+
+        // Act
+        let tables = LookupTables::from_dir(dir.path()).unwrap();
+
+        // Assert
+        assert_eq!(
+            vec!["codes", "teams"],
+            tables.iter().map(LookupTable::name).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(&json!("not found")),
+            tables.get("codes").and_then(|table| table.get("404"))
+        );
+        assert!(tables.get("unknown").is_none());
+    }
+
+    #[test]
+    fn load_from_dir_should_fail_with_duplicated_or_unsupported_tables() {
+        // Arrange
+        let duplicated_dir = tempfile::tempdir().unwrap();
+        fs::write(duplicated_dir.path().join("teams.csv"), "host,team\n").unwrap();
+        fs::write(duplicated_dir.path().join("teams.json"), "{}").unwrap();
+        let unsupported_dir = tempfile::tempdir().unwrap();
+        fs::write(unsupported_dir.path().join("teams.txt"), "web01 ops").unwrap();
+
+        // Act & Assert
+        assert!(LookupTables::from_dir(duplicated_dir.path()).is_err());
+        assert!(LookupTables::from_dir(unsupported_dir.path()).is_err());
+        assert_eq!(
+            LookupTables::default(),
+            LookupTables::from_dir(&duplicated_dir.path().join("missing")).unwrap()
+        );
+    }
+}
@@ -0,0 +1,463 @@
+//! The correlation module contains the logic to detect ordered sequences of events
+//! sharing the same correlation key.
+
+use crate::accessor::{Accessor, AccessorBuilder};
+use crate::config::nodes::Correlation;
+use crate::error::MatcherError;
+use crate::matcher::action::{ActionResolver, ActionResolverBuilder};
+use crate::matcher::operator::{Operator, OperatorBuilder};
+use crate::model::{InternalEvent, ProcessedCorrelation, ProcessedCorrelationStatus};
+use crate::resources::MatcherResources;
+use log::*;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Keeps the pending sequences of all the Correlation nodes of a processing tree.
+#[derive(Debug, Default)]
+pub struct CorrelationStore {
+    sequences: Mutex<HashMap<String, NodeSequences>>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct NodeSequences {
+    pending: HashMap<String, Sequence>,
+    // Sequences found expired while processing an event; they wait for the next expiration check.
+    expired: Vec<Sequence>,
+}
+
+#[derive(Debug, Clone)]
+struct Sequence {
+    key: String,
+    started_ms: u64,
+    matched_steps: usize,
+    event: Value,
+}
+
+enum SequenceProgress {
+    NotMatched,
+    Started,
+    Progressed(usize),
+    Completed(usize),
+}
+
+impl CorrelationStore {
+    pub fn new() -> CorrelationStore {
+        CorrelationStore::default()
+    }
+
+    /// Returns a detached copy of the current sequences.
+    /// Events recorded in the copy are not visible in the original store.
+    pub fn snapshot(&self) -> CorrelationStore {
+        let sequences = self.sequences.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        CorrelationStore { sequences: Mutex::new(sequences) }
+    }
+
+    /// Removes the sequences of the Correlation nodes whose path is not in `paths`
+    pub fn retain(&self, paths: &HashSet<String>) {
+        self.sequences
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|path, _| paths.contains(path));
+    }
+
+    /// Advances the sequence of the key if the event matches its next step, or starts a new
+    /// sequence if the event matches the first step and no sequence is pending for the key.
+    #[allow(clippy::too_many_arguments)]
+    fn advance<F: Fn(usize) -> bool>(
+        &self,
+        node_key: &str,
+        key: &str,
+        steps: usize,
+        timeout_ms: u64,
+        now_ms: u64,
+        event: &Value,
+        matches_step: F,
+    ) -> SequenceProgress {
+        let mut sequences = self.sequences.lock().unwrap_or_else(PoisonError::into_inner);
+        let node_sequences = sequences.entry(node_key.to_owned()).or_default();
+
+        let is_expired = node_sequences
+            .pending
+            .get(key)
+            .map(|sequence| sequence.started_ms + timeout_ms <= now_ms)
+            .unwrap_or(false);
+        if is_expired {
+            if let Some(sequence) = node_sequences.pending.remove(key) {
+                node_sequences.expired.push(sequence);
+            }
+        }
+
+        match node_sequences.pending.get_mut(key) {
+            Some(sequence) => {
+                if sequence.matched_steps >= steps || !matches_step(sequence.matched_steps) {
+                    return SequenceProgress::NotMatched;
+                }
+                sequence.matched_steps += 1;
+                let matched_steps = sequence.matched_steps;
+                if matched_steps == steps {
+                    node_sequences.pending.remove(key);
+                    SequenceProgress::Completed(matched_steps)
+                } else {
+                    SequenceProgress::Progressed(matched_steps)
+                }
+            }
+            None if matches_step(0) => {
+                if steps == 1 {
+                    return SequenceProgress::Completed(1);
+                }
+                node_sequences.pending.insert(
+                    key.to_owned(),
+                    Sequence {
+                        key: key.to_owned(),
+                        started_ms: now_ms,
+                        matched_steps: 1,
+                        event: event.clone(),
+                    },
+                );
+                SequenceProgress::Started
+            }
+            None => SequenceProgress::NotMatched,
+        }
+    }
+
+    /// Removes and returns all the sequences of the node that are expired at `now_ms`.
+    fn expire(&self, node_key: &str, timeout_ms: u64, now_ms: u64) -> Vec<Sequence> {
+        let mut sequences = self.sequences.lock().unwrap_or_else(PoisonError::into_inner);
+        let node_sequences = match sequences.get_mut(node_key) {
+            Some(node_sequences) => node_sequences,
+            None => return vec![],
+        };
+
+        let mut expired = std::mem::take(&mut node_sequences.expired);
+        let expired_keys = node_sequences
+            .pending
+            .iter()
+            .filter(|(_, sequence)| sequence.started_ms + timeout_ms <= now_ms)
+            .map(|(key, _)| key.to_owned())
+            .collect::<Vec<_>>();
+        for key in expired_keys {
+            if let Some(sequence) = node_sequences.pending.remove(&key) {
+                expired.push(sequence);
+            }
+        }
+        expired
+    }
+}
+
+/// The Matcher's internal representation of a config::nodes::Correlation.
+pub struct MatcherCorrelation {
+    node_key: String,
+    active: bool,
+    correlate_by: Accessor,
+    steps: Vec<Box<dyn Operator>>,
+    timeout_seconds: u64,
+    on_complete: Vec<ActionResolver>,
+    on_timeout: Vec<ActionResolver>,
+    store: Arc<CorrelationStore>,
+}
+
+impl MatcherCorrelation {
+    pub fn build(
+        name: &str,
+        node_key: String,
+        correlation: &Correlation,
+        store: Arc<CorrelationStore>,
+        resources: &MatcherResources,
+    ) -> Result<MatcherCorrelation, MatcherError> {
+        if correlation.steps.is_empty() || correlation.timeout_seconds == 0 {
+            return Err(MatcherError::ConfigurationError {
+                message: format!(
+                    "The correlation [{}] must have at least one step and a timeout_seconds greater than zero",
+                    name
+                ),
+            });
+        }
+
+        let operator_builder = OperatorBuilder::new().with_resources(resources.clone());
+        let action_builder = ActionResolverBuilder::new();
+
+        Ok(MatcherCorrelation {
+            node_key,
+            active: correlation.active,
+            correlate_by: AccessorBuilder::new().build(name, &correlation.correlate_by)?,
+            steps: correlation
+                .steps
+                .iter()
+                .map(|step| operator_builder.build(name, step))
+                .collect::<Result<Vec<_>, _>>()?,
+            timeout_seconds: correlation.timeout_seconds,
+            on_complete: action_builder.build_all(name, &correlation.on_complete)?,
+            on_timeout: action_builder.build_all(name, &correlation.on_timeout)?,
+            store,
+        })
+    }
+
+    /// Matches the event against the pending sequence of its correlation key.
+    /// When the sequence is completed, the `on_complete` actions are resolved against the event.
+    pub fn process(&self, event: &Value, now_ms: u64) -> ProcessedCorrelation {
+        let mut processed = ProcessedCorrelation::new(ProcessedCorrelationStatus::NotMatched);
+
+        if !self.active {
+            processed.status = ProcessedCorrelationStatus::Inactive;
+            return processed;
+        }
+
+        let mut extracted_vars = Value::Object(Map::new());
+        let internal_event = InternalEvent { event, extracted_variables: &mut extracted_vars };
+
+        let key = match self.correlate_by.get(&internal_event) {
+            Some(value) => match value.as_ref() {
+                Value::String(text) => text.to_owned(),
+                value => value.to_string(),
+            },
+            None => {
+                trace!(
+                    "MatcherCorrelation - correlation [{}] cannot resolve the correlation key",
+                    self.node_key
+                );
+                return processed;
+            }
+        };
+
+        let progress = self.store.advance(
+            &self.node_key,
+            &key,
+            self.steps.len(),
+            self.timeout_seconds * 1000,
+            now_ms,
+            event,
+            |step| self.steps[step].evaluate(&internal_event),
+        );
+        trace!("MatcherCorrelation - correlation [{}] processed key [{}]", self.node_key, key);
+
+        match progress {
+            SequenceProgress::NotMatched => {}
+            SequenceProgress::Started => {
+                processed.status = ProcessedCorrelationStatus::Started;
+                processed.matched_steps = 1;
+            }
+            SequenceProgress::Progressed(matched_steps) => {
+                processed.status = ProcessedCorrelationStatus::Progressed;
+                processed.matched_steps = matched_steps;
+            }
+            SequenceProgress::Completed(matched_steps) => {
+                processed.matched_steps = matched_steps;
+                match resolve_actions(&self.on_complete, &internal_event) {
+                    Ok(actions) => {
+                        processed.status = ProcessedCorrelationStatus::Completed;
+                        processed.actions = actions;
+                    }
+                    Err(e) => {
+                        let message = format!("MatcherCorrelation - The sequence of correlation [{}] with key [{}] is completed; however, some actions cannot be resolved: [{:?}]", self.node_key, key, e);
+                        debug!("{}", &message);
+                        processed.status = ProcessedCorrelationStatus::PartiallyMatched;
+                        processed.message = Some(message);
+                    }
+                }
+            }
+        }
+
+        processed.key = Some(key);
+        processed
+    }
+
+    /// Removes the sequences expired at `now_ms` and resolves their `on_timeout` actions
+    /// against the event that started each sequence.
+    /// Returns, for each expired sequence, the starting event and the processing result.
+    pub fn expire(&self, now_ms: u64) -> Vec<(Value, ProcessedCorrelation)> {
+        self.store
+            .expire(&self.node_key, self.timeout_seconds * 1000, now_ms)
+            .into_iter()
+            .map(|sequence| {
+                let mut extracted_vars = Value::Object(Map::new());
+                let internal_event =
+                    InternalEvent { event: &sequence.event, extracted_variables: &mut extracted_vars };
+
+                let mut processed = ProcessedCorrelation::new(ProcessedCorrelationStatus::Expired);
+                processed.matched_steps = sequence.matched_steps;
+                match resolve_actions(&self.on_timeout, &internal_event) {
+                    Ok(actions) => processed.actions = actions,
+                    Err(e) => {
+                        let message = format!("MatcherCorrelation - The sequence of correlation [{}] with key [{}] is expired; however, some actions cannot be resolved: [{:?}]", self.node_key, sequence.key, e);
+                        debug!("{}", &message);
+                        processed.status = ProcessedCorrelationStatus::PartiallyMatched;
+                        processed.message = Some(message);
+                    }
+                }
+                processed.key = Some(sequence.key);
+                (sequence.event, processed)
+            })
+            .collect()
+    }
+}
+
+fn resolve_actions(
+    actions: &[ActionResolver],
+    internal_event: &InternalEvent,
+) -> Result<Vec<tornado_common_api::Action>, MatcherError> {
+    actions.iter().map(|action| action.resolve(internal_event)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::rule::{ConfigAction, Operator as ConfigOperator};
+    use serde_json::json;
+    use tornado_common_api::{Payload, ValueExt};
+
+    fn new_correlation(steps: Vec<&str>, store: Arc<CorrelationStore>) -> MatcherCorrelation {
+        let mut payload = Payload::new();
+        payload.insert("host".to_owned(), Value::String("${event.payload.host}".to_owned()));
+
+        let correlation = Correlation {
+            description: "".to_owned(),
+            active: true,
+            correlate_by: "${event.payload.host}".to_owned(),
+            steps: steps
+                .into_iter()
+                .map(|event_type| ConfigOperator::Equals {
+                    first: Value::String("${event.type}".to_owned()),
+                    second: Value::String(event_type.to_owned()),
+                })
+                .collect(),
+            timeout_seconds: 30,
+            on_complete: vec![ConfigAction {
+                id: "completed".to_owned(),
+                payload: payload.clone(),
+            }],
+            on_timeout: vec![ConfigAction { id: "expired".to_owned(), payload }],
+        };
+        MatcherCorrelation::build(
+            "correlation",
+            "correlation".to_owned(),
+            &correlation,
+            store,
+            &MatcherResources::default(),
+        )
+        .unwrap()
+    }
+
+    fn new_event(event_type: &str, host: &str) -> Value {
+        json!({"type": event_type, "created_ms": 0, "payload": {"host": host}})
+    }
+
+    #[test]
+    fn should_fail_if_steps_are_empty() {
+        // Arrange
+        let correlation = Correlation { timeout_seconds: 30, ..Default::default() };
+
+        // Act
+        let result = MatcherCorrelation::build(
+            "correlation",
+            "correlation".to_owned(),
+            &correlation,
+            Default::default(),
+            &MatcherResources::default(),
+        );
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_complete_a_sequence_within_the_timeout() {
+        // Arrange
+        let correlation = new_correlation(vec!["link_down", "bgp_down"], Default::default());
+
+        // Act
+        let first = correlation.process(&new_event("link_down", "host_1"), 1_000);
+        let second = correlation.process(&new_event("bgp_down", "host_1"), 2_000);
+        let third = correlation.process(&new_event("bgp_down", "host_1"), 3_000);
+
+        // Assert
+        assert_eq!(ProcessedCorrelationStatus::Started, first.status);
+        assert_eq!(Some("host_1".to_owned()), first.key);
+        assert!(first.actions.is_empty());
+
+        assert_eq!(ProcessedCorrelationStatus::Completed, second.status);
+        assert_eq!(2, second.matched_steps);
+        assert_eq!(1, second.actions.len());
+        assert_eq!("completed", second.actions[0].id);
+        assert_eq!(
+            Some("host_1"),
+            second.actions[0].payload.get("host").and_then(|host| host.get_text())
+        );
+
+        assert_eq!(ProcessedCorrelationStatus::NotMatched, third.status);
+    }
+
+    #[test]
+    fn should_correlate_events_by_key() {
+        // Arrange
+        let correlation = new_correlation(vec!["link_down", "bgp_down"], Default::default());
+
+        // Act
+        let first = correlation.process(&new_event("link_down", "host_1"), 1_000);
+        let second = correlation.process(&new_event("bgp_down", "host_2"), 2_000);
+
+        // Assert
+        assert_eq!(ProcessedCorrelationStatus::Started, first.status);
+        assert_eq!(ProcessedCorrelationStatus::NotMatched, second.status);
+    }
+
+    #[test]
+    fn should_match_the_steps_in_order() {
+        // Arrange
+        let correlation =
+            new_correlation(vec!["link_down", "bgp_down", "host_down"], Default::default());
+
+        // Act
+        let first = correlation.process(&new_event("link_down", "host_1"), 1_000);
+        let second = correlation.process(&new_event("host_down", "host_1"), 2_000);
+        let third = correlation.process(&new_event("bgp_down", "host_1"), 3_000);
+        let fourth = correlation.process(&new_event("host_down", "host_1"), 4_000);
+
+        // Assert
+        assert_eq!(ProcessedCorrelationStatus::Started, first.status);
+        assert_eq!(ProcessedCorrelationStatus::NotMatched, second.status);
+        assert_eq!(ProcessedCorrelationStatus::Progressed, third.status);
+        assert_eq!(2, third.matched_steps);
+        assert_eq!(ProcessedCorrelationStatus::Completed, fourth.status);
+    }
+
+    #[test]
+    fn should_expire_a_sequence_after_the_timeout() {
+        // Arrange
+        let correlation = new_correlation(vec!["problem", "recovery"], Default::default());
+        let problem = new_event("problem", "host_1");
+        correlation.process(&problem, 1_000);
+
+        // Act
+        let not_yet_expired = correlation.expire(30_999);
+        let expired = correlation.expire(31_000);
+        let recovery = correlation.process(&new_event("recovery", "host_1"), 32_000);
+
+        // Assert
+        assert!(not_yet_expired.is_empty());
+        assert_eq!(1, expired.len());
+        let (event, processed) = &expired[0];
+        assert_eq!(&problem, event);
+        assert_eq!(ProcessedCorrelationStatus::Expired, processed.status);
+        assert_eq!(Some("host_1".to_owned()), processed.key);
+        assert_eq!(1, processed.actions.len());
+        assert_eq!("expired", processed.actions[0].id);
+        assert_eq!(ProcessedCorrelationStatus::NotMatched, recovery.status);
+    }
+
+    #[test]
+    fn should_keep_a_sequence_found_expired_during_processing() {
+        // Arrange
+        let correlation = new_correlation(vec!["problem", "recovery"], Default::default());
+        correlation.process(&new_event("problem", "host_1"), 1_000);
+
+        // Act
+        let restarted = correlation.process(&new_event("problem", "host_1"), 40_000);
+        let expired = correlation.expire(41_000);
+
+        // Assert
+        assert_eq!(ProcessedCorrelationStatus::Started, restarted.status);
+        assert_eq!(1, expired.len());
+        assert_eq!(ProcessedCorrelationStatus::Expired, expired[0].1.status);
+    }
+}
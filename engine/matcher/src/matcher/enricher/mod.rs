@@ -0,0 +1,196 @@
+//! The enricher module contains the logic to add the variables extracted by an Enricher
+//! node to the payload of the Events.
+
+use crate::config::nodes::Enricher;
+use crate::error::MatcherError;
+use crate::matcher::extractor::{MatcherExtractor, MatcherExtractorBuilder};
+use crate::model::{InternalEvent, ProcessedEnricher, ProcessedEnricherStatus};
+use crate::resources::MatcherResources;
+use log::*;
+use serde_json::{Map, Value};
+use tornado_common_api::EVENT_PAYLOAD;
+
+/// The Matcher's internal representation of a config::nodes::Enricher.
+#[derive(Debug)]
+pub struct MatcherEnricher {
+    active: bool,
+    payload_key: Option<String>,
+    extractor: MatcherExtractor,
+}
+
+impl MatcherEnricher {
+    pub fn build(
+        name: &str,
+        enricher: &Enricher,
+        resources: &MatcherResources,
+    ) -> Result<MatcherEnricher, MatcherError> {
+        Ok(MatcherEnricher {
+            active: enricher.active,
+            payload_key: enricher.payload_key.clone(),
+            extractor: MatcherExtractorBuilder::new()
+                .with_resources(resources.clone())
+                .build(name, &enricher.with)?,
+        })
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Returns a copy of the Event with the extracted variables added to its payload.
+    /// The variables that cannot be extracted are skipped and reported in the message of the
+    /// ProcessedEnricher, whose status is then `PartiallyEnriched`.
+    pub fn enrich(&self, event: &Value) -> (Value, ProcessedEnricher) {
+        let mut values = Map::new();
+        let mut errors = vec![];
+        {
+            let internal_event =
+                InternalEvent { event, extracted_variables: &mut Value::Object(Map::new()) };
+            for (name, result) in self.extractor.extract_each(&internal_event) {
+                match result {
+                    Ok(value) => {
+                        values.insert(name.to_owned(), value);
+                    }
+                    Err(error) => {
+                        trace!("MatcherEnricher - cannot extract variable [{}]: {:?}", name, error);
+                        errors.push(format!("[{}]: {}", name, error));
+                    }
+                }
+            }
+        }
+
+        let mut enriched_event = event.clone();
+        if let Err(error) = self.add_to_payload(&mut enriched_event, values.clone()) {
+            errors.push(error);
+        }
+
+        let status = if errors.is_empty() {
+            ProcessedEnricherStatus::Enriched
+        } else {
+            ProcessedEnricherStatus::PartiallyEnriched
+        };
+        let message = if errors.is_empty() { None } else { Some(errors.join(", ")) };
+
+        (enriched_event, ProcessedEnricher { status, values: Value::Object(values), message })
+    }
+
+    fn add_to_payload(&self, event: &mut Value, values: Map<String, Value>) -> Result<(), String> {
+        let payload = match event {
+            Value::Object(event) => {
+                event.entry(EVENT_PAYLOAD).or_insert_with(|| Value::Object(Map::new()))
+            }
+            _ => return Err("The event is not an object".to_owned()),
+        };
+        let payload = match payload {
+            Value::Object(payload) => payload,
+            _ => return Err("The event payload is not an object".to_owned()),
+        };
+
+        match &self.payload_key {
+            Some(payload_key) => match payload.get_mut(payload_key) {
+                Some(Value::Object(existing)) => existing.extend(values),
+                _ => {
+                    payload.insert(payload_key.to_owned(), Value::Object(values));
+                }
+            },
+            None => payload.extend(values),
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::rule::{Extractor, ExtractorRegex, Modifier};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn enricher(payload_key: Option<&str>) -> MatcherEnricher {
+        let mut with = HashMap::new();
+        with.insert(
+            "host".to_owned(),
+            Extractor {
+                from: "${event.payload.hostname}".to_owned(),
+                regex: ExtractorRegex::Regex {
+                    regex: "^[a-z]+".to_owned(),
+                    group_match_idx: Some(0),
+                    all_matches: None,
+                },
+                modifiers_post: vec![Modifier::Uppercase {}],
+            },
+        );
+        with.insert(
+            "site".to_owned(),
+            Extractor {
+                from: "${event.payload.site}".to_owned(),
+                regex: ExtractorRegex::Regex {
+                    regex: ".*".to_owned(),
+                    group_match_idx: Some(0),
+                    all_matches: None,
+                },
+                modifiers_post: vec![],
+            },
+        );
+        MatcherEnricher::build(
+            "enricher",
+            &Enricher {
+                description: "".to_owned(),
+                active: true,
+                payload_key: payload_key.map(str::to_owned),
+                with,
+            },
+            &MatcherResources::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn should_add_the_variables_to_the_payload() {
+        // Arrange
+        let event = json!({ "type": "email", "payload": { "hostname": "web01", "site": "bz" } });
+
+        // Act
+        let (enriched, processed) = enricher(None).enrich(&event);
+
+        // Assert
+        assert_eq!(ProcessedEnricherStatus::Enriched, processed.status);
+        assert_eq!(None, processed.message);
+        assert_eq!(json!({ "host": "WEB", "site": "bz" }), processed.values);
+        assert_eq!(
+            json!({ "type": "email", "payload": { "hostname": "web01", "site": "bz", "host": "WEB" } }),
+            enriched
+        );
+    }
+
+    #[test]
+    fn should_add_the_variables_to_the_payload_key() {
+        // Arrange
+        let event = json!({ "payload": { "hostname": "web01", "site": "bz", "enrichment": { "team": "ops" } } });
+
+        // Act
+        let (enriched, processed) = enricher(Some("enrichment")).enrich(&event);
+
+        // Assert
+        assert_eq!(ProcessedEnricherStatus::Enriched, processed.status);
+        assert_eq!(
+            json!({ "team": "ops", "host": "WEB", "site": "bz" }),
+            enriched["payload"]["enrichment"]
+        );
+        assert_eq!(json!("web01"), enriched["payload"]["hostname"]);
+    }
+
+    #[test]
+    fn should_skip_the_variables_that_cannot_be_extracted() {
+        // Arrange
+        let event = json!({ "payload": { "hostname": "web01" } });
+
+        // Act
+        let (enriched, processed) = enricher(Some("enrichment")).enrich(&event);
+
+        // Assert
+        assert_eq!(ProcessedEnricherStatus::PartiallyEnriched, processed.status);
+        assert!(processed.message.unwrap().contains("[site]"));
+        assert_eq!(json!({ "host": "WEB" }), enriched["payload"]["enrichment"]);
+    }
+}
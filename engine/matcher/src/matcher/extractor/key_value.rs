@@ -0,0 +1,260 @@
+//! The key_value module contains the parser of the texts made of key-value pairs, like the
+//! logfmt lines, e.g. `level=warn host=web01 msg="disk almost full"`.
+
+use crate::config::rule::KeyValueFormat;
+use crate::error::MatcherError;
+use serde_json::{Map, Value};
+
+const DEFAULT_KEY_VALUE_SEPARATOR: &str = "=";
+const DEFAULT_QUOTE: char = '"';
+const ESCAPE: char = '\\';
+
+/// Parses key-value pairs into a Map.
+/// The pairs are separated by the `pair_separator` or, if not provided, by any whitespace.
+/// A value can be enclosed in quotes to contain the separators; within a quoted value,
+/// the quote and the backslash characters can be escaped with a backslash.
+/// A key without a value is mapped to an empty string.
+#[derive(Debug, PartialEq)]
+pub struct KeyValueParser {
+    pair_separator: Option<String>,
+    key_value_separator: String,
+    quote: char,
+}
+
+impl KeyValueParser {
+    pub fn build(format: &KeyValueFormat) -> Result<KeyValueParser, MatcherError> {
+        let key_value_separator = format
+            .key_value_separator
+            .clone()
+            .unwrap_or_else(|| DEFAULT_KEY_VALUE_SEPARATOR.to_owned());
+        if key_value_separator.is_empty() {
+            return Err(KeyValueParser::configuration_error(
+                "The key_value_separator cannot be empty",
+            ));
+        }
+
+        if let Some(pair_separator) = &format.pair_separator {
+            if pair_separator.is_empty() {
+                return Err(KeyValueParser::configuration_error(
+                    "The pair_separator cannot be empty",
+                ));
+            }
+            if *pair_separator == key_value_separator {
+                return Err(KeyValueParser::configuration_error(
+                    "The pair_separator and the key_value_separator must be different",
+                ));
+            }
+        }
+
+        let quote = match &format.quote {
+            None => DEFAULT_QUOTE,
+            Some(quote) => {
+                let mut chars = quote.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(quote), None) => quote,
+                    _ => {
+                        return Err(KeyValueParser::configuration_error(
+                            "The quote must be a single character",
+                        ))
+                    }
+                }
+            }
+        };
+
+        Ok(KeyValueParser {
+            pair_separator: format.pair_separator.clone(),
+            key_value_separator,
+            quote,
+        })
+    }
+
+    fn configuration_error(message: &str) -> MatcherError {
+        MatcherError::ConfigurationError {
+            message: format!("Invalid key_value_match extractor: {}", message),
+        }
+    }
+
+    pub fn parse(&self, text: &str) -> Map<String, Value> {
+        let mut result = Map::new();
+        let mut rest = text;
+
+        loop {
+            rest = self.skip_pair_separators(rest);
+            if rest.is_empty() {
+                break;
+            }
+
+            let key_end = self.find_key_end(rest);
+            let key = rest[..key_end].trim();
+            rest = &rest[key_end..];
+
+            let value = match rest.strip_prefix(self.key_value_separator.as_str()) {
+                Some(value_text) => {
+                    let (value, value_rest) = self.parse_value(value_text);
+                    rest = value_rest;
+                    value
+                }
+                None => String::new(),
+            };
+
+            if !key.is_empty() {
+                result.insert(key.to_owned(), Value::String(value));
+            }
+        }
+
+        result
+    }
+
+    fn skip_pair_separators<'a>(&self, mut text: &'a str) -> &'a str {
+        loop {
+            let trimmed = text.trim_start();
+            match &self.pair_separator {
+                Some(separator) => match trimmed.strip_prefix(separator.as_str()) {
+                    Some(stripped) => text = stripped,
+                    None => return trimmed,
+                },
+                None => return trimmed,
+            }
+        }
+    }
+
+    /// Returns the byte position where a pair separator starts, or the end of the text.
+    fn find_pair_end(&self, text: &str) -> usize {
+        match &self.pair_separator {
+            Some(separator) => text.find(separator.as_str()).unwrap_or(text.len()),
+            None => text.find(char::is_whitespace).unwrap_or(text.len()),
+        }
+    }
+
+    /// Returns the byte position where the key-value separator or the pair separator starts.
+    fn find_key_end(&self, text: &str) -> usize {
+        let pair_end = self.find_pair_end(text);
+        match text.find(self.key_value_separator.as_str()) {
+            Some(position) if position < pair_end => position,
+            _ => pair_end,
+        }
+    }
+
+    /// Parses a value and returns it with the text that follows it.
+    fn parse_value<'a>(&self, text: &'a str) -> (String, &'a str) {
+        // With the default whitespace separator, a whitespace after the key-value separator
+        // ends the pair
+        let text = if self.pair_separator.is_some() { text.trim_start() } else { text };
+        if let Some(quoted) = text.strip_prefix(self.quote) {
+            let mut value = String::new();
+            let mut chars = quoted.char_indices();
+            while let Some((position, c)) = chars.next() {
+                if c == self.quote {
+                    let rest = &quoted[position + c.len_utf8()..];
+                    // Any text between the closing quote and the next pair is discarded
+                    return (value, &rest[self.find_pair_end(rest)..]);
+                }
+                if c == ESCAPE {
+                    match chars.next() {
+                        Some((_, escaped)) if escaped == self.quote || escaped == ESCAPE => {
+                            value.push(escaped)
+                        }
+                        Some((_, escaped)) => {
+                            value.push(c);
+                            value.push(escaped);
+                        }
+                        None => value.push(c),
+                    }
+                } else {
+                    value.push(c);
+                }
+            }
+            // The quote is not closed: the value is the rest of the text
+            (value, "")
+        } else {
+            let value_end = self.find_pair_end(text);
+            (text[..value_end].trim().to_owned(), &text[value_end..])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn parser(
+        pair_separator: Option<&str>,
+        key_value_separator: Option<&str>,
+        quote: Option<&str>,
+    ) -> Result<KeyValueParser, MatcherError> {
+        KeyValueParser::build(&KeyValueFormat {
+            pair_separator: pair_separator.map(str::to_owned),
+            key_value_separator: key_value_separator.map(str::to_owned),
+            quote: quote.map(str::to_owned),
+        })
+    }
+
+    #[test]
+    fn should_parse_logfmt_text() {
+        // Arrange
+        let parser = parser(None, None, None).unwrap();
+
+        // Act
+        let result = parser.parse(
+            r#"level=warn  host=web01 msg="disk \"/var\" almost full" path="C:\\logs" debug empty= dur=1.5s"#,
+        );
+
+        // Assert
+        assert_eq!(
+            json!({
+                "level": "warn",
+                "host": "web01",
+                "msg": "disk \"/var\" almost full",
+                "path": "C:\\logs",
+                "debug": "",
+                "empty": "",
+                "dur": "1.5s"
+            }),
+            Value::Object(result)
+        );
+    }
+
+    #[test]
+    fn should_parse_text_with_custom_separators_and_quote() {
+        // Arrange
+        let parser = parser(Some(";"), Some(":"), Some("'")).unwrap();
+
+        // Act
+        let result =
+            parser.parse("host: web01 ; msg:'a; b: c' ;; url: http://host:8080/a=b ; city:città");
+
+        // Assert
+        assert_eq!(
+            json!({
+                "host": "web01",
+                "msg": "a; b: c",
+                "url": "http://host:8080/a=b",
+                "city": "città"
+            }),
+            Value::Object(result)
+        );
+    }
+
+    #[test]
+    fn should_handle_malformed_text() {
+        // Arrange
+        let parser = parser(None, None, None).unwrap();
+
+        // Act & Assert
+        assert!(parser.parse("").is_empty());
+        assert!(parser.parse("   =value  ").is_empty());
+        assert_eq!(json!({ "a": "1", "b": "open" }), Value::Object(parser.parse(r#"a=1 b="open"#)));
+        assert_eq!(json!({ "a": "x", "b": "2" }), Value::Object(parser.parse(r#"a="x"junk b=2"#)));
+        assert_eq!(json!({ "a": "2" }), Value::Object(parser.parse("a=1 a=2")));
+    }
+
+    #[test]
+    fn build_should_fail_with_invalid_format() {
+        assert!(parser(None, Some(""), None).is_err());
+        assert!(parser(Some(""), None, None).is_err());
+        assert!(parser(Some("="), None, None).is_err());
+        assert!(parser(None, None, Some("")).is_err());
+        assert!(parser(None, None, Some("''")).is_err());
+    }
+}
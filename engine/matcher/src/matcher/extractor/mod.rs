@@ -7,25 +7,39 @@
 use crate::accessor::{Accessor, AccessorBuilder};
 use crate::config::rule::{Extractor, ExtractorRegex};
 use crate::error::MatcherError;
+use crate::matcher::extractor::key_value::KeyValueParser;
 use crate::matcher::modifier::ValueModifier;
 use crate::model::InternalEvent;
 use crate::regex::RegexWrapper;
+use crate::resources::MatcherResources;
 use log::*;
 use regex::{Captures, Regex as RustRegex};
 use serde_json::{Map, Value};
 use std::collections::HashMap;
 use tornado_common_api::ValueExt;
 
+pub mod key_value;
+
 /// The MatcherExtractor instance builder.
 #[derive(Default)]
 pub struct MatcherExtractorBuilder {
     accessor: AccessorBuilder,
+    resources: MatcherResources,
 }
 
 impl MatcherExtractorBuilder {
     /// Returns a new MatcherExtractorBuilder instance.
     pub fn new() -> MatcherExtractorBuilder {
-        MatcherExtractorBuilder { accessor: AccessorBuilder::new() }
+        MatcherExtractorBuilder {
+            accessor: AccessorBuilder::new(),
+            resources: MatcherResources::default(),
+        }
+    }
+
+    /// Sets the resources, e.g. the grok patterns and the lookup tables, used to build the extractors.
+    pub fn with_resources(mut self, resources: MatcherResources) -> MatcherExtractorBuilder {
+        self.resources = resources;
+        self
     }
 
     /// Returns a specific MatcherExtractor instance based on the matcher.extractor rule configuration.
@@ -83,7 +97,7 @@ impl MatcherExtractorBuilder {
         for (key, extractor) in config.iter() {
             matcher_extractor.extractors.insert(
                 key.to_owned(),
-                ValueExtractor::build(rule_name, key, extractor, &self.accessor)?,
+                ValueExtractor::build(rule_name, key, extractor, &self.accessor, &self.resources)?,
             );
         }
 
@@ -133,6 +147,19 @@ impl MatcherExtractor {
         }
         Ok(())
     }
+
+    /// Extracts each variable independently from the others, so that a variable that cannot
+    /// be extracted does not prevent the extraction of the others.
+    /// The result contains the name of each variable with its value or error, ordered by name.
+    pub fn extract_each(&self, event: &InternalEvent) -> Vec<(&str, Result<Value, MatcherError>)> {
+        let mut results = self
+            .extractors
+            .iter()
+            .map(|(key, extractor)| (key.as_str(), extractor.extract(key, event)))
+            .collect::<Vec<_>>();
+        results.sort_by_key(|(name, _)| *name);
+        results
+    }
 }
 
 #[derive(Debug)]
@@ -148,11 +175,17 @@ impl ValueExtractor {
         key: &str,
         extractor: &Extractor,
         accessor: &AccessorBuilder,
+        resources: &MatcherResources,
     ) -> Result<ValueExtractor, MatcherError> {
         Ok(Self {
             key: key.to_owned(),
-            regex_extractor: RegexValueExtractor::build(rule_name, extractor, accessor)?,
-            modifiers_post: ValueModifier::build(rule_name, accessor, &extractor.modifiers_post)?,
+            regex_extractor: RegexValueExtractor::build(rule_name, extractor, accessor, resources)?,
+            modifiers_post: ValueModifier::build(
+                rule_name,
+                accessor,
+                &extractor.modifiers_post,
+                resources,
+            )?,
         })
     }
 
@@ -161,8 +194,23 @@ impl ValueExtractor {
         variable_name: &str,
         event: &InternalEvent,
     ) -> Result<Value, MatcherError> {
-        let mut extracted_value = self.regex_extractor.extract(variable_name, event)?;
-        for modifier in &self.modifiers_post {
+        let (mut extracted_value, modifiers) =
+            match self.regex_extractor.extract(variable_name, event) {
+                Ok(extracted_value) => (extracted_value, self.modifiers_post.as_slice()),
+                // If nothing is extracted, the processing continues from the first 'default' modifier
+                Err(err @ MatcherError::MissingExtractedVariableError { .. }) => {
+                    match self
+                        .modifiers_post
+                        .iter()
+                        .position(|modifier| matches!(modifier, ValueModifier::Default { .. }))
+                    {
+                        Some(position) => (Value::Null, &self.modifiers_post[position..]),
+                        None => return Err(err),
+                    }
+                }
+                Err(err) => return Err(err),
+            };
+        for modifier in modifiers {
             modifier.apply(variable_name, &mut extracted_value, event)?;
         }
         Ok(extracted_value)
@@ -178,6 +226,8 @@ enum RegexValueExtractor {
     SingleMatchNamedGroups { regex: RegexWrapper, target: Accessor },
     AllMatchesNamedGroups { regex: RegexWrapper, target: Accessor },
     SingleKeyMatch { regex: RegexWrapper, target: Accessor },
+    KeyValue { parser: KeyValueParser, target: Accessor },
+    JMESPath { expression: jmespath::Expression<'static>, target: Accessor },
 }
 
 impl RegexValueExtractor {
@@ -185,6 +235,7 @@ impl RegexValueExtractor {
         rule_name: &str,
         extractor: &Extractor,
         accessor: &AccessorBuilder,
+        resources: &MatcherResources,
     ) -> Result<RegexValueExtractor, MatcherError> {
         let target = accessor.build(rule_name, &extractor.from)?;
 
@@ -250,10 +301,40 @@ impl RegexValueExtractor {
                     Ok(RegexValueExtractor::SingleMatchNamedGroups { regex: rust_regex, target })
                 }
             }
+            ExtractorRegex::Grok { expression, all_matches } => {
+                let rust_regex = RegexWrapper::from_grok(expression, &resources.grok_patterns)?;
+
+                if !has_named_groups(&rust_regex) {
+                    return Err(MatcherError::ConfigurationError {
+                        message: format!(
+                            "The grok expression [{}] has no named fields but it is used in grok_match.",
+                            expression
+                        ),
+                    });
+                }
+
+                if all_matches.unwrap_or(false) {
+                    Ok(RegexValueExtractor::AllMatchesNamedGroups { regex: rust_regex, target })
+                } else {
+                    Ok(RegexValueExtractor::SingleMatchNamedGroups { regex: rust_regex, target })
+                }
+            }
             ExtractorRegex::SingleKeyRegex { regex } => {
                 let rust_regex = RegexWrapper::new(regex)?;
                 Ok(RegexValueExtractor::SingleKeyMatch { regex: rust_regex, target })
             }
+            ExtractorRegex::KeyValue { format } => {
+                Ok(RegexValueExtractor::KeyValue { parser: KeyValueParser::build(format)?, target })
+            }
+            ExtractorRegex::JMESPath { expression } => {
+                let expression = jmespath::compile(expression).map_err(|e| {
+                    MatcherError::ExtractorBuildFailError {
+                        message: format!("Cannot parse JMESPath expression [{}]", expression),
+                        cause: e.to_string(),
+                    }
+                })?;
+                Ok(RegexValueExtractor::JMESPath { expression, target })
+            }
         }
     }
 
@@ -458,6 +539,54 @@ impl RegexValueExtractor {
                     variable_name: variable_name.to_owned(),
                 })
             }
+            RegexValueExtractor::KeyValue { parser, target } => {
+                let cow_value = target.get(event).ok_or_else(|| {
+                    MatcherError::MissingExtractedVariableError {
+                        variable_name: variable_name.to_owned(),
+                    }
+                })?;
+                let text = cow_value.get_text().ok_or_else(|| {
+                    MatcherError::MissingExtractedVariableError {
+                        variable_name: variable_name.to_owned(),
+                    }
+                })?;
+                let pairs = parser.parse(text);
+                if !pairs.is_empty() {
+                    Ok(Value::Object(pairs))
+                } else {
+                    Err(MatcherError::MissingExtractedVariableError {
+                        variable_name: variable_name.to_owned(),
+                    })
+                }
+            }
+            RegexValueExtractor::JMESPath { expression, target } => {
+                let cow_value = target.get(event).ok_or_else(|| {
+                    MatcherError::MissingExtractedVariableError {
+                        variable_name: variable_name.to_owned(),
+                    }
+                })?;
+                let result = expression
+                    .search(cow_value.as_ref())
+                    .map_err(|e| e.to_string())
+                    .and_then(|result| {
+                        serde_json::to_value(result.as_ref()).map_err(|e| e.to_string())
+                    })
+                    .map_err(|message| MatcherError::ExtractedVariableError {
+                        message: format!(
+                            "Cannot evaluate the JMESPath expression [{}]: {}",
+                            expression, message
+                        ),
+                        variable_name: variable_name.to_owned(),
+                    })?;
+                // A JMESPath expression returns null when the searched data does not exist
+                if result.is_null() {
+                    Err(MatcherError::MissingExtractedVariableError {
+                        variable_name: variable_name.to_owned(),
+                    })
+                } else {
+                    Ok(result)
+                }
+            }
         }
     }
 }
@@ -499,7 +628,7 @@ fn has_named_groups(regex: &RustRegex) -> bool {
 mod test {
     use super::*;
     use crate::accessor::AccessorBuilder;
-    use crate::config::rule::{ExtractorRegex, Modifier};
+    use crate::config::rule::{ExtractorRegex, KeyValueFormat, Modifier};
     use maplit::*;
     use serde_json::json;
     use std::collections::HashMap;
@@ -520,6 +649,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         );
         assert!(extractor.is_ok());
     }
@@ -538,9 +668,14 @@ mod test {
         };
 
         // Act
-        let extractor =
-            ValueExtractor::build("rule_name", "key", &rule_extractor, &AccessorBuilder::new())
-                .unwrap();
+        let extractor = ValueExtractor::build(
+            "rule_name",
+            "key",
+            &rule_extractor,
+            &AccessorBuilder::new(),
+            &MatcherResources::default(),
+        )
+        .unwrap();
 
         // Assert
         assert_eq!(1, extractor.modifiers_post.len());
@@ -565,6 +700,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         );
         assert!(extractor.is_err());
     }
@@ -584,6 +720,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -610,6 +747,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -636,6 +774,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -662,6 +801,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -688,6 +828,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -711,6 +852,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -734,6 +876,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -829,6 +972,48 @@ mod test {
         assert!(extractor.process_all(&mut (&event, &mut extracted_vars).into()).is_err());
     }
 
+    #[test]
+    fn should_extract_each_variable_independently() {
+        let mut from_config = HashMap::new();
+
+        from_config.insert(
+            String::from("extracted_temp"),
+            Extractor {
+                from: String::from("${event.type}"),
+                regex: ExtractorRegex::Regex {
+                    regex: String::from(r"[0-9]+"),
+                    group_match_idx: Some(0),
+                    all_matches: None,
+                },
+                modifiers_post: vec![],
+            },
+        );
+
+        from_config.insert(
+            String::from("extracted_none"),
+            Extractor {
+                from: String::from("${event.payload.nothing}"),
+                regex: ExtractorRegex::Regex {
+                    regex: String::from(r"[a-z]+"),
+                    group_match_idx: Some(0),
+                    all_matches: None,
+                },
+                modifiers_post: vec![],
+            },
+        );
+
+        let extractor = MatcherExtractorBuilder::new().build("", &from_config).unwrap();
+
+        let event = new_event("temp=44'C");
+        let results = extractor.extract_each(&(&event, &mut Value::Null).into());
+
+        assert_eq!(2, results.len());
+        assert_eq!("extracted_none", results[0].0);
+        assert!(results[0].1.is_err());
+        assert_eq!("extracted_temp", results[1].0);
+        assert_eq!(Ok(json!("44")), results[1].1);
+    }
+
     #[test]
     fn should_return_all_matching_groups_if_no_idx() {
         let extractor = ValueExtractor::build(
@@ -844,6 +1029,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -875,6 +1061,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -914,6 +1101,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -937,6 +1125,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -960,6 +1149,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -984,6 +1174,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1015,6 +1206,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1045,6 +1237,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1073,6 +1266,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1096,6 +1290,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1132,6 +1327,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1154,7 +1350,7 @@ mod test {
                 },
                 modifiers_post: vec![],
             },
-            &AccessorBuilder::new(),
+            &AccessorBuilder::new(), &MatcherResources::default(),
         )
             .unwrap();
 
@@ -1239,6 +1435,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         );
         assert!(extractor.is_err());
     }
@@ -1255,6 +1452,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         );
 
         // Assert
@@ -1274,6 +1472,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1285,6 +1484,209 @@ mod test {
         }
     }
 
+    #[test]
+    fn grok_match_should_extract_the_fields_of_all_matches() {
+        // Arrange
+        let extractor = ValueExtractor::build(
+            "rule_name",
+            "key",
+            &Extractor {
+                from: "${event.payload.line}".to_string(),
+                regex: ExtractorRegex::Grok {
+                    expression: "%{WORD:action} %{IP:src}:%{POSINT:port}".to_owned(),
+                    all_matches: Some(true),
+                },
+                modifiers_post: vec![],
+            },
+            &AccessorBuilder::new(),
+            &MatcherResources::default(),
+        )
+        .unwrap();
+
+        let mut payload = Map::new();
+        payload.insert(
+            "line".to_owned(),
+            json!("DROP 10.0.0.1:22 ACCEPT 2001:db8::1:443 DROP host:80"),
+        );
+        let event = json!(Event::new_with_payload("event", payload));
+
+        // Act
+        let result = extractor.extract("var", &(&event, &mut Value::Null).into());
+
+        // Assert
+        assert_eq!(
+            json!([
+                { "action": "DROP", "src": "10.0.0.1", "port": "22" },
+                { "action": "ACCEPT", "src": "2001:db8::1", "port": "443" }
+            ]),
+            result.unwrap()
+        );
+    }
+
+    #[test]
+    fn build_should_fail_if_grok_match_has_no_named_fields() {
+        // Arrange
+        let extractor = |expression: &str| Extractor {
+            from: "${event.payload.line}".to_string(),
+            regex: ExtractorRegex::Grok { expression: expression.to_owned(), all_matches: None },
+            modifiers_post: vec![],
+        };
+
+        // Act & Assert
+        let build = |extractor: &Extractor| {
+            ValueExtractor::build(
+                "rule_name",
+                "key",
+                extractor,
+                &AccessorBuilder::new(),
+                &MatcherResources::default(),
+            )
+        };
+        assert!(build(&extractor("%{IP} %{POSINT}")).is_err());
+        assert!(build(&extractor("%{NOT_EXISTING:field}")).is_err());
+        assert!(build(&extractor("%{IP:src}")).is_ok());
+    }
+
+    #[test]
+    fn jmespath_match_should_filter_and_project_arrays() {
+        // Arrange
+        let extractor = |expression: &str| {
+            ValueExtractor::build(
+                "rule_name",
+                "key",
+                &Extractor {
+                    from: "${event.payload}".to_string(),
+                    regex: ExtractorRegex::JMESPath { expression: expression.to_owned() },
+                    modifiers_post: vec![],
+                },
+                &AccessorBuilder::new(),
+                &MatcherResources::default(),
+            )
+            .unwrap()
+        };
+
+        let event = json!(Event::new_with_payload(
+            "alertmanager",
+            json!({
+                "alerts": [
+                    { "status": "firing", "labels": { "host": "web01" } },
+                    { "status": "resolved", "labels": { "host": "web02" } },
+                    { "status": "firing", "labels": { "host": "db01" } }
+                ]
+            })
+            .as_object()
+            .unwrap()
+            .clone()
+        ));
+        let extract = |expression: &str| {
+            extractor(expression).extract("var", &(&event, &mut Value::Null).into())
+        };
+
+        // Act & Assert
+        assert_eq!(
+            json!(["web01", "db01"]),
+            extract("alerts[?status=='firing'].labels.host").unwrap()
+        );
+        assert_eq!(json!(3), extract("length(alerts)").unwrap());
+        assert_eq!(json!([]), extract("alerts[?status=='pending'].labels.host").unwrap());
+        assert!(matches!(
+            extract("alerts[0].labels.service"),
+            Err(MatcherError::MissingExtractedVariableError { .. })
+        ));
+        assert!(matches!(extract("abs(alerts)"), Err(MatcherError::ExtractedVariableError { .. })));
+    }
+
+    #[test]
+    fn build_should_fail_if_jmespath_match_has_not_valid_expression() {
+        // Arrange
+        let extractor = Extractor {
+            from: "${event}".to_string(),
+            regex: ExtractorRegex::JMESPath { expression: "alerts[?status==".to_owned() },
+            modifiers_post: vec![],
+        };
+
+        // Act
+        let result = ValueExtractor::build(
+            "rule_name",
+            "key",
+            &extractor,
+            &AccessorBuilder::new(),
+            &MatcherResources::default(),
+        );
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn key_value_match_should_extract_all_the_pairs() {
+        // Arrange
+        let extractor = ValueExtractor::build(
+            "rule_name",
+            "key",
+            &Extractor {
+                from: "${event.payload.line}".to_string(),
+                regex: ExtractorRegex::KeyValue {
+                    format: KeyValueFormat {
+                        pair_separator: None,
+                        key_value_separator: None,
+                        quote: None,
+                    },
+                },
+                modifiers_post: vec![],
+            },
+            &AccessorBuilder::new(),
+            &MatcherResources::default(),
+        )
+        .unwrap();
+
+        let extract = |line: Value| {
+            let mut payload = Map::new();
+            payload.insert("line".to_owned(), line);
+            let event = json!(Event::new_with_payload("event", payload));
+            extractor.extract("var", &(&event, &mut Value::Null).into())
+        };
+
+        // Act
+        let result = extract(json!(r#"level=warn host=web01 msg="disk almost full""#));
+
+        // Assert
+        assert_eq!(
+            json!({ "level": "warn", "host": "web01", "msg": "disk almost full" }),
+            result.unwrap()
+        );
+        assert!(extract(json!("   ")).is_err());
+        assert!(extract(json!({ "level": "warn" })).is_err());
+    }
+
+    #[test]
+    fn build_should_fail_if_key_value_match_has_not_valid_format() {
+        // Arrange
+        let extractor = Extractor {
+            from: "${event.payload.line}".to_string(),
+            regex: ExtractorRegex::KeyValue {
+                format: KeyValueFormat {
+                    pair_separator: Some(":".to_owned()),
+                    key_value_separator: Some(":".to_owned()),
+                    quote: None,
+                },
+            },
+            modifiers_post: vec![],
+        };
+
+        // Act
+        let result = ValueExtractor::build(
+            "rule_name",
+            "key",
+            &extractor,
+            &AccessorBuilder::new(),
+            &MatcherResources::default(),
+        );
+
+        // Assert
+        assert!(result.is_err());
+    }
+
     #[test]
     fn single_key_match_should_match_single_entry() {
         // Arrange
@@ -1319,6 +1721,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1358,6 +1761,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1408,6 +1812,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1450,6 +1855,7 @@ mod test {
                 modifiers_post: vec![],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1485,6 +1891,7 @@ mod test {
                 modifiers_post: vec![Modifier::Trim {}, Modifier::Trim {}],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1501,6 +1908,7 @@ mod test {
                 modifiers_post: vec![Modifier::Trim {}],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1537,6 +1945,7 @@ mod test {
                 modifiers_post: vec![Modifier::Trim {}],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1582,6 +1991,7 @@ mod test {
                 ],
             },
             &AccessorBuilder::new(),
+            &MatcherResources::default(),
         )
         .unwrap();
 
@@ -1594,6 +2004,86 @@ mod test {
         assert_eq!(Value::String("hello to be trimmed replaced_and lowercased".to_owned()), result);
     }
 
+    #[test]
+    fn should_apply_string_manipulation_modifiers() {
+        // Arrange
+        let mut payload = Map::new();
+        payload.insert("hosts".to_owned(), json!("db01;web01; web02"));
+
+        let extractor = ValueExtractor::build(
+            "rule_name",
+            "key",
+            &Extractor {
+                from: "${event.payload.hosts}".to_string(),
+                regex: ExtractorRegex::Regex {
+                    regex: r#".*"#.to_string(),
+                    all_matches: Some(false),
+                    group_match_idx: Some(0),
+                },
+                modifiers_post: vec![
+                    Modifier::Uppercase {},
+                    Modifier::Split { separator: r#";\s*"#.to_owned(), is_regex: true },
+                    Modifier::Join { separator: ",".to_owned() },
+                    Modifier::Substring { start: 5, end: None },
+                    Modifier::PadLeft { length: 13, padding: Some("*".to_owned()) },
+                ],
+            },
+            &AccessorBuilder::new(),
+            &MatcherResources::default(),
+        )
+        .unwrap();
+
+        let event = json!(Event::new_with_payload("event", payload));
+
+        // Act
+        let result = extractor.extract("var", &(&event, &mut Value::Null).into()).unwrap();
+
+        // Assert
+        assert_eq!(json!("**WEB01,WEB02"), result);
+    }
+
+    #[test]
+    fn should_apply_the_default_modifier_if_nothing_is_extracted() {
+        // Arrange
+        let build_extractor = |modifiers_post: Vec<Modifier>| {
+            ValueExtractor::build(
+                "rule_name",
+                "key",
+                &Extractor {
+                    from: "${event.payload.body}".to_string(),
+                    regex: ExtractorRegex::Regex {
+                        regex: r#"HOST: (\w*)"#.to_string(),
+                        all_matches: Some(false),
+                        group_match_idx: Some(1),
+                    },
+                    modifiers_post,
+                },
+                &AccessorBuilder::new(),
+                &MatcherResources::default(),
+            )
+            .unwrap()
+        };
+        let extractor = build_extractor(vec![
+            Modifier::Trim {},
+            Modifier::Default { value: json!("unknown") },
+            Modifier::Uppercase {},
+        ]);
+        let extractor_without_default = build_extractor(vec![Modifier::Trim {}]);
+
+        let extract = |extractor: &ValueExtractor, body: &str| {
+            let mut payload = Map::new();
+            payload.insert("body".to_owned(), json!(body));
+            let event = json!(Event::new_with_payload("event", payload));
+            extractor.extract("var", &(&event, &mut Value::Null).into())
+        };
+
+        // Act & Assert
+        assert_eq!(json!("WEB01"), extract(&extractor, "HOST: web01").unwrap());
+        assert_eq!(json!("UNKNOWN"), extract(&extractor, "HOST: ").unwrap());
+        assert_eq!(json!("UNKNOWN"), extract(&extractor, "no host").unwrap());
+        assert!(extract(&extractor_without_default, "no host").is_err());
+    }
+
     fn new_event(event_type: &str) -> Value {
         json!(Event::new(event_type))
     }
@@ -0,0 +1,446 @@
+//! The heartbeat module contains the logic to detect the keys that stop sending events
+//! and to generate the related synthetic events.
+
+use crate::accessor::{Accessor, AccessorBuilder};
+use crate::config::nodes::Heartbeat;
+use crate::error::MatcherError;
+use crate::matcher::operator::{Operator, OperatorBuilder};
+use crate::model::{InternalEvent, ProcessedHeartbeat, ProcessedHeartbeatStatus};
+use crate::resources::MatcherResources;
+use log::*;
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, PoisonError};
+use tornado_common_api::{Event, Payload, WithEventData};
+
+/// The type of the Event generated when a key does not send events for more than the
+/// max interval of a Heartbeat.
+pub const HEARTBEAT_MISSING_EVENT_TYPE: &str = "tornado.heartbeat_missing";
+
+/// The type of the Event generated when a missing key sends an event again.
+pub const HEARTBEAT_RECOVERED_EVENT_TYPE: &str = "tornado.heartbeat_recovered";
+
+const HEARTBEAT_EVENT_TYPE_PREFIX: &str = "tornado.heartbeat_";
+
+/// How long a missing key is kept after its missing event is generated. A key seen again after
+/// it has been removed is recorded as a new key and does not generate a recovered event.
+pub const MISSING_KEY_RETENTION_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Keeps the last time each key was seen by the Heartbeat nodes of a processing tree.
+#[derive(Debug, Default)]
+pub struct HeartbeatStore {
+    keys: Mutex<HashMap<String, NodeKeys>>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct NodeKeys {
+    seen: HashMap<String, KeyState>,
+    // Keys that sent an event after being missing; they wait for the next heartbeat check.
+    recovered: Vec<HeartbeatChange>,
+}
+
+#[derive(Debug, Clone)]
+struct KeyState {
+    last_seen_ms: u64,
+    metadata: Payload,
+    missing: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum HeartbeatChangeType {
+    Missing,
+    Recovered,
+}
+
+#[derive(Debug, Clone)]
+struct HeartbeatChange {
+    change_type: HeartbeatChangeType,
+    key: String,
+    last_seen_ms: u64,
+    metadata: Payload,
+}
+
+impl HeartbeatStore {
+    pub fn new() -> HeartbeatStore {
+        HeartbeatStore::default()
+    }
+
+    /// Returns a detached copy of the current keys.
+    /// Events recorded in the copy are not visible in the original store.
+    pub fn snapshot(&self) -> HeartbeatStore {
+        let keys = self.keys.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        HeartbeatStore { keys: Mutex::new(keys) }
+    }
+
+    /// Removes the keys of the Heartbeat nodes whose path is not in `paths`
+    pub fn retain(&self, paths: &HashSet<String>) {
+        self.keys
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|path, _| paths.contains(path));
+    }
+
+    /// Records that the key was seen at `now_ms` and returns whether the key was missing.
+    fn record(&self, node_key: &str, key: &str, metadata: Payload, now_ms: u64) -> bool {
+        let mut keys = self.keys.lock().unwrap_or_else(PoisonError::into_inner);
+        let node_keys = keys.entry(node_key.to_owned()).or_default();
+
+        let previous = node_keys
+            .seen
+            .insert(key.to_owned(), KeyState { last_seen_ms: now_ms, metadata, missing: false });
+
+        match previous {
+            Some(previous) if previous.missing => {
+                node_keys.recovered.push(HeartbeatChange {
+                    change_type: HeartbeatChangeType::Recovered,
+                    key: key.to_owned(),
+                    last_seen_ms: previous.last_seen_ms,
+                    metadata: previous.metadata,
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the recovered keys of the node and the keys that became missing at `now_ms`.
+    /// A missing key is returned only once, until it is seen again; it is removed when it
+    /// stays missing for more than MISSING_KEY_RETENTION_MS.
+    fn check(&self, node_key: &str, max_interval_ms: u64, now_ms: u64) -> Vec<HeartbeatChange> {
+        let mut keys = self.keys.lock().unwrap_or_else(PoisonError::into_inner);
+        let node_keys = match keys.get_mut(node_key) {
+            Some(node_keys) => node_keys,
+            None => return vec![],
+        };
+
+        node_keys.seen.retain(|_, state| {
+            !state.missing
+                || state.last_seen_ms + max_interval_ms + MISSING_KEY_RETENTION_MS > now_ms
+        });
+
+        let mut changes = std::mem::take(&mut node_keys.recovered);
+        for (key, state) in node_keys.seen.iter_mut() {
+            if !state.missing && state.last_seen_ms + max_interval_ms <= now_ms {
+                state.missing = true;
+                changes.push(HeartbeatChange {
+                    change_type: HeartbeatChangeType::Missing,
+                    key: key.to_owned(),
+                    last_seen_ms: state.last_seen_ms,
+                    metadata: state.metadata.clone(),
+                });
+            }
+        }
+        changes
+    }
+}
+
+/// The Matcher's internal representation of a config::nodes::Heartbeat.
+pub struct MatcherHeartbeat {
+    node_key: String,
+    active: bool,
+    filter: Box<dyn Operator>,
+    key: Accessor,
+    max_interval_seconds: u64,
+    store: Arc<HeartbeatStore>,
+}
+
+impl MatcherHeartbeat {
+    pub fn build(
+        name: &str,
+        node_key: String,
+        heartbeat: &Heartbeat,
+        store: Arc<HeartbeatStore>,
+        resources: &MatcherResources,
+    ) -> Result<MatcherHeartbeat, MatcherError> {
+        if heartbeat.max_interval_seconds == 0 {
+            return Err(MatcherError::ConfigurationError {
+                message: format!(
+                    "The heartbeat [{}] must have a max_interval_seconds greater than zero",
+                    name
+                ),
+            });
+        }
+
+        Ok(MatcherHeartbeat {
+            node_key,
+            active: heartbeat.active,
+            filter: OperatorBuilder::new()
+                .with_resources(resources.clone())
+                .build_option(name, &heartbeat.filter.clone().into())?,
+            key: AccessorBuilder::new().build(name, &heartbeat.key)?,
+            max_interval_seconds: heartbeat.max_interval_seconds,
+            store,
+        })
+    }
+
+    /// Records the key of the event if the event matches the filter of the Heartbeat.
+    /// The synthetic events generated by the heartbeats are never recorded.
+    pub fn process(&self, event: &Value, now_ms: u64) -> ProcessedHeartbeat {
+        let mut processed = ProcessedHeartbeat::new(ProcessedHeartbeatStatus::NotMatched);
+
+        if !self.active {
+            processed.status = ProcessedHeartbeatStatus::Inactive;
+            return processed;
+        }
+
+        if event
+            .event_type()
+            .map(|event_type| event_type.starts_with(HEARTBEAT_EVENT_TYPE_PREFIX))
+            .unwrap_or(false)
+        {
+            return processed;
+        }
+
+        let mut extracted_vars = Value::Object(Map::new());
+        let internal_event = InternalEvent { event, extracted_variables: &mut extracted_vars };
+
+        if !self.filter.evaluate(&internal_event) {
+            return processed;
+        }
+
+        let key = match self.key.get(&internal_event) {
+            Some(value) => match value.as_ref() {
+                Value::String(text) => text.to_owned(),
+                value => value.to_string(),
+            },
+            None => {
+                trace!("MatcherHeartbeat - heartbeat [{}] cannot resolve the key", self.node_key);
+                return processed;
+            }
+        };
+
+        let metadata = match event.metadata() {
+            Some(Value::Object(metadata)) => metadata.clone(),
+            _ => Map::new(),
+        };
+
+        processed.status = if self.store.record(&self.node_key, &key, metadata, now_ms) {
+            debug!("MatcherHeartbeat - heartbeat [{}] key [{}] recovered", self.node_key, key);
+            ProcessedHeartbeatStatus::Recovered
+        } else {
+            ProcessedHeartbeatStatus::Seen
+        };
+        processed.key = Some(key);
+        processed
+    }
+
+    /// Returns the synthetic events of the keys that became missing at `now_ms` and of the
+    /// keys recovered since the previous check.
+    /// Each event carries the metadata of the last event received for its key.
+    pub fn check(&self, now_ms: u64) -> Vec<Value> {
+        self.store
+            .check(&self.node_key, self.max_interval_seconds * 1000, now_ms)
+            .into_iter()
+            .map(|change| {
+                let event_type = match change.change_type {
+                    HeartbeatChangeType::Missing => HEARTBEAT_MISSING_EVENT_TYPE,
+                    HeartbeatChangeType::Recovered => HEARTBEAT_RECOVERED_EVENT_TYPE,
+                };
+                debug!(
+                    "MatcherHeartbeat - heartbeat [{}] generates [{}] for key [{}]",
+                    self.node_key, event_type, change.key
+                );
+
+                let mut payload = Payload::new();
+                payload.insert("heartbeat".to_owned(), Value::String(self.node_key.clone()));
+                payload.insert("key".to_owned(), Value::String(change.key));
+                payload.insert("last_seen_ms".to_owned(), json!(change.last_seen_ms));
+                payload.insert("max_interval_seconds".to_owned(), json!(self.max_interval_seconds));
+
+                let mut event = Event::new_with_payload(event_type, payload);
+                event.created_ms = now_ms;
+                event.metadata = change.metadata;
+                json!(event)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::rule::Operator as ConfigOperator;
+    use crate::config::Defaultable;
+
+    fn new_heartbeat(store: Arc<HeartbeatStore>) -> MatcherHeartbeat {
+        let heartbeat = Heartbeat {
+            description: "".to_owned(),
+            active: true,
+            filter: Defaultable::Value(ConfigOperator::Equals {
+                first: Value::String("${event.type}".to_owned()),
+                second: Value::String("check_result".to_owned()),
+            }),
+            key: "${event.payload.host}".to_owned(),
+            max_interval_seconds: 10,
+        };
+        MatcherHeartbeat::build(
+            "heartbeat",
+            "root.heartbeat".to_owned(),
+            &heartbeat,
+            store,
+            &MatcherResources::default(),
+        )
+        .unwrap()
+    }
+
+    fn new_event(event_type: &str, host: &str) -> Value {
+        json!({
+            "type": event_type,
+            "created_ms": 0,
+            "payload": {"host": host},
+            "metadata": {"tenant_id": "alpha"}
+        })
+    }
+
+    fn event_type(event: &Value) -> &str {
+        event.event_type().unwrap()
+    }
+
+    #[test]
+    fn should_fail_if_max_interval_is_zero() {
+        // Arrange
+        let heartbeat = Heartbeat { max_interval_seconds: 0, ..Default::default() };
+
+        // Act
+        let result = MatcherHeartbeat::build(
+            "heartbeat",
+            "heartbeat".to_owned(),
+            &heartbeat,
+            Default::default(),
+            &MatcherResources::default(),
+        );
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_record_only_the_events_matching_the_filter() {
+        // Arrange
+        let heartbeat = new_heartbeat(Default::default());
+
+        // Act
+        let seen = heartbeat.process(&new_event("check_result", "host_1"), 1_000);
+        let not_matched = heartbeat.process(&new_event("email", "host_2"), 1_000);
+
+        // Assert
+        assert_eq!(ProcessedHeartbeatStatus::Seen, seen.status);
+        assert_eq!(Some("host_1".to_owned()), seen.key);
+        assert_eq!(ProcessedHeartbeatStatus::NotMatched, not_matched.status);
+        assert_eq!(1, heartbeat.check(20_000).len());
+    }
+
+    #[test]
+    fn should_generate_a_missing_event_once_per_silence() {
+        // Arrange
+        let heartbeat = new_heartbeat(Default::default());
+        heartbeat.process(&new_event("check_result", "host_1"), 1_000);
+
+        // Act
+        let before_interval = heartbeat.check(10_999);
+        let after_interval = heartbeat.check(11_000);
+        let later = heartbeat.check(30_000);
+
+        // Assert
+        assert!(before_interval.is_empty());
+        assert_eq!(1, after_interval.len());
+        assert!(later.is_empty());
+
+        let missing = &after_interval[0];
+        assert_eq!(HEARTBEAT_MISSING_EVENT_TYPE, event_type(missing));
+        assert_eq!(11_000, missing["created_ms"]);
+        assert_eq!("root.heartbeat", missing["payload"]["heartbeat"]);
+        assert_eq!("host_1", missing["payload"]["key"]);
+        assert_eq!(1_000, missing["payload"]["last_seen_ms"]);
+        assert_eq!(10, missing["payload"]["max_interval_seconds"]);
+        assert_eq!("alpha", missing["metadata"]["tenant_id"]);
+    }
+
+    #[test]
+    fn should_generate_a_recovered_event_when_a_missing_key_resumes() {
+        // Arrange
+        let heartbeat = new_heartbeat(Default::default());
+        heartbeat.process(&new_event("check_result", "host_1"), 1_000);
+        heartbeat.check(11_000);
+
+        // Act
+        let processed = heartbeat.process(&new_event("check_result", "host_1"), 15_000);
+        let changes = heartbeat.check(16_000);
+        let missing_again = heartbeat.check(25_000);
+
+        // Assert
+        assert_eq!(ProcessedHeartbeatStatus::Recovered, processed.status);
+        assert_eq!(1, changes.len());
+        assert_eq!(HEARTBEAT_RECOVERED_EVENT_TYPE, event_type(&changes[0]));
+        assert_eq!("host_1", changes[0]["payload"]["key"]);
+        assert_eq!(1_000, changes[0]["payload"]["last_seen_ms"]);
+        assert_eq!(1, missing_again.len());
+        assert_eq!(HEARTBEAT_MISSING_EVENT_TYPE, event_type(&missing_again[0]));
+    }
+
+    #[test]
+    fn should_remove_the_keys_that_stay_missing_after_the_retention() {
+        // Arrange
+        let heartbeat = new_heartbeat(Default::default());
+        heartbeat.process(&new_event("check_result", "host_1"), 1_000);
+        heartbeat.check(11_000);
+        heartbeat.check(11_000 + MISSING_KEY_RETENTION_MS);
+
+        // Act
+        let processed = heartbeat
+            .process(&new_event("check_result", "host_1"), 12_000 + MISSING_KEY_RETENTION_MS);
+        let changes = heartbeat.check(13_000 + MISSING_KEY_RETENTION_MS);
+
+        // Assert
+        assert_eq!(ProcessedHeartbeatStatus::Seen, processed.status);
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn should_ignore_the_synthetic_heartbeat_events() {
+        // Arrange
+        let heartbeat = Heartbeat {
+            active: true,
+            key: "${event.payload.key}".to_owned(),
+            max_interval_seconds: 10,
+            ..Default::default()
+        };
+        let heartbeat = MatcherHeartbeat::build(
+            "heartbeat",
+            "heartbeat".to_owned(),
+            &heartbeat,
+            Default::default(),
+            &MatcherResources::default(),
+        )
+        .unwrap();
+        let event = json!({"type": HEARTBEAT_MISSING_EVENT_TYPE, "created_ms": 0, "payload": {"key": "host_1"}});
+
+        // Act
+        let processed = heartbeat.process(&event, 1_000);
+
+        // Assert
+        assert_eq!(ProcessedHeartbeatStatus::NotMatched, processed.status);
+        assert!(heartbeat.check(20_000).is_empty());
+    }
+
+    #[test]
+    fn should_not_record_events_if_inactive() {
+        // Arrange
+        let heartbeat = Heartbeat { active: false, max_interval_seconds: 10, ..Default::default() };
+        let heartbeat = MatcherHeartbeat::build(
+            "heartbeat",
+            "heartbeat".to_owned(),
+            &heartbeat,
+            Default::default(),
+            &MatcherResources::default(),
+        )
+        .unwrap();
+
+        // Act
+        let processed = heartbeat.process(&new_event("check_result", "host_1"), 1_000);
+
+        // Assert
+        assert_eq!(ProcessedHeartbeatStatus::Inactive, processed.status);
+        assert!(heartbeat.check(20_000).is_empty());
+    }
+}
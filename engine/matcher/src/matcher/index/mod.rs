@@ -0,0 +1,239 @@
+//! The index module contains the logic to skip the evaluation of the Filters whose condition
+//! is an 'equals' between an Event value and a constant string, e.g. `${event.type}` and "email".
+//!
+//! At build time, these Filters are grouped by the accessor of the Event value and indexed by
+//! the constant string. At processing time, the accessor of each group is evaluated only once and
+//! all the Filters whose constant is different from the Event value are reported as not matched
+//! without evaluating their operator.
+
+use crate::accessor::{Accessor, AccessorBuilder};
+use crate::config::nodes::Filter;
+use crate::config::rule::Operator;
+use crate::config::{Defaultable, MatcherConfig};
+use crate::error::MatcherError;
+use crate::model::InternalEvent;
+use log::*;
+use serde_json::Value;
+use std::collections::HashMap;
+use tornado_common_api::cow_to_str;
+
+/// An index of the sibling Filters of a processing tree node.
+#[derive(Debug, Default)]
+pub struct FilterIndex {
+    groups: Vec<IndexGroup>,
+    indexed: Vec<bool>,
+}
+
+/// The indexed Filters that compare the same Event value.
+#[derive(Debug)]
+struct IndexGroup {
+    accessor: Accessor,
+    positions_by_value: HashMap<String, Vec<usize>>,
+}
+
+impl FilterIndex {
+    /// Builds the index of the given sibling nodes. The positions in the index are the positions
+    /// of the nodes in the slice.
+    pub fn build(nodes: &[MatcherConfig]) -> Result<FilterIndex, MatcherError> {
+        let mut groups: Vec<(String, IndexGroup)> = vec![];
+        let mut indexed = vec![false; nodes.len()];
+
+        for (position, node) in nodes.iter().enumerate() {
+            let (name, filter) = match node {
+                MatcherConfig::Filter { name, filter, .. } => (name, filter),
+                _ => continue,
+            };
+            if let Some((source, accessor, constant)) = FilterIndex::index_key(name, filter)? {
+                trace!(
+                    "FilterIndex build - index filter [{}] by [{}] equals [{}]",
+                    name,
+                    source,
+                    constant
+                );
+                let group =
+                    match groups.iter_mut().find(|(group_source, _)| *group_source == source) {
+                        Some((_, group)) => group,
+                        None => {
+                            groups.push((
+                                source,
+                                IndexGroup { accessor, positions_by_value: HashMap::new() },
+                            ));
+                            &mut groups.last_mut().expect("a group was just pushed").1
+                        }
+                    };
+                group.positions_by_value.entry(constant).or_default().push(position);
+                indexed[position] = true;
+            }
+        }
+
+        if groups.is_empty() {
+            return Ok(FilterIndex::default());
+        }
+        Ok(FilterIndex { groups: groups.into_iter().map(|(_, group)| group).collect(), indexed })
+    }
+
+    /// Returns the accessor source, the accessor and the constant string of a Filter that can be
+    /// indexed. Only active Filters without a schedule can be indexed, as their processing result
+    /// depends only on their operator.
+    fn index_key(
+        name: &str,
+        filter: &Filter,
+    ) -> Result<Option<(String, Accessor, String)>, MatcherError> {
+        let (first, second) = match filter {
+            Filter {
+                active: true,
+                schedule: None,
+                filter: Defaultable::Value(Operator::Equals { first, second }),
+                ..
+            } => (first, second),
+            _ => return Ok(None),
+        };
+
+        let builder = AccessorBuilder::new();
+        let first_accessor = builder.build_from_value(name, first)?;
+        let second_accessor = builder.build_from_value(name, second)?;
+
+        let key = match (first, first_accessor.constant_value(), second_accessor.constant_value()) {
+            (Value::String(source), None, Some(Value::String(constant))) => {
+                Some((source.to_owned(), first_accessor, constant.to_owned()))
+            }
+            (_, Some(Value::String(constant)), None) => match second {
+                Value::String(source) => {
+                    Some((source.to_owned(), second_accessor, constant.to_owned()))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        Ok(key)
+    }
+
+    /// Returns, for each node position, whether the node is an indexed Filter that does not match
+    /// the Event. The result is empty if there are no indexed Filters.
+    pub fn not_matched(&self, event: &Value) -> Vec<bool> {
+        if self.groups.is_empty() {
+            return vec![];
+        }
+
+        let internal_event = InternalEvent { event, extracted_variables: &mut Value::Null };
+        let mut not_matched = self.indexed.clone();
+        for group in &self.groups {
+            let value = group.accessor.get(&internal_event);
+            if let Some(positions) =
+                cow_to_str(&value).and_then(|value| group.positions_by_value.get(value))
+            {
+                positions.iter().for_each(|position| not_matched[*position] = false);
+            }
+        }
+        not_matched
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn filter_node(name: &str, operator: Option<Operator>) -> MatcherConfig {
+        MatcherConfig::Filter {
+            name: name.to_owned(),
+            filter: Filter {
+                description: "".to_owned(),
+                active: true,
+                filter: operator.into(),
+                schedule: None,
+            },
+            nodes: vec![],
+        }
+    }
+
+    fn equals(first: Value, second: Value) -> Option<Operator> {
+        Some(Operator::Equals { first, second })
+    }
+
+    #[test]
+    fn should_index_filters_comparing_an_accessor_with_a_constant_string() {
+        // Arrange
+        let nodes = vec![
+            filter_node("email", equals(json!("${event.type}"), json!("email"))),
+            filter_node("syslog", equals(json!("syslog"), json!("${event.type}"))),
+            filter_node("host", equals(json!("${event.payload.host}"), json!("host01"))),
+            MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![] },
+            filter_node("other_email", equals(json!("${event.type}"), json!("email"))),
+        ];
+
+        // Act
+        let index = FilterIndex::build(&nodes).unwrap();
+
+        // Assert
+        assert_eq!(2, index.groups.len());
+        assert_eq!(vec![true, true, true, false, true], index.indexed);
+
+        let event = json!({ "type": "email", "payload": { "host": "host02" } });
+        assert_eq!(vec![false, true, true, false, false], index.not_matched(&event));
+
+        let event = json!({ "type": "syslog", "payload": { "host": "host01" } });
+        assert_eq!(vec![true, false, false, false, true], index.not_matched(&event));
+
+        let event = json!({ "type": "trap", "payload": {} });
+        assert_eq!(vec![true, true, true, false, true], index.not_matched(&event));
+    }
+
+    #[test]
+    fn should_not_index_filters_that_need_to_be_evaluated() {
+        // Arrange
+        let mut inactive = filter_node("inactive", equals(json!("${event.type}"), json!("email")));
+        if let MatcherConfig::Filter { filter, .. } = &mut inactive {
+            filter.active = false;
+        }
+        let mut scheduled =
+            filter_node("scheduled", equals(json!("${event.type}"), json!("email")));
+        if let MatcherConfig::Filter { filter, .. } = &mut scheduled {
+            filter.schedule = Some(Default::default());
+        }
+        let nodes = vec![
+            inactive,
+            scheduled,
+            filter_node("no_operator", None),
+            filter_node("number", equals(json!("${event.payload.value}"), json!(12))),
+            filter_node("two_accessors", equals(json!("${event.type}"), json!("${event.payload}"))),
+            filter_node("two_constants", equals(json!("email"), json!("email"))),
+            filter_node(
+                "not_equals",
+                Some(Operator::NotEquals { first: json!("${event.type}"), second: json!("email") }),
+            ),
+        ];
+
+        // Act
+        let index = FilterIndex::build(&nodes).unwrap();
+
+        // Assert
+        assert!(index.groups.is_empty());
+        assert!(index.not_matched(&json!({ "type": "trap" })).is_empty());
+    }
+
+    #[test]
+    fn should_report_all_indexed_filters_as_not_matched_if_the_value_is_not_a_string() {
+        // Arrange
+        let nodes = vec![filter_node("five", equals(json!("${event.payload.value}"), json!("5")))];
+        let index = FilterIndex::build(&nodes).unwrap();
+
+        // Act
+        let not_matched = index.not_matched(&json!({ "type": "trap", "payload": { "value": 5 } }));
+
+        // Assert
+        assert_eq!(vec![true], not_matched);
+    }
+
+    #[test]
+    fn build_should_fail_if_the_accessor_is_not_valid() {
+        // Arrange
+        let nodes = vec![filter_node("wrong", equals(json!("${WRONG}"), json!("email")))];
+
+        // Act
+        let result = FilterIndex::build(&nodes);
+
+        // Assert
+        assert!(result.is_err());
+    }
+}
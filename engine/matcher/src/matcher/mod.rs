@@ -1,23 +1,44 @@
 pub mod action;
+pub mod correlation;
+pub mod enricher;
 pub mod extractor;
+pub mod heartbeat;
+pub mod index;
 pub mod modifier;
 pub mod operator;
+pub mod schedule;
+pub mod state;
+pub mod suppression;
+pub mod thread_pool;
+pub mod threshold;
 
+use rayon::prelude::*;
 use tracing::instrument;
 
 use crate::accessor::AccessorBuilder;
 use crate::config::rule::Rule;
 use crate::config::MatcherConfig;
 use crate::error::MatcherError;
+use crate::matcher::correlation::MatcherCorrelation;
+use crate::matcher::enricher::MatcherEnricher;
 use crate::matcher::extractor::{MatcherExtractor, MatcherExtractorBuilder};
+use crate::matcher::heartbeat::MatcherHeartbeat;
+use crate::matcher::index::FilterIndex;
+use crate::matcher::schedule::MatcherSchedule;
+use crate::matcher::state::MatcherState;
+use crate::matcher::suppression::MatcherSuppression;
+use crate::matcher::thread_pool::MatcherThreadPool;
+use crate::matcher::threshold::MatcherThreshold;
 use crate::model::{
-    InternalEvent, ProcessedEvent, ProcessedFilter, ProcessedFilterStatus, ProcessedIteration,
-    ProcessedIterator, ProcessedNode, ProcessedRule, ProcessedRuleMetaData, ProcessedRuleStatus,
-    ProcessedRules,
+    InternalEvent, ProcessedEnricher, ProcessedEnricherStatus, ProcessedEvent, ProcessedFilter,
+    ProcessedFilterStatus, ProcessedIteration, ProcessedIterator, ProcessedNode, ProcessedRule,
+    ProcessedRuleMetaData, ProcessedRuleStatus, ProcessedRules,
 };
+use crate::resources::MatcherResources;
 use crate::validator::MatcherConfigValidator;
 use log::*;
 use serde_json::{json, Map, Value};
+use tornado_common_api::WithEventData;
 use tornado_common_parser::AccessorExpression;
 
 /// The Matcher's internal Rule representation, which contains the operators and executors built
@@ -28,6 +49,9 @@ pub struct MatcherRule {
     operator: Box<dyn operator::Operator>,
     extractor: MatcherExtractor,
     actions: Vec<action::ActionResolver>,
+    threshold: Option<MatcherThreshold>,
+    suppression: Option<MatcherSuppression>,
+    schedule: Option<MatcherSchedule>,
 }
 
 /// The Matcher's internal Filter representation, which contains the operators and executors built
@@ -35,25 +59,88 @@ pub struct MatcherRule {
 pub struct MatcherFilter {
     pub active: bool,
     pub filter: Box<dyn operator::Operator>,
+    pub schedule: Option<MatcherSchedule>,
 }
 
+/// The `index` of the Filter, Iterator and Enricher nodes allows skipping the evaluation of the
+/// child Filters that cannot match an Event.
 pub enum ProcessingNode {
-    Filter { name: String, filter: MatcherFilter, nodes: Vec<ProcessingNode> },
-    Iterator { name: String, target: AccessorExpression, nodes: Vec<ProcessingNode> },
+    Filter {
+        name: String,
+        filter: MatcherFilter,
+        nodes: Vec<ProcessingNode>,
+        index: FilterIndex,
+    },
+    Iterator {
+        name: String,
+        target: AccessorExpression,
+        nodes: Vec<ProcessingNode>,
+        index: FilterIndex,
+        /// Whether the child nodes keep a state. All the iterations share the state of the same
+        /// Rules and nodes, so they are processed sequentially to keep the results deterministic.
+        is_stateful: bool,
+    },
     Ruleset { name: String, rules: Vec<MatcherRule> },
+    Correlation { name: String, correlation: MatcherCorrelation },
+    Heartbeat { name: String, heartbeat: MatcherHeartbeat },
+    Enricher {
+        name: String,
+        enricher: MatcherEnricher,
+        nodes: Vec<ProcessingNode>,
+        index: FilterIndex,
+    },
+}
+
+/// The options of the processing of an Event.
+#[derive(Debug, Clone, Copy)]
+struct ProcessOptions {
+    /// Whether the processed Rules contain their metadata and the Iterators their events
+    include_metadata: bool,
+    /// Whether the processed Rules contain the trace of the evaluation of their WHERE clause
+    include_trace: bool,
+}
+
+impl ProcessingNode {
+    /// Returns whether the node or one of its descendants keeps a state in the MatcherState
+    fn is_stateful(&self) -> bool {
+        match self {
+            ProcessingNode::Filter { nodes, .. }
+            | ProcessingNode::Iterator { nodes, .. }
+            | ProcessingNode::Enricher { nodes, .. } => {
+                nodes.iter().any(ProcessingNode::is_stateful)
+            }
+            ProcessingNode::Ruleset { rules, .. } => {
+                rules.iter().any(|rule| rule.threshold.is_some() || rule.suppression.is_some())
+            }
+            ProcessingNode::Correlation { .. } | ProcessingNode::Heartbeat { .. } => true,
+        }
+    }
 }
 
 /// The Matcher contains the core logic of the Tornado Engine.
 /// It matches incoming Events against the defined Rules.
-/// A Matcher instance is stateless and thread-safe; consequently, a single instance can serve the entire application.
+/// A Matcher instance is thread-safe; consequently, a single instance can serve the entire application.
+/// The only state kept by a Matcher is the one of the threshold and suppression Rules and of the
+/// Correlation and Heartbeat nodes, which is stored in a MatcherState.
+/// When a MatcherThreadPool is set, the child nodes of a Filter and the iterations of an Iterator
+/// are processed in parallel; the order of the processing results is not affected.
+/// The iterations of an Iterator whose subtree keeps a state are always processed sequentially.
 pub struct Matcher {
     node: ProcessingNode,
+    thread_pool: Option<MatcherThreadPool>,
 }
 
-fn build_matcher_rule(rule: &Rule) -> Result<MatcherRule, MatcherError> {
+fn build_matcher_rule(
+    rule: &Rule,
+    ruleset_path: &str,
+    state: &MatcherState,
+    resources: &MatcherResources,
+) -> Result<MatcherRule, MatcherError> {
+    let rule_key = format!("{}.{}", ruleset_path, rule.name);
+
     let action_builder = action::ActionResolverBuilder::new();
-    let operator_builder = operator::OperatorBuilder::new();
-    let extractor_builder = MatcherExtractorBuilder::new();
+    let operator_builder = operator::OperatorBuilder::new().with_resources(resources.clone());
+    let extractor_builder = MatcherExtractorBuilder::new().with_resources(resources.clone());
 
     debug!("Matcher build - Processing rule: [{}]", &rule.name);
     trace!("Matcher build - Processing rule definition:\n{:?}", rule);
@@ -64,30 +151,107 @@ fn build_matcher_rule(rule: &Rule) -> Result<MatcherRule, MatcherError> {
         operator: operator_builder.build_option(&rule.name, &rule.constraint.where_operator)?,
         extractor: extractor_builder.build(&rule.name, &rule.constraint.with)?,
         actions: action_builder.build_all(&rule.name, &rule.actions)?,
+        threshold: rule
+            .threshold
+            .as_ref()
+            .map(|threshold| {
+                MatcherThreshold::build(
+                    &rule.name,
+                    rule_key.clone(),
+                    threshold,
+                    state.thresholds.clone(),
+                )
+            })
+            .transpose()?,
+        suppression: rule
+            .suppression
+            .as_ref()
+            .map(|suppression| {
+                MatcherSuppression::build(
+                    &rule.name,
+                    rule_key.clone(),
+                    suppression,
+                    state.suppressions.clone(),
+                )
+            })
+            .transpose()?,
+        schedule: rule
+            .schedule
+            .as_ref()
+            .map(|schedule| MatcherSchedule::build(&rule.name, schedule))
+            .transpose()?,
     })
 }
 
-pub fn validate_rule(rule: &Rule) -> Result<(), MatcherError> {
-    let _ = build_matcher_rule(rule)?;
+pub fn validate_rule(rule: &Rule, resources: &MatcherResources) -> Result<(), MatcherError> {
+    let _ = build_matcher_rule(rule, "", &MatcherState::new(), resources)?;
     Ok(())
 }
 
 impl Matcher {
     /// Builds a new Matcher and configures it to operate with a set of Rules.
+    /// The grok expressions of the Rules can use only the built-in patterns.
     pub fn build(config: &MatcherConfig) -> Result<Matcher, MatcherError> {
+        Matcher::build_with_resources(config, &MatcherResources::default())
+    }
+
+    /// Builds a new Matcher whose Rules use the given resources, e.g. the grok patterns.
+    pub fn build_with_resources(
+        config: &MatcherConfig,
+        resources: &MatcherResources,
+    ) -> Result<Matcher, MatcherError> {
+        Matcher::build_with_state(config, MatcherState::new(), resources)
+    }
+
+    /// Builds a new Matcher that keeps the state of the threshold and suppression Rules and of the
+    /// Correlation and Heartbeat nodes in the given MatcherState. The state of a Rule or of a node
+    /// is identified by its path in the processing tree; the state of the Rules and nodes that
+    /// are not in the configuration is removed only if the build succeeds, so a rejected
+    /// configuration leaves the state of the running Matcher untouched.
+    pub fn build_with_state(
+        config: &MatcherConfig,
+        state: MatcherState,
+        resources: &MatcherResources,
+    ) -> Result<Matcher, MatcherError> {
+        info!("Matcher build start");
+        MatcherConfigValidator::new().validate(config)?;
+        let node = Matcher::build_processing_tree(config, "", &state, resources)?;
+        state.retain(config);
+        Ok(Matcher { node, thread_pool: None })
+    }
+
+    /// Builds a new Matcher like `build_with_state`, but without removing the state of the Rules
+    /// and nodes that are not in the configuration. It is used to process Events with a part of
+    /// the processing tree that shares the state of the whole tree.
+    pub fn build_with_shared_state(
+        config: &MatcherConfig,
+        state: MatcherState,
+        resources: &MatcherResources,
+    ) -> Result<Matcher, MatcherError> {
         info!("Matcher build start");
         MatcherConfigValidator::new().validate(config)?;
-        Matcher::build_processing_tree(config).map(|node| Matcher { node })
+        Matcher::build_processing_tree(config, "", &state, resources)
+            .map(|node| Matcher { node, thread_pool: None })
     }
 
-    fn build_processing_tree(config: &MatcherConfig) -> Result<ProcessingNode, MatcherError> {
+    fn build_processing_tree(
+        config: &MatcherConfig,
+        parent_path: &str,
+        state: &MatcherState,
+        resources: &MatcherResources,
+    ) -> Result<ProcessingNode, MatcherError> {
+        let path = if parent_path.is_empty() {
+            config.get_name().to_owned()
+        } else {
+            format!("{}.{}", parent_path, config.get_name())
+        };
         match config {
             MatcherConfig::Ruleset { name, rules } => {
                 info!("Start processing {} Matcher Config Rules", rules.len());
                 let processed_rules = rules
                     .iter()
                     .filter(|rule| rule.active)
-                    .map(build_matcher_rule)
+                    .map(|rule| build_matcher_rule(rule, &path, state, resources))
                     .collect::<Result<_, _>>()?;
 
                 info!("Matcher Rules build completed");
@@ -96,18 +260,27 @@ impl Matcher {
             }
             MatcherConfig::Filter { name, filter, nodes } => {
                 debug!("Start processing Matcher Filter [{}] Config", name);
-                let operator_builder = operator::OperatorBuilder::new();
+                let operator_builder =
+                    operator::OperatorBuilder::new().with_resources(resources.clone());
 
                 let matcher_filter = MatcherFilter {
                     active: filter.active,
                     filter: operator_builder.build_option(name, &filter.filter.clone().into())?,
+                    schedule: filter
+                        .schedule
+                        .as_ref()
+                        .map(|schedule| MatcherSchedule::build(name, schedule))
+                        .transpose()?,
                 };
 
                 let mut matcher_nodes = vec![];
+                let mut index = FilterIndex::default();
                 if matcher_filter.active {
                     for node in nodes {
-                        matcher_nodes.push(Matcher::build_processing_tree(node)?);
+                        matcher_nodes
+                            .push(Matcher::build_processing_tree(node, &path, state, resources)?);
                     }
+                    index = FilterIndex::build(nodes)?;
                 };
 
                 debug!("Matcher Filter [{}] build completed", name);
@@ -115,6 +288,7 @@ impl Matcher {
                     name: name.to_owned(),
                     filter: matcher_filter,
                     nodes: matcher_nodes,
+                    index,
                 })
             }
             MatcherConfig::Iterator { name, iterator, nodes } => {
@@ -123,41 +297,204 @@ impl Matcher {
                 let children = nodes
                     .iter()
                     .filter(|_| iterator.is_active())
-                    .map(Matcher::build_processing_tree)
+                    .map(|node| Matcher::build_processing_tree(node, &path, state, resources))
                     .collect::<Result<Vec<_>, _>>()?;
+                let index = if iterator.is_active() {
+                    FilterIndex::build(nodes)?
+                } else {
+                    FilterIndex::default()
+                };
+
+                Ok(ProcessingNode::Iterator {
+                    name: name.clone(),
+                    target: exp,
+                    is_stateful: children.iter().any(ProcessingNode::is_stateful),
+                    nodes: children,
+                    index,
+                })
+            }
+            MatcherConfig::Correlation { name, correlation } => {
+                debug!("Start processing Matcher Correlation [{}] Config", name);
+                let matcher_correlation = MatcherCorrelation::build(
+                    name,
+                    path,
+                    correlation,
+                    state.correlations.clone(),
+                    resources,
+                )?;
+                Ok(ProcessingNode::Correlation {
+                    name: name.to_owned(),
+                    correlation: matcher_correlation,
+                })
+            }
+            MatcherConfig::Heartbeat { name, heartbeat } => {
+                debug!("Start processing Matcher Heartbeat [{}] Config", name);
+                let matcher_heartbeat = MatcherHeartbeat::build(
+                    name,
+                    path,
+                    heartbeat,
+                    state.heartbeats.clone(),
+                    resources,
+                )?;
+                Ok(ProcessingNode::Heartbeat {
+                    name: name.to_owned(),
+                    heartbeat: matcher_heartbeat,
+                })
+            }
+            MatcherConfig::Enricher { name, enricher, nodes } => {
+                debug!("Start processing Matcher Enricher [{}] Config", name);
+                let matcher_enricher = MatcherEnricher::build(name, enricher, resources)?;
+                // The child nodes are built also when the enricher is inactive, because the
+                // Event is passed to them anyway.
+                let mut children = vec![];
+                for node in nodes {
+                    children.push(Matcher::build_processing_tree(node, &path, state, resources)?);
+                }
+                let index = FilterIndex::build(nodes)?;
 
-                Ok(ProcessingNode::Iterator { name: name.clone(), target: exp, nodes: children })
+                Ok(ProcessingNode::Enricher {
+                    name: name.to_owned(),
+                    enricher: matcher_enricher,
+                    nodes: children,
+                    index,
+                })
             }
         }
     }
 
+    /// Sets the thread pool used to process in parallel the independent nodes of the
+    /// processing tree.
+    pub fn with_thread_pool(mut self, thread_pool: MatcherThreadPool) -> Matcher {
+        self.thread_pool = Some(thread_pool);
+        self
+    }
+
     /// Processes an incoming Event and compares it against the set of Rules defined at the Matcher's creation time.
     /// The result is a ProcessedEvent.
     pub fn process(&self, event: Value, include_metadata: bool) -> ProcessedEvent {
-        trace!(
-            "Matcher process - processing event: [{:?}], include metadata: [{}]",
-            &event,
-            include_metadata
-        );
-        let result = Matcher::process_node(&self.node, &event, include_metadata);
+        self.process_with_options(event, ProcessOptions { include_metadata, include_trace: false })
+    }
+
+    /// Processes an incoming Event like `process`; in addition, each processed Rule contains
+    /// the trace of the evaluation of its WHERE clause.
+    pub fn process_with_trace(&self, event: Value, include_metadata: bool) -> ProcessedEvent {
+        self.process_with_options(event, ProcessOptions { include_metadata, include_trace: true })
+    }
+
+    fn process_with_options(&self, event: Value, options: ProcessOptions) -> ProcessedEvent {
+        trace!("Matcher process - processing event: [{:?}], options: [{:?}]", &event, options);
+        let result = match &self.thread_pool {
+            Some(thread_pool) => {
+                thread_pool.install(|| Matcher::process_node(&self.node, &event, options, true))
+            }
+            None => Matcher::process_node(&self.node, &event, options, false),
+        };
         ProcessedEvent { event, result }
     }
 
+    /// Removes the expired sequences of all the Correlation nodes.
+    /// The result contains a ProcessedEvent for each expired sequence, with the event that
+    /// started the sequence and the `on_timeout` actions of the Correlation.
+    pub fn expire_correlations(&self) -> Vec<ProcessedEvent> {
+        let now_ms = chrono::Utc::now().timestamp_millis() as u64;
+        let mut expired = vec![];
+        Matcher::expire_node(&self.node, now_ms, &mut expired);
+        expired
+    }
+
+    fn expire_node(node: &ProcessingNode, now_ms: u64, expired: &mut Vec<ProcessedEvent>) {
+        match node {
+            ProcessingNode::Filter { nodes, .. }
+            | ProcessingNode::Iterator { nodes, .. }
+            | ProcessingNode::Enricher { nodes, .. } => {
+                nodes.iter().for_each(|node| Matcher::expire_node(node, now_ms, expired))
+            }
+            ProcessingNode::Ruleset { .. } | ProcessingNode::Heartbeat { .. } => {}
+            ProcessingNode::Correlation { name, correlation } => {
+                for (event, processed) in correlation.expire(now_ms) {
+                    debug!(
+                        "Matcher expire - sequence with key [{:?}] of correlation [{}] expired",
+                        processed.key, name
+                    );
+                    expired.push(ProcessedEvent {
+                        event,
+                        result: ProcessedNode::Correlation {
+                            name: name.to_owned(),
+                            correlation: processed,
+                        },
+                    });
+                }
+            }
+        }
+    }
+
+    /// Checks the Heartbeat nodes for keys that stopped sending events or that sent events again.
+    /// The result contains the synthetic `tornado.heartbeat_missing` and
+    /// `tornado.heartbeat_recovered` Events to be processed by the Matcher.
+    pub fn check_heartbeats(&self) -> Vec<Value> {
+        let now_ms = chrono::Utc::now().timestamp_millis() as u64;
+        let mut events = vec![];
+        Matcher::check_heartbeat_node(&self.node, now_ms, &mut events);
+        events
+    }
+
+    fn check_heartbeat_node(node: &ProcessingNode, now_ms: u64, events: &mut Vec<Value>) {
+        match node {
+            ProcessingNode::Filter { nodes, .. }
+            | ProcessingNode::Iterator { nodes, .. }
+            | ProcessingNode::Enricher { nodes, .. } => {
+                nodes.iter().for_each(|node| Matcher::check_heartbeat_node(node, now_ms, events))
+            }
+            ProcessingNode::Ruleset { .. } | ProcessingNode::Correlation { .. } => {}
+            ProcessingNode::Heartbeat { heartbeat, .. } => events.extend(heartbeat.check(now_ms)),
+        }
+    }
+
     fn process_node(
         node: &ProcessingNode,
         internal_event: &Value,
-        include_metadata: bool,
+        options: ProcessOptions,
+        parallel: bool,
     ) -> ProcessedNode {
         match node {
-            ProcessingNode::Filter { name, filter, nodes } => {
-                Matcher::process_filter(name, filter, nodes, internal_event, include_metadata)
-            }
+            ProcessingNode::Filter { name, filter, nodes, index } => Matcher::process_filter(
+                name,
+                filter,
+                nodes,
+                index,
+                internal_event,
+                options,
+                parallel,
+            ),
             ProcessingNode::Ruleset { name, rules } => {
-                Matcher::process_rules(name, rules, internal_event, include_metadata)
+                Matcher::process_rules(name, rules, internal_event, options)
+            }
+            ProcessingNode::Iterator { name, target, nodes, index, is_stateful } => {
+                Matcher::process_iterator(
+                    name,
+                    target,
+                    nodes,
+                    index,
+                    internal_event,
+                    options,
+                    parallel && !is_stateful,
+                )
             }
-            ProcessingNode::Iterator { name, target, nodes } => {
-                Matcher::process_iterator(name, target, nodes, internal_event, include_metadata)
+            ProcessingNode::Correlation { name, correlation } => {
+                Matcher::process_correlation(name, correlation, internal_event)
             }
+            ProcessingNode::Heartbeat { name, heartbeat } => {
+                Matcher::process_heartbeat(name, heartbeat, internal_event)
+            }
+            ProcessingNode::Enricher { name, enricher, nodes, index } => Matcher::process_enricher(
+                name,
+                enricher,
+                nodes,
+                index,
+                internal_event,
+                options,
+                parallel,
+            ),
         }
     }
 
@@ -166,24 +503,31 @@ impl Matcher {
         filter_name: &str,
         filter: &MatcherFilter,
         nodes: &[ProcessingNode],
+        index: &FilterIndex,
         event: &Value,
-        include_metadata: bool,
+        options: ProcessOptions,
+        parallel: bool,
     ) -> ProcessedNode {
         trace!("Matcher process - check matching of filter: [{}]", filter_name);
 
         let mut result_nodes = vec![];
 
         let filter_status = if filter.active {
+            if !Matcher::is_in_schedule(&filter.schedule, event) {
+                trace!("Matcher process - filter [{}] is out of schedule", filter_name);
+                return ProcessedNode::Filter {
+                    name: filter_name.to_owned(),
+                    filter: ProcessedFilter { status: ProcessedFilterStatus::OutOfSchedule },
+                    nodes: result_nodes,
+                };
+            }
             let internal_event = InternalEvent { event, extracted_variables: &mut Value::Null };
             if filter.filter.evaluate(&internal_event) {
                 trace!(
                         "Matcher process - event matches filter: [{}]. Passing the Event to the nested nodes.",
                         filter_name
                     );
-                nodes.iter().for_each(|node| {
-                    let processed_node = Matcher::process_node(node, event, include_metadata);
-                    result_nodes.push(processed_node);
-                });
+                result_nodes = Matcher::process_nodes(nodes, index, event, options, parallel);
                 ProcessedFilterStatus::Matched
             } else {
                 ProcessedFilterStatus::NotMatched
@@ -199,13 +543,87 @@ impl Matcher {
         }
     }
 
+    #[instrument(level = "debug", skip_all, fields(otel.name = format ! ("Process Enricher: {}", name).as_str()))]
+    fn process_enricher(
+        name: &str,
+        enricher: &MatcherEnricher,
+        nodes: &[ProcessingNode],
+        index: &FilterIndex,
+        event: &Value,
+        options: ProcessOptions,
+        parallel: bool,
+    ) -> ProcessedNode {
+        trace!("Matcher process - enrich event with enricher: [{}]", name);
+
+        if !enricher.is_active() {
+            trace!(
+                "Matcher process - enricher [{}] is inactive. Passing the original Event to the nested nodes.",
+                name
+            );
+            return ProcessedNode::Enricher {
+                name: name.to_owned(),
+                enricher: ProcessedEnricher::new(ProcessedEnricherStatus::Inactive),
+                nodes: Matcher::process_nodes(nodes, index, event, options, parallel),
+            };
+        }
+
+        let (enriched_event, processed) = enricher.enrich(event);
+        let result_nodes = Matcher::process_nodes(nodes, index, &enriched_event, options, parallel);
+
+        ProcessedNode::Enricher { name: name.to_owned(), enricher: processed, nodes: result_nodes }
+    }
+
+    /// Processes the child nodes of a Filter, of an Iterator or of an Enricher.
+    /// The indexed Filters that cannot match the Event are reported as not matched without
+    /// being evaluated.
+    /// If `parallel` is true, the nodes are processed in parallel on the current thread pool;
+    /// the results are returned in the same order of the nodes.
+    fn process_nodes(
+        nodes: &[ProcessingNode],
+        index: &FilterIndex,
+        event: &Value,
+        options: ProcessOptions,
+        parallel: bool,
+    ) -> Vec<ProcessedNode> {
+        let not_matched = index.not_matched(event);
+        let process = |position: usize, node: &ProcessingNode| match node {
+            ProcessingNode::Filter { name, .. }
+                if not_matched.get(position).copied().unwrap_or(false) =>
+            {
+                trace!("Matcher process - filter [{}] skipped by the index", name);
+                ProcessedNode::Filter {
+                    name: name.to_owned(),
+                    filter: ProcessedFilter { status: ProcessedFilterStatus::NotMatched },
+                    nodes: vec![],
+                }
+            }
+            _ => Matcher::process_node(node, event, options, parallel),
+        };
+
+        if parallel && nodes.len() > 1 {
+            let span = tracing::Span::current();
+            nodes
+                .par_iter()
+                .enumerate()
+                .map(|(position, node)| {
+                    let _entered = span.enter();
+                    process(position, node)
+                })
+                .collect()
+        } else {
+            nodes.iter().enumerate().map(|(position, node)| process(position, node)).collect()
+        }
+    }
+
     #[instrument(level = "debug", skip_all, fields(otel.name = format ! ("Process Iterator: {}", name).as_str()))]
     fn process_iterator(
         name: &str,
         target: &AccessorExpression,
         nodes: &[ProcessingNode],
+        index: &FilterIndex,
         event: &Value,
-        include_metadata: bool,
+        options: ProcessOptions,
+        parallel: bool,
     ) -> ProcessedNode {
         trace!("Matcher process - check matching of iterator: [{}]", name);
         let internal_event = InternalEvent { event, extracted_variables: &mut Default::default() };
@@ -221,12 +639,12 @@ impl Matcher {
         match target.as_ref() {
             Value::Array(slice) => {
                 let iterator = slice.iter().enumerate();
-                Matcher::iterate_over(name, iterator, event, nodes, include_metadata)
+                Matcher::iterate_over(name, iterator, event, nodes, index, options, parallel)
             }
             Value::Object(map) => {
                 let iterator =
                     map.keys().flat_map(|key| map.get(key).map(|value| (key.as_str(), value)));
-                Matcher::iterate_over(name, iterator, event, nodes, include_metadata)
+                Matcher::iterate_over(name, iterator, event, nodes, index, options, parallel)
             }
             _ => {
                 return ProcessedNode::Iterator {
@@ -243,35 +661,50 @@ impl Matcher {
         iterator: Iter,
         event: &Value,
         nodes: &[ProcessingNode],
-        include_metadata: bool,
+        index: &FilterIndex,
+        options: ProcessOptions,
+        parallel: bool,
     ) -> ProcessedNode
     where
-        Key: Into<Value> + Copy,
+        Key: Into<Value> + Copy + Send,
         Iter: Iterator<Item = (Key, &'a Value)>,
     {
+        if parallel {
+            let span = tracing::Span::current();
+            let items: Vec<_> = iterator.collect();
+            let processed_events = items
+                .into_par_iter()
+                .filter_map(|(iteration, item)| {
+                    let _entered = span.enter();
+                    let mut iterator_event = event.clone();
+                    if !Matcher::set_iteration(&mut iterator_event, iteration, item) {
+                        return None;
+                    }
+                    let processed_nodes =
+                        Matcher::process_nodes(nodes, index, &iterator_event, options, true);
+                    let event = if options.include_metadata { iterator_event } else { Value::Null };
+                    Some(ProcessedIteration { event, result: processed_nodes })
+                })
+                .collect();
+
+            return ProcessedNode::Iterator {
+                name: name.to_string(),
+                iterator: ProcessedIterator::Matched,
+                events: processed_events,
+            };
+        }
+
         let mut processed_events = vec![];
         let mut iterator_event = event.clone();
         for (iteration, item) in iterator {
-            let Some(event_inner) = iterator_event.as_object_mut() else {
-                continue;
-            };
-
-            let Some(iterator) = event_inner.entry("iterator").or_insert(json!({})).as_object_mut()
-            else {
-                // we just inserted it as an iterator. This path will not be taken.
+            if !Matcher::set_iteration(&mut iterator_event, iteration, item) {
                 continue;
-            };
-
-            iterator.insert("iteration".to_string(), iteration.into());
-            iterator.insert("item".to_string(), item.clone());
-
-            let mut processed_nodes = vec![];
-            for node in nodes {
-                let processed_node = Matcher::process_node(node, &iterator_event, include_metadata);
-                processed_nodes.push(processed_node)
             }
 
-            if include_metadata {
+            let processed_nodes =
+                Matcher::process_nodes(nodes, index, &iterator_event, options, false);
+
+            if options.include_metadata {
                 processed_events
                     .push(ProcessedIteration { event: iterator_event, result: processed_nodes });
                 iterator_event = event.clone();
@@ -288,12 +721,48 @@ impl Matcher {
         }
     }
 
+    /// Sets the current iteration and item in the `iterator` field of the Event.
+    /// Returns false if the Event is not an object.
+    fn set_iteration<Key: Into<Value>>(event: &mut Value, iteration: Key, item: &Value) -> bool {
+        let Some(event_inner) = event.as_object_mut() else {
+            return false;
+        };
+
+        let Some(iterator) = event_inner.entry("iterator").or_insert(json!({})).as_object_mut()
+        else {
+            // we just inserted it as an iterator. This path will not be taken.
+            return false;
+        };
+
+        iterator.insert("iteration".to_string(), iteration.into());
+        iterator.insert("item".to_string(), item.clone());
+        true
+    }
+
+    #[instrument(level = "debug", skip_all, fields(otel.name = format ! ("Process Correlation: {}", name).as_str()))]
+    fn process_correlation(
+        name: &str,
+        correlation: &MatcherCorrelation,
+        event: &Value,
+    ) -> ProcessedNode {
+        trace!("Matcher process - check matching of correlation: [{}]", name);
+        let processed = correlation.process(event, chrono::Utc::now().timestamp_millis() as u64);
+        ProcessedNode::Correlation { name: name.to_owned(), correlation: processed }
+    }
+
+    #[instrument(level = "debug", skip_all, fields(otel.name = format ! ("Process Heartbeat: {}", name).as_str()))]
+    fn process_heartbeat(name: &str, heartbeat: &MatcherHeartbeat, event: &Value) -> ProcessedNode {
+        trace!("Matcher process - check matching of heartbeat: [{}]", name);
+        let processed = heartbeat.process(event, chrono::Utc::now().timestamp_millis() as u64);
+        ProcessedNode::Heartbeat { name: name.to_owned(), heartbeat: processed }
+    }
+
     #[instrument(level = "debug", skip_all, fields(otel.name = format ! ("Process Ruleset: {}", ruleset_name).as_str()))]
     fn process_rules(
         ruleset_name: &str,
         rules: &[MatcherRule],
         event: &Value,
-        include_metadata: bool,
+        options: ProcessOptions,
     ) -> ProcessedNode {
         trace!("Matcher process - check matching of ruleset: [{}]", ruleset_name);
         let mut extracted_vars = Value::Object(Map::new());
@@ -316,13 +785,26 @@ impl Matcher {
                 actions: vec![],
                 message: None,
                 meta: None,
+                trace: None,
             };
 
-            if include_metadata {
-                processed_rule.meta = Some(ProcessedRuleMetaData { actions: vec![] })
+            if options.include_metadata {
+                processed_rule.meta = Some(ProcessedRuleMetaData {
+                    actions: vec![],
+                    threshold: None,
+                    suppression: None,
+                })
             }
 
-            if rule.operator.evaluate(&internal_event) {
+            if !Matcher::is_in_schedule(&rule.schedule, event) {
+                trace!("Matcher process - rule [{}] is out of schedule", &rule.name);
+                processed_rule.status = ProcessedRuleStatus::OutOfSchedule;
+            } else if Matcher::evaluate_operator(
+                rule,
+                &internal_event,
+                options.include_trace,
+                &mut processed_rule,
+            ) {
                 trace!(
                     "Matcher process - event matches rule: [{}]. Checking extracted variables.",
                     &rule.name
@@ -332,24 +814,41 @@ impl Matcher {
                     Ok(_) => {
                         trace!("Matcher process - event matches rule: [{}] and its extracted variables.", &rule.name);
 
-                        match Matcher::process_actions(
-                            &internal_event,
-                            &mut processed_rule,
-                            &rule.actions,
-                        ) {
-                            Ok(_) => {
-                                processed_rule.status = ProcessedRuleStatus::Matched;
-                                if !rule.do_continue {
-                                    processed_rules.push(processed_rule);
-                                    break;
+                        let now_ms = chrono::Utc::now().timestamp_millis() as u64;
+                        if Matcher::process_threshold(rule, &internal_event, &mut processed_rule)
+                            && Matcher::process_suppression(
+                                rule,
+                                &internal_event,
+                                now_ms,
+                                &mut processed_rule,
+                            )
+                        {
+                            match Matcher::process_actions(
+                                &internal_event,
+                                &mut processed_rule,
+                                &rule.actions,
+                            ) {
+                                Ok(_) => {
+                                    Matcher::reset_threshold(rule, &internal_event);
+                                    processed_rule.status = ProcessedRuleStatus::Matched;
+                                    if !rule.do_continue {
+                                        processed_rules.push(processed_rule);
+                                        break;
+                                    }
+                                }
+                                Err(e) => {
+                                    Matcher::release_suppression(rule, &internal_event, now_ms);
+                                    let message = format!("Matcher process - The event matches the rule [{}] and all variables are extracted correctly; however, some actions cannot be resolved: [{:?}]", &rule.name, e);
+                                    debug!("{}", &message);
+                                    processed_rule.status = ProcessedRuleStatus::PartiallyMatched;
+                                    processed_rule.message = Some(message);
                                 }
                             }
-                            Err(e) => {
-                                let message = format!("Matcher process - The event matches the rule [{}] and all variables are extracted correctly; however, some actions cannot be resolved: [{:?}]", &rule.name, e);
-                                debug!("{}", &message);
-                                processed_rule.status = ProcessedRuleStatus::PartiallyMatched;
-                                processed_rule.message = Some(message);
-                            }
+                        } else if processed_rule.status == ProcessedRuleStatus::Duplicate
+                            && !rule.do_continue
+                        {
+                            processed_rules.push(processed_rule);
+                            break;
                         }
                     }
                     Err(e) => {
@@ -372,6 +871,135 @@ impl Matcher {
         result
     }
 
+    /// Returns whether the schedule, if any, allows the node to process the event.
+    /// The schedule is evaluated at the creation time of the event.
+    fn is_in_schedule(schedule: &Option<MatcherSchedule>, event: &Value) -> bool {
+        match schedule {
+            Some(schedule) => schedule.is_active_at(
+                event.created_ms().unwrap_or_else(|| chrono::Utc::now().timestamp_millis() as u64),
+            ),
+            None => true,
+        }
+    }
+
+    /// Evaluates the WHERE clause of the rule; if `include_trace` is true, the trace of the
+    /// evaluation is added to the processed rule.
+    fn evaluate_operator(
+        rule: &MatcherRule,
+        internal_event: &InternalEvent,
+        include_trace: bool,
+        processed_rule: &mut ProcessedRule,
+    ) -> bool {
+        if !include_trace {
+            return rule.operator.evaluate(internal_event);
+        }
+        let trace = rule.operator.explain(internal_event);
+        let result = trace.result;
+        processed_rule.trace = Some(trace);
+        result
+    }
+
+    /// Records the event in the sliding window of the rule threshold, if any.
+    /// Returns true if the rule has no threshold or if the threshold is reached.
+    fn process_threshold(
+        rule: &MatcherRule,
+        internal_event: &InternalEvent,
+        processed_rule: &mut ProcessedRule,
+    ) -> bool {
+        let threshold = match &rule.threshold {
+            Some(threshold) => threshold,
+            None => return true,
+        };
+
+        match threshold.record(internal_event, chrono::Utc::now().timestamp_millis() as u64) {
+            Ok(window) => {
+                let is_reached = window.is_reached();
+                if !is_reached {
+                    processed_rule.status = ProcessedRuleStatus::ThresholdNotReached;
+                    let message = format!("Matcher process - The event matches the rule [{}] but the threshold is not reached: [{}] of [{}] events in [{}] seconds", &rule.name, window.count, window.threshold, window.window_seconds);
+                    trace!("{}", &message);
+                    processed_rule.message = Some(message);
+                }
+                if let Some(metadata) = &mut processed_rule.meta {
+                    metadata.threshold = Some(window);
+                }
+                is_reached
+            }
+            Err(e) => {
+                let message = format!("Matcher process - The event matches the rule [{}] but its threshold cannot be evaluated: [{:?}]", &rule.name, e);
+                debug!("{}", &message);
+                processed_rule.status = ProcessedRuleStatus::PartiallyMatched;
+                processed_rule.message = Some(message);
+                false
+            }
+        }
+    }
+
+    /// Resets the window of the rule threshold, if any.
+    /// It is called only after the actions are produced, so that the events counted in the
+    /// window are not lost if the actions cannot be resolved.
+    fn reset_threshold(rule: &MatcherRule, internal_event: &InternalEvent) {
+        if let Some(threshold) = &rule.threshold {
+            if let Err(e) = threshold.reset(internal_event) {
+                debug!(
+                    "Matcher process - Cannot reset the threshold of rule [{}]: [{:?}]",
+                    &rule.name, e
+                );
+            }
+        }
+    }
+
+    /// Records the fingerprint of the event in the rule suppression, if any, at `now_ms`.
+    /// Returns true if the rule has no suppression or if the event is not a duplicate.
+    fn process_suppression(
+        rule: &MatcherRule,
+        internal_event: &InternalEvent,
+        now_ms: u64,
+        processed_rule: &mut ProcessedRule,
+    ) -> bool {
+        let suppression = match &rule.suppression {
+            Some(suppression) => suppression,
+            None => return true,
+        };
+
+        match suppression.record(internal_event, now_ms) {
+            Ok(window) => {
+                let is_duplicate = window.is_duplicate();
+                if is_duplicate {
+                    let message = format!("Matcher process - The event matches the rule [{}] but it is a duplicate: [{}] duplicates of fingerprint [{}] in [{}] seconds", &rule.name, window.duplicates, window.fingerprint, window.ttl_seconds);
+                    trace!("{}", &message);
+                    processed_rule.status = ProcessedRuleStatus::Duplicate;
+                    processed_rule.message = Some(message);
+                }
+                if let Some(metadata) = &mut processed_rule.meta {
+                    metadata.suppression = Some(window);
+                }
+                !is_duplicate
+            }
+            Err(e) => {
+                let message = format!("Matcher process - The event matches the rule [{}] but its suppression fingerprint cannot be evaluated: [{:?}]", &rule.name, e);
+                debug!("{}", &message);
+                processed_rule.status = ProcessedRuleStatus::PartiallyMatched;
+                processed_rule.message = Some(message);
+                false
+            }
+        }
+    }
+
+    /// Forgets the fingerprint recorded at `recorded_ms` in the rule suppression, if any.
+    /// It is called when the actions cannot be resolved, so that the next copies of the event
+    /// are not dropped as duplicates of an event that produced no actions.
+    fn release_suppression(rule: &MatcherRule, internal_event: &InternalEvent, recorded_ms: u64) {
+        if let Some(suppression) = &rule.suppression {
+            if let Err(e) = suppression.release(internal_event, recorded_ms) {
+                debug!(
+                    "Matcher process - Cannot release the suppression fingerprint of rule [{}]: [{:?}]",
+                    &rule.name, e
+                );
+            }
+        }
+    }
+
     fn process_actions(
         processed_event: &InternalEvent,
         processed_rule: &mut ProcessedRule,
@@ -399,16 +1027,21 @@ impl Matcher {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::config::nodes::{Filter, MatcherIterator};
+    use crate::config::nodes::{Correlation, Enricher, Filter, Heartbeat, MatcherIterator};
     use crate::config::rule::{
-        ConfigAction, Constraint, Extractor, ExtractorRegex, Operator, Rule,
+        ConfigAction, Constraint, Extractor, ExtractorRegex, Modifier, Operator, Rule, Schedule,
+        ScheduleWindow, Suppression, Threshold,
     };
     use crate::config::v2::{parse_from_file, FsMatcherConfigManagerV2};
     use crate::config::{Defaultable, MatcherConfigReader};
+    use crate::model::{
+        OperatorTrace, OperatorTraceValue, ProcessedCorrelationStatus, ProcessedHeartbeatStatus,
+    };
     use serde_json::json;
     use std::collections::HashMap;
     use std::path::Path;
     use tornado_common_api::{Action, Event, Payload, ValueExt, ValueGet};
+    use tornado_common_grok::GrokPatterns;
 
     #[test]
     fn should_build_the_matcher_with_a_rule_set() {
@@ -455,7 +1088,7 @@ mod test {
 
         // Assert
         match &matcher.node {
-            ProcessingNode::Filter { name, filter: _filter, nodes } => {
+            ProcessingNode::Filter { name, filter: _filter, nodes, .. } => {
                 assert_eq!(0, nodes.len());
                 assert_eq!("filter", name);
             }
@@ -482,7 +1115,7 @@ mod test {
 
         // Assert
         match &matcher.node {
-            ProcessingNode::Filter { name, filter: _filter, nodes } => {
+            ProcessingNode::Filter { name, filter: _filter, nodes, .. } => {
                 assert_eq!(0, nodes.len());
                 assert_eq!("filter", name)
             }
@@ -520,12 +1153,12 @@ mod test {
 
         // Assert
         match &matcher.node {
-            ProcessingNode::Filter { name, filter: _filter1, nodes: nodes1 } => {
+            ProcessingNode::Filter { name, filter: _filter1, nodes: nodes1, .. } => {
                 assert_eq!(2, nodes1.len());
                 assert_eq!("filter", name);
 
                 match &nodes1.first().unwrap() {
-                    ProcessingNode::Filter { name, filter: _filter2, nodes: nodes2 } => {
+                    ProcessingNode::Filter { name, filter: _filter2, nodes: nodes2, .. } => {
                         assert_eq!(1, nodes2.len());
                         assert_eq!("node1", name);
 
@@ -610,7 +1243,9 @@ mod test {
             nodes: vec![],
         };
 
-        let matcher = Matcher::build_processing_tree(&config).unwrap();
+        let matcher =
+            Matcher::build_processing_tree(&config, "", &Default::default(), &Default::default())
+                .unwrap();
 
         match matcher {
             ProcessingNode::Iterator { target, .. } => {
@@ -684,7 +1319,7 @@ mod test {
 
         fn collect_actions(result: ProcessedNode) -> Vec<Action> {
             match result {
-                ProcessedNode::Filter { nodes, .. } => {
+                ProcessedNode::Filter { nodes, .. } | ProcessedNode::Enricher { nodes, .. } => {
                     nodes.into_iter().flat_map(collect_actions).collect()
                 }
                 ProcessedNode::Iterator { events, .. } => {
@@ -693,6 +1328,8 @@ mod test {
                 ProcessedNode::Ruleset { rules, .. } => {
                     rules.rules.into_iter().flat_map(|r| r.actions).collect()
                 }
+                ProcessedNode::Correlation { correlation, .. } => correlation.actions,
+                ProcessedNode::Heartbeat { .. } => vec![],
             }
         }
 
@@ -891,6 +1528,64 @@ mod test {
         };
     }
 
+    #[test]
+    fn actions_and_rules_should_access_the_fields_of_decoded_variables() {
+        // Arrange
+        let mut rule_1 = new_rule("rule1", None);
+        rule_1.constraint.with.insert(
+            String::from("decoded"),
+            Extractor {
+                from: String::from("${event.payload.data}"),
+                regex: ExtractorRegex::Regex {
+                    regex: String::from(r".*"),
+                    group_match_idx: Some(0),
+                    all_matches: None,
+                },
+                modifiers_post: vec![Modifier::Base64Decode {}, Modifier::ParseJson {}],
+            },
+        );
+        let mut action = ConfigAction { id: String::from("action_id"), payload: Map::new() };
+        action.payload.insert("host".to_owned(), json!("${_variables.decoded.host}"));
+        action.payload.insert("check".to_owned(), json!("${_variables.decoded.checks[1]}"));
+        rule_1.actions.push(action);
+
+        let rule_2 = new_rule(
+            "rule2",
+            Operator::Equals {
+                first: json!("${_variables.rule1.decoded.host}"),
+                second: json!("web01"),
+            },
+        );
+
+        let matcher = new_matcher(&MatcherConfig::Ruleset {
+            name: "ruleset".to_owned(),
+            rules: vec![rule_1, rule_2],
+        })
+        .unwrap();
+
+        let mut payload = Map::new();
+        // {"host": "web01", "checks": ["ping", "disk"]}
+        payload.insert(
+            "data".to_owned(),
+            json!("eyJob3N0IjogIndlYjAxIiwgImNoZWNrcyI6IFsicGluZyIsICJkaXNrIl19"),
+        );
+
+        // Act
+        let result = matcher.process(json!(Event::new_with_payload("webhook", payload)), false);
+
+        // Assert
+        match result.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                assert_eq!(ProcessedRuleStatus::Matched, rules.rules[0].status);
+                assert_eq!(ProcessedRuleStatus::Matched, rules.rules[1].status);
+                let action = &rules.rules[0].actions[0];
+                assert_eq!(&json!("web01"), action.payload.get("host").unwrap());
+                assert_eq!(&json!("disk"), action.payload.get("check").unwrap());
+            }
+            _ => unreachable!(),
+        };
+    }
+
     #[test]
     fn should_return_status_not_matched_if_where_returns_false() {
         // Arrange
@@ -2757,6 +3452,1017 @@ mod test {
         };
     }
 
+    #[test]
+    fn should_return_the_trace_of_the_where_clause_only_if_requested() {
+        // Arrange
+        let rule = new_rule(
+            "rule_name",
+            Operator::And {
+                operators: vec![
+                    Operator::Equals { first: json!("${event.type}"), second: json!("email") },
+                    Operator::In {
+                        first: json!("${event.payload.severity}"),
+                        values: vec![json!(1), json!(2)],
+                    },
+                ],
+            },
+        );
+
+        let matcher =
+            new_matcher(&MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule] })
+                .unwrap();
+
+        // Act
+        let result_without_trace = matcher.process(json!(Event::new("email")), true);
+        let result_with_trace = matcher.process_with_trace(json!(Event::new("email")), false);
+
+        // Assert
+        match result_without_trace.result {
+            ProcessedNode::Ruleset { rules, .. } => assert!(rules.rules[0].trace.is_none()),
+            _ => unreachable!(),
+        };
+
+        match result_with_trace.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                let processed_rule = &rules.rules[0];
+                assert_eq!(ProcessedRuleStatus::NotMatched, processed_rule.status);
+                assert!(processed_rule.meta.is_none());
+                let expected_trace = OperatorTrace {
+                    operator: "and".to_owned(),
+                    result: false,
+                    values: vec![],
+                    operators: vec![
+                        OperatorTrace {
+                            operator: "equals".to_owned(),
+                            result: true,
+                            values: vec![
+                                OperatorTraceValue {
+                                    argument: "first".to_owned(),
+                                    value: Some(json!("email")),
+                                },
+                                OperatorTraceValue {
+                                    argument: "second".to_owned(),
+                                    value: Some(json!("email")),
+                                },
+                            ],
+                            operators: vec![],
+                        },
+                        OperatorTrace {
+                            operator: "in".to_owned(),
+                            result: false,
+                            values: vec![
+                                OperatorTraceValue { argument: "first".to_owned(), value: None },
+                                OperatorTraceValue {
+                                    argument: "values[0]".to_owned(),
+                                    value: Some(json!(1)),
+                                },
+                                OperatorTraceValue {
+                                    argument: "values[1]".to_owned(),
+                                    value: Some(json!(2)),
+                                },
+                            ],
+                            operators: vec![],
+                        },
+                    ],
+                };
+                assert_eq!(Some(expected_trace), processed_rule.trace);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn should_produce_actions_only_when_the_threshold_is_reached() {
+        // Arrange
+        let mut rule = new_rule("rule_name", None);
+        rule.actions.push(ConfigAction { id: String::from("action_1"), payload: Map::new() });
+        rule.threshold = Some(Threshold {
+            count: 2,
+            window_seconds: 3600,
+            group_by: Some("${event.type}".to_owned()),
+        });
+
+        let matcher =
+            new_matcher(&MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule] })
+                .unwrap();
+
+        // Act
+        let first_email = matcher.process(json!(Event::new("email")), true);
+        let first_sms = matcher.process(json!(Event::new("sms")), true);
+        let second_email = matcher.process(json!(Event::new("email")), true);
+
+        // Assert
+        match first_email.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                let processed_rule = &rules.rules[0];
+                assert_eq!(ProcessedRuleStatus::ThresholdNotReached, processed_rule.status);
+                assert!(processed_rule.actions.is_empty());
+                assert!(processed_rule.message.is_some());
+                let threshold = processed_rule.meta.as_ref().unwrap().threshold.as_ref().unwrap();
+                assert_eq!("email", threshold.group);
+                assert_eq!(1, threshold.count);
+                assert_eq!(2, threshold.threshold);
+            }
+            _ => unreachable!(),
+        };
+
+        match first_sms.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                assert_eq!(ProcessedRuleStatus::ThresholdNotReached, rules.rules[0].status);
+            }
+            _ => unreachable!(),
+        };
+
+        match second_email.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                let processed_rule = &rules.rules[0];
+                assert_eq!(ProcessedRuleStatus::Matched, processed_rule.status);
+                assert_eq!(1, processed_rule.actions.len());
+                let threshold = processed_rule.meta.as_ref().unwrap().threshold.as_ref().unwrap();
+                assert_eq!(2, threshold.count);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn should_not_reset_the_threshold_window_if_the_actions_cannot_be_resolved() {
+        // Arrange
+        let mut rule = new_rule("rule_name", None);
+        let mut payload = Map::new();
+        payload.insert("host".to_owned(), json!("${event.payload.host}"));
+        rule.actions.push(ConfigAction { id: String::from("action_1"), payload });
+        rule.threshold = Some(Threshold { count: 1, window_seconds: 3600, group_by: None });
+
+        let matcher =
+            new_matcher(&MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule] })
+                .unwrap();
+
+        // Act
+        let _ = matcher.process(json!(Event::new("email")), true);
+        let result = matcher.process(json!(Event::new("email")), true);
+
+        // Assert
+        match result.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                let processed_rule = &rules.rules[0];
+                assert_eq!(ProcessedRuleStatus::PartiallyMatched, processed_rule.status);
+                let threshold = processed_rule.meta.as_ref().unwrap().threshold.as_ref().unwrap();
+                assert_eq!(2, threshold.count);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn should_share_the_threshold_windows_between_matchers_with_the_same_state() {
+        // Arrange
+        let mut rule = new_rule("rule_name", None);
+        rule.threshold = Some(Threshold { count: 2, window_seconds: 3600, group_by: None });
+        let config = MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule] };
+
+        let state = MatcherState::new();
+        let matcher_1 =
+            Matcher::build_with_state(&config, state.clone(), &MatcherResources::default())
+                .unwrap();
+        let matcher_2 =
+            Matcher::build_with_state(&config, state, &MatcherResources::default()).unwrap();
+
+        // Act
+        let _ = matcher_1.process(json!(Event::new("email")), false);
+        let result = matcher_2.process(json!(Event::new("email")), false);
+
+        // Assert
+        match result.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                assert_eq!(ProcessedRuleStatus::Matched, rules.rules[0].status);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn build_with_state_should_remove_the_state_of_the_rules_not_in_the_config() {
+        // Arrange
+        let mut rule_1 = new_rule("rule_1", None);
+        rule_1.threshold = Some(Threshold { count: 2, window_seconds: 3600, group_by: None });
+        let mut rule_2 = new_rule("rule_2", None);
+        rule_2.threshold = rule_1.threshold.clone();
+        let config = MatcherConfig::Ruleset {
+            name: "ruleset".to_owned(),
+            rules: vec![rule_1.clone(), rule_2.clone()],
+        };
+        let config_without_rule_2 =
+            MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule_1] };
+
+        let state = MatcherState::new();
+        let matcher =
+            Matcher::build_with_state(&config, state.clone(), &MatcherResources::default())
+                .unwrap();
+        let _ = matcher.process(json!(Event::new("email")), false);
+
+        // Act
+        Matcher::build_with_state(
+            &config_without_rule_2,
+            state.clone(),
+            &MatcherResources::default(),
+        )
+        .unwrap();
+        let result = Matcher::build_with_shared_state(&config, state, &MatcherResources::default())
+            .unwrap()
+            .process(json!(Event::new("email")), false);
+
+        // Assert
+        match result.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                assert_eq!(ProcessedRuleStatus::Matched, rules.rules[0].status);
+                assert_eq!(ProcessedRuleStatus::ThresholdNotReached, rules.rules[1].status);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn build_with_state_should_not_remove_the_state_if_the_config_is_rejected() {
+        // Arrange
+        let mut rule_1 = new_rule("rule_1", None);
+        rule_1.threshold = Some(Threshold { count: 2, window_seconds: 3600, group_by: None });
+        let mut rule_2 = new_rule("rule_2", None);
+        rule_2.threshold = rule_1.threshold.clone();
+        let config = MatcherConfig::Ruleset {
+            name: "ruleset".to_owned(),
+            rules: vec![rule_1.clone(), rule_2.clone()],
+        };
+        let mut invalid_rule_1 = rule_1;
+        invalid_rule_1.constraint.where_operator = Some(Operator::Regex {
+            regex: "(".to_owned(),
+            target: "${event.type}".to_owned(),
+            grok: false,
+        });
+        let invalid_config_without_rule_2 =
+            MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![invalid_rule_1] };
+
+        let state = MatcherState::new();
+        let matcher =
+            Matcher::build_with_state(&config, state.clone(), &MatcherResources::default())
+                .unwrap();
+        let _ = matcher.process(json!(Event::new("email")), false);
+
+        // Act
+        let rejected = Matcher::build_with_state(
+            &invalid_config_without_rule_2,
+            state.clone(),
+            &MatcherResources::default(),
+        );
+        let result = matcher.process(json!(Event::new("email")), false);
+
+        // Assert
+        assert!(rejected.is_err());
+        match result.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                assert_eq!(ProcessedRuleStatus::Matched, rules.rules[0].status);
+                assert_eq!(ProcessedRuleStatus::Matched, rules.rules[1].status);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn should_expand_the_grok_expressions_with_the_patterns_of_the_resources() {
+        // Arrange
+        let rule = new_rule(
+            "rule_name",
+            Operator::Regex {
+                regex: "^%{MAIL_TYPE}$".to_owned(),
+                target: "${event.type}".to_owned(),
+                grok: true,
+            },
+        );
+        let config = MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule] };
+        let mut grok_patterns = GrokPatterns::default();
+        grok_patterns.add_patterns("MAIL_TYPE email|sms").unwrap();
+
+        // Act
+        let built_in_matcher = Matcher::build(&config);
+        let resources = MatcherResources::new(grok_patterns, Default::default());
+        let matcher = Matcher::build_with_resources(&config, &resources).unwrap();
+        let result = matcher.process(json!(Event::new("sms")), false);
+
+        // Assert
+        assert!(built_in_matcher.is_err());
+        match result.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                assert_eq!(ProcessedRuleStatus::Matched, rules.rules[0].status);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn build_should_fail_if_threshold_count_is_zero() {
+        // Arrange
+        let mut rule = new_rule("rule_name", None);
+        rule.threshold = Some(Threshold { count: 0, window_seconds: 60, group_by: None });
+
+        // Act
+        let matcher =
+            new_matcher(&MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule] });
+
+        // Assert
+        assert!(matcher.is_err());
+    }
+
+    #[test]
+    fn should_mark_duplicated_events_and_not_produce_actions() {
+        // Arrange
+        let mut rule = new_rule("rule_name", None);
+        rule.actions.push(ConfigAction { id: String::from("action_1"), payload: Map::new() });
+        rule.suppression =
+            Some(Suppression { fingerprint: vec!["${event.type}".to_owned()], ttl_seconds: 3600 });
+
+        let matcher =
+            new_matcher(&MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule] })
+                .unwrap();
+
+        // Act
+        let first_email = matcher.process(json!(Event::new("email")), true);
+        let first_sms = matcher.process(json!(Event::new("sms")), true);
+        let second_email = matcher.process(json!(Event::new("email")), true);
+
+        // Assert
+        match first_email.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                let processed_rule = &rules.rules[0];
+                assert_eq!(ProcessedRuleStatus::Matched, processed_rule.status);
+                assert_eq!(1, processed_rule.actions.len());
+                let suppression =
+                    processed_rule.meta.as_ref().unwrap().suppression.as_ref().unwrap();
+                assert_eq!(0, suppression.duplicates);
+            }
+            _ => unreachable!(),
+        };
+
+        match first_sms.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                assert_eq!(ProcessedRuleStatus::Matched, rules.rules[0].status);
+            }
+            _ => unreachable!(),
+        };
+
+        match second_email.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                let processed_rule = &rules.rules[0];
+                assert_eq!(ProcessedRuleStatus::Duplicate, processed_rule.status);
+                assert!(processed_rule.actions.is_empty());
+                assert!(processed_rule.message.is_some());
+                let suppression =
+                    processed_rule.meta.as_ref().unwrap().suppression.as_ref().unwrap();
+                assert_eq!(r#"["email"]"#, suppression.fingerprint);
+                assert_eq!(1, suppression.duplicates);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn should_not_suppress_the_copies_of_an_event_whose_actions_cannot_be_resolved() {
+        // Arrange
+        let mut rule = new_rule("rule_name", None);
+        let mut payload = Map::new();
+        payload.insert("host".to_owned(), json!("${event.payload.host}"));
+        rule.actions.push(ConfigAction { id: String::from("action_1"), payload });
+        rule.suppression =
+            Some(Suppression { fingerprint: vec!["${event.type}".to_owned()], ttl_seconds: 3600 });
+
+        let matcher =
+            new_matcher(&MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule] })
+                .unwrap();
+        let mut event_with_host = json!(Event::new("email"));
+        event_with_host["payload"]["host"] = json!("host_1");
+
+        // Act
+        let first = matcher.process(json!(Event::new("email")), true);
+        let second = matcher.process(event_with_host.clone(), true);
+        let third = matcher.process(event_with_host, true);
+
+        // Assert
+        match first.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                assert_eq!(ProcessedRuleStatus::PartiallyMatched, rules.rules[0].status);
+            }
+            _ => unreachable!(),
+        };
+        match second.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                assert_eq!(ProcessedRuleStatus::Matched, rules.rules[0].status);
+                assert_eq!(1, rules.rules[0].actions.len());
+            }
+            _ => unreachable!(),
+        };
+        match third.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                assert_eq!(ProcessedRuleStatus::Duplicate, rules.rules[0].status);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn should_stop_execution_on_duplicate_if_continue_is_false() {
+        // Arrange
+        let mut rule_1 = new_rule("rule1", None);
+        rule_1.do_continue = false;
+        rule_1.suppression =
+            Some(Suppression { fingerprint: vec!["${event.type}".to_owned()], ttl_seconds: 3600 });
+        let rule_2 = new_rule("rule2", None);
+
+        let matcher = new_matcher(&MatcherConfig::Ruleset {
+            name: "ruleset".to_owned(),
+            rules: vec![rule_1, rule_2],
+        })
+        .unwrap();
+
+        // Act
+        let _ = matcher.process(json!(Event::new("email")), false);
+        let result = matcher.process(json!(Event::new("email")), false);
+
+        // Assert
+        match result.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                assert_eq!(1, rules.rules.len());
+                assert_eq!(ProcessedRuleStatus::Duplicate, rules.rules[0].status);
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn should_complete_a_correlation_in_a_filter() {
+        // Arrange
+        let step = |event_type: &str| Operator::Equals {
+            first: Value::String("${event.type}".to_owned()),
+            second: Value::String(event_type.to_owned()),
+        };
+        let correlation = MatcherConfig::Correlation {
+            name: "link_and_bgp_down".to_owned(),
+            correlation: Correlation {
+                description: "".to_owned(),
+                active: true,
+                correlate_by: "${event.payload.host}".to_owned(),
+                steps: vec![step("link_down"), step("bgp_down")],
+                timeout_seconds: 30,
+                on_complete: vec![ConfigAction { id: "action_1".to_owned(), payload: Map::new() }],
+                on_timeout: vec![],
+            },
+        };
+        let matcher = new_matcher(&MatcherConfig::Filter {
+            name: "root".to_owned(),
+            filter: new_filter(None),
+            nodes: vec![correlation],
+        })
+        .unwrap();
+
+        let mut link_down = Event::new("link_down");
+        link_down.payload.insert("host".to_owned(), json!("host_1"));
+        let mut bgp_down = Event::new("bgp_down");
+        bgp_down.payload.insert("host".to_owned(), json!("host_1"));
+
+        // Act
+        let first = matcher.process(json!(link_down), false);
+        let second = matcher.process(json!(bgp_down), false);
+
+        // Assert
+        let get_correlation = |result: ProcessedNode| match result {
+            ProcessedNode::Filter { mut nodes, .. } => match nodes.remove(0) {
+                ProcessedNode::Correlation { name, correlation } => {
+                    assert_eq!("link_and_bgp_down", name);
+                    correlation
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+
+        let first = get_correlation(first.result);
+        assert_eq!(ProcessedCorrelationStatus::Started, first.status);
+        assert!(first.actions.is_empty());
+
+        let second = get_correlation(second.result);
+        assert_eq!(ProcessedCorrelationStatus::Completed, second.status);
+        assert_eq!(1, second.actions.len());
+        assert_eq!("action_1", second.actions[0].id);
+
+        assert!(matcher.expire_correlations().is_empty());
+    }
+
+    #[test]
+    fn build_should_fail_if_correlation_has_no_steps() {
+        // Arrange
+        let config = MatcherConfig::Correlation {
+            name: "correlation".to_owned(),
+            correlation: Correlation {
+                correlate_by: "${event.payload.host}".to_owned(),
+                timeout_seconds: 30,
+                ..Default::default()
+            },
+        };
+
+        // Act
+        let matcher = new_matcher(&config);
+
+        // Assert
+        assert!(matcher.is_err());
+    }
+
+    #[test]
+    fn should_record_heartbeat_keys_and_match_synthetic_events() {
+        // Arrange
+        let heartbeat = MatcherConfig::Heartbeat {
+            name: "host_heartbeat".to_owned(),
+            heartbeat: Heartbeat {
+                description: "".to_owned(),
+                active: true,
+                filter: Defaultable::Default {},
+                key: "${event.payload.host}".to_owned(),
+                max_interval_seconds: 60,
+            },
+        };
+        let mut rule = new_rule(
+            "heartbeat_missing",
+            Operator::Equals {
+                first: Value::String("${event.type}".to_owned()),
+                second: Value::String(heartbeat::HEARTBEAT_MISSING_EVENT_TYPE.to_owned()),
+            },
+        );
+        rule.actions.push(ConfigAction { id: "notify".to_owned(), payload: Map::new() });
+        let ruleset = MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule] };
+        let matcher = new_matcher(&MatcherConfig::Filter {
+            name: "root".to_owned(),
+            filter: new_filter(None),
+            nodes: vec![heartbeat, ruleset],
+        })
+        .unwrap();
+
+        let mut check_result = Event::new("check_result");
+        check_result.payload.insert("host".to_owned(), json!("host_1"));
+        let mut missing = Event::new(heartbeat::HEARTBEAT_MISSING_EVENT_TYPE);
+        missing.payload.insert("key".to_owned(), json!("host_1"));
+
+        // Act
+        let seen = matcher.process(json!(check_result), false);
+        let missing = matcher.process(json!(missing), false);
+
+        // Assert
+        let get_heartbeat = |result: ProcessedNode| match result {
+            ProcessedNode::Filter { mut nodes, .. } => match nodes.remove(0) {
+                ProcessedNode::Heartbeat { name, heartbeat } => {
+                    assert_eq!("host_heartbeat", name);
+                    heartbeat
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+
+        let seen = get_heartbeat(seen.result);
+        assert_eq!(ProcessedHeartbeatStatus::Seen, seen.status);
+        assert_eq!(Some("host_1".to_owned()), seen.key);
+
+        match missing.result {
+            ProcessedNode::Filter { nodes, .. } => {
+                match &nodes[0] {
+                    ProcessedNode::Heartbeat { heartbeat, .. } => {
+                        assert_eq!(ProcessedHeartbeatStatus::NotMatched, heartbeat.status)
+                    }
+                    _ => unreachable!(),
+                }
+                match &nodes[1] {
+                    ProcessedNode::Ruleset { rules, .. } => {
+                        assert_eq!(ProcessedRuleStatus::Matched, rules.rules[0].status)
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        };
+
+        assert!(matcher.check_heartbeats().is_empty());
+    }
+
+    #[test]
+    fn should_pass_the_enriched_event_to_the_enricher_nodes() {
+        // Arrange
+        let mut with = HashMap::new();
+        with.insert(
+            "site".to_owned(),
+            Extractor {
+                from: "${event.payload.host}".to_owned(),
+                regex: ExtractorRegex::Regex {
+                    regex: "^[a-z]+".to_owned(),
+                    group_match_idx: Some(0),
+                    all_matches: None,
+                },
+                modifiers_post: vec![],
+            },
+        );
+        let mut rule = new_rule(
+            "rule",
+            Operator::Equals {
+                first: Value::String("${event.payload.enrichment.site}".to_owned()),
+                second: Value::String("bz".to_owned()),
+            },
+        );
+        let mut action_payload = Map::new();
+        action_payload.insert(
+            "site".to_owned(),
+            Value::String("${event.payload.enrichment.site}".to_owned()),
+        );
+        rule.actions.push(ConfigAction { id: "notify".to_owned(), payload: action_payload });
+        let matcher = new_matcher(&MatcherConfig::Enricher {
+            name: "enricher".to_owned(),
+            enricher: Enricher {
+                description: "".to_owned(),
+                active: true,
+                payload_key: Some("enrichment".to_owned()),
+                with,
+            },
+            nodes: vec![MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule] }],
+        })
+        .unwrap();
+
+        let mut event = Event::new("check_result");
+        event.payload.insert("host".to_owned(), json!("bz01"));
+
+        // Act
+        let result = matcher.process(json!(event), false);
+
+        // Assert
+        assert_eq!(json!("bz01"), result.event["payload"]["host"]);
+        assert!(result.event["payload"].get("enrichment").is_none());
+        match result.result {
+            ProcessedNode::Enricher { name, enricher, nodes } => {
+                assert_eq!("enricher", name);
+                assert_eq!(ProcessedEnricherStatus::Enriched, enricher.status);
+                assert_eq!(json!({ "site": "bz" }), enricher.values);
+                match &nodes[0] {
+                    ProcessedNode::Ruleset { rules, .. } => {
+                        assert_eq!(ProcessedRuleStatus::Matched, rules.rules[0].status);
+                        assert_eq!(json!("bz"), rules.rules[0].actions[0].payload["site"]);
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn should_pass_the_original_event_to_the_nodes_of_an_inactive_enricher() {
+        // Arrange
+        let mut with = HashMap::new();
+        with.insert(
+            "site".to_owned(),
+            Extractor {
+                from: "${event.payload.host}".to_owned(),
+                regex: ExtractorRegex::Regex {
+                    regex: "^[a-z]+".to_owned(),
+                    group_match_idx: Some(0),
+                    all_matches: None,
+                },
+                modifiers_post: vec![],
+            },
+        );
+        let original_rule = new_rule(
+            "original_rule",
+            Operator::Equals {
+                first: Value::String("${event.payload.host}".to_owned()),
+                second: Value::String("bz01".to_owned()),
+            },
+        );
+        let enriched_rule = new_rule(
+            "enriched_rule",
+            Operator::Equals {
+                first: Value::String("${event.payload.enrichment.site}".to_owned()),
+                second: Value::String("bz".to_owned()),
+            },
+        );
+        let matcher = new_matcher(&MatcherConfig::Enricher {
+            name: "enricher".to_owned(),
+            enricher: Enricher {
+                description: "".to_owned(),
+                active: false,
+                payload_key: Some("enrichment".to_owned()),
+                with,
+            },
+            nodes: vec![MatcherConfig::Ruleset {
+                name: "ruleset".to_owned(),
+                rules: vec![original_rule, enriched_rule],
+            }],
+        })
+        .unwrap();
+
+        let mut event = Event::new("check_result");
+        event.payload.insert("host".to_owned(), json!("bz01"));
+
+        // Act
+        let result = matcher.process(json!(event), false);
+
+        // Assert
+        match result.result {
+            ProcessedNode::Enricher { enricher, nodes, .. } => {
+                assert_eq!(ProcessedEnricherStatus::Inactive, enricher.status);
+                match &nodes[0] {
+                    ProcessedNode::Ruleset { rules, .. } => {
+                        assert_eq!(ProcessedRuleStatus::Matched, rules.rules[0].status);
+                        assert_eq!(ProcessedRuleStatus::NotMatched, rules.rules[1].status);
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        };
+    }
+
+    #[test]
+    fn should_not_process_filters_and_rules_out_of_schedule() {
+        // Arrange
+        let maintenance = Schedule {
+            timezone: Some("Europe/Rome".to_owned()),
+            active_windows: vec![],
+            inactive_windows: vec![ScheduleWindow::Range {
+                from: "2024-03-01T02:00:00".to_owned(),
+                to: "2024-03-01T04:00:00".to_owned(),
+            }],
+        };
+        let mut scheduled_rule = new_rule("scheduled_rule", None);
+        scheduled_rule.schedule = Some(maintenance.clone());
+        let rule = new_rule("rule", None);
+
+        let mut scheduled_filter = new_filter(None);
+        scheduled_filter.schedule = Some(maintenance);
+        let matcher = new_matcher(&MatcherConfig::Filter {
+            name: "root".to_owned(),
+            filter: new_filter(None),
+            nodes: vec![
+                MatcherConfig::Filter {
+                    name: "scheduled_filter".to_owned(),
+                    filter: scheduled_filter,
+                    nodes: vec![MatcherConfig::Ruleset {
+                        name: "ruleset".to_owned(),
+                        rules: vec![rule.clone()],
+                    }],
+                },
+                MatcherConfig::Ruleset {
+                    name: "ruleset".to_owned(),
+                    rules: vec![scheduled_rule, rule],
+                },
+            ],
+        })
+        .unwrap();
+
+        let new_event = |created_ms: u64| {
+            let mut event = Event::new("email");
+            event.created_ms = created_ms;
+            json!(event)
+        };
+        // 2024-03-01T03:00:00+01:00 and 2024-03-01T05:00:00+01:00
+        let in_maintenance = new_event(1_709_258_400_000);
+        let after_maintenance = new_event(1_709_265_600_000);
+
+        // Act
+        let in_maintenance = matcher.process(in_maintenance, false);
+        let after_maintenance = matcher.process(after_maintenance, false);
+
+        // Assert
+        let get_statuses = |result: ProcessedNode| match result {
+            ProcessedNode::Filter { nodes, .. } => match (&nodes[0], &nodes[1]) {
+                (ProcessedNode::Filter { filter, .. }, ProcessedNode::Ruleset { rules, .. }) => (
+                    filter.status.clone(),
+                    rules.rules.iter().map(|rule| rule.status.clone()).collect::<Vec<_>>(),
+                ),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+
+        let (filter_status, rule_statuses) = get_statuses(in_maintenance.result);
+        assert_eq!(ProcessedFilterStatus::OutOfSchedule, filter_status);
+        assert_eq!(
+            vec![ProcessedRuleStatus::OutOfSchedule, ProcessedRuleStatus::Matched],
+            rule_statuses
+        );
+
+        let (filter_status, rule_statuses) = get_statuses(after_maintenance.result);
+        assert_eq!(ProcessedFilterStatus::Matched, filter_status);
+        assert_eq!(vec![ProcessedRuleStatus::Matched, ProcessedRuleStatus::Matched], rule_statuses);
+    }
+
+    #[test]
+    fn build_should_fail_if_schedule_is_not_valid() {
+        // Arrange
+        let mut rule = new_rule("rule", None);
+        rule.schedule = Some(Schedule {
+            timezone: None,
+            active_windows: vec![ScheduleWindow::Recurring {
+                cron: "every sunday".to_owned(),
+                duration_seconds: 60,
+            }],
+            inactive_windows: vec![],
+        });
+
+        // Act
+        let matcher =
+            new_matcher(&MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule] });
+
+        // Assert
+        assert!(matcher.is_err());
+    }
+
+    #[test]
+    fn indexed_filters_should_produce_the_same_result_of_evaluated_filters() {
+        // Arrange
+        let build_config = |indexed: bool| {
+            let equals = |first: &str, second: &str| {
+                let operator = Operator::Equals { first: json!(first), second: json!(second) };
+                if indexed {
+                    operator
+                } else {
+                    // The index considers only the 'equals' operators at the top level
+                    Operator::And { operators: vec![operator] }
+                }
+            };
+            let filter_node = |name: &str, operator: Operator| MatcherConfig::Filter {
+                name: name.to_owned(),
+                filter: new_filter(operator),
+                nodes: vec![MatcherConfig::Ruleset {
+                    name: "ruleset".to_owned(),
+                    rules: vec![new_rule("rule", None)],
+                }],
+            };
+            MatcherConfig::Filter {
+                name: "root".to_owned(),
+                filter: new_filter(None),
+                nodes: vec![
+                    filter_node("email", equals("${event.type}", "email")),
+                    filter_node("syslog", equals("syslog", "${event.type}")),
+                    filter_node("other_email", equals("${event.type}", "email")),
+                    filter_node("host", equals("${event.payload.host}", "host_1")),
+                    MatcherConfig::Ruleset {
+                        name: "ruleset".to_owned(),
+                        rules: vec![new_rule("rule", None)],
+                    },
+                    MatcherConfig::Iterator {
+                        name: "iterator".to_owned(),
+                        iterator: MatcherIterator::new(
+                            "".to_owned(),
+                            true,
+                            "${event.payload.items}".to_owned(),
+                        ),
+                        nodes: vec![
+                            filter_node("item_a", equals("${event.iterator.item}", "a")),
+                            filter_node("item_b", equals("${event.iterator.item}", "b")),
+                        ],
+                    },
+                ],
+            }
+        };
+        let indexed_matcher = new_matcher(&build_config(true)).unwrap();
+        let matcher = new_matcher(&build_config(false)).unwrap();
+
+        let events = vec![
+            json!({ "type": "email", "created_ms": 0, "payload": { "host": "host_1", "items": ["a", "c"] } }),
+            json!({ "type": "syslog", "created_ms": 0, "payload": { "host": 1, "items": ["b", "a"] } }),
+            json!({ "type": "trap", "created_ms": 0, "payload": { "items": [] } }),
+            json!({ "type": 12, "created_ms": 0, "payload": {} }),
+        ];
+
+        for event in events {
+            // Act
+            let indexed_result = indexed_matcher.process(event.clone(), true);
+            let result = matcher.process(event, true);
+
+            // Assert
+            assert_eq!(format!("{:?}", result), format!("{:?}", indexed_result));
+        }
+    }
+
+    #[test]
+    fn parallel_processing_should_produce_the_same_result_of_sequential_processing() {
+        // Arrange
+        let rule_with_action = |name: &str| {
+            let mut rule = new_rule(
+                name,
+                Operator::NotEquals { first: json!("${event.type}"), second: json!("trap") },
+            );
+            let mut action = ConfigAction { id: name.to_owned(), payload: Map::new() };
+            action.payload.insert("item".to_owned(), json!("${event.iterator.item}"));
+            rule.actions.push(action);
+            rule
+        };
+        let ruleset = |name: &str| MatcherConfig::Ruleset {
+            name: name.to_owned(),
+            rules: vec![rule_with_action("rule_1"), rule_with_action("rule_2")],
+        };
+        let filter_node = |name: &str, operator: Option<Operator>, nodes: Vec<MatcherConfig>| {
+            MatcherConfig::Filter { name: name.to_owned(), filter: new_filter(operator), nodes }
+        };
+        let config = filter_node(
+            "root",
+            None,
+            vec![
+                filter_node(
+                    "email",
+                    Some(Operator::Equals {
+                        first: json!("${event.type}"),
+                        second: json!("email"),
+                    }),
+                    vec![ruleset("email_ruleset")],
+                ),
+                filter_node("all_1", None, vec![ruleset("ruleset_1"), ruleset("ruleset_2")]),
+                ruleset("ruleset_3"),
+                MatcherConfig::Iterator {
+                    name: "iterator".to_owned(),
+                    iterator: MatcherIterator::new(
+                        "".to_owned(),
+                        true,
+                        "${event.payload.items}".to_owned(),
+                    ),
+                    nodes: vec![ruleset("item_ruleset_1"), ruleset("item_ruleset_2")],
+                },
+                filter_node("all_2", None, vec![ruleset("ruleset_4")]),
+            ],
+        );
+        let matcher = new_matcher(&config).unwrap();
+        let parallel_matcher =
+            new_matcher(&config).unwrap().with_thread_pool(MatcherThreadPool::build(4).unwrap());
+
+        let events = vec![
+            json!({ "type": "email", "created_ms": 0, "payload": { "items": ["a", "b", "c", "d", "e"] } }),
+            json!({ "type": "syslog", "created_ms": 0, "payload": { "items": { "a": 1, "b": 2, "c": 3 } } }),
+            json!({ "type": "trap", "created_ms": 0, "payload": { "items": [] } }),
+        ];
+
+        for include_metadata in [true, false] {
+            for event in &events {
+                // Act
+                let result = matcher.process(event.clone(), include_metadata);
+                let parallel_result = parallel_matcher.process(event.clone(), include_metadata);
+
+                // Assert
+                assert_eq!(format!("{:?}", result), format!("{:?}", parallel_result));
+            }
+        }
+    }
+
+    #[test]
+    fn parallel_processing_should_process_the_iterations_of_stateful_nodes_sequentially() {
+        // Arrange
+        let mut rule = new_rule("rule", None);
+        let mut action = ConfigAction { id: "action".to_owned(), payload: Map::new() };
+        action.payload.insert("item".to_owned(), json!("${event.iterator.item}"));
+        rule.actions.push(action);
+        rule.threshold = Some(Threshold { count: 3, window_seconds: 3600, group_by: None });
+        let config = MatcherConfig::Filter {
+            name: "root".to_owned(),
+            filter: new_filter(None),
+            nodes: vec![MatcherConfig::Iterator {
+                name: "iterator".to_owned(),
+                iterator: MatcherIterator::new(
+                    "".to_owned(),
+                    true,
+                    "${event.payload.items}".to_owned(),
+                ),
+                nodes: vec![MatcherConfig::Ruleset {
+                    name: "ruleset".to_owned(),
+                    rules: vec![rule],
+                }],
+            }],
+        };
+        let matcher = new_matcher(&config).unwrap();
+        let parallel_matcher =
+            new_matcher(&config).unwrap().with_thread_pool(MatcherThreadPool::build(4).unwrap());
+        let event = json!({
+            "type": "email",
+            "created_ms": 0,
+            "payload": { "items": (0..200).collect::<Vec<_>>() }
+        });
+
+        for _ in 0..3 {
+            // Act
+            let result = matcher.process(event.clone(), true);
+            let parallel_result = parallel_matcher.process(event.clone(), true);
+
+            // Assert
+            assert_eq!(format!("{:?}", result), format!("{:?}", parallel_result));
+        }
+    }
+
+    #[test]
+    fn matcher_should_be_shareable_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Matcher>();
+    }
+
     fn new_matcher(config: &MatcherConfig) -> Result<Matcher, MatcherError> {
         //crate::test_root::start_context();
         Matcher::build(config)
@@ -2772,12 +4478,15 @@ mod test {
             actions: vec![],
             description: "".to_owned(),
             constraint,
+            threshold: None,
+            suppression: None,
+            schedule: None,
         }
     }
 
     fn new_filter<O: Into<Option<Operator>>>(filter: O) -> Filter {
         let filter =
             filter.into().map(Defaultable::Value).unwrap_or_else(|| Defaultable::Default {});
-        Filter { active: true, description: "".to_owned(), filter }
+        Filter { active: true, description: "".to_owned(), filter, schedule: None }
     }
 }
@@ -0,0 +1,247 @@
+use crate::error::MatcherError;
+use crate::matcher::modifier::dateandtime::timestamp_to_date;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde_json::Value;
+use tornado_common_api::ValueExt;
+
+const RFC3339_FORMAT: &str = "rfc3339";
+const RFC2822_FORMAT: &str = "rfc2822";
+const SYSLOG_FORMAT: &str = "syslog";
+
+/// The formats tried by the ParseDate modifier when none is configured.
+pub const DEFAULT_DATE_FORMATS: [&str; 3] = [RFC3339_FORMAT, RFC2822_FORMAT, SYSLOG_FORMAT];
+
+/// A format accepted by the ParseDate modifier.
+#[derive(Debug, PartialEq)]
+pub enum DateFormat {
+    Rfc3339,
+    Rfc2822,
+    /// The RFC3164 syslog timestamp, e.g. `Oct 11 22:14:15`, which has no year
+    Syslog,
+    /// A strftime pattern, e.g. `%d/%m/%Y %H:%M`
+    Custom(String),
+}
+
+impl DateFormat {
+    pub fn build(format: &str) -> Result<DateFormat, MatcherError> {
+        match format.trim().to_lowercase().as_str() {
+            RFC3339_FORMAT => Ok(DateFormat::Rfc3339),
+            RFC2822_FORMAT => Ok(DateFormat::Rfc2822),
+            SYSLOG_FORMAT => Ok(DateFormat::Syslog),
+            _ => {
+                validate_strftime_pattern(format)?;
+                Ok(DateFormat::Custom(format.to_owned()))
+            }
+        }
+    }
+
+    fn parse(&self, text: &str, timezone: &Tz, now: DateTime<Utc>) -> Option<DateTime<Tz>> {
+        match self {
+            DateFormat::Rfc3339 => {
+                DateTime::parse_from_rfc3339(text).ok().map(|date| date.with_timezone(timezone))
+            }
+            DateFormat::Rfc2822 => {
+                DateTime::parse_from_rfc2822(text).ok().map(|date| date.with_timezone(timezone))
+            }
+            DateFormat::Syslog => parse_syslog(text, timezone, now),
+            DateFormat::Custom(pattern) => {
+                if let Ok(date) = DateTime::parse_from_str(text, pattern) {
+                    return Some(date.with_timezone(timezone));
+                }
+                let naive = NaiveDateTime::parse_from_str(text, pattern).ok().or_else(|| {
+                    NaiveDate::parse_from_str(text, pattern)
+                        .ok()
+                        .and_then(|date| date.and_hms_opt(0, 0, 0))
+                })?;
+                timezone.from_local_datetime(&naive).earliest()
+            }
+        }
+    }
+}
+
+/// Returns an error if the pattern is not a valid strftime pattern.
+pub fn validate_strftime_pattern(pattern: &str) -> Result<(), MatcherError> {
+    if StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error)) {
+        return Err(MatcherError::ConfigurationError {
+            message: format!("The date format [{}] is not a valid strftime pattern", pattern),
+        });
+    }
+    Ok(())
+}
+
+/// Parses a syslog timestamp. As it has no year, the year is the current one, unless the
+/// resulting date is more than one day in the future; in that case, it is the previous year.
+fn parse_syslog(text: &str, timezone: &Tz, now: DateTime<Utc>) -> Option<DateTime<Tz>> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let current_year = now.with_timezone(timezone).year();
+    let parse = |year: i32| {
+        NaiveDateTime::parse_from_str(&format!("{} {}", year, text), "%Y %b %d %H:%M:%S")
+            .ok()
+            .and_then(|naive| timezone.from_local_datetime(&naive).earliest())
+    };
+    let date = parse(current_year)?;
+    if date.with_timezone(&Utc) > now + chrono::Duration::days(1) {
+        parse(current_year - 1)
+    } else {
+        Some(date)
+    }
+}
+
+/// Parses a date string with the first matching format and replaces it with its epoch milliseconds.
+/// The timezone is used for the dates without an explicit offset.
+#[inline]
+pub fn parse_date(
+    variable_name: &str,
+    value: &mut Value,
+    formats: &[DateFormat],
+    timezone: &Tz,
+) -> Result<(), MatcherError> {
+    parse_date_at(variable_name, value, formats, timezone, Utc::now())
+}
+
+fn parse_date_at(
+    variable_name: &str,
+    value: &mut Value,
+    formats: &[DateFormat],
+    timezone: &Tz,
+    now: DateTime<Utc>,
+) -> Result<(), MatcherError> {
+    let Some(text) = value.get_text() else {
+        return Err(MatcherError::ExtractedVariableError {
+            message: "The 'parse_date' modifier can be used only with values of type 'string'"
+                .to_owned(),
+            variable_name: variable_name.to_owned(),
+        });
+    };
+    let text = text.trim();
+    match formats.iter().find_map(|format| format.parse(text, timezone, now)) {
+        Some(date) => {
+            *value = Value::from(date.timestamp_millis());
+            Ok(())
+        }
+        None => Err(MatcherError::ExtractedVariableError {
+            message: format!(
+                "The 'parse_date' modifier cannot parse [{}] with any of the formats {:?}",
+                text, formats
+            ),
+            variable_name: variable_name.to_owned(),
+        }),
+    }
+}
+
+/// Formats an epoch timestamp with a strftime pattern in the given timezone.
+/// Whether the timestamp is in seconds, milliseconds, microseconds or nanoseconds is autodetected.
+#[inline]
+pub fn format_date(
+    variable_name: &str,
+    value: &mut Value,
+    format: &str,
+    timezone: &Tz,
+) -> Result<(), MatcherError> {
+    let Some(timestamp) = value.as_i64() else {
+        return Err(MatcherError::ExtractedVariableError {
+            message: format!(
+                "The value passed to the 'format_date' modifier is not valid (must be an integer): {}",
+                value
+            ),
+            variable_name: variable_name.to_owned(),
+        });
+    };
+    let date = timestamp_to_date(timestamp, timezone);
+    *value = Value::String(date.format(format).to_string());
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn formats(formats: &[&str]) -> Vec<DateFormat> {
+        formats.iter().map(|format| DateFormat::build(format).unwrap()).collect()
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn parse_date_modifier_should_parse_the_default_formats() {
+        let formats = formats(&DEFAULT_DATE_FORMATS);
+        let parse = |text: &str, timezone: &Tz| {
+            let mut input = json!(text);
+            parse_date_at("", &mut input, &formats, timezone, now()).unwrap();
+            input
+        };
+
+        assert_eq!(json!(1709290800000i64), parse("2024-03-01T11:00:00Z", &Tz::UTC));
+        assert_eq!(json!(1709290800123i64), parse("2024-03-01T12:00:00.123+01:00", &Tz::UTC));
+        assert_eq!(json!(1709290800000i64), parse("Fri, 01 Mar 2024 12:00:00 +0100", &Tz::UTC));
+        assert_eq!(json!(1709290800000i64), parse("Mar  1 11:00:00", &Tz::UTC));
+        assert_eq!(json!(1709287200000i64), parse("Mar 1 11:00:00", &Tz::Europe__Rome));
+    }
+
+    #[test]
+    fn parse_date_modifier_should_use_the_previous_year_for_future_syslog_dates() {
+        // Arrange
+        let formats = formats(&[SYSLOG_FORMAT]);
+        let mut input = json!("Dec 31 23:59:59");
+
+        // Act
+        parse_date_at("", &mut input, &formats, &Tz::UTC, now()).unwrap();
+
+        // Assert
+        let expected = Utc.with_ymd_and_hms(2023, 12, 31, 23, 59, 59).unwrap();
+        assert_eq!(json!(expected.timestamp_millis()), input);
+    }
+
+    #[test]
+    fn parse_date_modifier_should_parse_custom_formats() {
+        let formats = formats(&["%d/%m/%Y %H:%M %z", "%d/%m/%Y %H:%M", "%d/%m/%Y"]);
+        let parse = |text: &str| {
+            let mut input = json!(text);
+            parse_date_at("", &mut input, &formats, &Tz::Europe__Rome, now()).map(|_| input)
+        };
+
+        assert_eq!(json!(1709290800000i64), parse("01/03/2024 11:00 +0000").unwrap());
+        assert_eq!(json!(1709290800000i64), parse("01/03/2024 12:00").unwrap());
+        assert_eq!(json!(1709247600000i64), parse("01/03/2024").unwrap());
+        assert!(parse("2024-03-01").is_err());
+    }
+
+    #[test]
+    fn parse_date_modifier_should_fail_if_value_not_a_string() {
+        let formats = formats(&DEFAULT_DATE_FORMATS);
+        let mut input = json!(1709290800000i64);
+        assert!(parse_date("", &mut input, &formats, &Tz::UTC).is_err());
+    }
+
+    #[test]
+    fn date_format_should_fail_with_invalid_pattern() {
+        assert_eq!(DateFormat::Rfc3339, DateFormat::build(" RFC3339").unwrap());
+        assert!(DateFormat::build("%Y-%m-%d %Q").is_err());
+    }
+
+    #[test]
+    fn format_date_modifier_should_format_a_timestamp() {
+        {
+            let mut input = json!(1709290800000i64);
+            format_date("", &mut input, "%d/%m/%Y %H:%M", &Tz::Europe__Rome).unwrap();
+            assert_eq!(json!("01/03/2024 12:00"), input);
+        }
+
+        {
+            let mut input = json!(1709290800);
+            format_date("", &mut input, "%Y-%m-%dT%H:%M:%S%.3f%:z", &Tz::UTC).unwrap();
+            assert_eq!(json!("2024-03-01T11:00:00.000+00:00"), input);
+        }
+    }
+
+    #[test]
+    fn format_date_modifier_should_fail_if_value_not_an_integer() {
+        let mut input = json!("2024-03-01");
+        assert!(format_date("", &mut input, "%Y", &Tz::UTC).is_err());
+    }
+}
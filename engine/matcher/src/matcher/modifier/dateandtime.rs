@@ -1,7 +1,7 @@
 #![allow(illegal_floating_point_literal_pattern)]
 
 use crate::error::MatcherError;
-use chrono::TimeZone;
+use chrono::{DateTime, TimeZone};
 use chrono_tz::Tz;
 use serde_json::Value;
 
@@ -18,10 +18,19 @@ pub fn dateandtime(
         });
     };
 
+    let date = timestamp_to_date(timestamp, timezone);
+
+    *value = Value::String(date.format("%Y-%m-%d %H:%M:%S%:z").to_string());
+    Ok(())
+}
+
+/// Converts a timestamp into a date in the given timezone.
+/// Whether the timestamp is in seconds, milliseconds, microseconds or nanoseconds is autodetected.
+pub fn timestamp_to_date(timestamp: i64, timezone: &Tz) -> DateTime<Tz> {
     // This is triggering a warning during the build.
     // It should be fixed automatically with a newer version of rust, for
     // more info: https://github.com/rust-lang/rust/issues/41620)
-    let date = match timestamp as f64 {
+    match timestamp as f64 {
         // timestamp is in seconds
         -1e11..=1e11 => timezone.timestamp_opt(timestamp, 0).unwrap(),
         // timestamp is in milliseconds
@@ -30,10 +39,7 @@ pub fn dateandtime(
         -1e17..=1e17 => timezone.timestamp_nanos(timestamp * 1000),
         // timestamp is in nanoseconds
         _ => timezone.timestamp_nanos(timestamp),
-    };
-
-    *value = Value::String(date.format("%Y-%m-%d %H:%M:%S%:z").to_string());
-    Ok(())
+    }
 }
 
 #[cfg(test)]
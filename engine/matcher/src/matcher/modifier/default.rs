@@ -0,0 +1,48 @@
+use serde_json::Value;
+
+use crate::error::MatcherError;
+
+/// Replaces the value with `default_value` if it is null, an empty string, an empty array
+/// or an empty object.
+#[inline]
+pub fn default_if_empty(
+    _variable_name: &str,
+    value: &mut Value,
+    default_value: &Value,
+) -> Result<(), MatcherError> {
+    let is_empty = match value {
+        Value::Null => true,
+        Value::String(text) => text.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        Value::Bool(_) | Value::Number(_) => false,
+    };
+    if is_empty {
+        *value = default_value.clone();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn default_modifier_should_replace_empty_values() {
+        for empty in [json!(null), json!(""), json!([]), json!({})] {
+            let mut input = empty;
+            default_if_empty("", &mut input, &json!("unknown")).unwrap();
+            assert_eq!(json!("unknown"), input);
+        }
+    }
+
+    #[test]
+    fn default_modifier_should_keep_non_empty_values() {
+        for value in [json!(" "), json!(0), json!(false), json!(["a"]), json!({ "a": null })] {
+            let mut input = value.clone();
+            default_if_empty("", &mut input, &json!("unknown")).unwrap();
+            assert_eq!(value, input);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use base64::{engine::general_purpose::STANDARD as base64, Engine as _};
+use serde_json::Value;
+use tornado_common_api::ValueExt;
+
+use crate::error::MatcherError;
+
+#[inline]
+pub fn base64_decode(variable_name: &str, value: &mut Value) -> Result<(), MatcherError> {
+    decode(variable_name, "base64_decode", value, |text| {
+        base64.decode(text.trim()).map_err(|err| err.to_string())
+    })
+}
+
+#[inline]
+pub fn base64_encode(variable_name: &str, value: &mut Value) -> Result<(), MatcherError> {
+    let text = get_text(variable_name, "base64_encode", value)?;
+    *value = Value::String(base64.encode(text));
+    Ok(())
+}
+
+#[inline]
+pub fn url_decode(variable_name: &str, value: &mut Value) -> Result<(), MatcherError> {
+    decode(variable_name, "url_decode", value, |text| {
+        Ok(percent_encoding::percent_decode_str(text).collect())
+    })
+}
+
+#[inline]
+pub fn hex_decode(variable_name: &str, value: &mut Value) -> Result<(), MatcherError> {
+    decode(variable_name, "hex_decode", value, |text| {
+        hex::decode(text.trim()).map_err(|err| err.to_string())
+    })
+}
+
+fn get_text<'a>(
+    variable_name: &str,
+    modifier_name: &str,
+    value: &'a Value,
+) -> Result<&'a str, MatcherError> {
+    value.get_text().ok_or_else(|| MatcherError::ExtractedVariableError {
+        message: format!(
+            "The '{}' modifier can be used only with values of type 'string'",
+            modifier_name
+        ),
+        variable_name: variable_name.to_owned(),
+    })
+}
+
+/// Decodes a string into bytes and replaces the value with the resulting UTF-8 string.
+fn decode<F: Fn(&str) -> Result<Vec<u8>, String>>(
+    variable_name: &str,
+    modifier_name: &str,
+    value: &mut Value,
+    decoder: F,
+) -> Result<(), MatcherError> {
+    let text = get_text(variable_name, modifier_name, value)?;
+    let bytes = decoder(text).map_err(|err| MatcherError::ExtractedVariableError {
+        message: format!("The '{}' modifier cannot decode the string: {}", modifier_name, err),
+        variable_name: variable_name.to_owned(),
+    })?;
+    let decoded = String::from_utf8(bytes).map_err(|_| MatcherError::ExtractedVariableError {
+        message: format!(
+            "The '{}' modifier decoded a string that is not valid UTF-8",
+            modifier_name
+        ),
+        variable_name: variable_name.to_owned(),
+    })?;
+    *value = Value::String(decoded);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn base64_modifiers_should_encode_and_decode_a_string() {
+        {
+            let mut input = json!("eyJob3N0IjogIndlYjAxIn0=");
+            base64_decode("", &mut input).unwrap();
+            assert_eq!(json!(r#"{"host": "web01"}"#), input);
+        }
+
+        {
+            let mut input = json!("città");
+            base64_encode("", &mut input).unwrap();
+            assert_eq!(json!("Y2l0dMOg"), input);
+            base64_decode("", &mut input).unwrap();
+            assert_eq!(json!("città"), input);
+        }
+    }
+
+    #[test]
+    fn url_decode_modifier_should_decode_a_string() {
+        let mut input = json!("host%3Dweb01%26msg%3Dcitt%C3%A0+down");
+        url_decode("", &mut input).unwrap();
+        assert_eq!(json!("host=web01&msg=città+down"), input);
+    }
+
+    #[test]
+    fn hex_decode_modifier_should_decode_a_string() {
+        let mut input = json!("776562303120646f776e");
+        hex_decode("", &mut input).unwrap();
+        assert_eq!(json!("web01 down"), input);
+    }
+
+    #[test]
+    fn decoding_modifiers_should_fail_with_invalid_input() {
+        let modifiers = [base64_decode, url_decode, hex_decode];
+
+        for modifier in modifiers {
+            let mut input = json!(12);
+            assert!(modifier("", &mut input).is_err());
+        }
+
+        assert!(base64_decode("", &mut json!("not base64!")).is_err());
+        assert!(hex_decode("", &mut json!("0g")).is_err());
+        // The decoded bytes are not valid UTF-8
+        assert!(hex_decode("", &mut json!("ff")).is_err());
+        assert!(url_decode("", &mut json!("%FF")).is_err());
+        assert!(base64_encode("", &mut json!(["web01"])).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use serde_json::Value;
+
+use crate::error::MatcherError;
+
+/// Joins the elements of an array in a single string. The elements must be strings, numbers
+/// or booleans.
+#[inline]
+pub fn join(variable_name: &str, value: &mut Value, separator: &str) -> Result<(), MatcherError> {
+    if let Value::Array(items) = value {
+        let mut parts = Vec::with_capacity(items.len());
+        for item in items.iter() {
+            match item {
+                Value::String(text) => parts.push(text.to_owned()),
+                Value::Number(number) => parts.push(number.to_string()),
+                Value::Bool(boolean) => parts.push(boolean.to_string()),
+                _ => {
+                    return Err(MatcherError::ExtractedVariableError {
+                        message: format!(
+                            "The 'join' modifier cannot join the array element [{}]. Only strings, numbers and booleans are allowed",
+                            item
+                        ),
+                        variable_name: variable_name.to_owned(),
+                    })
+                }
+            }
+        }
+        *value = Value::String(parts.join(separator));
+        Ok(())
+    } else {
+        Err(MatcherError::ExtractedVariableError {
+            message: "The 'join' modifier can be used only with values of type 'array'".to_owned(),
+            variable_name: variable_name.to_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn join_modifier_should_join_an_array() {
+        {
+            let mut input = json!(["web01", "web02", "db01"]);
+            join("", &mut input, ", ").unwrap();
+            assert_eq!(json!("web01, web02, db01"), input);
+        }
+
+        {
+            let mut input = json!(["port", 8080, true]);
+            join("", &mut input, ":").unwrap();
+            assert_eq!(json!("port:8080:true"), input);
+        }
+
+        {
+            let mut input = json!([]);
+            join("", &mut input, ",").unwrap();
+            assert_eq!(json!(""), input);
+        }
+    }
+
+    #[test]
+    fn join_modifier_should_fail_if_value_not_an_array_of_scalars() {
+        {
+            let mut input = json!("web01");
+            assert!(join("", &mut input, ",").is_err());
+        }
+
+        {
+            let mut input = json!(["web01", ["web02"]]);
+            assert!(join("", &mut input, ",").is_err());
+        }
+
+        {
+            let mut input = json!(["web01", null]);
+            assert!(join("", &mut input, ",").is_err());
+        }
+    }
+}
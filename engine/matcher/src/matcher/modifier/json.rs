@@ -0,0 +1,66 @@
+use serde_json::Value;
+use tornado_common_api::ValueExt;
+
+use crate::error::MatcherError;
+
+/// Parses a string containing a JSON document into the corresponding Value.
+#[inline]
+pub fn parse_json(variable_name: &str, value: &mut Value) -> Result<(), MatcherError> {
+    if let Some(text) = value.get_text() {
+        *value =
+            serde_json::from_str(text).map_err(|err| MatcherError::ExtractedVariableError {
+                message: format!(
+                    "The 'parse_json' modifier cannot parse the string as JSON: {}",
+                    err
+                ),
+                variable_name: variable_name.to_owned(),
+            })?;
+        Ok(())
+    } else {
+        Err(MatcherError::ExtractedVariableError {
+            message: "The 'parse_json' modifier can be used only with values of type 'string'"
+                .to_owned(),
+            variable_name: variable_name.to_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_json_modifier_should_parse_a_json_string() {
+        {
+            let mut input = json!(r#"{"host": "web01", "checks": [1, 2], "ok": true}"#);
+            parse_json("", &mut input).unwrap();
+            assert_eq!(json!({ "host": "web01", "checks": [1, 2], "ok": true }), input);
+        }
+
+        {
+            let mut input = json!(" 12.5 ");
+            parse_json("", &mut input).unwrap();
+            assert_eq!(json!(12.5), input);
+        }
+
+        {
+            let mut input = json!(r#""text""#);
+            parse_json("", &mut input).unwrap();
+            assert_eq!(json!("text"), input);
+        }
+    }
+
+    #[test]
+    fn parse_json_modifier_should_fail_if_value_is_not_a_json_string() {
+        {
+            let mut input = json!("{ not json }");
+            assert!(parse_json("", &mut input).is_err());
+        }
+
+        {
+            let mut input = json!({ "host": "web01" });
+            assert!(parse_json("", &mut input).is_err());
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use crate::error::MatcherError;
+use crate::lookup::LookupTable;
+use serde_json::Value;
+
+/// Replaces the value with the row of the lookup table that has the value as key or, if a
+/// field is specified, with that field of the row.
+/// If the key or the field are not found, the value is replaced by the default value, if any.
+#[inline]
+pub fn lookup(
+    variable_name: &str,
+    value: &mut Value,
+    table: &LookupTable,
+    field: &Option<String>,
+    default_value: &Option<Value>,
+) -> Result<(), MatcherError> {
+    let key = match value {
+        Value::String(text) => text.to_owned(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(boolean) => boolean.to_string(),
+        _ => {
+            return Err(MatcherError::ExtractedVariableError {
+                message: "The 'lookup' modifier can be used only with values of type 'string', 'number' or 'bool'".to_owned(),
+                variable_name: variable_name.to_owned(),
+            })
+        }
+    };
+
+    let found = match (table.get(&key), field) {
+        (Some(row), Some(field)) => row.get(field).ok_or_else(|| {
+            format!(
+                "The 'lookup' modifier cannot find the field [{}] in the row with key [{}] of the lookup table [{}]",
+                field, key, table.name()
+            )
+        }),
+        (Some(row), None) => Ok(row),
+        (None, _) => Err(format!(
+            "The 'lookup' modifier cannot find the key [{}] in the lookup table [{}]",
+            key, table.name()
+        )),
+    };
+
+    match (found, default_value) {
+        (Ok(found), _) => {
+            *value = found.clone();
+            Ok(())
+        }
+        (Err(_), Some(default_value)) => {
+            *value = default_value.clone();
+            Ok(())
+        }
+        (Err(message), None) => Err(MatcherError::ExtractedVariableError {
+            message,
+            variable_name: variable_name.to_owned(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::lookup::LookupTables;
+    use serde_json::json;
+
+    fn tables() -> LookupTables {
+        let mut tables = LookupTables::default();
+        tables
+            .add(
+                LookupTable::from_csv(
+                    "teams",
+                    "teams.csv",
+                    "host,team,email\nweb01,ops,ops@example.com\n",
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        tables
+            .add(
+                LookupTable::from_json("codes", "codes.json", r#"{ "404": "not found" }"#).unwrap(),
+            )
+            .unwrap();
+        tables
+    }
+
+    #[test]
+    fn lookup_modifier_should_replace_the_value_with_the_row() {
+        // Arrange
+        let tables = tables();
+        let teams = tables.get("teams").unwrap();
+        let mut input = json!("web01");
+
+        // Act
+        lookup("", &mut input, teams, &None, &None).unwrap();
+
+        // Assert
+        assert_eq!(json!({ "host": "web01", "team": "ops", "email": "ops@example.com" }), input);
+    }
+
+    #[test]
+    fn lookup_modifier_should_replace_the_value_with_a_field_of_the_row() {
+        let tables = tables();
+        let teams = tables.get("teams").unwrap();
+        let codes = tables.get("codes").unwrap();
+
+        {
+            let mut input = json!("web01");
+            lookup("", &mut input, teams, &Some("email".to_owned()), &None).unwrap();
+            assert_eq!(json!("ops@example.com"), input);
+        }
+
+        {
+            let mut input = json!(404);
+            lookup("", &mut input, codes, &None, &None).unwrap();
+            assert_eq!(json!("not found"), input);
+        }
+    }
+
+    #[test]
+    fn lookup_modifier_should_use_the_default_value_if_not_found() {
+        let tables = tables();
+        let teams = tables.get("teams").unwrap();
+        let default_value = Some(json!("nobody@example.com"));
+
+        {
+            let mut input = json!("db01");
+            lookup("", &mut input, teams, &Some("email".to_owned()), &default_value).unwrap();
+            assert_eq!(json!("nobody@example.com"), input);
+        }
+
+        {
+            let mut input = json!("web01");
+            lookup("", &mut input, teams, &Some("phone".to_owned()), &default_value).unwrap();
+            assert_eq!(json!("nobody@example.com"), input);
+        }
+    }
+
+    #[test]
+    fn lookup_modifier_should_fail_if_not_found() {
+        let tables = tables();
+        let teams = tables.get("teams").unwrap();
+
+        assert!(lookup("", &mut json!("db01"), teams, &None, &None).is_err());
+        assert!(lookup("", &mut json!("web01"), teams, &Some("phone".to_owned()), &None).is_err());
+        assert!(lookup("", &mut json!(["web01"]), teams, &None, &None).is_err());
+        assert!(lookup("", &mut Value::Null, teams, &None, &Some(json!(""))).is_err());
+    }
+}
@@ -1,19 +1,33 @@
 use crate::accessor::{Accessor, AccessorBuilder};
 use crate::config::rule::Modifier;
 use crate::error::MatcherError;
+use crate::lookup::LookupTable;
+use crate::matcher::modifier::date::{DateFormat, DEFAULT_DATE_FORMATS};
 use crate::model::InternalEvent;
 use crate::regex::RegexWrapper;
+use crate::resources::MatcherResources;
 use chrono_tz::Tz;
 use log::*;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
 
+pub mod date;
 pub mod dateandtime;
+pub mod default;
+pub mod encoding;
+pub mod join;
+pub mod json;
+pub mod lookup;
 pub mod lowercase;
 pub mod map;
 pub mod number;
+pub mod pad;
 pub mod replace;
+pub mod split;
+pub mod substring;
 pub mod trim;
+pub mod uppercase;
 
 #[derive(Debug)]
 pub enum ValueModifier {
@@ -24,6 +38,54 @@ pub enum ValueModifier {
     ToNumber,
     Trim,
     DateAndTime { timezone: Tz },
+    Uppercase,
+    Substring { start: i64, end: Option<i64> },
+    Split { separator: String },
+    SplitRegex { separator_regex: RegexWrapper },
+    Join { separator: String },
+    PadLeft { length: usize, padding: char },
+    PadRight { length: usize, padding: char },
+    Default { value: Value },
+    ParseJson,
+    Base64Decode,
+    Base64Encode,
+    UrlDecode,
+    HexDecode,
+    ParseDate { formats: Vec<DateFormat>, timezone: Tz },
+    FormatDate { format: String, timezone: Tz },
+    Lookup { table: Arc<LookupTable>, field: Option<String>, default_value: Option<Value> },
+}
+
+const DEFAULT_PADDING: char = ' ';
+
+fn build_timezone(timezone: &str) -> Result<Tz, MatcherError> {
+    timezone.trim().parse().map_err(|_| MatcherError::ConfigurationError {
+        message: format!(
+            "Unknown timezone ({timezone}). Expected timezone from the IANA timezone database."
+        ),
+    })
+}
+
+fn build_optional_timezone(timezone: &Option<String>) -> Result<Tz, MatcherError> {
+    timezone.as_deref().map(build_timezone).unwrap_or(Ok(Tz::UTC))
+}
+
+fn build_padding(padding: &Option<String>) -> Result<char, MatcherError> {
+    match padding {
+        None => Ok(DEFAULT_PADDING),
+        Some(padding) => {
+            let mut chars = padding.chars();
+            match (chars.next(), chars.next()) {
+                (Some(padding), None) => Ok(padding),
+                _ => Err(MatcherError::ConfigurationError {
+                    message: format!(
+                        "The padding of the PadLeft and PadRight modifiers must be a single character. Found [{}]",
+                        padding
+                    ),
+                }),
+            }
+        }
+    }
 }
 
 impl ValueModifier {
@@ -31,6 +93,7 @@ impl ValueModifier {
         rule_name: &str,
         accessor_builder: &AccessorBuilder,
         modifiers: &[Modifier],
+        resources: &MatcherResources,
     ) -> Result<Vec<ValueModifier>, MatcherError> {
         let mut value_modifiers = vec![];
 
@@ -75,13 +138,127 @@ impl ValueModifier {
                 }
                 Modifier::DateAndTime { timezone } => {
                     trace!("Add post modifier to extractor: DateAndTime (timezone: {})", timezone);
-                    let Ok(timezone): Result<Tz, _> = timezone.trim().parse() else {
-                        return Err(MatcherError::ConfigurationError {
-                            message: format!("Unknown timezone ({timezone}). Expected timezone from the IANA timezone database.")
-                        })
-                    };
+                    let timezone = build_timezone(timezone)?;
                     value_modifiers.push(ValueModifier::DateAndTime { timezone });
                 }
+                Modifier::Uppercase {} => {
+                    trace!("Add post modifier to extractor: uppercase");
+                    value_modifiers.push(ValueModifier::Uppercase);
+                }
+                Modifier::Substring { start, end } => {
+                    trace!("Add post modifier to extractor: substring ({}, {:?})", start, end);
+                    value_modifiers.push(ValueModifier::Substring { start: *start, end: *end });
+                }
+                Modifier::Split { separator, is_regex } => {
+                    trace!("Add post modifier to extractor: split. Is it regex? {}", is_regex);
+                    if *is_regex {
+                        value_modifiers.push(ValueModifier::SplitRegex {
+                            separator_regex: RegexWrapper::new(separator)?,
+                        });
+                    } else {
+                        if separator.is_empty() {
+                            return Err(MatcherError::ConfigurationError {
+                                message: "The separator of the Split modifier cannot be empty"
+                                    .to_owned(),
+                            });
+                        }
+                        value_modifiers
+                            .push(ValueModifier::Split { separator: separator.to_owned() });
+                    }
+                }
+                Modifier::Join { separator } => {
+                    trace!("Add post modifier to extractor: join");
+                    value_modifiers.push(ValueModifier::Join { separator: separator.to_owned() });
+                }
+                Modifier::PadLeft { length, padding } => {
+                    trace!("Add post modifier to extractor: PadLeft (length: {})", length);
+                    value_modifiers.push(ValueModifier::PadLeft {
+                        length: *length,
+                        padding: build_padding(padding)?,
+                    });
+                }
+                Modifier::PadRight { length, padding } => {
+                    trace!("Add post modifier to extractor: PadRight (length: {})", length);
+                    value_modifiers.push(ValueModifier::PadRight {
+                        length: *length,
+                        padding: build_padding(padding)?,
+                    });
+                }
+                Modifier::Default { value } => {
+                    trace!("Add post modifier to extractor: Default ({})", value);
+                    value_modifiers.push(ValueModifier::Default { value: value.clone() });
+                }
+                Modifier::ParseJson {} => {
+                    trace!("Add post modifier to extractor: ParseJson");
+                    value_modifiers.push(ValueModifier::ParseJson);
+                }
+                Modifier::Base64Decode {} => {
+                    trace!("Add post modifier to extractor: Base64Decode");
+                    value_modifiers.push(ValueModifier::Base64Decode);
+                }
+                Modifier::Base64Encode {} => {
+                    trace!("Add post modifier to extractor: Base64Encode");
+                    value_modifiers.push(ValueModifier::Base64Encode);
+                }
+                Modifier::UrlDecode {} => {
+                    trace!("Add post modifier to extractor: UrlDecode");
+                    value_modifiers.push(ValueModifier::UrlDecode);
+                }
+                Modifier::HexDecode {} => {
+                    trace!("Add post modifier to extractor: HexDecode");
+                    value_modifiers.push(ValueModifier::HexDecode);
+                }
+                Modifier::ParseDate { formats, timezone } => {
+                    trace!(
+                        "Add post modifier to extractor: ParseDate (formats: {:?}, timezone: {:?})",
+                        formats,
+                        timezone
+                    );
+                    let formats: Result<Vec<_>, _> = if formats.is_empty() {
+                        DEFAULT_DATE_FORMATS
+                            .iter()
+                            .map(|format| DateFormat::build(format))
+                            .collect()
+                    } else {
+                        formats.iter().map(|format| DateFormat::build(format)).collect()
+                    };
+                    value_modifiers.push(ValueModifier::ParseDate {
+                        formats: formats?,
+                        timezone: build_optional_timezone(timezone)?,
+                    });
+                }
+                Modifier::FormatDate { format, timezone } => {
+                    trace!(
+                        "Add post modifier to extractor: FormatDate (format: {}, timezone: {:?})",
+                        format,
+                        timezone
+                    );
+                    date::validate_strftime_pattern(format)?;
+                    value_modifiers.push(ValueModifier::FormatDate {
+                        format: format.to_owned(),
+                        timezone: build_optional_timezone(timezone)?,
+                    });
+                }
+                Modifier::Lookup { table, field, default_value } => {
+                    trace!(
+                        "Add post modifier to extractor: Lookup (table: {}, field: {:?})",
+                        table,
+                        field
+                    );
+                    let table = resources.lookup_tables.get_shared(table).ok_or_else(|| {
+                        MatcherError::ConfigurationError {
+                            message: format!(
+                                "The Lookup modifier references the unknown lookup table [{}]",
+                                table
+                            ),
+                        }
+                    })?;
+                    value_modifiers.push(ValueModifier::Lookup {
+                        table,
+                        field: field.clone(),
+                        default_value: default_value.clone(),
+                    });
+                }
             }
         }
 
@@ -110,6 +287,38 @@ impl ValueModifier {
             ValueModifier::DateAndTime { timezone } => {
                 dateandtime::dateandtime(variable_name, value, timezone)
             }
+            ValueModifier::Uppercase => uppercase::uppercase(variable_name, value),
+            ValueModifier::Substring { start, end } => {
+                substring::substring(variable_name, value, *start, *end)
+            }
+            ValueModifier::Split { separator } => split::split(variable_name, value, separator),
+            ValueModifier::SplitRegex { separator_regex } => {
+                split::split_with_regex(variable_name, value, separator_regex)
+            }
+            ValueModifier::Join { separator } => join::join(variable_name, value, separator),
+            ValueModifier::PadLeft { length, padding } => {
+                pad::pad_left(variable_name, value, *length, *padding)
+            }
+            ValueModifier::PadRight { length, padding } => {
+                pad::pad_right(variable_name, value, *length, *padding)
+            }
+            ValueModifier::Default { value: default_value } => {
+                default::default_if_empty(variable_name, value, default_value)
+            }
+            ValueModifier::ParseJson => json::parse_json(variable_name, value),
+            ValueModifier::Base64Decode => encoding::base64_decode(variable_name, value),
+            ValueModifier::Base64Encode => encoding::base64_encode(variable_name, value),
+            ValueModifier::UrlDecode => encoding::url_decode(variable_name, value),
+            ValueModifier::HexDecode => encoding::hex_decode(variable_name, value),
+            ValueModifier::ParseDate { formats, timezone } => {
+                date::parse_date(variable_name, value, formats, timezone)
+            }
+            ValueModifier::FormatDate { format, timezone } => {
+                date::format_date(variable_name, value, format, timezone)
+            }
+            ValueModifier::Lookup { table, field, default_value } => {
+                lookup::lookup(variable_name, value, table, field, default_value)
+            }
         }
     }
 }
@@ -119,6 +328,7 @@ mod test {
     #![allow(clippy::approx_constant)]
 
     use super::*;
+    use crate::lookup::LookupTables;
     use maplit::*;
     use serde_json::json;
     use tornado_common_api::Event;
@@ -130,7 +340,8 @@ mod test {
 
         // Act
         let value_modifiers =
-            ValueModifier::build("", &AccessorBuilder::new(), &modifiers).unwrap();
+            ValueModifier::build("", &AccessorBuilder::new(), &modifiers, &Default::default())
+                .unwrap();
 
         // Assert
         assert!(value_modifiers.is_empty());
@@ -143,7 +354,8 @@ mod test {
 
         // Act
         let value_modifiers =
-            ValueModifier::build("", &AccessorBuilder::new(), &modifiers).unwrap();
+            ValueModifier::build("", &AccessorBuilder::new(), &modifiers, &Default::default())
+                .unwrap();
 
         // Assert
         assert_eq!(2, value_modifiers.len());
@@ -156,7 +368,8 @@ mod test {
 
         // Act
         let value_modifiers =
-            ValueModifier::build("", &AccessorBuilder::new(), &modifiers).unwrap();
+            ValueModifier::build("", &AccessorBuilder::new(), &modifiers, &Default::default())
+                .unwrap();
 
         // Assert
         assert_eq!(2, value_modifiers.len());
@@ -169,7 +382,8 @@ mod test {
 
         // Act
         let value_modifiers =
-            ValueModifier::build("", &AccessorBuilder::new(), &modifiers).unwrap();
+            ValueModifier::build("", &AccessorBuilder::new(), &modifiers, &Default::default())
+                .unwrap();
 
         // Assert
         assert_eq!(1, value_modifiers.len());
@@ -190,7 +404,8 @@ mod test {
 
         // Act
         let value_modifiers =
-            ValueModifier::build("", &AccessorBuilder::new(), &modifiers).unwrap();
+            ValueModifier::build("", &AccessorBuilder::new(), &modifiers, &Default::default())
+                .unwrap();
 
         // Assert
         assert_eq!(1, value_modifiers.len());
@@ -214,7 +429,8 @@ mod test {
 
         // Act
         let value_modifiers =
-            ValueModifier::build("", &AccessorBuilder::new(), &modifiers).unwrap();
+            ValueModifier::build("", &AccessorBuilder::new(), &modifiers, &Default::default())
+                .unwrap();
 
         // Assert
         assert_eq!(1, value_modifiers.len());
@@ -243,7 +459,8 @@ mod test {
 
         // Act
         let value_modifiers =
-            ValueModifier::build("", &AccessorBuilder::new(), &modifiers).unwrap();
+            ValueModifier::build("", &AccessorBuilder::new(), &modifiers, &Default::default())
+                .unwrap();
 
         // Assert
         assert_eq!(1, value_modifiers.len());
@@ -265,7 +482,119 @@ mod test {
         }];
 
         // Act & Assert
-        assert!(ValueModifier::build("", &AccessorBuilder::new(), &modifiers).is_err());
+        assert!(ValueModifier::build("", &AccessorBuilder::new(), &modifiers, &Default::default())
+            .is_err());
+    }
+
+    #[test]
+    fn should_build_string_manipulation_value_modifiers() {
+        // Arrange
+        let modifiers = vec![
+            Modifier::Uppercase {},
+            Modifier::Substring { start: 1, end: Some(-1) },
+            Modifier::Split { separator: ",".to_owned(), is_regex: false },
+            Modifier::Split { separator: "[,;]".to_owned(), is_regex: true },
+            Modifier::Join { separator: "".to_owned() },
+            Modifier::PadLeft { length: 3, padding: Some("0".to_owned()) },
+            Modifier::PadRight { length: 3, padding: None },
+            Modifier::Default { value: json!("unknown") },
+        ];
+
+        // Act
+        let value_modifiers =
+            ValueModifier::build("", &AccessorBuilder::new(), &modifiers, &Default::default())
+                .unwrap();
+
+        // Assert
+        assert_eq!(8, value_modifiers.len());
+        match &value_modifiers[3] {
+            ValueModifier::SplitRegex { separator_regex } => {
+                assert_eq!(&RegexWrapper::new("[,;]").unwrap(), separator_regex);
+            }
+            _ => unreachable!(),
+        }
+        match (&value_modifiers[5], &value_modifiers[6]) {
+            (
+                ValueModifier::PadLeft { length: 3, padding: '0' },
+                ValueModifier::PadRight { length: 3, padding: ' ' },
+            ) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn build_should_fail_if_string_manipulation_modifiers_are_not_valid() {
+        let build = |modifier: Modifier| {
+            ValueModifier::build("", &AccessorBuilder::new(), &[modifier], &Default::default())
+        };
+
+        assert!(build(Modifier::Split { separator: "".to_owned(), is_regex: false }).is_err());
+        assert!(build(Modifier::Split { separator: "[".to_owned(), is_regex: true }).is_err());
+        assert!(build(Modifier::PadLeft { length: 3, padding: Some("".to_owned()) }).is_err());
+        assert!(build(Modifier::PadRight { length: 3, padding: Some("ab".to_owned()) }).is_err());
+    }
+
+    #[test]
+    fn should_build_date_value_modifiers() {
+        // Arrange
+        let modifiers = vec![
+            Modifier::ParseDate { formats: vec![], timezone: None },
+            Modifier::ParseDate {
+                formats: vec!["syslog".to_owned(), "%d/%m/%Y".to_owned()],
+                timezone: Some("Europe/Rome".to_owned()),
+            },
+            Modifier::FormatDate { format: "%Y-%m-%d".to_owned(), timezone: None },
+        ];
+
+        // Act
+        let value_modifiers =
+            ValueModifier::build("", &AccessorBuilder::new(), &modifiers, &Default::default())
+                .unwrap();
+
+        // Assert
+        assert_eq!(3, value_modifiers.len());
+        match &value_modifiers[0] {
+            ValueModifier::ParseDate { formats, timezone: Tz::UTC } => {
+                assert_eq!(
+                    &vec![DateFormat::Rfc3339, DateFormat::Rfc2822, DateFormat::Syslog],
+                    formats
+                );
+            }
+            _ => unreachable!(),
+        }
+        match &value_modifiers[1] {
+            ValueModifier::ParseDate { formats, timezone: Tz::Europe__Rome } => {
+                assert_eq!(
+                    &vec![DateFormat::Syslog, DateFormat::Custom("%d/%m/%Y".to_owned())],
+                    formats
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn build_should_fail_if_date_modifiers_are_not_valid() {
+        let build = |modifier: Modifier| {
+            ValueModifier::build("", &AccessorBuilder::new(), &[modifier], &Default::default())
+        };
+
+        assert!(build(Modifier::ParseDate {
+            formats: vec!["%d/%m/%Q".to_owned()],
+            timezone: None
+        })
+        .is_err());
+        assert!(build(Modifier::ParseDate {
+            formats: vec![],
+            timezone: Some("Mars/Olympus".to_owned())
+        })
+        .is_err());
+        assert!(build(Modifier::FormatDate { format: "%Q".to_owned(), timezone: None }).is_err());
+        assert!(build(Modifier::FormatDate {
+            format: "%Y".to_owned(),
+            timezone: Some("Mars/Olympus".to_owned())
+        })
+        .is_err());
     }
 
     #[test]
@@ -405,4 +734,34 @@ mod test {
             assert_eq!(Value::String("David Gilmour".to_owned()), input);
         }
     }
+
+    #[test]
+    fn lookup_modifier_should_use_the_lookup_tables_of_the_resources() {
+        // Arrange
+        let event = json!(Event::new(""));
+        let modifiers = vec![Modifier::Lookup {
+            table: "teams".to_owned(),
+            field: Some("team".to_owned()),
+            default_value: None,
+        }];
+        let mut tables = LookupTables::default();
+        tables.add(LookupTable::from_csv("teams", "", "host,team\nweb01,ops\n").unwrap()).unwrap();
+        let resources = MatcherResources::default().with_lookup_tables(tables);
+
+        // Act
+        let value_modifiers =
+            ValueModifier::build("", &AccessorBuilder::new(), &modifiers, &resources).unwrap();
+
+        // Assert
+        let mut input = json!("web01");
+        value_modifiers[0].apply("", &mut input, &(&event, &mut Value::Null).into()).unwrap();
+        assert_eq!(json!("ops"), input);
+        assert!(ValueModifier::build(
+            "",
+            &AccessorBuilder::new(),
+            &modifiers,
+            &MatcherResources::default()
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,103 @@
+use serde_json::Value;
+
+use crate::error::MatcherError;
+
+/// Prepends the `padding` character to a string until it reaches `length` characters.
+/// Numbers are converted to strings before being padded.
+#[inline]
+pub fn pad_left(
+    variable_name: &str,
+    value: &mut Value,
+    length: usize,
+    padding: char,
+) -> Result<(), MatcherError> {
+    pad(variable_name, "pad_left", value, length, padding, |text, fill| fill + text)
+}
+
+/// Appends the `padding` character to a string until it reaches `length` characters.
+/// Numbers are converted to strings before being padded.
+#[inline]
+pub fn pad_right(
+    variable_name: &str,
+    value: &mut Value,
+    length: usize,
+    padding: char,
+) -> Result<(), MatcherError> {
+    pad(variable_name, "pad_right", value, length, padding, |text, fill| text.to_owned() + &fill)
+}
+
+fn pad<F: Fn(&str, String) -> String>(
+    variable_name: &str,
+    modifier_name: &str,
+    value: &mut Value,
+    length: usize,
+    padding: char,
+    concat: F,
+) -> Result<(), MatcherError> {
+    let text = match value {
+        Value::String(text) => text.to_owned(),
+        Value::Number(number) => number.to_string(),
+        _ => {
+            return Err(MatcherError::ExtractedVariableError {
+                message: format!(
+                    "The '{}' modifier can be used only with values of type 'string' or 'number'",
+                    modifier_name
+                ),
+                variable_name: variable_name.to_owned(),
+            })
+        }
+    };
+    let missing = length.saturating_sub(text.chars().count());
+    let fill = std::iter::repeat_n(padding, missing).collect();
+    *value = Value::String(concat(&text, fill));
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn pad_left_modifier_should_pad_a_string() {
+        {
+            let mut input = json!("7");
+            pad_left("", &mut input, 3, '0').unwrap();
+            assert_eq!(json!("007"), input);
+        }
+
+        {
+            let mut input = json!("1234");
+            pad_left("", &mut input, 3, '0').unwrap();
+            assert_eq!(json!("1234"), input);
+        }
+
+        {
+            let mut input = json!(42);
+            pad_left("", &mut input, 5, ' ').unwrap();
+            assert_eq!(json!("   42"), input);
+        }
+    }
+
+    #[test]
+    fn pad_right_modifier_should_pad_a_string() {
+        {
+            let mut input = json!("già");
+            pad_right("", &mut input, 5, '.').unwrap();
+            assert_eq!(json!("già.."), input);
+        }
+
+        {
+            let mut input = json!("");
+            pad_right("", &mut input, 2, '-').unwrap();
+            assert_eq!(json!("--"), input);
+        }
+    }
+
+    #[test]
+    fn pad_modifiers_should_fail_if_value_not_a_string_or_number() {
+        let mut input = json!([]);
+        assert!(pad_left("", &mut input, 3, '0').is_err());
+        assert!(pad_right("", &mut input, 3, '0').is_err());
+    }
+}
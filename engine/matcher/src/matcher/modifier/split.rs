@@ -0,0 +1,85 @@
+use regex::Regex;
+use serde_json::Value;
+use tornado_common_api::ValueExt;
+
+use crate::error::MatcherError;
+
+#[inline]
+pub fn split(variable_name: &str, value: &mut Value, separator: &str) -> Result<(), MatcherError> {
+    if let Some(text) = value.get_text() {
+        *value = to_array(text.split(separator));
+        Ok(())
+    } else {
+        Err(not_a_string_error(variable_name))
+    }
+}
+
+#[inline]
+pub fn split_with_regex(
+    variable_name: &str,
+    value: &mut Value,
+    separator_regex: &Regex,
+) -> Result<(), MatcherError> {
+    if let Some(text) = value.get_text() {
+        *value = to_array(separator_regex.split(text));
+        Ok(())
+    } else {
+        Err(not_a_string_error(variable_name))
+    }
+}
+
+fn to_array<'a, I: Iterator<Item = &'a str>>(parts: I) -> Value {
+    Value::Array(parts.map(|part| Value::String(part.to_owned())).collect())
+}
+
+fn not_a_string_error(variable_name: &str) -> MatcherError {
+    MatcherError::ExtractedVariableError {
+        message: "The 'split' modifier can be used only with values of type 'string'".to_owned(),
+        variable_name: variable_name.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::regex::RegexWrapper;
+    use serde_json::json;
+
+    #[test]
+    fn split_modifier_should_split_a_string() {
+        {
+            let mut input = json!("web01,web02,,db01");
+            split("", &mut input, ",").unwrap();
+            assert_eq!(json!(["web01", "web02", "", "db01"]), input);
+        }
+
+        {
+            let mut input = json!("web01");
+            split("", &mut input, ",").unwrap();
+            assert_eq!(json!(["web01"]), input);
+        }
+
+        {
+            let mut input = json!("");
+            split("", &mut input, ",").unwrap();
+            assert_eq!(json!([""]), input);
+        }
+    }
+
+    #[test]
+    fn split_with_regex_modifier_should_split_a_string() {
+        let separator_regex = RegexWrapper::new(r"\s*[,;]\s*").unwrap();
+        let mut input = json!("web01 , web02;db01");
+        split_with_regex("", &mut input, &separator_regex).unwrap();
+        assert_eq!(json!(["web01", "web02", "db01"]), input);
+    }
+
+    #[test]
+    fn split_modifier_should_fail_if_value_not_a_string() {
+        let separator_regex = RegexWrapper::new(",").unwrap();
+
+        let mut input = json!(["web01"]);
+        assert!(split("", &mut input, ",").is_err());
+        assert!(split_with_regex("", &mut input, &separator_regex).is_err());
+    }
+}
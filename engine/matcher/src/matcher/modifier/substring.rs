@@ -0,0 +1,78 @@
+use serde_json::Value;
+use tornado_common_api::ValueExt;
+
+use crate::error::MatcherError;
+
+/// Keeps the characters of a string from the `start` index (inclusive) to the `end` index
+/// (exclusive). The indexes refer to characters, not bytes; a negative index counts from the
+/// end of the string. Indexes out of the string bounds are clamped to the bounds.
+#[inline]
+pub fn substring(
+    variable_name: &str,
+    value: &mut Value,
+    start: i64,
+    end: Option<i64>,
+) -> Result<(), MatcherError> {
+    if let Some(text) = value.get_text() {
+        let length = text.chars().count();
+        let start = char_index(start, length);
+        let end = end.map(|end| char_index(end, length)).unwrap_or(length);
+        let result = if start < end {
+            text.chars().skip(start).take(end - start).collect()
+        } else {
+            String::new()
+        };
+        *value = Value::String(result);
+        Ok(())
+    } else {
+        Err(MatcherError::ExtractedVariableError {
+            message: "The 'substring' modifier can be used only with values of type 'string'"
+                .to_owned(),
+            variable_name: variable_name.to_owned(),
+        })
+    }
+}
+
+fn char_index(index: i64, length: usize) -> usize {
+    if index < 0 {
+        length.saturating_sub(index.unsigned_abs() as usize)
+    } else {
+        (index as usize).min(length)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn substring_modifier_should_keep_the_characters_between_the_indexes() {
+        let apply = |text: &str, start: i64, end: Option<i64>| {
+            let mut input = json!(text);
+            substring("", &mut input, start, end).unwrap();
+            input
+        };
+
+        assert_eq!(json!("host"), apply("hostname", 0, Some(4)));
+        assert_eq!(json!("name"), apply("hostname", 4, None));
+        assert_eq!(json!("name"), apply("hostname", -4, None));
+        assert_eq!(json!("stna"), apply("hostname", 2, Some(-2)));
+        assert_eq!(json!("hostname"), apply("hostname", -100, Some(100)));
+        assert_eq!(json!(""), apply("hostname", 5, Some(2)));
+        assert_eq!(json!(""), apply("", 0, Some(3)));
+    }
+
+    #[test]
+    fn substring_modifier_should_count_characters_instead_of_bytes() {
+        let mut input = json!("città già");
+        substring("", &mut input, 3, Some(-2)).unwrap();
+        assert_eq!(json!("tà g"), input);
+    }
+
+    #[test]
+    fn substring_modifier_should_fail_if_value_not_a_string() {
+        let mut input = json!(12);
+        assert!(substring("", &mut input, 0, None).is_err());
+    }
+}
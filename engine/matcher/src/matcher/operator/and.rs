@@ -1,7 +1,7 @@
 use crate::config;
 use crate::error::MatcherError;
 use crate::matcher::operator::{Operator, OperatorBuilder};
-use crate::model::InternalEvent;
+use crate::model::{InternalEvent, OperatorTrace};
 
 const OPERATOR_NAME: &str = "and";
 
@@ -34,6 +34,19 @@ impl Operator for And {
     fn evaluate(&self, event: &InternalEvent) -> bool {
         self.operators.iter().all(|op| op.evaluate(event))
     }
+
+    /// Unlike `evaluate`, all the child operators are evaluated, so that the trace
+    /// contains the results of all of them.
+    fn explain(&self, event: &InternalEvent) -> OperatorTrace {
+        let operators: Vec<OperatorTrace> =
+            self.operators.iter().map(|op| op.explain(event)).collect();
+        OperatorTrace {
+            operator: self.name().to_owned(),
+            result: operators.iter().all(|trace| trace.result),
+            values: vec![],
+            operators,
+        }
+    }
 }
 
 #[cfg(test)]
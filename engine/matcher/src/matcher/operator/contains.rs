@@ -52,6 +52,10 @@ impl Operator for Contains {
             None => false,
         }
     }
+
+    fn accessors(&self) -> Vec<(String, &Accessor)> {
+        vec![("first".to_owned(), &self.first), ("second".to_owned(), &self.second)]
+    }
 }
 
 #[cfg(test)]
@@ -312,4 +316,25 @@ mod test {
 
         assert!(!operator.evaluate(&(&json!(event), &mut Value::Null).into()));
     }
+
+    #[test]
+    fn should_evaluate_projected_arrays() {
+        let operator = Contains::build(
+            AccessorBuilder::new().build("", "${event.payload.hosts[*].name}").unwrap(),
+            AccessorBuilder::new().build("", "db01").unwrap(),
+        )
+        .unwrap();
+
+        let mut event = Event::new("test_type");
+        event.payload.insert(
+            "hosts".to_owned(),
+            json!([{ "name": "web01" }, { "address": "10.0.0.1" }, { "name": "db01" }]),
+        );
+
+        assert!(operator.evaluate(&(&json!(event), &mut Value::Null).into()));
+
+        event.payload.insert("hosts".to_owned(), json!([{ "name": "web01" }]));
+
+        assert!(!operator.evaluate(&(&json!(event), &mut Value::Null).into()));
+    }
 }
@@ -66,6 +66,10 @@ impl Operator for ContainsIgnoreCase {
             None => false,
         }
     }
+
+    fn accessors(&self) -> Vec<(String, &Accessor)> {
+        vec![("first".to_owned(), &self.first), ("second".to_owned(), &self.second)]
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,125 @@
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::{accessor::Accessor, model::InternalEvent};
+use tornado_common_api::cow_to_str;
+
+const OPERATOR_NAME: &str = "endsWith";
+
+/// A matching matcher.operator that evaluates whether the first argument ends with the second
+#[derive(Debug)]
+pub struct EndsWith {
+    first: Accessor,
+    second: Accessor,
+}
+
+impl EndsWith {
+    pub fn build(first: Accessor, second: Accessor) -> Result<EndsWith, MatcherError> {
+        Ok(EndsWith { first, second })
+    }
+}
+
+impl Operator for EndsWith {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent) -> bool {
+        let first = self.first.get(event);
+        let second = self.second.get(event);
+        match (cow_to_str(&first), cow_to_str(&second)) {
+            (Some(first), Some(second)) => first.ends_with(second),
+            _ => false,
+        }
+    }
+
+    fn accessors(&self) -> Vec<(String, &Accessor)> {
+        vec![("first".to_owned(), &self.first), ("second".to_owned(), &self.second)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use serde_json::json;
+    use tornado_common_api::*;
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = EndsWith {
+            first: AccessorBuilder::new().build("", "").unwrap(),
+            second: AccessorBuilder::new().build("", "").unwrap(),
+        };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_text_ends_with_the_second_argument() {
+        let operator = EndsWith::build(
+            AccessorBuilder::new().build("", "two or one").unwrap(),
+            AccessorBuilder::new().build("", "one").unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(operator.evaluate(&(&json!(event), &mut Value::Null).into()));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_text_does_not_end_with_the_second_argument() {
+        let operator = EndsWith::build(
+            AccessorBuilder::new().build("", "two or one").unwrap(),
+            AccessorBuilder::new().build("", "two").unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&(&json!(event), &mut Value::Null).into()));
+    }
+
+    #[test]
+    fn should_evaluate_using_accessors() {
+        let operator = EndsWith::build(
+            AccessorBuilder::new().build("", "${event.payload.hostname}").unwrap(),
+            AccessorBuilder::new().build("", "${event.payload.ends}").unwrap(),
+        )
+        .unwrap();
+
+        let mut payload = Map::new();
+        payload.insert("hostname".to_owned(), json!("web01.example.com"));
+        payload.insert("starts".to_owned(), json!("web"));
+        payload.insert("ends".to_owned(), json!(".example.com"));
+        let event = Event::new_with_payload("test_type", payload);
+
+        assert!(operator.evaluate(&(&json!(event), &mut Value::Null).into()));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_arguments_are_not_text() {
+        let operator = EndsWith::build(
+            AccessorBuilder::new().build_from_value("", &json!(12)).unwrap(),
+            AccessorBuilder::new().build_from_value("", &json!(1)).unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&(&json!(event), &mut Value::Null).into()));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_field_does_not_exist() {
+        let operator = EndsWith::build(
+            AccessorBuilder::new().build("", "${event.payload.missing}").unwrap(),
+            AccessorBuilder::new().build("", "").unwrap(),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        assert!(!operator.evaluate(&(&json!(event), &mut Value::Null).into()));
+    }
+}
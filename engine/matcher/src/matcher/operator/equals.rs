@@ -28,6 +28,10 @@ impl Operator for Equals {
         let second = self.second_arg.get(event);
         first == second
     }
+
+    fn accessors(&self) -> Vec<(String, &Accessor)> {
+        vec![("first".to_owned(), &self.first_arg), ("second".to_owned(), &self.second_arg)]
+    }
 }
 
 #[cfg(test)]
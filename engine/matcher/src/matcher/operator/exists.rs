@@ -0,0 +1,85 @@
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::{accessor::Accessor, model::InternalEvent};
+
+const OPERATOR_NAME: &str = "exists";
+
+/// A matching matcher.operator that checks whether the value returned by an accessor exists.
+/// A value explicitly set to null is considered existing.
+#[derive(Debug)]
+pub struct Exists {
+    target: Accessor,
+}
+
+impl Exists {
+    pub fn build(target: Accessor) -> Result<Exists, MatcherError> {
+        Ok(Exists { target })
+    }
+}
+
+impl Operator for Exists {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent) -> bool {
+        self.target.get(event).is_some()
+    }
+
+    fn accessors(&self) -> Vec<(String, &Accessor)> {
+        vec![("target".to_owned(), &self.target)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use serde_json::json;
+    use tornado_common_api::*;
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = Exists { target: AccessorBuilder::new().build("", "").unwrap() };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_evaluate_the_existence_of_event_fields() {
+        // Arrange
+        let mut payload = Map::new();
+        payload.insert("hostname".to_owned(), json!("web01"));
+        payload.insert("nothing".to_owned(), Value::Null);
+        let event = json!(Event::new_with_payload("test_type", payload));
+
+        let evaluate = |target: &str| {
+            Exists::build(AccessorBuilder::new().build("", target).unwrap())
+                .unwrap()
+                .evaluate(&(&event, &mut Value::Null).into())
+        };
+
+        // Act & Assert
+        assert!(evaluate("${event.type}"));
+        assert!(evaluate("${event.payload.hostname}"));
+        assert!(evaluate("${event.payload.nothing}"));
+        assert!(!evaluate("${event.payload.missing}"));
+        assert!(!evaluate("${event.payload.hostname.missing}"));
+    }
+
+    #[test]
+    fn should_evaluate_the_existence_of_extracted_variables() {
+        // Arrange
+        let operator =
+            Exists::build(AccessorBuilder::new().build("rule", "${_variables.extracted}").unwrap())
+                .unwrap();
+        let event = json!(Event::new("test_type"));
+        let mut extracted_vars = json!({ "rule": { "extracted": "value" } });
+
+        // Act
+        let result = operator.evaluate(&(&event, &mut extracted_vars).into());
+
+        // Assert
+        assert!(result);
+    }
+}
@@ -28,6 +28,10 @@ impl Operator for GreaterThan {
         let cmp = partial_cmp_option_cow_value(&self.first.get(event), || self.second.get(event));
         cmp == Some(Ordering::Greater)
     }
+
+    fn accessors(&self) -> Vec<(String, &Accessor)> {
+        vec![("first".to_owned(), &self.first), ("second".to_owned(), &self.second)]
+    }
 }
 
 #[cfg(test)]
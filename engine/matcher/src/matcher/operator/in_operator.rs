@@ -0,0 +1,155 @@
+use crate::accessor::Accessor;
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::model::InternalEvent;
+
+const OPERATOR_NAME: &str = "in";
+
+/// A matching matcher.operator that checks whether a value is equal to at least one
+/// of the values of a list
+#[derive(Debug)]
+pub struct In {
+    first: Accessor,
+    values: Vec<Accessor>,
+}
+
+impl In {
+    pub fn build(first: Accessor, values: Vec<Accessor>) -> Result<In, MatcherError> {
+        Ok(In { first, values })
+    }
+}
+
+impl Operator for In {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent) -> bool {
+        match self.first.get(event) {
+            Some(first) => {
+                self.values.iter().any(|value| value.get(event).as_ref() == Some(&first))
+            }
+            None => false,
+        }
+    }
+
+    fn accessors(&self) -> Vec<(String, &Accessor)> {
+        let mut accessors = vec![("first".to_owned(), &self.first)];
+        for (index, value) in self.values.iter().enumerate() {
+            accessors.push((format!("values[{}]", index), value));
+        }
+        accessors
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use serde_json::json;
+    use tornado_common_api::*;
+
+    fn accessors(values: &[Value]) -> Vec<Accessor> {
+        values
+            .iter()
+            .map(|value| AccessorBuilder::new().build_from_value("", value).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = In { first: AccessorBuilder::new().build("", "").unwrap(), values: vec![] };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_evaluate_to_true_if_the_value_is_in_the_list() {
+        // Arrange
+        let operator = In::build(
+            AccessorBuilder::new().build("", "${event.type}").unwrap(),
+            accessors(&[json!("email"), json!("syslog"), json!("snmptrapd")]),
+        )
+        .unwrap();
+
+        let event = Event::new("syslog");
+
+        // Act
+        let result = operator.evaluate(&(&json!(event), &mut Value::Null).into());
+
+        // Assert
+        assert!(result);
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_the_value_is_not_in_the_list() {
+        // Arrange
+        let operator = In::build(
+            AccessorBuilder::new().build("", "${event.type}").unwrap(),
+            accessors(&[json!("email"), json!("snmptrapd")]),
+        )
+        .unwrap();
+
+        let event = Event::new("syslog");
+
+        // Act
+        let result = operator.evaluate(&(&json!(event), &mut Value::Null).into());
+
+        // Assert
+        assert!(!result);
+    }
+
+    #[test]
+    fn should_compare_values_of_any_type() {
+        // Arrange
+        let operator = In::build(
+            AccessorBuilder::new().build("", "${event.payload.severity}").unwrap(),
+            accessors(&[json!(1), json!(2), json!("${event.payload.max_severity}")]),
+        )
+        .unwrap();
+
+        let mut payload = Map::new();
+        payload.insert("severity".to_owned(), json!(5));
+        payload.insert("max_severity".to_owned(), json!(5));
+        let event = Event::new_with_payload("test_type", payload);
+
+        // Act
+        let result = operator.evaluate(&(&json!(event), &mut Value::Null).into());
+
+        // Assert
+        assert!(result);
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_the_value_does_not_exist() {
+        // Arrange
+        let operator = In::build(
+            AccessorBuilder::new().build("", "${event.payload.missing}").unwrap(),
+            accessors(&[json!("${event.payload.other_missing}")]),
+        )
+        .unwrap();
+
+        let event = Event::new("test_type");
+
+        // Act
+        let result = operator.evaluate(&(&json!(event), &mut Value::Null).into());
+
+        // Assert
+        assert!(!result);
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_the_list_is_empty() {
+        // Arrange
+        let operator =
+            In::build(AccessorBuilder::new().build("", "${event.type}").unwrap(), vec![]).unwrap();
+
+        let event = Event::new("test_type");
+
+        // Act
+        let result = operator.evaluate(&(&json!(event), &mut Value::Null).into());
+
+        // Assert
+        assert!(!result);
+    }
+}
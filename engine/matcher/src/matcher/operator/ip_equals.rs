@@ -0,0 +1,109 @@
+use crate::error::MatcherError;
+use crate::matcher::operator::ip_in_cidr::parse_ip_addr;
+use crate::matcher::operator::Operator;
+use crate::{accessor::Accessor, model::InternalEvent};
+use tornado_common_api::cow_to_str;
+
+const OPERATOR_NAME: &str = "ipEquals";
+
+/// A matching matcher.operator that checks whether two strings represent the same IP address,
+/// regardless of their textual representation
+#[derive(Debug)]
+pub struct IpEquals {
+    first: Accessor,
+    second: Accessor,
+}
+
+impl IpEquals {
+    pub fn build(first: Accessor, second: Accessor) -> Result<IpEquals, MatcherError> {
+        Ok(IpEquals { first, second })
+    }
+}
+
+impl Operator for IpEquals {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent) -> bool {
+        let first = self.first.get(event);
+        let second = self.second.get(event);
+        match (
+            cow_to_str(&first).and_then(parse_ip_addr),
+            cow_to_str(&second).and_then(parse_ip_addr),
+        ) {
+            (Some(first), Some(second)) => first == second,
+            _ => false,
+        }
+    }
+
+    fn accessors(&self) -> Vec<(String, &Accessor)> {
+        vec![("first".to_owned(), &self.first), ("second".to_owned(), &self.second)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use serde_json::json;
+    use tornado_common_api::*;
+
+    fn evaluate(first: &str, second: &str) -> bool {
+        let operator = IpEquals::build(
+            AccessorBuilder::new().build("", first).unwrap(),
+            AccessorBuilder::new().build("", second).unwrap(),
+        )
+        .unwrap();
+        let event = Event::new("test_type");
+        operator.evaluate(&(&json!(event), &mut Value::Null).into())
+    }
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = IpEquals {
+            first: AccessorBuilder::new().build("", "").unwrap(),
+            second: AccessorBuilder::new().build("", "").unwrap(),
+        };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_compare_normalized_addresses() {
+        assert!(evaluate("10.0.0.1", "10.0.0.1"));
+        assert!(evaluate("2001:db8::1", "2001:0DB8:0000:0000:0000:0000:0000:0001"));
+        assert!(evaluate("::ffff:10.0.0.1", "10.0.0.1"));
+        assert!(evaluate(" 10.0.0.1", "10.0.0.1 "));
+
+        assert!(!evaluate("10.0.0.1", "10.0.0.2"));
+        assert!(!evaluate("2001:db8::1", "2001:db8::2"));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_an_argument_is_not_an_address() {
+        assert!(!evaluate("host", "host"));
+        assert!(!evaluate("10.0.0.1", "${event.payload.missing}"));
+        assert!(!evaluate("${event.payload.missing}", "${event.payload.missing}"));
+    }
+
+    #[test]
+    fn should_evaluate_using_accessors() {
+        // Arrange
+        let operator = IpEquals::build(
+            AccessorBuilder::new().build("", "${event.payload.source}").unwrap(),
+            AccessorBuilder::new().build("", "${event.payload.gateway}").unwrap(),
+        )
+        .unwrap();
+        let mut payload = Map::new();
+        payload.insert("source".to_owned(), json!("fe80::0001"));
+        payload.insert("gateway".to_owned(), json!("FE80::1"));
+        let event = Event::new_with_payload("test_type", payload);
+
+        // Act
+        let result = operator.evaluate(&(&json!(event), &mut Value::Null).into());
+
+        // Assert
+        assert!(result);
+    }
+}
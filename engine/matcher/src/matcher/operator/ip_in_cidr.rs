@@ -0,0 +1,147 @@
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::{accessor::Accessor, model::InternalEvent};
+use ipnet::IpNet;
+use std::net::IpAddr;
+use tornado_common_api::cow_to_str;
+
+const OPERATOR_NAME: &str = "ipInCidr";
+
+/// A matching matcher.operator that checks whether an IPv4 or IPv6 address belongs to at least
+/// one of a list of networks
+#[derive(Debug)]
+pub struct IpInCidr {
+    target: Accessor,
+    networks: Vec<IpNet>,
+}
+
+impl IpInCidr {
+    pub fn build(target: Accessor, networks: &[String]) -> Result<IpInCidr, MatcherError> {
+        let networks =
+            networks.iter().map(|network| parse_network(network)).collect::<Result<_, _>>()?;
+        Ok(IpInCidr { target, networks })
+    }
+}
+
+/// Parses a network in CIDR notation, e.g. "10.0.0.0/8" or "2001:db8::/32".
+/// A single address is accepted too and it is considered a network containing only that address.
+pub fn parse_network(network: &str) -> Result<IpNet, MatcherError> {
+    let network = network.trim();
+    network.parse::<IpNet>().or_else(|_| network.parse::<IpAddr>().map(IpNet::from)).map_err(|e| {
+        MatcherError::OperatorBuildFailError {
+            message: format!("Cannot parse network [{}]", network),
+            cause: e.to_string(),
+        }
+    })
+}
+
+/// Parses an IPv4 or IPv6 address. IPv4-mapped IPv6 addresses, e.g. "::ffff:10.0.0.1",
+/// are converted to their IPv4 representation.
+pub fn parse_ip_addr(address: &str) -> Option<IpAddr> {
+    address.trim().parse::<IpAddr>().ok().map(|address| address.to_canonical())
+}
+
+impl Operator for IpInCidr {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent) -> bool {
+        let cow_value = self.target.get(event);
+        match cow_to_str(&cow_value).and_then(parse_ip_addr) {
+            Some(address) => self.networks.iter().any(|network| network.contains(&address)),
+            None => false,
+        }
+    }
+
+    fn accessors(&self) -> Vec<(String, &Accessor)> {
+        vec![("target".to_owned(), &self.target)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use serde_json::json;
+    use tornado_common_api::*;
+
+    fn ip_in_cidr(networks: &[&str]) -> IpInCidr {
+        let networks: Vec<String> = networks.iter().map(|network| network.to_string()).collect();
+        IpInCidr::build(
+            AccessorBuilder::new().build("", "${event.payload.source}").unwrap(),
+            &networks,
+        )
+        .unwrap()
+    }
+
+    fn evaluate(operator: &IpInCidr, source: Value) -> bool {
+        let mut payload = Map::new();
+        payload.insert("source".to_owned(), source);
+        let event = Event::new_with_payload("test_type", payload);
+        operator.evaluate(&(&json!(event), &mut Value::Null).into())
+    }
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = ip_in_cidr(&[]);
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn build_should_fail_if_invalid_network() {
+        let target = AccessorBuilder::new().build("", "${event.payload.source}").unwrap();
+        assert!(IpInCidr::build(target, &["10.0.0.0/33".to_owned()]).is_err());
+
+        let target = AccessorBuilder::new().build("", "${event.payload.source}").unwrap();
+        assert!(IpInCidr::build(target, &["not.a.network".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn should_evaluate_ipv4_addresses() {
+        // Arrange
+        let operator = ip_in_cidr(&["10.0.0.0/8", "192.168.1.0/24", "172.16.0.1"]);
+
+        // Act & Assert
+        assert!(evaluate(&operator, json!("10.20.30.40")));
+        assert!(evaluate(&operator, json!("192.168.1.255")));
+        assert!(evaluate(&operator, json!("172.16.0.1")));
+        assert!(!evaluate(&operator, json!("192.168.2.1")));
+        assert!(!evaluate(&operator, json!("172.16.0.2")));
+    }
+
+    #[test]
+    fn should_evaluate_ipv6_addresses() {
+        // Arrange
+        let operator = ip_in_cidr(&["2001:db8::/32"]);
+
+        // Act & Assert
+        assert!(evaluate(&operator, json!("2001:db8::1")));
+        assert!(evaluate(&operator, json!("2001:0DB8:0000:0000:0000:0000:0000:00ff")));
+        assert!(!evaluate(&operator, json!("2001:db9::1")));
+        assert!(!evaluate(&operator, json!("10.0.0.1")));
+    }
+
+    #[test]
+    fn should_evaluate_ipv4_mapped_ipv6_addresses_as_ipv4() {
+        // Arrange
+        let operator = ip_in_cidr(&["10.0.0.0/8"]);
+
+        // Act & Assert
+        assert!(evaluate(&operator, json!("::ffff:10.1.2.3")));
+        assert!(!evaluate(&operator, json!("::ffff:11.1.2.3")));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_the_target_is_not_an_address() {
+        // Arrange
+        let operator = ip_in_cidr(&["0.0.0.0/0", "::/0"]);
+
+        // Act & Assert
+        assert!(!evaluate(&operator, json!("host.example.com")));
+        assert!(!evaluate(&operator, json!("10.0.0.1/8")));
+        assert!(!evaluate(&operator, json!(10)));
+        assert!(!evaluate(&operator, Value::Null));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::config::rule::ValueType;
+use crate::error::MatcherError;
+use crate::matcher::operator::Operator;
+use crate::{accessor::Accessor, model::InternalEvent};
+use tornado_common_api::Value;
+
+const OPERATOR_NAME: &str = "isType";
+
+/// A matching matcher.operator that checks whether the value returned by an accessor
+/// is of the expected type
+#[derive(Debug)]
+pub struct IsType {
+    target: Accessor,
+    value_type: ValueType,
+}
+
+impl IsType {
+    pub fn build(target: Accessor, value_type: ValueType) -> Result<IsType, MatcherError> {
+        Ok(IsType { target, value_type })
+    }
+}
+
+impl Operator for IsType {
+    fn name(&self) -> &str {
+        OPERATOR_NAME
+    }
+
+    fn evaluate(&self, event: &InternalEvent) -> bool {
+        match self.target.get(event) {
+            Some(value) => matches!(
+                (&self.value_type, value.as_ref()),
+                (ValueType::String, Value::String(_))
+                    | (ValueType::Number, Value::Number(_))
+                    | (ValueType::Bool, Value::Bool(_))
+                    | (ValueType::Array, Value::Array(_))
+                    | (ValueType::Object, Value::Object(_))
+                    | (ValueType::Null, Value::Null)
+            ),
+            None => false,
+        }
+    }
+
+    fn accessors(&self) -> Vec<(String, &Accessor)> {
+        vec![("target".to_owned(), &self.target)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::accessor::AccessorBuilder;
+    use serde_json::json;
+    use tornado_common_api::*;
+
+    #[test]
+    fn should_return_the_operator_name() {
+        let operator = IsType {
+            target: AccessorBuilder::new().build("", "").unwrap(),
+            value_type: ValueType::String,
+        };
+        assert_eq!(OPERATOR_NAME, operator.name());
+    }
+
+    #[test]
+    fn should_evaluate_the_type_of_event_fields() {
+        // Arrange
+        let mut payload = Map::new();
+        payload.insert("string".to_owned(), json!("text"));
+        payload.insert("number".to_owned(), json!(12.5));
+        payload.insert("bool".to_owned(), json!(true));
+        payload.insert("array".to_owned(), json!([1, 2]));
+        payload.insert("object".to_owned(), json!({ "key": "value" }));
+        payload.insert("null".to_owned(), Value::Null);
+        let event = json!(Event::new_with_payload("test_type", payload));
+
+        let evaluate = |target: &str, value_type: ValueType| {
+            IsType::build(AccessorBuilder::new().build("", target).unwrap(), value_type)
+                .unwrap()
+                .evaluate(&(&event, &mut Value::Null).into())
+        };
+
+        // Act & Assert
+        assert!(evaluate("${event.payload.string}", ValueType::String));
+        assert!(evaluate("${event.payload.number}", ValueType::Number));
+        assert!(evaluate("${event.payload.bool}", ValueType::Bool));
+        assert!(evaluate("${event.payload.array}", ValueType::Array));
+        assert!(evaluate("${event.payload.object}", ValueType::Object));
+        assert!(evaluate("${event.payload.null}", ValueType::Null));
+
+        assert!(!evaluate("${event.payload.number}", ValueType::String));
+        assert!(!evaluate("${event.payload.string}", ValueType::Number));
+        assert!(!evaluate("${event.payload.array}", ValueType::Object));
+        assert!(!evaluate("${event.payload.object}", ValueType::Array));
+        assert!(!evaluate("${event.payload.string}", ValueType::Null));
+    }
+
+    #[test]
+    fn should_evaluate_to_false_if_field_does_not_exist() {
+        // Arrange
+        let operator = IsType::build(
+            AccessorBuilder::new().build("", "${event.payload.missing}").unwrap(),
+            ValueType::Null,
+        )
+        .unwrap();
+        let event = Event::new("test_type");
+
+        // Act
+        let result = operator.evaluate(&(&json!(event), &mut Value::Null).into());
+
+        // Assert
+        assert!(!result);
+    }
+}
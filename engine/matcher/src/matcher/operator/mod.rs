@@ -4,26 +4,36 @@
 //! An *Operator* is linked to the "WHERE" clause of a Rule and determines whether the rule
 //! is matched by an Event.
 
-use crate::accessor::AccessorBuilder;
+use crate::accessor::{Accessor, AccessorBuilder};
 use crate::config::rule;
 use crate::error::MatcherError;
-use crate::model::InternalEvent;
+use crate::model::{InternalEvent, OperatorTrace, OperatorTraceValue};
+use crate::resources::MatcherResources;
 use log::*;
+use std::borrow::Cow;
 use std::fmt;
 
 pub mod and;
 pub mod contains;
 pub mod contains_ignore_case;
+pub mod ends_with;
 pub mod equals;
 pub mod equals_ignore_case;
+pub mod exists;
 pub mod ge;
 pub mod gt;
+pub mod in_operator;
+pub mod ip_equals;
+pub mod ip_in_cidr;
+pub mod is_type;
 pub mod le;
 pub mod lt;
 pub mod ne;
 pub mod not;
+pub mod not_exists;
 pub mod or;
 pub mod regex;
+pub mod starts_with;
 pub mod true_operator;
 
 /// The Trait for a generic matcher.operator
@@ -33,17 +43,47 @@ pub trait Operator: fmt::Debug + Send + Sync {
 
     /// Executes the current matcher.operator on a target Event and returns whether the Event matches it.
     fn evaluate(&self, event: &InternalEvent) -> bool;
+
+    /// Returns the Accessors of the operator arguments with the argument names.
+    fn accessors(&self) -> Vec<(String, &Accessor)> {
+        vec![]
+    }
+
+    /// Executes the current matcher.operator like `evaluate` and returns the trace of the
+    /// evaluation with the values resolved by its Accessors.
+    fn explain(&self, event: &InternalEvent) -> OperatorTrace {
+        OperatorTrace {
+            operator: self.name().to_owned(),
+            result: self.evaluate(event),
+            values: self
+                .accessors()
+                .into_iter()
+                .map(|(argument, accessor)| OperatorTraceValue {
+                    argument,
+                    value: accessor.get(event).map(Cow::into_owned),
+                })
+                .collect(),
+            operators: vec![],
+        }
+    }
 }
 
 /// The Operator instance builder
 #[derive(Default)]
 pub struct OperatorBuilder {
     accessor: AccessorBuilder,
+    resources: MatcherResources,
 }
 
 impl OperatorBuilder {
     pub fn new() -> OperatorBuilder {
-        OperatorBuilder { accessor: AccessorBuilder::new() }
+        OperatorBuilder { accessor: AccessorBuilder::new(), resources: MatcherResources::default() }
+    }
+
+    /// Sets the resources, e.g. the grok patterns, used to build the operators.
+    pub fn with_resources(mut self, resources: MatcherResources) -> OperatorBuilder {
+        self.resources = resources;
+        self
     }
 
     pub fn build_option(
@@ -155,12 +195,70 @@ impl OperatorBuilder {
                     self.accessor.build_from_value(rule_name, second)?,
                 )?,
             )),
-            rule::Operator::Regex { regex, target } => {
-                Ok(Box::new(crate::matcher::operator::regex::Regex::build(
-                    regex,
+            rule::Operator::Regex { regex, target, grok } => {
+                let target = self.accessor.build(rule_name, target)?;
+                if *grok {
+                    Ok(Box::new(crate::matcher::operator::regex::Regex::build_grok(
+                        regex,
+                        target,
+                        &self.resources.grok_patterns,
+                    )?))
+                } else {
+                    Ok(Box::new(crate::matcher::operator::regex::Regex::build(regex, target)?))
+                }
+            }
+            rule::Operator::In { first, values } => {
+                Ok(Box::new(crate::matcher::operator::in_operator::In::build(
+                    self.accessor.build_from_value(rule_name, first)?,
+                    values
+                        .iter()
+                        .map(|value| self.accessor.build_from_value(rule_name, value))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )?))
+            }
+            rule::Operator::StartsWith { first, second } => {
+                Ok(Box::new(crate::matcher::operator::starts_with::StartsWith::build(
+                    self.accessor.build_from_value(rule_name, first)?,
+                    self.accessor.build_from_value(rule_name, second)?,
+                )?))
+            }
+            rule::Operator::EndsWith { first, second } => {
+                Ok(Box::new(crate::matcher::operator::ends_with::EndsWith::build(
+                    self.accessor.build_from_value(rule_name, first)?,
+                    self.accessor.build_from_value(rule_name, second)?,
+                )?))
+            }
+            rule::Operator::Exists { target } => {
+                Ok(Box::new(crate::matcher::operator::exists::Exists::build(
+                    self.accessor.build(rule_name, target)?,
+                )?))
+            }
+            rule::Operator::NotExists { target } => {
+                Ok(Box::new(crate::matcher::operator::not_exists::NotExists::build(
                     self.accessor.build(rule_name, target)?,
                 )?))
             }
+            rule::Operator::IsType { target, value_type } => {
+                Ok(Box::new(crate::matcher::operator::is_type::IsType::build(
+                    self.accessor.build(rule_name, target)?,
+                    value_type.clone(),
+                )?))
+            }
+            rule::Operator::IpInCidr { target, networks } => {
+                Ok(Box::new(crate::matcher::operator::ip_in_cidr::IpInCidr::build(
+                    self.accessor.build(rule_name, target)?,
+                    networks,
+                )?))
+            }
+            rule::Operator::IpEquals { first, second } => {
+                Ok(Box::new(crate::matcher::operator::ip_equals::IpEquals::build(
+                    self.accessor.build_from_value(rule_name, first)?,
+                    self.accessor.build_from_value(rule_name, second)?,
+                )?))
+            }
+            rule::Operator::Expression { expression } => {
+                self.build(rule_name, &rule::Operator::parse_expression(expression)?)
+            }
         };
 
         trace!(
@@ -283,8 +381,11 @@ mod test {
 
     #[test]
     fn build_should_return_the_regex_operator() {
-        let ops =
-            rule::Operator::Regex { regex: "[a-fA-F0-9]".to_owned(), target: "target".to_owned() };
+        let ops = rule::Operator::Regex {
+            regex: "[a-fA-F0-9]".to_owned(),
+            target: "target".to_owned(),
+            grok: false,
+        };
 
         let builder = OperatorBuilder::new();
         let operator = builder.build_option("", &Some(ops)).unwrap();
@@ -292,6 +393,160 @@ mod test {
         assert_eq!("regex", operator.name());
     }
 
+    #[test]
+    fn build_should_return_the_regex_operator_with_grok_expression() {
+        let ops = |regex: &str| rule::Operator::Regex {
+            regex: regex.to_owned(),
+            target: "target".to_owned(),
+            grok: true,
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops("^%{IPV4} port %{POSINT}$"))).unwrap();
+
+        assert_eq!("regex", operator.name());
+        assert!(builder.build_option("", &Some(ops("%{NOT_EXISTING}"))).is_err());
+    }
+
+    #[test]
+    fn build_should_return_the_in_operator() {
+        let ops = rule::Operator::In {
+            first: Value::String("${event.type}".to_owned()),
+            values: vec![Value::String("email".to_owned()), Value::String("syslog".to_owned())],
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("in", operator.name());
+    }
+
+    #[test]
+    fn build_should_fail_if_wrong_in_operator_value() {
+        let ops = rule::Operator::In {
+            first: Value::String("${event.type}".to_owned()),
+            values: vec![Value::String("${WRONG_ARG}".to_owned())],
+        };
+
+        let builder = OperatorBuilder::new();
+        assert!(builder.build_option("", &Some(ops)).is_err());
+    }
+
+    #[test]
+    fn build_should_return_the_starts_with_operator() {
+        let ops = rule::Operator::StartsWith {
+            first: Value::String("first_arg".to_owned()),
+            second: Value::String("second_arg".to_owned()),
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("startsWith", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_the_ends_with_operator() {
+        let ops = rule::Operator::EndsWith {
+            first: Value::String("first_arg".to_owned()),
+            second: Value::String("second_arg".to_owned()),
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("endsWith", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_the_exists_and_not_exists_operators() {
+        let builder = OperatorBuilder::new();
+
+        let ops = rule::Operator::Exists { target: "${event.payload.value}".to_owned() };
+        assert_eq!("exists", builder.build_option("", &Some(ops)).unwrap().name());
+
+        let ops = rule::Operator::NotExists { target: "${event.payload.value}".to_owned() };
+        assert_eq!("notExists", builder.build_option("", &Some(ops)).unwrap().name());
+    }
+
+    #[test]
+    fn build_should_return_the_is_type_operator() {
+        let ops = rule::Operator::IsType {
+            target: "${event.payload.value}".to_owned(),
+            value_type: rule::ValueType::Number,
+        };
+
+        let builder = OperatorBuilder::new();
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        assert_eq!("isType", operator.name());
+    }
+
+    #[test]
+    fn build_should_return_the_ip_operators() {
+        let builder = OperatorBuilder::new();
+
+        let ops = rule::Operator::IpInCidr {
+            target: "${event.payload.source}".to_owned(),
+            networks: vec!["10.0.0.0/8".to_owned(), "2001:db8::/32".to_owned()],
+        };
+        assert_eq!("ipInCidr", builder.build_option("", &Some(ops)).unwrap().name());
+
+        let ops = rule::Operator::IpEquals {
+            first: Value::String("${event.payload.source}".to_owned()),
+            second: Value::String("10.0.0.1".to_owned()),
+        };
+        assert_eq!("ipEquals", builder.build_option("", &Some(ops)).unwrap().name());
+    }
+
+    #[test]
+    fn build_should_fail_if_wrong_ip_in_cidr_network() {
+        let ops = rule::Operator::IpInCidr {
+            target: "${event.payload.source}".to_owned(),
+            networks: vec!["10.0.0.0/64".to_owned()],
+        };
+
+        let builder = OperatorBuilder::new();
+        assert!(builder.build_option("", &Some(ops)).is_err());
+    }
+
+    #[test]
+    fn build_should_parse_the_expression_operator() {
+        // Arrange
+        let ops = rule::Operator::Expression {
+            expression:
+                r#"event.type == "syslog" && (payload.severity <= 3 || payload.msg =~ /panic/)"#
+                    .to_owned(),
+        };
+        let builder = OperatorBuilder::new();
+
+        // Act
+        let operator = builder.build_option("", &Some(ops)).unwrap();
+
+        // Assert
+        assert_eq!("and", operator.name());
+        let evaluate = |event: Value| operator.evaluate(&(&event, &mut Value::Null).into());
+        assert!(evaluate(serde_json::json!({ "type": "syslog", "payload": { "severity": 2 } })));
+        assert!(evaluate(
+            serde_json::json!({ "type": "syslog", "payload": { "severity": 5, "msg": "kernel panic" } })
+        ));
+        assert!(!evaluate(serde_json::json!({ "type": "syslog", "payload": { "severity": 5 } })));
+        assert!(!evaluate(serde_json::json!({ "type": "email", "payload": { "severity": 2 } })));
+    }
+
+    #[test]
+    fn build_should_fail_if_wrong_expression() {
+        let ops = rule::Operator::Expression { expression: "event.type = 1".to_owned() };
+
+        let builder = OperatorBuilder::new();
+        match builder.build_option("", &Some(ops)) {
+            Err(MatcherError::ExpressionSyntaxError { line, column, .. }) => {
+                assert_eq!((1, 12), (line, column))
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn build_should_return_the_and_operator() {
         let ops = rule::Operator::And {
@@ -1,7 +1,7 @@
 use crate::config;
 use crate::error::MatcherError;
 use crate::matcher::operator::{Operator, OperatorBuilder};
-use crate::model::InternalEvent;
+use crate::model::{InternalEvent, OperatorTrace};
 
 const OPERATOR_NAME: &str = "not";
 
@@ -30,6 +30,16 @@ impl Operator for Not {
     fn evaluate(&self, event: &InternalEvent) -> bool {
         !self.operator.evaluate(event)
     }
+
+    fn explain(&self, event: &InternalEvent) -> OperatorTrace {
+        let trace = self.operator.explain(event);
+        OperatorTrace {
+            operator: self.name().to_owned(),
+            result: !trace.result,
+            values: vec![],
+            operators: vec![trace],
+        }
+    }
 }
 
 #[cfg(test)]
@@ -199,4 +209,25 @@ mod test {
 
         assert!(operator.evaluate(&(&json!(event), &mut Value::Null).into()));
     }
+
+    #[test]
+    fn explain_should_return_the_negated_trace_of_the_child() {
+        let operator = Not::build(
+            "",
+            &config::rule::Operator::Exists { target: "${event.payload.host}".to_owned() },
+            &OperatorBuilder::new(),
+        )
+        .unwrap();
+
+        let event = json!(Event::new("test_type"));
+        let trace = operator.explain(&(&event, &mut Value::Null).into());
+
+        assert_eq!("not", trace.operator);
+        assert!(trace.result);
+        assert_eq!(1, trace.operators.len());
+        assert_eq!("exists", trace.operators[0].operator);
+        assert!(!trace.operators[0].result);
+        assert_eq!("target", trace.operators[0].values[0].argument);
+        assert_eq!(None, trace.operators[0].values[0].value);
+    }
 }
@@ -3,6 +3,7 @@ use crate::matcher::operator::Operator;
 use crate::{accessor::Accessor, model::InternalEvent};
 use regex::Regex as RustRegex;
 use tornado_common_api::cow_to_str;
+use tornado_common_grok::GrokPatterns;
 
 const OPERATOR_NAME: &str = "regex";
 
@@ -22,6 +23,21 @@ impl Regex {
 
         Ok(Regex { target, regex })
     }
+
+    /// Builds the operator with the regex obtained by expanding a grok expression with the
+    /// given patterns.
+    pub fn build_grok(
+        expression: &str,
+        target: Accessor,
+        grok_patterns: &GrokPatterns,
+    ) -> Result<Regex, MatcherError> {
+        let regex =
+            grok_patterns.expand(expression).map_err(|e| MatcherError::OperatorBuildFailError {
+                message: format!("Cannot expand grok expression [{}]", expression),
+                cause: e.to_string(),
+            })?;
+        Regex::build(&regex, target)
+    }
 }
 
 impl Operator for Regex {
@@ -33,6 +49,10 @@ impl Operator for Regex {
         let cow_value = self.target.get(event);
         cow_to_str(&cow_value).map_or(false, |text| self.regex.is_match(text))
     }
+
+    fn accessors(&self) -> Vec<(String, &Accessor)> {
+        vec![("target".to_owned(), &self.target)]
+    }
 }
 
 #[cfg(test)]
@@ -66,6 +86,19 @@ mod test {
         );
     }
 
+    #[test]
+    fn should_build_the_operator_with_grok_expression() {
+        let patterns = GrokPatterns::default();
+        let operator =
+            Regex::build_grok("%{INT}", AccessorBuilder::new().build("", "").unwrap(), &patterns)
+                .unwrap();
+        assert_eq!("(?:[+-]?[0-9]+)", operator.regex.to_string());
+
+        let operator =
+            Regex::build_grok("%{INT", AccessorBuilder::new().build("", "").unwrap(), &patterns);
+        assert!(operator.is_err());
+    }
+
     #[test]
     fn build_should_fail_if_invalid_regex() {
         let operator = Regex::build("[", AccessorBuilder::new().build("", "two").unwrap());
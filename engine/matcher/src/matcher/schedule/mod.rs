@@ -0,0 +1,249 @@
+//! The schedule module contains the logic to decide whether a Filter or a Rule
+//! is active at the creation time of an event.
+
+use crate::config::rule::{Schedule, ScheduleWindow};
+use crate::error::MatcherError;
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use std::str::FromStr;
+
+const RANGE_DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// The Matcher's internal representation of a config::rule::Schedule.
+#[derive(Debug)]
+pub struct MatcherSchedule {
+    timezone: Tz,
+    active_windows: Vec<MatcherScheduleWindow>,
+    inactive_windows: Vec<MatcherScheduleWindow>,
+}
+
+#[derive(Debug)]
+enum MatcherScheduleWindow {
+    Recurring { cron: Box<cron::Schedule>, duration: Duration },
+    Range { from: NaiveDateTime, to: NaiveDateTime },
+}
+
+impl MatcherScheduleWindow {
+    fn build(node_name: &str, window: &ScheduleWindow) -> Result<Self, MatcherError> {
+        match window {
+            ScheduleWindow::Recurring { cron, duration_seconds } => {
+                let schedule = cron::Schedule::from_str(cron).map_err(|e| {
+                    MatcherError::ConfigurationError {
+                        message: format!(
+                            "The schedule of [{}] contains the invalid cron expression [{}]: {}",
+                            node_name, cron, e
+                        ),
+                    }
+                })?;
+                if *duration_seconds == 0 {
+                    return Err(MatcherError::ConfigurationError {
+                        message: format!(
+                            "The recurring windows in the schedule of [{}] must have a duration_seconds greater than zero",
+                            node_name
+                        ),
+                    });
+                }
+                Ok(MatcherScheduleWindow::Recurring {
+                    cron: Box::new(schedule),
+                    duration: Duration::seconds(*duration_seconds as i64),
+                })
+            }
+            ScheduleWindow::Range { from, to } => {
+                let parse = |date_time: &str| {
+                    NaiveDateTime::parse_from_str(date_time, RANGE_DATE_TIME_FORMAT).map_err(|e| {
+                        MatcherError::ConfigurationError {
+                            message: format!(
+                                "The schedule of [{}] contains the invalid date-time [{}]. Expected format [{}]: {}",
+                                node_name, date_time, RANGE_DATE_TIME_FORMAT, e
+                            ),
+                        }
+                    })
+                };
+                let from = parse(from)?;
+                let to = parse(to)?;
+                if from >= to {
+                    return Err(MatcherError::ConfigurationError {
+                        message: format!(
+                            "The schedule of [{}] contains a range whose start is not before its end",
+                            node_name
+                        ),
+                    });
+                }
+                Ok(MatcherScheduleWindow::Range { from, to })
+            }
+        }
+    }
+
+    fn contains(&self, date_time: &DateTime<Tz>) -> bool {
+        match self {
+            MatcherScheduleWindow::Recurring { cron, duration } => {
+                // The window contains the date-time if it opened within the last `duration`
+                cron.after(&(*date_time - *duration))
+                    .next()
+                    .map(|opening| opening <= *date_time)
+                    .unwrap_or(false)
+            }
+            MatcherScheduleWindow::Range { from, to } => {
+                let local = date_time.naive_local();
+                *from <= local && local < *to
+            }
+        }
+    }
+}
+
+impl MatcherSchedule {
+    pub fn build(node_name: &str, schedule: &Schedule) -> Result<MatcherSchedule, MatcherError> {
+        let timezone = match &schedule.timezone {
+            Some(timezone) => timezone.trim().parse().map_err(|_| MatcherError::ConfigurationError {
+                message: format!(
+                    "The schedule of [{}] has an unknown timezone ({}). Expected timezone from the IANA timezone database.",
+                    node_name, timezone
+                ),
+            })?,
+            None => Tz::UTC,
+        };
+
+        let build_all = |windows: &[ScheduleWindow]| {
+            windows
+                .iter()
+                .map(|window| MatcherScheduleWindow::build(node_name, window))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(MatcherSchedule {
+            timezone,
+            active_windows: build_all(&schedule.active_windows)?,
+            inactive_windows: build_all(&schedule.inactive_windows)?,
+        })
+    }
+
+    /// Returns whether the schedule allows the node to be active at the given epoch milliseconds.
+    pub fn is_active_at(&self, timestamp_ms: u64) -> bool {
+        let date_time = match self.timezone.timestamp_millis_opt(timestamp_ms as i64).single() {
+            Some(date_time) => date_time,
+            None => return false,
+        };
+
+        let in_active_window = self.active_windows.is_empty()
+            || self.active_windows.iter().any(|window| window.contains(&date_time));
+
+        in_active_window && !self.inactive_windows.iter().any(|window| window.contains(&date_time))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn timestamp_ms(timezone: Tz, date_time: &str) -> u64 {
+        let naive = NaiveDateTime::parse_from_str(date_time, RANGE_DATE_TIME_FORMAT).unwrap();
+        timezone.from_local_datetime(&naive).unwrap().timestamp_millis() as u64
+    }
+
+    fn recurring(cron: &str, duration_seconds: u64) -> ScheduleWindow {
+        ScheduleWindow::Recurring { cron: cron.to_owned(), duration_seconds }
+    }
+
+    fn range(from: &str, to: &str) -> ScheduleWindow {
+        ScheduleWindow::Range { from: from.to_owned(), to: to.to_owned() }
+    }
+
+    #[test]
+    fn should_be_always_active_without_windows() {
+        // Arrange
+        let schedule = MatcherSchedule::build("node", &Schedule::default()).unwrap();
+
+        // Act & Assert
+        assert!(schedule.is_active_at(0));
+        assert!(schedule.is_active_at(timestamp_ms(Tz::UTC, "2024-03-01T02:00:00")));
+    }
+
+    #[test]
+    fn should_be_inactive_during_a_recurring_maintenance_window() {
+        // Arrange
+        let schedule = Schedule {
+            timezone: Some("Europe/Rome".to_owned()),
+            active_windows: vec![],
+            // Every Sunday from 02:00 to 04:00
+            inactive_windows: vec![recurring("0 0 2 * * Sun", 2 * 60 * 60)],
+        };
+        let schedule = MatcherSchedule::build("node", &schedule).unwrap();
+        let at = |date_time: &str| schedule.is_active_at(timestamp_ms(Tz::Europe__Rome, date_time));
+
+        // Act & Assert
+        // 2024-03-03 is a Sunday
+        assert!(at("2024-03-03T01:59:59"));
+        assert!(!at("2024-03-03T02:00:00"));
+        assert!(!at("2024-03-03T03:59:59"));
+        assert!(at("2024-03-03T04:00:00"));
+        assert!(at("2024-03-04T02:30:00"));
+    }
+
+    #[test]
+    fn should_evaluate_the_windows_in_the_schedule_timezone() {
+        // Arrange
+        let schedule = Schedule {
+            timezone: Some("America/New_York".to_owned()),
+            // Business hours, from Monday to Friday
+            active_windows: vec![recurring("0 0 9 * * Mon-Fri", 8 * 60 * 60)],
+            inactive_windows: vec![],
+        };
+        let schedule = MatcherSchedule::build("node", &schedule).unwrap();
+
+        // Act & Assert
+        // 2024-03-01 is a Friday
+        assert!(schedule.is_active_at(timestamp_ms(Tz::America__New_York, "2024-03-01T10:00:00")));
+        assert!(!schedule.is_active_at(timestamp_ms(Tz::UTC, "2024-03-01T10:00:00")));
+        assert!(!schedule.is_active_at(timestamp_ms(Tz::America__New_York, "2024-03-02T10:00:00")));
+    }
+
+    #[test]
+    fn should_combine_active_and_inactive_windows() {
+        // Arrange
+        let schedule = Schedule {
+            timezone: None,
+            active_windows: vec![range("2024-03-01T00:00:00", "2024-04-01T00:00:00")],
+            inactive_windows: vec![range("2024-03-10T00:00:00", "2024-03-11T00:00:00")],
+        };
+        let schedule = MatcherSchedule::build("node", &schedule).unwrap();
+        let at = |date_time: &str| schedule.is_active_at(timestamp_ms(Tz::UTC, date_time));
+
+        // Act & Assert
+        assert!(!at("2024-02-29T23:59:59"));
+        assert!(at("2024-03-01T00:00:00"));
+        assert!(!at("2024-03-10T12:00:00"));
+        assert!(at("2024-03-11T00:00:00"));
+        assert!(!at("2024-04-01T00:00:00"));
+    }
+
+    #[test]
+    fn should_fail_with_invalid_windows() {
+        let build = |window: ScheduleWindow| {
+            MatcherSchedule::build(
+                "node",
+                &Schedule {
+                    timezone: None,
+                    active_windows: vec![window],
+                    inactive_windows: vec![],
+                },
+            )
+        };
+
+        assert!(build(recurring("not a cron", 60)).is_err());
+        assert!(build(recurring("0 0 2 * * Sun", 0)).is_err());
+        assert!(build(range("2024-03-01", "2024-04-01")).is_err());
+        assert!(build(range("2024-04-01T00:00:00", "2024-03-01T00:00:00")).is_err());
+    }
+
+    #[test]
+    fn should_fail_with_unknown_timezone() {
+        // Arrange
+        let schedule = Schedule { timezone: Some("Mars/Olympus".to_owned()), ..Default::default() };
+
+        // Act
+        let result = MatcherSchedule::build("node", &schedule);
+
+        // Assert
+        assert!(result.is_err());
+    }
+}
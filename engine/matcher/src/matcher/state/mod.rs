@@ -0,0 +1,80 @@
+//! The state module contains the in-memory state kept by the stateful Rules and nodes of a Matcher.
+
+use crate::config::MatcherConfig;
+use crate::matcher::correlation::CorrelationStore;
+use crate::matcher::heartbeat::HeartbeatStore;
+use crate::matcher::suppression::SuppressionStore;
+use crate::matcher::threshold::ThresholdStore;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// The state of the stateful Rules and of the Correlation and Heartbeat nodes of a processing tree.
+/// Cloning a MatcherState returns a new handle to the same state; consequently, the same
+/// state can be shared by Matcher instances built from the same configuration to preserve
+/// it when the Matcher is rebuilt.
+#[derive(Debug, Default, Clone)]
+pub struct MatcherState {
+    pub thresholds: Arc<ThresholdStore>,
+    pub suppressions: Arc<SuppressionStore>,
+    pub correlations: Arc<CorrelationStore>,
+    pub heartbeats: Arc<HeartbeatStore>,
+}
+
+impl MatcherState {
+    pub fn new() -> MatcherState {
+        MatcherState::default()
+    }
+
+    /// Returns a detached copy of the current state.
+    /// Events recorded in the copy are not visible in the original state.
+    pub fn snapshot(&self) -> MatcherState {
+        MatcherState {
+            thresholds: Arc::new(self.thresholds.snapshot()),
+            suppressions: Arc::new(self.suppressions.snapshot()),
+            correlations: Arc::new(self.correlations.snapshot()),
+            heartbeats: Arc::new(self.heartbeats.snapshot()),
+        }
+    }
+
+    /// Removes the state of the stateful Rules and nodes that are not in the configuration,
+    /// so that a node created again with the same path does not inherit a stale state.
+    pub fn retain(&self, config: &MatcherConfig) {
+        let mut paths = HashSet::new();
+        collect_paths(config, "", &mut paths);
+        self.thresholds.retain(&paths);
+        self.suppressions.retain(&paths);
+        self.correlations.retain(&paths);
+        self.heartbeats.retain(&paths);
+    }
+
+    /// Removes the state of all the stateful Rules and nodes.
+    pub fn clear(&self) {
+        let paths = HashSet::new();
+        self.thresholds.retain(&paths);
+        self.suppressions.retain(&paths);
+        self.correlations.retain(&paths);
+        self.heartbeats.retain(&paths);
+    }
+}
+
+/// Collects the paths of all the nodes and Rules of the processing tree, in the same format
+/// used to identify their state.
+fn collect_paths(config: &MatcherConfig, parent_path: &str, paths: &mut HashSet<String>) {
+    let path = if parent_path.is_empty() {
+        config.get_name().to_owned()
+    } else {
+        format!("{}.{}", parent_path, config.get_name())
+    };
+    match config {
+        MatcherConfig::Filter { nodes, .. }
+        | MatcherConfig::Iterator { nodes, .. }
+        | MatcherConfig::Enricher { nodes, .. } => {
+            nodes.iter().for_each(|node| collect_paths(node, &path, paths))
+        }
+        MatcherConfig::Ruleset { rules, .. } => {
+            paths.extend(rules.iter().map(|rule| format!("{}.{}", path, rule.name)))
+        }
+        MatcherConfig::Correlation { .. } | MatcherConfig::Heartbeat { .. } => {}
+    }
+    paths.insert(path);
+}
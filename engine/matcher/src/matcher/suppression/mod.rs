@@ -0,0 +1,291 @@
+//! The suppression module contains the logic to detect the duplicated events of a Rule
+//! in a time window.
+
+use crate::accessor::{Accessor, AccessorBuilder};
+use crate::config::rule::Suppression;
+use crate::error::MatcherError;
+use crate::model::{InternalEvent, SuppressionMetaData};
+use log::*;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Keeps the fingerprints of the events recently matched by the Rules with a suppression.
+#[derive(Debug, Default)]
+pub struct SuppressionStore {
+    windows: Mutex<HashMap<String, RuleFingerprints>>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct RuleFingerprints {
+    last_purge_ms: u64,
+    fingerprints: HashMap<String, FingerprintEntry>,
+}
+
+#[derive(Debug, Clone)]
+struct FingerprintEntry {
+    first_seen_ms: u64,
+    duplicates: usize,
+}
+
+impl SuppressionStore {
+    pub fn new() -> SuppressionStore {
+        SuppressionStore::default()
+    }
+
+    /// Returns a detached copy of the current fingerprints.
+    /// Events recorded in the copy are not visible in the original store.
+    pub fn snapshot(&self) -> SuppressionStore {
+        let windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        SuppressionStore { windows: Mutex::new(windows) }
+    }
+
+    /// Removes the fingerprints of the Rules whose path is not in `paths`
+    pub fn retain(&self, paths: &HashSet<String>) {
+        self.windows
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|path, _| paths.contains(path));
+    }
+
+    /// Records a new event with the given fingerprint and returns the number of duplicates
+    /// received since the fingerprint was first seen. Zero means the event is not a duplicate.
+    fn record(&self, rule_key: &str, fingerprint: &str, ttl_ms: u64, now_ms: u64) -> usize {
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+        let rule_fingerprints = windows.entry(rule_key.to_owned()).or_default();
+        let window_start = now_ms.saturating_sub(ttl_ms);
+
+        // Drop the expired fingerprints once per window to keep the memory bounded
+        if now_ms.saturating_sub(rule_fingerprints.last_purge_ms) >= ttl_ms {
+            rule_fingerprints.fingerprints.retain(|_, entry| entry.first_seen_ms > window_start);
+            rule_fingerprints.last_purge_ms = now_ms;
+        }
+
+        match rule_fingerprints.fingerprints.get_mut(fingerprint) {
+            Some(entry) if entry.first_seen_ms > window_start => {
+                entry.duplicates += 1;
+                entry.duplicates
+            }
+            _ => {
+                rule_fingerprints.fingerprints.insert(
+                    fingerprint.to_owned(),
+                    FingerprintEntry { first_seen_ms: now_ms, duplicates: 0 },
+                );
+                0
+            }
+        }
+    }
+
+    /// Removes a fingerprint if it was first seen at `first_seen_ms`, so that the next event
+    /// with the same fingerprint is not a duplicate.
+    fn remove(&self, rule_key: &str, fingerprint: &str, first_seen_ms: u64) {
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(rule_fingerprints) = windows.get_mut(rule_key) {
+            if rule_fingerprints
+                .fingerprints
+                .get(fingerprint)
+                .is_some_and(|entry| entry.first_seen_ms == first_seen_ms)
+            {
+                rule_fingerprints.fingerprints.remove(fingerprint);
+            }
+        }
+    }
+}
+
+/// The Matcher's internal representation of a config::rule::Suppression.
+#[derive(Debug)]
+pub struct MatcherSuppression {
+    rule_key: String,
+    fingerprint: Vec<Accessor>,
+    ttl_seconds: u64,
+    store: Arc<SuppressionStore>,
+}
+
+impl MatcherSuppression {
+    pub fn build(
+        rule_name: &str,
+        rule_key: String,
+        suppression: &Suppression,
+        store: Arc<SuppressionStore>,
+    ) -> Result<MatcherSuppression, MatcherError> {
+        if suppression.fingerprint.is_empty() || suppression.ttl_seconds == 0 {
+            return Err(MatcherError::ConfigurationError {
+                message: format!(
+                    "The suppression of rule [{}] must have at least one fingerprint accessor and a ttl_seconds greater than zero",
+                    rule_name
+                ),
+            });
+        }
+
+        let accessor_builder = AccessorBuilder::new();
+        let fingerprint = suppression
+            .fingerprint
+            .iter()
+            .map(|accessor| accessor_builder.build(rule_name, accessor))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(MatcherSuppression {
+            rule_key,
+            fingerprint,
+            ttl_seconds: suppression.ttl_seconds,
+            store,
+        })
+    }
+
+    /// Records the fingerprint of the event and returns whether the event is a duplicate.
+    pub fn record(
+        &self,
+        event: &InternalEvent,
+        now_ms: u64,
+    ) -> Result<SuppressionMetaData, MatcherError> {
+        let fingerprint = self.fingerprint(event)?;
+        let duplicates =
+            self.store.record(&self.rule_key, &fingerprint, self.ttl_seconds * 1000, now_ms);
+        trace!(
+            "MatcherSuppression - rule [{}] fingerprint [{}] has [{}] duplicates",
+            self.rule_key,
+            fingerprint,
+            duplicates
+        );
+
+        Ok(SuppressionMetaData { fingerprint, duplicates, ttl_seconds: self.ttl_seconds })
+    }
+
+    /// Forgets the fingerprint of an event recorded at `recorded_ms` that was not a duplicate.
+    /// It is called when the actions of the Rule cannot be resolved, so that the next copies
+    /// of the event are not suppressed although nothing was dispatched.
+    pub fn release(&self, event: &InternalEvent, recorded_ms: u64) -> Result<(), MatcherError> {
+        let fingerprint = self.fingerprint(event)?;
+        self.store.remove(&self.rule_key, &fingerprint, recorded_ms);
+        Ok(())
+    }
+
+    fn fingerprint(&self, event: &InternalEvent) -> Result<String, MatcherError> {
+        let values = self
+            .fingerprint
+            .iter()
+            .map(|accessor| accessor.get(event).map(|value| value.into_owned()))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| MatcherError::UnresolvedKeyError {
+                key: "suppression fingerprint",
+                rule_name: self.rule_key.to_owned(),
+            })?;
+        Ok(Value::Array(values).to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    fn new_suppression(store: Arc<SuppressionStore>) -> MatcherSuppression {
+        let suppression = Suppression {
+            fingerprint: vec!["${event.type}".to_owned(), "${event.payload.host}".to_owned()],
+            ttl_seconds: 10,
+        };
+        MatcherSuppression::build("rule", "rule".to_owned(), &suppression, store).unwrap()
+    }
+
+    #[test]
+    fn should_fail_if_fingerprint_is_empty() {
+        // Arrange
+        let suppression = Suppression { fingerprint: vec![], ttl_seconds: 10 };
+
+        // Act
+        let result =
+            MatcherSuppression::build("rule", "rule".to_owned(), &suppression, Default::default());
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_mark_duplicates_within_the_ttl() {
+        // Arrange
+        let suppression = new_suppression(Default::default());
+        let event = json!({"type": "alarm", "created_ms": 0, "payload": {"host": "host_1"}});
+        let mut extracted_vars = Value::Null;
+
+        // Act
+        let first = suppression.record(&(&event, &mut extracted_vars).into(), 1_000).unwrap();
+        let second = suppression.record(&(&event, &mut extracted_vars).into(), 2_000).unwrap();
+        let third = suppression.record(&(&event, &mut extracted_vars).into(), 3_000).unwrap();
+
+        // Assert
+        assert!(!first.is_duplicate());
+        assert_eq!(r#"["alarm","host_1"]"#, first.fingerprint);
+        assert!(second.is_duplicate());
+        assert_eq!(1, second.duplicates);
+        assert_eq!(2, third.duplicates);
+    }
+
+    #[test]
+    fn should_not_mark_duplicates_after_the_fingerprint_is_released() {
+        // Arrange
+        let suppression = new_suppression(Default::default());
+        let event = json!({"type": "alarm", "created_ms": 0, "payload": {"host": "host_1"}});
+        let mut extracted_vars = Value::Null;
+        let _ = suppression.record(&(&event, &mut extracted_vars).into(), 1_000).unwrap();
+
+        // Act
+        let stale_release = suppression.release(&(&event, &mut extracted_vars).into(), 500);
+        let duplicate = suppression.record(&(&event, &mut extracted_vars).into(), 2_000).unwrap();
+        suppression.release(&(&event, &mut extracted_vars).into(), 1_000).unwrap();
+        let released = suppression.record(&(&event, &mut extracted_vars).into(), 3_000).unwrap();
+
+        // Assert
+        assert!(stale_release.is_ok());
+        assert!(duplicate.is_duplicate());
+        assert!(!released.is_duplicate());
+    }
+
+    #[test]
+    fn should_not_mark_duplicates_after_the_ttl() {
+        // Arrange
+        let suppression = new_suppression(Default::default());
+        let event = json!({"type": "alarm", "created_ms": 0, "payload": {"host": "host_1"}});
+        let mut extracted_vars = Value::Null;
+
+        // Act
+        let first = suppression.record(&(&event, &mut extracted_vars).into(), 1_000).unwrap();
+        let second = suppression.record(&(&event, &mut extracted_vars).into(), 11_000).unwrap();
+        let third = suppression.record(&(&event, &mut extracted_vars).into(), 12_000).unwrap();
+
+        // Assert
+        assert!(!first.is_duplicate());
+        assert!(!second.is_duplicate());
+        assert!(third.is_duplicate());
+    }
+
+    #[test]
+    fn should_distinguish_fingerprints() {
+        // Arrange
+        let suppression = new_suppression(Default::default());
+        let event_1 = json!({"type": "alarm", "created_ms": 0, "payload": {"host": "host_1"}});
+        let event_2 = json!({"type": "alarm", "created_ms": 0, "payload": {"host": "host_2"}});
+        let mut extracted_vars = Value::Null;
+
+        // Act
+        let first = suppression.record(&(&event_1, &mut extracted_vars).into(), 1_000).unwrap();
+        let second = suppression.record(&(&event_2, &mut extracted_vars).into(), 2_000).unwrap();
+
+        // Assert
+        assert!(!first.is_duplicate());
+        assert!(!second.is_duplicate());
+    }
+
+    #[test]
+    fn should_fail_if_the_fingerprint_cannot_be_resolved() {
+        // Arrange
+        let suppression = new_suppression(Default::default());
+        let event = json!({"type": "alarm", "created_ms": 0, "payload": {}});
+        let mut extracted_vars = Value::Null;
+
+        // Act
+        let result = suppression.record(&(&event, &mut extracted_vars).into(), 1_000);
+
+        // Assert
+        assert!(matches!(result, Err(MatcherError::UnresolvedKeyError { .. })));
+    }
+}
@@ -0,0 +1,53 @@
+//! The thread_pool module contains the pool used by a Matcher to process in parallel the
+//! independent nodes of the processing tree.
+
+use crate::error::MatcherError;
+use std::sync::Arc;
+
+/// A work-stealing thread pool used to process in parallel the child nodes of a Filter and the
+/// iterations of an Iterator.
+/// Cloning a MatcherThreadPool returns a new handle to the same pool; consequently, the same
+/// threads can be shared by all the Matcher instances of an application.
+#[derive(Debug, Clone)]
+pub struct MatcherThreadPool {
+    pool: Arc<rayon::ThreadPool>,
+}
+
+impl MatcherThreadPool {
+    pub fn build(threads: usize) -> Result<MatcherThreadPool, MatcherError> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|index| format!("tornado-matcher-{}", index))
+            .build()
+            .map_err(|e| MatcherError::ConfigurationError {
+                message: format!("Cannot build the matcher thread pool: {}", e),
+            })?;
+        Ok(MatcherThreadPool { pool: Arc::new(pool) })
+    }
+
+    /// Returns the number of threads of the pool.
+    pub fn threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// Executes the operation within the pool. The parallel iterators used by the operation
+    /// are executed by the threads of the pool.
+    pub(crate) fn install<R: Send, F: FnOnce() -> R + Send>(&self, operation: F) -> R {
+        self.pool.install(operation)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_build_a_pool_with_the_requested_threads() {
+        // Act
+        let thread_pool = MatcherThreadPool::build(3).unwrap();
+
+        // Assert
+        assert_eq!(3, thread_pool.threads());
+        assert_eq!(3, thread_pool.clone().threads());
+    }
+}
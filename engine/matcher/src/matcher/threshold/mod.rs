@@ -0,0 +1,319 @@
+//! The threshold module contains the logic to count the events matched by a Rule
+//! in a sliding time window.
+
+use crate::accessor::{Accessor, AccessorBuilder};
+use crate::config::rule::Threshold;
+use crate::error::MatcherError;
+use crate::model::{InternalEvent, ThresholdMetaData};
+use log::*;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Keeps the sliding windows of all the threshold Rules of a processing tree.
+#[derive(Debug, Default)]
+pub struct ThresholdStore {
+    windows: Mutex<HashMap<String, RuleWindows>>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct RuleWindows {
+    last_purge_ms: u64,
+    groups: HashMap<String, VecDeque<u64>>,
+}
+
+impl ThresholdStore {
+    pub fn new() -> ThresholdStore {
+        ThresholdStore::default()
+    }
+
+    /// Returns a detached copy of the current windows.
+    /// Events recorded in the copy are not visible in the original store.
+    pub fn snapshot(&self) -> ThresholdStore {
+        let windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        ThresholdStore { windows: Mutex::new(windows) }
+    }
+
+    /// Removes the windows of the Rules whose path is not in `paths`
+    pub fn retain(&self, paths: &HashSet<String>) {
+        self.windows
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|path, _| paths.contains(path));
+    }
+
+    /// Records a new event in the window of the group and returns the number of events
+    /// in the window.
+    fn record(&self, rule_key: &str, group: &str, window_ms: u64, now_ms: u64) -> usize {
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+        let rule_windows = windows.entry(rule_key.to_owned()).or_default();
+        let window_start = now_ms.saturating_sub(window_ms);
+
+        // Drop the expired groups once per window to keep the memory bounded
+        if now_ms.saturating_sub(rule_windows.last_purge_ms) >= window_ms {
+            rule_windows.groups.retain(|_, timestamps| {
+                timestamps.back().map(|last| *last > window_start).unwrap_or(false)
+            });
+            rule_windows.last_purge_ms = now_ms;
+        }
+
+        let timestamps = rule_windows.groups.entry(group.to_owned()).or_default();
+        while timestamps.front().map(|first| *first <= window_start).unwrap_or(false) {
+            timestamps.pop_front();
+        }
+        timestamps.push_back(now_ms);
+        timestamps.len()
+    }
+
+    /// Removes all the events from the window of the group
+    fn reset(&self, rule_key: &str, group: &str) {
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(rule_windows) = windows.get_mut(rule_key) {
+            rule_windows.groups.remove(group);
+        }
+    }
+}
+
+/// The Matcher's internal representation of a config::rule::Threshold.
+#[derive(Debug)]
+pub struct MatcherThreshold {
+    rule_key: String,
+    count: usize,
+    window_seconds: u64,
+    group_by: Option<Accessor>,
+    store: Arc<ThresholdStore>,
+}
+
+impl MatcherThreshold {
+    pub fn build(
+        rule_name: &str,
+        rule_key: String,
+        threshold: &Threshold,
+        store: Arc<ThresholdStore>,
+    ) -> Result<MatcherThreshold, MatcherError> {
+        if threshold.count == 0 || threshold.window_seconds == 0 {
+            return Err(MatcherError::ConfigurationError {
+                message: format!(
+                    "The threshold of rule [{}] must have a count and a window_seconds greater than zero",
+                    rule_name
+                ),
+            });
+        }
+
+        let group_by = match &threshold.group_by {
+            Some(group_by) => Some(AccessorBuilder::new().build(rule_name, group_by)?),
+            None => None,
+        };
+
+        Ok(MatcherThreshold {
+            rule_key,
+            count: threshold.count,
+            window_seconds: threshold.window_seconds,
+            group_by,
+            store,
+        })
+    }
+
+    /// Records the event in the sliding window of its group and returns the current state
+    /// of the window. The window is not reset when the threshold is reached; it must be reset
+    /// with `reset` once the actions of the Rule are produced.
+    pub fn record(
+        &self,
+        event: &InternalEvent,
+        now_ms: u64,
+    ) -> Result<ThresholdMetaData, MatcherError> {
+        let group = self.group(event)?;
+        let count = self.store.record(&self.rule_key, &group, self.window_seconds * 1000, now_ms);
+        trace!(
+            "MatcherThreshold - rule [{}] group [{}] has [{}] events in the window",
+            self.rule_key,
+            group,
+            count
+        );
+
+        Ok(ThresholdMetaData {
+            group,
+            count,
+            threshold: self.count,
+            window_seconds: self.window_seconds,
+        })
+    }
+
+    /// Resets the window of the group of the event
+    pub fn reset(&self, event: &InternalEvent) -> Result<(), MatcherError> {
+        let group = self.group(event)?;
+        self.store.reset(&self.rule_key, &group);
+        Ok(())
+    }
+
+    fn group(&self, event: &InternalEvent) -> Result<String, MatcherError> {
+        match &self.group_by {
+            Some(accessor) => match accessor.get(event) {
+                Some(value) => Ok(match value.as_ref() {
+                    Value::String(text) => text.to_owned(),
+                    value => value.to_string(),
+                }),
+                None => Err(MatcherError::UnresolvedKeyError {
+                    key: "threshold group_by",
+                    rule_name: self.rule_key.to_owned(),
+                }),
+            },
+            None => Ok("".to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use tornado_common_api::Event;
+
+    #[test]
+    fn should_fail_if_count_is_zero() {
+        // Arrange
+        let threshold = Threshold { count: 0, window_seconds: 60, group_by: None };
+
+        // Act
+        let result =
+            MatcherThreshold::build("rule", "rule".to_owned(), &threshold, Default::default());
+
+        // Assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_reach_the_threshold_within_the_window() {
+        // Arrange
+        let threshold = Threshold { count: 3, window_seconds: 10, group_by: None };
+        let matcher_threshold =
+            MatcherThreshold::build("rule", "rule".to_owned(), &threshold, Default::default())
+                .unwrap();
+        let event = json!(Event::new("test"));
+        let mut extracted_vars = Value::Null;
+        let internal_event: InternalEvent = (&event, &mut extracted_vars).into();
+
+        // Act
+        let first = matcher_threshold.record(&internal_event, 1_000).unwrap();
+        let second = matcher_threshold.record(&internal_event, 2_000).unwrap();
+        let third = matcher_threshold.record(&internal_event, 3_000).unwrap();
+        let fourth = matcher_threshold.record(&internal_event, 4_000).unwrap();
+        matcher_threshold.reset(&internal_event).unwrap();
+        let fifth = matcher_threshold.record(&internal_event, 5_000).unwrap();
+
+        // Assert
+        assert_eq!(1, first.count);
+        assert!(!first.is_reached());
+        assert_eq!(2, second.count);
+        assert_eq!(3, third.count);
+        assert!(third.is_reached());
+        // The window is reset only explicitly
+        assert_eq!(4, fourth.count);
+        assert_eq!(1, fifth.count);
+    }
+
+    #[test]
+    fn should_discard_events_outside_the_window() {
+        // Arrange
+        let threshold = Threshold { count: 2, window_seconds: 10, group_by: None };
+        let matcher_threshold =
+            MatcherThreshold::build("rule", "rule".to_owned(), &threshold, Default::default())
+                .unwrap();
+        let event = json!(Event::new("test"));
+        let mut extracted_vars = Value::Null;
+        let internal_event: InternalEvent = (&event, &mut extracted_vars).into();
+
+        // Act
+        let first = matcher_threshold.record(&internal_event, 1_000).unwrap();
+        let second = matcher_threshold.record(&internal_event, 11_000).unwrap();
+        let third = matcher_threshold.record(&internal_event, 12_000).unwrap();
+
+        // Assert
+        assert_eq!(1, first.count);
+        assert_eq!(1, second.count);
+        assert_eq!(2, third.count);
+        assert!(third.is_reached());
+    }
+
+    #[test]
+    fn should_count_events_by_group() {
+        // Arrange
+        let threshold = Threshold {
+            count: 2,
+            window_seconds: 60,
+            group_by: Some("${event.payload.host}".to_owned()),
+        };
+        let matcher_threshold =
+            MatcherThreshold::build("rule", "rule".to_owned(), &threshold, Default::default())
+                .unwrap();
+
+        let event_1 = json!({"type": "test", "created_ms": 0, "payload": {"host": "host_1"}});
+        let event_2 = json!({"type": "test", "created_ms": 0, "payload": {"host": "host_2"}});
+        let mut extracted_vars = Value::Null;
+
+        // Act
+        let first =
+            matcher_threshold.record(&(&event_1, &mut extracted_vars).into(), 1_000).unwrap();
+        let second =
+            matcher_threshold.record(&(&event_2, &mut extracted_vars).into(), 2_000).unwrap();
+        let third =
+            matcher_threshold.record(&(&event_1, &mut extracted_vars).into(), 3_000).unwrap();
+
+        // Assert
+        assert_eq!("host_1", first.group);
+        assert_eq!(1, first.count);
+        assert_eq!("host_2", second.group);
+        assert_eq!(1, second.count);
+        assert_eq!(2, third.count);
+        assert!(third.is_reached());
+    }
+
+    #[test]
+    fn should_fail_if_the_group_cannot_be_resolved() {
+        // Arrange
+        let threshold = Threshold {
+            count: 2,
+            window_seconds: 60,
+            group_by: Some("${event.payload.host}".to_owned()),
+        };
+        let matcher_threshold =
+            MatcherThreshold::build("rule", "rule".to_owned(), &threshold, Default::default())
+                .unwrap();
+        let event = json!(Event::new("test"));
+        let mut extracted_vars = Value::Null;
+
+        // Act
+        let result = matcher_threshold.record(&(&event, &mut extracted_vars).into(), 1_000);
+
+        // Assert
+        assert!(matches!(result, Err(MatcherError::UnresolvedKeyError { .. })));
+    }
+
+    #[test]
+    fn snapshot_should_not_change_the_original_store() {
+        // Arrange
+        let store = Arc::new(ThresholdStore::new());
+        let threshold = Threshold { count: 5, window_seconds: 60, group_by: None };
+        let original =
+            MatcherThreshold::build("rule", "rule".to_owned(), &threshold, store.clone()).unwrap();
+        let copy = MatcherThreshold::build(
+            "rule",
+            "rule".to_owned(),
+            &threshold,
+            Arc::new(store.snapshot()),
+        )
+        .unwrap();
+        let event = json!(Event::new("test"));
+        let mut extracted_vars = Value::Null;
+        let internal_event: InternalEvent = (&event, &mut extracted_vars).into();
+        original.record(&internal_event, 1_000).unwrap();
+
+        // Act
+        let copy_state = copy.record(&internal_event, 2_000).unwrap();
+        let original_state = original.record(&internal_event, 3_000).unwrap();
+
+        // Assert
+        assert_eq!(1, copy_state.count);
+        assert_eq!(2, original_state.count);
+    }
+}
@@ -63,6 +63,9 @@ pub enum ProcessedNode {
     Filter { name: String, filter: ProcessedFilter, nodes: Vec<ProcessedNode> },
     Iterator { name: String, iterator: ProcessedIterator, events: Vec<ProcessedIteration> },
     Ruleset { name: String, rules: ProcessedRules },
+    Correlation { name: String, correlation: ProcessedCorrelation },
+    Heartbeat { name: String, heartbeat: ProcessedHeartbeat },
+    Enricher { name: String, enricher: ProcessedEnricher, nodes: Vec<ProcessedNode> },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -70,6 +73,7 @@ pub enum ProcessedFilterStatus {
     Matched,
     NotMatched,
     Inactive,
+    OutOfSchedule,
 }
 #[derive(Debug, Clone)]
 pub struct ProcessedRules {
@@ -84,6 +88,8 @@ pub struct ProcessedRule {
     pub actions: Vec<Action>,
     pub message: Option<String>,
     pub meta: Option<ProcessedRuleMetaData>,
+    /// The trace of the evaluation of the WHERE clause; it is present only if requested.
+    pub trace: Option<OperatorTrace>,
 }
 
 impl ProcessedRule {
@@ -94,21 +100,145 @@ impl ProcessedRule {
             actions: vec![],
             message: None,
             meta: None,
+            trace: None,
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ProcessedRuleStatus {
     Matched,
     PartiallyMatched,
     NotMatched,
     NotProcessed,
+    Duplicate,
+    OutOfSchedule,
+    ThresholdNotReached,
+}
+
+/// The result of the processing of an event by a Correlation node.
+#[derive(Debug, Clone)]
+pub struct ProcessedCorrelation {
+    pub status: ProcessedCorrelationStatus,
+    pub key: Option<String>,
+    pub matched_steps: usize,
+    pub actions: Vec<Action>,
+    pub message: Option<String>,
+}
+
+impl ProcessedCorrelation {
+    pub fn new(status: ProcessedCorrelationStatus) -> ProcessedCorrelation {
+        ProcessedCorrelation { status, key: None, matched_steps: 0, actions: vec![], message: None }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessedCorrelationStatus {
+    Started,
+    Progressed,
+    Completed,
+    Expired,
+    PartiallyMatched,
+    NotMatched,
+    Inactive,
+}
+
+/// The result of the processing of an event by a Heartbeat node.
+#[derive(Debug, Clone)]
+pub struct ProcessedHeartbeat {
+    pub status: ProcessedHeartbeatStatus,
+    pub key: Option<String>,
+}
+
+impl ProcessedHeartbeat {
+    pub fn new(status: ProcessedHeartbeatStatus) -> ProcessedHeartbeat {
+        ProcessedHeartbeat { status, key: None }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessedHeartbeatStatus {
+    Seen,
+    Recovered,
+    NotMatched,
+    Inactive,
+}
+
+/// The result of the processing of an event by an Enricher node.
+/// The `values` are the variables added to the payload of the event.
+#[derive(Debug, Clone)]
+pub struct ProcessedEnricher {
+    pub status: ProcessedEnricherStatus,
+    pub values: Value,
+    pub message: Option<String>,
+}
+
+impl ProcessedEnricher {
+    pub fn new(status: ProcessedEnricherStatus) -> ProcessedEnricher {
+        ProcessedEnricher { status, values: Value::Null, message: None }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessedEnricherStatus {
+    Enriched,
+    PartiallyEnriched,
+    Inactive,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TypeScriptify)]
 pub struct ProcessedRuleMetaData {
     pub actions: Vec<ActionMetaData>,
+    pub threshold: Option<ThresholdMetaData>,
+    pub suppression: Option<SuppressionMetaData>,
+}
+
+/// The trace of the evaluation of an operator of the WHERE clause of a Rule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TypeScriptify)]
+pub struct OperatorTrace {
+    pub operator: String,
+    pub result: bool,
+    /// The values resolved by the accessors of the operator.
+    pub values: Vec<OperatorTraceValue>,
+    /// The traces of the child operators of `AND`, `OR` and `NOT`.
+    pub operators: Vec<OperatorTrace>,
+}
+
+/// The value resolved by the accessor of an operator argument; it is `None` if the accessor
+/// cannot be resolved.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TypeScriptify)]
+pub struct OperatorTraceValue {
+    pub argument: String,
+    pub value: Option<Value>,
+}
+
+/// The state of the sliding window of a threshold Rule after processing an event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TypeScriptify)]
+pub struct ThresholdMetaData {
+    pub group: String,
+    pub count: usize,
+    pub threshold: usize,
+    pub window_seconds: u64,
+}
+
+impl ThresholdMetaData {
+    pub fn is_reached(&self) -> bool {
+        self.count >= self.threshold
+    }
+}
+
+/// The state of the deduplication window of a Rule after processing an event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TypeScriptify)]
+pub struct SuppressionMetaData {
+    pub fingerprint: String,
+    pub duplicates: usize,
+    pub ttl_seconds: u64,
+}
+
+impl SuppressionMetaData {
+    pub fn is_duplicate(&self) -> bool {
+        self.duplicates > 0
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TypeScriptify)]
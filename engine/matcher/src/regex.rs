@@ -1,6 +1,7 @@
 use crate::error::MatcherError;
 use regex::Regex;
 use std::ops::Deref;
+use tornado_common_grok::GrokPatterns;
 
 /// A struct that allow high level operation on a basic Regex.
 /// For example, this allow a regex to be used in PartialEq checks.
@@ -21,6 +22,17 @@ impl RegexWrapper {
         Ok(Self { regex, regex_string })
     }
 
+    /// Builds the regex obtained by expanding a grok expression with the given patterns.
+    pub fn from_grok(expression: &str, grok_patterns: &GrokPatterns) -> Result<Self, MatcherError> {
+        let regex_string = grok_patterns.expand(expression).map_err(|e| {
+            MatcherError::ExtractorBuildFailError {
+                message: format!("Cannot expand grok expression [{}]", expression),
+                cause: e.to_string(),
+            }
+        })?;
+        RegexWrapper::new(regex_string)
+    }
+
     pub fn regex(&self) -> &Regex {
         &self.regex
     }
@@ -49,4 +61,20 @@ mod test {
         assert_eq!(RegexWrapper::new(".*").unwrap(), RegexWrapper::new(".*").unwrap());
         assert_ne!(RegexWrapper::new(".*").unwrap(), RegexWrapper::new(".*[1-9]").unwrap());
     }
+
+    #[test]
+    fn should_build_regex_from_grok_expression() {
+        // Arrange
+        let patterns = GrokPatterns::default();
+
+        // Act
+        let regex = RegexWrapper::from_grok("^%{IPV4:src} %{POSINT:port}$", &patterns).unwrap();
+
+        // Assert
+        let captures = regex.captures("10.0.0.1 8080").unwrap();
+        assert_eq!("10.0.0.1", &captures["src"]);
+        assert_eq!("8080", &captures["port"]);
+        assert!(RegexWrapper::from_grok("%{UNKNOWN_PATTERN:src}", &patterns).is_err());
+        assert!(RegexWrapper::from_grok("%{IPV4:src} %{IPV4:src}", &patterns).is_err());
+    }
 }
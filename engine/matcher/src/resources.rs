@@ -0,0 +1,33 @@
+//! The resources module contains the resources that the Rules use besides the configuration
+//! of the processing tree.
+
+use crate::lookup::LookupTables;
+use std::sync::Arc;
+use tornado_common_grok::GrokPatterns;
+
+/// The resources used to build the Rules besides the configuration of the processing tree.
+/// They are passed explicitly to the Matcher builder; cloning a MatcherResources is cheap
+/// because the resources are shared between the clones.
+#[derive(Debug, Clone, Default)]
+pub struct MatcherResources {
+    /// The library of patterns used to expand the grok expressions.
+    /// By default, it contains only the built-in patterns.
+    pub grok_patterns: Arc<GrokPatterns>,
+    /// The lookup tables used by the Lookup modifiers.
+    /// By default, there are no tables.
+    pub lookup_tables: Arc<LookupTables>,
+}
+
+impl MatcherResources {
+    pub fn new(grok_patterns: GrokPatterns, lookup_tables: LookupTables) -> MatcherResources {
+        MatcherResources {
+            grok_patterns: Arc::new(grok_patterns),
+            lookup_tables: Arc::new(lookup_tables),
+        }
+    }
+
+    /// Returns a copy of these resources with the lookup tables replaced.
+    pub fn with_lookup_tables(&self, lookup_tables: LookupTables) -> MatcherResources {
+        MatcherResources { lookup_tables: Arc::new(lookup_tables), ..self.clone() }
+    }
+}
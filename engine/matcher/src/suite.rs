@@ -0,0 +1,581 @@
+//! The suite module contains the test cases of the Rules and the logic to run them offline
+//! against a Matcher.
+//!
+//! The test cases of a Ruleset are stored, one per file, in the `tests` directory of the Ruleset,
+//! e.g. `rules.d/master/ruleset_a/tests/syslog_panic.json`. Each case contains an input event
+//! and the expected result of the Rules of the Ruleset.
+
+use crate::config::MatcherConfig;
+use crate::error::MatcherError;
+use crate::matcher::state::MatcherState;
+use crate::matcher::Matcher;
+use crate::model::{ProcessedNode, ProcessedRule, ProcessedRuleStatus, ProcessedRules};
+use crate::resources::MatcherResources;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// The name of the directory of a Ruleset that contains its test cases.
+pub const TESTS_DIR: &str = "tests";
+
+const PATH_SEPARATOR: &str = "/";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RuleTestCase {
+    #[serde(default)]
+    pub description: String,
+    pub event: Value,
+    /// The names of the Rules of the Ruleset that match the event, in processing order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched_rules: Option<Vec<String>>,
+    /// The expected result of single Rules of the Ruleset, by Rule name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rules: BTreeMap<String, RuleTestExpectation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RuleTestExpectation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ProcessedRuleStatus>,
+    /// The resolved actions of the Rule; their creation time is not compared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Vec<RuleTestAction>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RuleTestAction {
+    pub id: String,
+    pub payload: Value,
+}
+
+/// The test cases of a Ruleset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleTestSuite {
+    /// The names of the nodes from the root, excluded, to the Ruleset, included.
+    pub ruleset_path: Vec<String>,
+    /// The test cases by name.
+    pub cases: BTreeMap<String, RuleTestCase>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleTestResult {
+    pub ruleset_path: Vec<String>,
+    pub case: String,
+    /// The differences between the expected and the actual result; the case passes if
+    /// there are none.
+    pub differences: Vec<RuleTestDifference>,
+    /// The pretty printed JSON of the processed Ruleset, or None if the event did not reach it.
+    pub processed_ruleset: Option<String>,
+}
+
+impl RuleTestResult {
+    pub fn is_success(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+impl Display for RuleTestResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let outcome = if self.is_success() { "PASS" } else { "FAIL" };
+        write!(
+            f,
+            "[{}] {}{}{}",
+            outcome,
+            self.ruleset_path.join(PATH_SEPARATOR),
+            PATH_SEPARATOR,
+            self.case
+        )?;
+        for difference in &self.differences {
+            write!(f, "\n{}", difference)?;
+        }
+        if !self.is_success() {
+            match &self.processed_ruleset {
+                Some(processed_ruleset) => {
+                    write!(f, "\n  processed ruleset:")?;
+                    for line in processed_ruleset.lines() {
+                        write!(f, "\n    {}", line)?;
+                    }
+                }
+                None => write!(f, "\n  processed ruleset: not reached by the event")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A difference between the expected and the actual result of a test case.
+/// The values are pretty printed JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleTestDifference {
+    pub subject: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl RuleTestDifference {
+    fn new<E: Serialize, A: Serialize>(subject: String, expected: &E, actual: &A) -> Self {
+        RuleTestDifference {
+            subject,
+            expected: to_pretty_json(expected),
+            actual: to_pretty_json(actual),
+        }
+    }
+
+    /// Returns a line by line diff of the expected and the actual values; the lines are
+    /// prefixed by `-` if they are only expected, by `+` if they are only in the actual value.
+    pub fn diff(&self) -> Vec<String> {
+        diff_lines(&self.expected, &self.actual)
+    }
+}
+
+impl Display for RuleTestDifference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "  {}:", self.subject)?;
+        for line in self.diff() {
+            write!(f, "\n    {}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the paths of all the Rulesets of the configuration; each path contains the names of
+/// the nodes from the root, excluded, to the Ruleset, included.
+pub fn ruleset_paths(config: &MatcherConfig) -> Vec<Vec<String>> {
+    fn collect(config: &MatcherConfig, path: &mut Vec<String>, paths: &mut Vec<Vec<String>>) {
+        path.push(config.get_name().to_owned());
+        match config {
+            MatcherConfig::Ruleset { .. } => paths.push(path[1..].to_vec()),
+            MatcherConfig::Filter { nodes, .. }
+            | MatcherConfig::Iterator { nodes, .. }
+            | MatcherConfig::Enricher { nodes, .. } => {
+                for node in nodes {
+                    collect(node, path, paths);
+                }
+            }
+            MatcherConfig::Correlation { .. } | MatcherConfig::Heartbeat { .. } => {}
+        }
+        path.pop();
+    }
+
+    let mut paths = vec![];
+    collect(config, &mut vec![], &mut paths);
+    paths
+}
+
+/// Runs the test suites against the configuration.
+/// The Matcher is built once and its state is cleared before each case, so that the state of
+/// the stateful Rules and nodes does not depend on the other cases.
+pub fn run_test_suites(
+    config: &MatcherConfig,
+    suites: &[RuleTestSuite],
+    resources: &MatcherResources,
+) -> Result<Vec<RuleTestResult>, MatcherError> {
+    let mut results = vec![];
+    if suites.is_empty() {
+        return Ok(results);
+    }
+    let state = MatcherState::new();
+    let matcher = Matcher::build_with_state(config, state.clone(), resources)?;
+    for suite in suites {
+        let rule_names = find_ruleset_rules(config, &suite.ruleset_path).ok_or_else(|| {
+            MatcherError::ConfigurationError {
+                message: format!(
+                    "Cannot find the Ruleset [{}] of the test suite",
+                    suite.ruleset_path.join(PATH_SEPARATOR)
+                ),
+            }
+        })?;
+        for (name, case) in &suite.cases {
+            state.clear();
+            let processed = matcher.process(case.event.clone(), false);
+            let processed_rules = find_processed_rules(&processed.result, &suite.ruleset_path);
+            let differences = compare(
+                case,
+                &rule_names,
+                processed_rules.map(|processed| processed.rules.as_slice()).unwrap_or_default(),
+            );
+            results.push(RuleTestResult {
+                ruleset_path: suite.ruleset_path.clone(),
+                case: name.to_owned(),
+                differences,
+                processed_ruleset: processed_rules.map(processed_ruleset_to_json),
+            });
+        }
+    }
+    Ok(results)
+}
+
+fn find_ruleset_rules(config: &MatcherConfig, path: &[String]) -> Option<Vec<String>> {
+    match (config, path) {
+        (MatcherConfig::Ruleset { rules, .. }, []) => {
+            Some(rules.iter().map(|rule| rule.name.to_owned()).collect())
+        }
+        (
+            MatcherConfig::Filter { nodes, .. }
+            | MatcherConfig::Iterator { nodes, .. }
+            | MatcherConfig::Enricher { nodes, .. },
+            [name, rest @ ..],
+        ) => nodes
+            .iter()
+            .find(|node| node.get_name() == name)
+            .and_then(|node| find_ruleset_rules(node, rest)),
+        _ => None,
+    }
+}
+
+/// Returns the processed Rules of the Ruleset, if the event reached it. If the Ruleset is
+/// below an Iterator, the first iteration that reached it is used.
+fn find_processed_rules<'a>(
+    node: &'a ProcessedNode,
+    path: &[String],
+) -> Option<&'a ProcessedRules> {
+    let children = match (node, path) {
+        (ProcessedNode::Ruleset { rules, .. }, []) => return Some(rules),
+        (ProcessedNode::Filter { nodes, .. } | ProcessedNode::Enricher { nodes, .. }, _) => {
+            nodes.iter().collect::<Vec<_>>()
+        }
+        (ProcessedNode::Iterator { events, .. }, _) => {
+            events.iter().flat_map(|iteration| iteration.result.iter()).collect()
+        }
+        _ => return None,
+    };
+    let (name, rest) = path.split_first()?;
+    children
+        .into_iter()
+        .filter(|child| child_name(child) == name)
+        .find_map(|child| find_processed_rules(child, rest))
+}
+
+fn child_name(node: &ProcessedNode) -> &str {
+    match node {
+        ProcessedNode::Filter { name, .. }
+        | ProcessedNode::Iterator { name, .. }
+        | ProcessedNode::Ruleset { name, .. }
+        | ProcessedNode::Correlation { name, .. }
+        | ProcessedNode::Heartbeat { name, .. }
+        | ProcessedNode::Enricher { name, .. } => name,
+    }
+}
+
+/// Returns the pretty printed JSON of the processed Rules; the creation time of the actions
+/// is omitted.
+fn processed_ruleset_to_json(processed_rules: &ProcessedRules) -> String {
+    let rules: Vec<Value> = processed_rules
+        .rules
+        .iter()
+        .map(|rule| {
+            json!({
+                "name": rule.name,
+                "status": rule.status,
+                "actions": test_actions(rule),
+                "message": rule.message,
+            })
+        })
+        .collect();
+    to_pretty_json(&json!({
+        "rules": rules,
+        "extracted_vars": processed_rules.extracted_vars,
+    }))
+}
+
+fn compare(
+    case: &RuleTestCase,
+    rule_names: &[String],
+    processed_rules: &[ProcessedRule],
+) -> Vec<RuleTestDifference> {
+    let mut differences = vec![];
+
+    if let Some(expected) = &case.matched_rules {
+        let actual: Vec<&str> = processed_rules
+            .iter()
+            .filter(|rule| rule.status == ProcessedRuleStatus::Matched)
+            .map(|rule| rule.name.as_str())
+            .collect();
+        if expected.iter().map(String::as_str).ne(actual.iter().copied()) {
+            differences.push(RuleTestDifference::new(
+                "matched rules".to_owned(),
+                expected,
+                &actual,
+            ));
+        }
+    }
+
+    for (rule_name, expectation) in &case.rules {
+        if !rule_names.contains(rule_name) {
+            differences.push(RuleTestDifference::new(
+                format!("rule [{}]", rule_name),
+                &"an existing rule",
+                &"the rule does not exist in the Ruleset",
+            ));
+            continue;
+        }
+
+        // The Rules that are inactive or that follow a matched Rule are not processed.
+        let processed = processed_rules.iter().find(|rule| &rule.name == rule_name);
+        let actual_status =
+            processed.map_or(ProcessedRuleStatus::NotProcessed, |rule| rule.status.clone());
+        if let Some(status) = &expectation.status {
+            if status != &actual_status {
+                differences.push(RuleTestDifference::new(
+                    format!("rule [{}] status", rule_name),
+                    status,
+                    &actual_status,
+                ));
+            }
+        }
+
+        if let Some(actions) = &expectation.actions {
+            let actual_actions = processed.map(test_actions).unwrap_or_default();
+            if actions != &actual_actions {
+                differences.push(RuleTestDifference::new(
+                    format!("rule [{}] actions", rule_name),
+                    actions,
+                    &actual_actions,
+                ));
+            }
+        }
+    }
+
+    differences
+}
+
+fn test_actions(rule: &ProcessedRule) -> Vec<RuleTestAction> {
+    rule.actions
+        .iter()
+        .map(|action| RuleTestAction {
+            id: action.id.to_owned(),
+            payload: Value::Object(action.payload.clone()),
+        })
+        .collect()
+}
+
+fn to_pretty_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|err| format!("{:?}", err))
+}
+
+/// A line diff based on the longest common subsequence of the lines.
+/// The lines are prefixed by `-` if they are only in `expected`, by `+` if they are only
+/// in `actual`, and by a space if they are in both.
+pub fn diff_lines(expected: &str, actual: &str) -> Vec<String> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // common[i][j] is the length of the longest common subsequence of expected[i..] and actual[j..]
+    let mut common = vec![vec![0_usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            lines.push(format!("  {}", expected[i]));
+            i += 1;
+            j += 1;
+        } else if j == actual.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1])
+        {
+            lines.push(format!("- {}", expected[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", actual[j]));
+            j += 1;
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::nodes::Filter;
+    use crate::config::rule::{ConfigAction, Constraint, Operator, Rule, Suppression};
+    use serde_json::json;
+
+    fn rule(name: &str, event_type: &str) -> Rule {
+        let mut payload = tornado_common_api::Map::new();
+        payload.insert("host".to_owned(), json!("${event.payload.host}"));
+        Rule {
+            name: name.to_owned(),
+            do_continue: true,
+            active: true,
+            constraint: Constraint {
+                where_operator: Some(Operator::Equals {
+                    first: json!("${event.type}"),
+                    second: json!(event_type),
+                }),
+                with: Default::default(),
+            },
+            actions: vec![ConfigAction { id: "logger".to_owned(), payload }],
+            ..Default::default()
+        }
+    }
+
+    fn config() -> MatcherConfig {
+        MatcherConfig::Filter {
+            name: "root".to_owned(),
+            filter: Filter { active: true, ..Default::default() },
+            nodes: vec![MatcherConfig::Filter {
+                name: "master".to_owned(),
+                filter: Filter { active: true, ..Default::default() },
+                nodes: vec![MatcherConfig::Ruleset {
+                    name: "ruleset".to_owned(),
+                    rules: vec![rule("syslog", "syslog"), rule("email", "email")],
+                }],
+            }],
+        }
+    }
+
+    fn suite(case: Value) -> RuleTestSuite {
+        let mut cases = BTreeMap::new();
+        cases.insert("case_1".to_owned(), serde_json::from_value(case).unwrap());
+        RuleTestSuite { ruleset_path: vec!["master".to_owned(), "ruleset".to_owned()], cases }
+    }
+
+    #[test]
+    fn should_return_the_ruleset_paths() {
+        assert_eq!(vec![vec!["master".to_owned(), "ruleset".to_owned()]], ruleset_paths(&config()));
+    }
+
+    #[test]
+    fn should_pass_if_the_result_is_the_expected_one() {
+        // Arrange
+        let suite = suite(json!({
+            "event": { "type": "syslog", "created_ms": 0, "payload": { "host": "web01" } },
+            "matched_rules": ["syslog"],
+            "rules": {
+                "syslog": { "status": "Matched", "actions": [{ "id": "logger", "payload": { "host": "web01" } }] },
+                "email": { "status": "NotMatched", "actions": [] }
+            }
+        }));
+
+        // Act
+        let results = run_test_suites(&config(), &[suite], &MatcherResources::default()).unwrap();
+
+        // Assert
+        assert_eq!(1, results.len());
+        assert!(results[0].is_success(), "{}", results[0]);
+        assert_eq!("[PASS] master/ruleset/case_1", results[0].to_string());
+    }
+
+    #[test]
+    fn should_return_the_differences_from_the_expected_result() {
+        // Arrange
+        let suite = suite(json!({
+            "event": { "type": "email", "created_ms": 0, "payload": { "host": "web01" } },
+            "matched_rules": ["syslog"],
+            "rules": {
+                "email": { "actions": [{ "id": "logger", "payload": { "host": "web02" } }] },
+                "unknown": { "status": "Matched" }
+            }
+        }));
+
+        // Act
+        let results = run_test_suites(&config(), &[suite], &MatcherResources::default()).unwrap();
+
+        // Assert
+        let result = &results[0];
+        assert!(!result.is_success());
+        assert_eq!(
+            vec!["matched rules", "rule [email] actions", "rule [unknown]"],
+            result
+                .differences
+                .iter()
+                .map(|difference| difference.subject.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["  [", "-   \"syslog\"", "+   \"email\"", "  ]"],
+            result.differences[0].diff()
+        );
+        assert!(result
+            .to_string()
+            .contains("    -       \"host\": \"web02\"\n    +       \"host\": \"web01\""));
+        let processed_ruleset = result.processed_ruleset.as_ref().unwrap();
+        assert!(processed_ruleset.contains("\"name\": \"email\""));
+        assert!(result.to_string().contains("\n  processed ruleset:\n    {\n"));
+    }
+
+    #[test]
+    fn should_process_each_case_with_a_new_state() {
+        // Arrange
+        let mut config = config();
+        if let MatcherConfig::Filter { nodes, .. } = &mut config {
+            if let MatcherConfig::Filter { nodes, .. } = &mut nodes[0] {
+                if let MatcherConfig::Ruleset { rules, .. } = &mut nodes[0] {
+                    rules[0].suppression = Some(Suppression {
+                        fingerprint: vec!["${event.payload.host}".to_owned()],
+                        ttl_seconds: 600,
+                    });
+                }
+            }
+        }
+        let case = json!({
+            "event": { "type": "syslog", "created_ms": 0, "payload": { "host": "web01" } },
+            "rules": {
+                "syslog": { "status": "Matched", "actions": [{ "id": "logger", "payload": { "host": "web01" } }] }
+            }
+        });
+        let mut suite = suite(case.clone());
+        suite.cases.insert("case_2".to_owned(), serde_json::from_value(case).unwrap());
+
+        // Act
+        let results = run_test_suites(&config, &[suite], &MatcherResources::default()).unwrap();
+
+        // Assert
+        assert_eq!(2, results.len());
+        for result in &results {
+            assert!(result.is_success(), "{}", result);
+        }
+    }
+
+    #[test]
+    fn should_consider_not_processed_the_rules_of_a_ruleset_not_reached() {
+        // Arrange
+        let mut config = config();
+        if let MatcherConfig::Filter { nodes, .. } = &mut config {
+            if let MatcherConfig::Filter { filter, .. } = &mut nodes[0] {
+                filter.filter = Some(Operator::Equals {
+                    first: json!("${event.type}"),
+                    second: json!("email"),
+                })
+                .into();
+            }
+        }
+        let suite = suite(json!({
+            "event": { "type": "syslog", "created_ms": 0, "payload": {} },
+            "matched_rules": [],
+            "rules": { "syslog": { "status": "NotProcessed" } }
+        }));
+
+        // Act
+        let results = run_test_suites(&config, &[suite], &MatcherResources::default()).unwrap();
+
+        // Assert
+        assert!(results[0].is_success(), "{}", results[0]);
+        assert!(results[0].processed_ruleset.is_none());
+    }
+
+    #[test]
+    fn should_fail_if_the_ruleset_does_not_exist() {
+        let mut suite = suite(json!({ "event": {} }));
+        suite.ruleset_path = vec!["unknown".to_owned()];
+        assert!(run_test_suites(&config(), &[suite], &MatcherResources::default()).is_err());
+    }
+
+    #[test]
+    fn should_diff_lines() {
+        assert_eq!(vec!["  a", "- b", "+ c", "  d", "+ e"], diff_lines("a\nb\nd", "a\nc\nd\ne"));
+        assert!(diff_lines("", "").is_empty());
+    }
+}
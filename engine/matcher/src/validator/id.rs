@@ -60,6 +60,45 @@ impl IdValidator {
         self.validate(name, error_message)
     }
 
+    /// Validates a correlation name.
+    pub fn validate_correlation_name(
+        &self,
+        parent: &NodePath,
+        name: &str,
+    ) -> Result<(), MatcherError> {
+        let error_message = format!(
+            "Correlation name [{}] in path [{}] is not valid. It should respect the pattern {}",
+            name, parent, ID_REGEX_PATTERN
+        );
+        self.validate(name, error_message)
+    }
+
+    /// Validates a heartbeat name.
+    pub fn validate_heartbeat_name(
+        &self,
+        parent: &NodePath,
+        name: &str,
+    ) -> Result<(), MatcherError> {
+        let error_message = format!(
+            "Heartbeat name [{}] in path [{}] is not valid. It should respect the pattern {}",
+            name, parent, ID_REGEX_PATTERN
+        );
+        self.validate(name, error_message)
+    }
+
+    /// Validates an enricher name.
+    pub fn validate_enricher_name(
+        &self,
+        parent: &NodePath,
+        name: &str,
+    ) -> Result<(), MatcherError> {
+        let error_message = format!(
+            "Enricher name [{}] in path [{}] is not valid. It should respect the pattern {}",
+            name, parent, ID_REGEX_PATTERN
+        );
+        self.validate(name, error_message)
+    }
+
     /// Validates a ruleset name.
     pub fn validate_ruleset_name(&self, parent: &NodePath, name: &str) -> Result<(), MatcherError> {
         let error_message = format!(
@@ -0,0 +1,763 @@
+//! The lint module contains the semantic checks of a configuration.
+//!
+//! Unlike the validation, the lint does not reject a configuration: it reports the Rules and
+//! the extracted variables that are valid but that probably do not behave as expected.
+
+use crate::config::rule::{Extractor, ExtractorRegex, Operator, Rule};
+use crate::config::MatcherConfig;
+use crate::resources::MatcherResources;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use tornado_common_parser::{Parser, ValueGetter, EXTRACTED_VARIABLES_KEY};
+
+const PATH_SEPARATOR: &str = ".";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintWarningKind {
+    /// A Rule that can never match because a previous Rule always stops the processing first
+    UnreachableRule,
+    /// An accessor to an extracted variable of a Rule that does not exist or that comes later
+    UnknownVariable,
+    /// An extracted variable that is never used
+    UnusedVariable,
+    /// An accessor to a group that is not defined by the regex of an extracted variable
+    UnknownRegexGroup,
+}
+
+/// A warning about a part of the configuration that is valid but that probably does not
+/// behave as expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintWarning {
+    /// The names of the nodes from the root to the Rule
+    pub path: Vec<String>,
+    pub kind: LintWarningKind,
+    pub message: String,
+}
+
+impl Display for LintWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.path.join(PATH_SEPARATOR), self.message)
+    }
+}
+
+/// Returns the lint warnings of all the Rulesets of the configuration.
+/// The grok expressions are expanded with the patterns of the resources.
+pub fn lint(config: &MatcherConfig, resources: &MatcherResources) -> Vec<LintWarning> {
+    let mut warnings = vec![];
+    lint_node(config, &mut vec![], resources, &mut warnings);
+    warnings
+}
+
+fn lint_node<'a>(
+    config: &'a MatcherConfig,
+    path: &mut Vec<&'a str>,
+    resources: &MatcherResources,
+    warnings: &mut Vec<LintWarning>,
+) {
+    path.push(config.get_name());
+    match config {
+        MatcherConfig::Ruleset { rules, .. } => lint_ruleset(path, rules, resources, warnings),
+        MatcherConfig::Filter { nodes, .. }
+        | MatcherConfig::Iterator { nodes, .. }
+        | MatcherConfig::Enricher { nodes, .. } => {
+            for node in nodes {
+                lint_node(node, path, resources, warnings);
+            }
+        }
+        MatcherConfig::Correlation { .. } | MatcherConfig::Heartbeat { .. } => {}
+    }
+    path.pop();
+}
+
+/// A reference to an extracted variable, e.g. `${_variables.rule_name.var_name.group}`.
+struct VariableReference {
+    /// The index of the Rule where the reference is used
+    from: usize,
+    rule: String,
+    variable: Option<String>,
+    /// The first key accessed inside the variable value, if any
+    key: Option<String>,
+}
+
+fn lint_ruleset(
+    path: &[&str],
+    rules: &[Rule],
+    resources: &MatcherResources,
+    warnings: &mut Vec<LintWarning>,
+) {
+    let rules: Vec<&Rule> = rules.iter().filter(|rule| rule.active).collect();
+    let rule_path = |rule: &Rule| {
+        path.iter().map(|name| name.to_string()).chain(Some(rule.name.to_owned())).collect()
+    };
+    let mut warn = |rule: &Rule, kind: LintWarningKind, message: String| {
+        warnings.push(LintWarning { path: rule_path(rule), kind, message })
+    };
+
+    let operators: Vec<Option<Operator>> =
+        rules.iter().map(|rule| rule.constraint.where_operator.as_ref().map(normalize)).collect();
+    for (index, rule) in rules.iter().enumerate() {
+        let shadowing = (0..index).find(|&previous| {
+            always_stops(rules[previous])
+                && implies(operators[index].as_ref(), operators[previous].as_ref())
+        });
+        if let Some(previous) = shadowing {
+            warn(
+                rule,
+                LintWarningKind::UnreachableRule,
+                format!(
+                    "The rule can never match: the previous rule [{}] matches all its events and stops the processing",
+                    rules[previous].name
+                ),
+            );
+        }
+    }
+
+    let mut used: BTreeSet<(&str, &str)> = BTreeSet::new();
+    let mut references = vec![];
+    for (index, rule) in rules.iter().enumerate() {
+        for keys in rule_strings(rule, operators[index].as_ref())
+            .iter()
+            .flat_map(|text| Parser::accessor_keys(text))
+        {
+            // A bare `${_variables}` returns the variables of the current and of all the previous rules
+            if keys == [ValueGetter::from(EXTRACTED_VARIABLES_KEY)] {
+                used.extend(rules[..=index].iter().flat_map(|rule| {
+                    rule.constraint.with.keys().map(move |name| (rule.name.as_str(), name.as_str()))
+                }));
+                continue;
+            }
+            if let Some(reference) = variable_reference(index, rule, &keys) {
+                references.push(reference);
+            }
+        }
+    }
+
+    let positions: BTreeMap<&str, usize> =
+        rules.iter().enumerate().map(|(index, rule)| (rule.name.as_str(), index)).collect();
+    for reference in &references {
+        let from = rules[reference.from];
+        let Some(&position) = positions.get(reference.rule.as_str()) else {
+            warn(
+                from,
+                LintWarningKind::UnknownVariable,
+                format!(
+                    "The accessor [{}] refers to a rule or a variable that does not exist",
+                    reference_text(reference)
+                ),
+            );
+            continue;
+        };
+        let rule = rules[position];
+        if position > reference.from {
+            warn(
+                from,
+                LintWarningKind::UnknownVariable,
+                format!(
+                    "The accessor [{}] refers to the rule [{}] that comes later in the ruleset",
+                    reference_text(reference),
+                    rule.name
+                ),
+            );
+            continue;
+        }
+        let Some(variable) = &reference.variable else {
+            used.extend(
+                rule.constraint.with.keys().map(|name| (rule.name.as_str(), name.as_str())),
+            );
+            continue;
+        };
+        let Some(extractor) = rule.constraint.with.get(variable) else {
+            warn(
+                from,
+                LintWarningKind::UnknownVariable,
+                format!(
+                    "The accessor [{}] refers to the variable [{}] that is not extracted by the rule [{}]",
+                    reference_text(reference),
+                    variable,
+                    rule.name
+                ),
+            );
+            continue;
+        };
+        used.insert((rule.name.as_str(), variable.as_str()));
+        if let (Some(key), Some(groups)) = (&reference.key, regex_groups(extractor, resources)) {
+            if !groups.contains(key) {
+                warn(
+                    from,
+                    LintWarningKind::UnknownRegexGroup,
+                    format!(
+                        "The accessor [{}] refers to the group [{}] that is not defined by the regex of the variable [{}]",
+                        reference_text(reference),
+                        key,
+                        variable
+                    ),
+                );
+            }
+        }
+    }
+
+    for rule in &rules {
+        let mut names: Vec<&String> = rule.constraint.with.keys().collect();
+        names.sort();
+        for name in names {
+            if !used.contains(&(rule.name.as_str(), name.as_str())) {
+                warn(
+                    rule,
+                    LintWarningKind::UnusedVariable,
+                    format!("The extracted variable [{}] is never used", name),
+                );
+            }
+        }
+    }
+}
+
+/// Returns whether a matched Rule always stops the processing of the Ruleset.
+/// A Rule that extracts variables or that has a threshold, a suppression or a schedule
+/// does not stop the processing when its WHERE clause matches but the rest does not.
+fn always_stops(rule: &Rule) -> bool {
+    !rule.do_continue
+        && rule.constraint.with.is_empty()
+        && rule.threshold.is_none()
+        && rule.suppression.is_none()
+        && rule.schedule.is_none()
+}
+
+/// Replaces the textual expressions with the equivalent operators; the expressions that
+/// cannot be parsed are kept.
+fn normalize(operator: &Operator) -> Operator {
+    match operator {
+        Operator::And { operators } => {
+            Operator::And { operators: operators.iter().map(normalize).collect() }
+        }
+        Operator::Or { operators } => {
+            Operator::Or { operators: operators.iter().map(normalize).collect() }
+        }
+        Operator::Not { operator } => Operator::Not { operator: Box::new(normalize(operator)) },
+        Operator::Expression { expression } => Operator::parse_expression(expression)
+            .map(|operator| normalize(&operator))
+            .unwrap_or_else(|_| operator.clone()),
+        _ => operator.clone(),
+    }
+}
+
+/// Returns whether every event matched by `operator` is matched by `other` too.
+/// A missing operator matches all events.
+/// The check is structural, so it can return false even if the implication holds.
+fn implies(operator: Option<&Operator>, other: Option<&Operator>) -> bool {
+    let Some(other) = other else {
+        return true;
+    };
+    if operator == Some(other) {
+        return true;
+    }
+    match other {
+        Operator::And { operators } if operators.is_empty() => return true,
+        Operator::And { operators } => {
+            return operators.iter().all(|other| implies(operator, Some(other)))
+        }
+        Operator::Or { operators }
+            if operators.iter().any(|other| implies(operator, Some(other))) =>
+        {
+            return true
+        }
+        _ => {}
+    }
+    match operator {
+        Some(Operator::And { operators }) => {
+            operators.iter().any(|operator| implies(Some(operator), Some(other)))
+        }
+        Some(Operator::Or { operators }) if !operators.is_empty() => {
+            operators.iter().all(|operator| implies(Some(operator), Some(other)))
+        }
+        _ => false,
+    }
+}
+
+/// Returns the strings of a Rule that can contain accessors.
+fn rule_strings(rule: &Rule, operator: Option<&Operator>) -> Vec<String> {
+    let mut strings = vec![];
+    if let Some(operator) = operator {
+        collect_strings(&serde_json::to_value(operator).unwrap_or_default(), &mut strings);
+    }
+    for extractor in rule.constraint.with.values() {
+        strings.push(extractor.from.to_owned());
+        collect_strings(
+            &serde_json::to_value(&extractor.modifiers_post).unwrap_or_default(),
+            &mut strings,
+        );
+    }
+    for action in &rule.actions {
+        for value in action.payload.values() {
+            collect_strings(value, &mut strings);
+        }
+    }
+    if let Some(threshold) = &rule.threshold {
+        strings.extend(threshold.group_by.iter().cloned());
+    }
+    if let Some(suppression) = &rule.suppression {
+        strings.extend(suppression.fingerprint.iter().cloned());
+    }
+    strings
+}
+
+fn collect_strings(value: &Value, strings: &mut Vec<String>) {
+    match value {
+        Value::String(text) => strings.push(text.to_owned()),
+        Value::Array(values) => values.iter().for_each(|value| collect_strings(value, strings)),
+        Value::Object(map) => map.values().for_each(|value| collect_strings(value, strings)),
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+/// Resolves an accessor to the extracted variables like the Matcher does:
+/// `_variables.name` refers to the variable `name` of the current Rule if it exists,
+/// otherwise to the variables of the Rule `name`.
+fn variable_reference(from: usize, rule: &Rule, keys: &[ValueGetter]) -> Option<VariableReference> {
+    let (root, keys) = keys.split_first()?;
+    if root != &ValueGetter::from(EXTRACTED_VARIABLES_KEY) {
+        return None;
+    }
+    let (first, rest) = keys.split_first()?;
+    let first = map_key(first)?;
+    if rule.constraint.with.contains_key(first) {
+        return Some(VariableReference {
+            from,
+            rule: rule.name.to_owned(),
+            variable: Some(first.to_owned()),
+            key: first_map_key(rest),
+        });
+    }
+    let (variable, rest) = match rest.split_first() {
+        Some((variable, rest)) => (map_key(variable).map(str::to_owned), rest),
+        None => (None, rest),
+    };
+    Some(VariableReference {
+        from,
+        rule: first.to_owned(),
+        key: variable.as_ref().and_then(|_| first_map_key(rest)),
+        variable,
+    })
+}
+
+fn map_key(getter: &ValueGetter) -> Option<&str> {
+    match getter {
+        ValueGetter::Map { key } => Some(key),
+        _ => None,
+    }
+}
+
+/// Returns the first key of a map accessed by the getters, skipping the array elements.
+fn first_map_key(getters: &[ValueGetter]) -> Option<String> {
+    getters
+        .iter()
+        .find(|getter| {
+            !matches!(
+                getter,
+                ValueGetter::Array { .. }
+                    | ValueGetter::ArrayFromEnd { .. }
+                    | ValueGetter::Slice { .. }
+                    | ValueGetter::Wildcard
+            )
+        })
+        .and_then(map_key)
+        .map(str::to_owned)
+}
+
+fn reference_text(reference: &VariableReference) -> String {
+    let mut text = format!("{}{}{}", EXTRACTED_VARIABLES_KEY, PATH_SEPARATOR, reference.rule);
+    if let Some(variable) = &reference.variable {
+        text.push_str(PATH_SEPARATOR);
+        text.push_str(variable);
+    }
+    text
+}
+
+/// Returns the names of the groups of a variable extracted with named groups.
+/// Returns `None` if the variable is not a map of groups, or if its value is transformed
+/// by modifiers.
+fn regex_groups(extractor: &Extractor, resources: &MatcherResources) -> Option<BTreeSet<String>> {
+    if !extractor.modifiers_post.is_empty() {
+        return None;
+    }
+    let regex = match &extractor.regex {
+        ExtractorRegex::RegexNamedGroups { regex, .. } => regex.to_owned(),
+        ExtractorRegex::Grok { expression, .. } => {
+            resources.grok_patterns.expand(expression).ok()?
+        }
+        _ => return None,
+    };
+    let regex = Regex::new(&regex).ok()?;
+    Some(regex.capture_names().flatten().map(str::to_owned).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::nodes::Filter;
+    use serde_json::json;
+
+    fn rule(name: &str, do_continue: bool, rule: Value) -> Rule {
+        let mut rule = rule;
+        rule["name"] = json!(name);
+        rule["description"] = json!("");
+        rule["continue"] = json!(do_continue);
+        rule["active"] = json!(true);
+        if rule.get("actions").is_none() {
+            rule["actions"] = json!([]);
+        }
+        if rule["constraint"].get("WITH").is_none() {
+            rule["constraint"]["WITH"] = json!({});
+        }
+        serde_json::from_value(rule).unwrap()
+    }
+
+    fn config(rules: Vec<Rule>) -> MatcherConfig {
+        MatcherConfig::Filter {
+            name: "root".to_owned(),
+            filter: Filter { active: true, ..Default::default() },
+            nodes: vec![MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules }],
+        }
+    }
+
+    fn kinds(warnings: &[LintWarning]) -> Vec<(String, LintWarningKind)> {
+        warnings.iter().map(|warning| (warning.path.join("."), warning.kind)).collect()
+    }
+
+    #[test]
+    fn should_return_no_warnings_for_a_valid_ruleset() {
+        // Arrange
+        let config = config(vec![
+            rule(
+                "extract",
+                true,
+                json!({
+                    "constraint": {
+                        "WHERE": "event.type == \"syslog\"",
+                        "WITH": {
+                            "host": {
+                                "from": "${event.payload.line}",
+                                "regex": { "named_match": "(?P<name>[a-z]+) (?P<ip>[0-9.]+)" }
+                            }
+                        }
+                    }
+                }),
+            ),
+            rule(
+                "forward",
+                false,
+                json!({
+                    "constraint": { "WHERE": "event.type == \"syslog\" && event.payload.severity <= 3" },
+                    "actions": [{ "id": "logger", "payload": { "host": "${_variables.extract.host.name}" } }]
+                }),
+            ),
+        ]);
+
+        // Act
+        let warnings = lint(&config, &MatcherResources::default());
+
+        // Assert
+        assert!(warnings.is_empty(), "{:?}", warnings);
+    }
+
+    #[test]
+    fn should_detect_rules_shadowed_by_a_weaker_rule_that_stops_the_processing() {
+        // Arrange
+        let config = config(vec![
+            rule("syslog", false, json!({ "constraint": { "WHERE": "event.type == \"syslog\"" } })),
+            rule(
+                "critical_syslog",
+                false,
+                json!({
+                    "constraint": {
+                        "WHERE": {
+                            "type": "AND",
+                            "operators": [
+                                { "type": "equals", "first": "${event.type}", "second": "syslog" },
+                                { "type": "le", "first": "${event.payload.severity}", "second": 1 }
+                            ]
+                        }
+                    }
+                }),
+            ),
+            rule("email", false, json!({ "constraint": { "WHERE": "event.type == \"email\"" } })),
+        ]);
+
+        // Act
+        let warnings = lint(&config, &MatcherResources::default());
+
+        // Assert
+        assert_eq!(
+            vec![("root.ruleset.critical_syslog".to_owned(), LintWarningKind::UnreachableRule)],
+            kinds(&warnings)
+        );
+        assert_eq!(
+            "[root.ruleset.critical_syslog] The rule can never match: the previous rule [syslog] matches all its events and stops the processing",
+            warnings[0].to_string()
+        );
+    }
+
+    #[test]
+    fn should_not_detect_shadowed_rules_if_the_previous_rule_continues_or_has_a_threshold() {
+        // Arrange
+        let config = config(vec![
+            rule("continue", true, json!({ "constraint": {} })),
+            rule(
+                "threshold",
+                false,
+                json!({
+                    "constraint": {},
+                    "threshold": { "count": 3, "window_seconds": 60 }
+                }),
+            ),
+            rule("any", false, json!({ "constraint": { "WHERE": "event.type == \"syslog\"" } })),
+        ]);
+
+        // Act
+        let warnings = lint(&config, &MatcherResources::default());
+
+        // Assert
+        assert!(warnings.is_empty(), "{:?}", warnings);
+    }
+
+    #[test]
+    fn should_detect_accessors_to_unknown_or_later_variables() {
+        // Arrange
+        let config = config(vec![
+            rule(
+                "first",
+                true,
+                json!({
+                    "constraint": { "WHERE": "_variables.second.value == \"1\" && _variables.missing.value == \"1\"" },
+                    "actions": [{ "id": "logger", "payload": { "value": "${_variables.first.value}" } }]
+                }),
+            ),
+            rule(
+                "second",
+                true,
+                json!({
+                    "constraint": {
+                        "WITH": {
+                            "value": { "from": "${event.type}", "regex": { "match": ".*" } }
+                        }
+                    },
+                    "actions": [{ "id": "logger", "payload": { "value": "${_variables.value}" } }]
+                }),
+            ),
+            rule(
+                "third",
+                true,
+                json!({
+                    "constraint": { "WHERE": "_variables.second.other == \"1\"" }
+                }),
+            ),
+        ]);
+
+        // Act
+        let warnings = lint(&config, &MatcherResources::default());
+
+        // Assert
+        assert_eq!(
+            vec![
+                ("root.ruleset.first".to_owned(), LintWarningKind::UnknownVariable),
+                ("root.ruleset.first".to_owned(), LintWarningKind::UnknownVariable),
+                ("root.ruleset.first".to_owned(), LintWarningKind::UnknownVariable),
+                ("root.ruleset.third".to_owned(), LintWarningKind::UnknownVariable),
+            ],
+            kinds(&warnings)
+        );
+    }
+
+    #[test]
+    fn should_detect_unused_variables() {
+        // Arrange
+        let config = config(vec![
+            rule(
+                "extract",
+                true,
+                json!({
+                    "constraint": {
+                        "WITH": {
+                            "used": { "from": "${event.type}", "regex": { "match": ".*" } },
+                            "unused": { "from": "${event.type}", "regex": { "match": ".*" } }
+                        }
+                    },
+                    "actions": [{ "id": "logger", "payload": { "value": "${_variables.used}" } }]
+                }),
+            ),
+            rule(
+                "all",
+                true,
+                json!({
+                    "constraint": {
+                        "WITH": {
+                            "value": { "from": "${event.type}", "regex": { "match": ".*" } }
+                        }
+                    }
+                }),
+            ),
+            rule(
+                "whole",
+                true,
+                json!({
+                    "constraint": {},
+                    "actions": [{ "id": "logger", "payload": { "all": "${_variables.all}" } }]
+                }),
+            ),
+        ]);
+
+        // Act
+        let warnings = lint(&config, &MatcherResources::default());
+
+        // Assert
+        assert_eq!(
+            vec![("root.ruleset.extract".to_owned(), LintWarningKind::UnusedVariable)],
+            kinds(&warnings)
+        );
+        assert!(warnings[0].message.contains("[unused]"));
+    }
+
+    #[test]
+    fn should_consider_all_the_previous_variables_used_by_a_bare_variables_accessor() {
+        // Arrange
+        let config = config(vec![
+            rule(
+                "extract",
+                true,
+                json!({
+                    "constraint": {
+                        "WITH": {
+                            "first": { "from": "${event.type}", "regex": { "match": ".*" } }
+                        }
+                    }
+                }),
+            ),
+            rule(
+                "forward",
+                true,
+                json!({
+                    "constraint": {
+                        "WITH": {
+                            "second": { "from": "${event.type}", "regex": { "match": ".*" } }
+                        }
+                    },
+                    "actions": [{ "id": "logger", "payload": { "variables": "${_variables}" } }]
+                }),
+            ),
+            rule(
+                "later",
+                true,
+                json!({
+                    "constraint": {
+                        "WITH": {
+                            "third": { "from": "${event.type}", "regex": { "match": ".*" } }
+                        }
+                    }
+                }),
+            ),
+        ]);
+
+        // Act
+        let warnings = lint(&config, &MatcherResources::default());
+
+        // Assert
+        assert_eq!(
+            vec![("root.ruleset.later".to_owned(), LintWarningKind::UnusedVariable)],
+            kinds(&warnings)
+        );
+    }
+
+    #[test]
+    fn should_detect_the_accessors_in_the_modifier_arguments() {
+        // Arrange
+        let config = config(vec![
+            rule(
+                "extract",
+                true,
+                json!({
+                    "constraint": {
+                        "WITH": {
+                            "host": { "from": "${event.type}", "regex": { "match": ".*" } }
+                        }
+                    }
+                }),
+            ),
+            rule(
+                "replace",
+                true,
+                json!({
+                    "constraint": {
+                        "WITH": {
+                            "line": {
+                                "from": "${event.payload.line}",
+                                "regex": { "match": ".*" },
+                                "modifiers_post": [
+                                    { "type": "ReplaceAll", "find": "HOST", "replace": "${_variables.extract.host}" },
+                                    { "type": "ReplaceAll", "find": "IP", "replace": "${_variables.unknown.ip}" }
+                                ]
+                            }
+                        }
+                    },
+                    "actions": [{ "id": "logger", "payload": { "line": "${_variables.line}" } }]
+                }),
+            ),
+        ]);
+
+        // Act
+        let warnings = lint(&config, &MatcherResources::default());
+
+        // Assert
+        assert_eq!(
+            vec![("root.ruleset.replace".to_owned(), LintWarningKind::UnknownVariable)],
+            kinds(&warnings)
+        );
+        assert!(warnings[0].message.contains("[_variables.unknown.ip]"));
+    }
+
+    #[test]
+    fn should_detect_accessors_to_regex_groups_that_do_not_exist() {
+        // Arrange
+        let config = config(vec![rule(
+            "extract",
+            true,
+            json!({
+                "constraint": {
+                    "WITH": {
+                        "named": {
+                            "from": "${event.payload.line}",
+                            "regex": { "named_match": "(?P<name>[a-z]+)", "all_matches": true }
+                        },
+                        "grok": {
+                            "from": "${event.payload.line}",
+                            "regex": { "grok_match": "%{IP:src}" }
+                        }
+                    }
+                },
+                "actions": [{
+                    "id": "logger",
+                    "payload": {
+                        "names": "${_variables.named[0].name}",
+                        "wrong": "${_variables.extract.named[*].nmae}",
+                        "src": "${_variables.grok.src}",
+                        "dst": "${_variables.grok.dst}"
+                    }
+                }]
+            }),
+        )]);
+
+        // Act
+        let warnings = lint(&config, &MatcherResources::default());
+
+        // Assert
+        assert_eq!(
+            vec![
+                ("root.ruleset.extract".to_owned(), LintWarningKind::UnknownRegexGroup),
+                ("root.ruleset.extract".to_owned(), LintWarningKind::UnknownRegexGroup),
+            ],
+            kinds(&warnings)
+        );
+        assert!(warnings.iter().any(|warning| warning.message.contains("[nmae]")));
+        assert!(warnings.iter().any(|warning| warning.message.contains("[dst]")));
+    }
+}
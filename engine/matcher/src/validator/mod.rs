@@ -1,9 +1,11 @@
 pub mod id;
+pub mod lint;
 
-use crate::config::nodes::{Filter, MatcherIterator};
-use crate::config::rule::Rule;
-use crate::config::MatcherConfig;
+use crate::config::nodes::{Correlation, Enricher, Filter, Heartbeat, MatcherIterator};
+use crate::config::rule::{Operator, Rule};
+use crate::config::{Defaultable, MatcherConfig};
 use crate::error::MatcherError;
+use crate::matcher::operator::ip_in_cidr;
 use log::*;
 use std::fmt::{Display, Formatter};
 
@@ -59,15 +61,24 @@ impl MatcherConfigValidator {
             MatcherConfig::Iterator { name, iterator, nodes } => {
                 self.validate_iterator(name, iterator, nodes, parent)
             }
+            MatcherConfig::Correlation { name, correlation } => {
+                self.validate_correlation(name, correlation, parent)
+            }
+            MatcherConfig::Heartbeat { name, heartbeat } => {
+                self.validate_heartbeat(name, heartbeat, parent)
+            }
+            MatcherConfig::Enricher { name, enricher, nodes } => {
+                self.validate_enricher(name, enricher, nodes, parent)
+            }
         }
     }
 
-    /// Validates that a Filter has a valid name and triggers the validation recursively
-    /// for all filter's nodes.
+    /// Validates that a Filter has a valid name and operator and triggers the validation
+    /// recursively for all filter's nodes.
     fn validate_filter(
         &self,
         name: &str,
-        _filter: &Filter,
+        filter: &Filter,
         nodes: &[MatcherConfig],
         parent: &NodePath,
     ) -> Result<(), MatcherError> {
@@ -75,6 +86,10 @@ impl MatcherConfigValidator {
         let node_path = NodePath::Parent { name, parent, is_iterator: false };
         self.id.validate_filter_name(parent, name)?;
 
+        if let Defaultable::Value(operator) = &filter.filter {
+            self.validate_operator(&node_path, operator)?;
+        }
+
         for node in nodes {
             self.validate_inner(node, &node_path)?;
         }
@@ -106,6 +121,80 @@ impl MatcherConfigValidator {
         Ok(())
     }
 
+    /// Checks that a correlation:
+    /// - has a valid name
+    /// - has at least one step
+    /// - has valid step operators
+    /// - has valid action IDs
+    fn validate_correlation(
+        &self,
+        name: &str,
+        correlation: &Correlation,
+        parent: &NodePath,
+    ) -> Result<(), MatcherError> {
+        debug!("MatcherConfigValidator validate_correlation - validate correlation [{}]", name);
+        let node_path = NodePath::Parent { name, parent, is_iterator: false };
+        self.id.validate_correlation_name(parent, name)?;
+
+        if correlation.steps.is_empty() {
+            return Err(MatcherError::ConfigurationError {
+                message: format!("Correlation in path [{node_path}] must have at least one step"),
+            });
+        }
+
+        for step in &correlation.steps {
+            self.validate_operator(&node_path, step)?;
+        }
+
+        for action in correlation.on_complete.iter().chain(correlation.on_timeout.iter()) {
+            self.id.validate_action_id(&node_path, &action.id)?
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a heartbeat has a valid name and filter operator.
+    fn validate_heartbeat(
+        &self,
+        name: &str,
+        heartbeat: &Heartbeat,
+        parent: &NodePath,
+    ) -> Result<(), MatcherError> {
+        debug!("MatcherConfigValidator validate_heartbeat - validate heartbeat [{}]", name);
+        self.id.validate_heartbeat_name(parent, name)?;
+
+        if let Defaultable::Value(operator) = &heartbeat.filter {
+            let node_path = NodePath::Parent { name, parent, is_iterator: false };
+            self.validate_operator(&node_path, operator)?;
+        }
+
+        Ok(())
+    }
+
+    /// Validates that an enricher has a valid name and valid extracted variable names and
+    /// triggers the validation recursively for all enricher's nodes.
+    fn validate_enricher(
+        &self,
+        name: &str,
+        enricher: &Enricher,
+        nodes: &[MatcherConfig],
+        parent: &NodePath,
+    ) -> Result<(), MatcherError> {
+        debug!("MatcherConfigValidator validate_enricher - validate enricher [{}]", name);
+        let node_path = NodePath::Parent { name, parent, is_iterator: false };
+        self.id.validate_enricher_name(parent, name)?;
+
+        for var_name in enricher.with.keys() {
+            self.id.validate_extracted_var_name(&node_path, var_name)?
+        }
+
+        for node in nodes {
+            self.validate_inner(node, &node_path)?;
+        }
+
+        Ok(())
+    }
+
     /// Validates a set of Rules.
     /// In addition to the checks performed by the validate(rule) method,
     /// it verifies that rule names are unique.
@@ -134,6 +223,7 @@ impl MatcherConfigValidator {
 
     /// Checks that a rule:
     /// - has a valid name
+    /// - has a valid WHERE operator
     /// - has valid extracted variable names
     /// - has valid action IDs
     fn validate_rule(&self, parent: &NodePath, rule: &Rule) -> Result<(), MatcherError> {
@@ -143,6 +233,10 @@ impl MatcherConfigValidator {
         let rule_node = NodePath::Parent { name: rule_name, parent, is_iterator: false };
         self.id.validate_rule_name(parent, rule_name)?;
 
+        if let Some(operator) = &rule.constraint.where_operator {
+            self.validate_operator(&rule_node, operator)?;
+        }
+
         for var_name in rule.constraint.with.keys() {
             self.id.validate_extracted_var_name(&rule_node, var_name)?
         }
@@ -154,6 +248,34 @@ impl MatcherConfigValidator {
         Ok(())
     }
 
+    /// Checks the operator arguments that can be verified without an Event,
+    /// e.g. that the networks of an 'ipInCidr' operator are valid.
+    fn validate_operator(&self, node: &NodePath, operator: &Operator) -> Result<(), MatcherError> {
+        match operator {
+            Operator::And { operators } | Operator::Or { operators } => {
+                for operator in operators {
+                    self.validate_operator(node, operator)?;
+                }
+                Ok(())
+            }
+            Operator::Not { operator } => self.validate_operator(node, operator),
+            Operator::IpInCidr { networks, .. } => {
+                for network in networks {
+                    ip_in_cidr::parse_network(network).map_err(|e| {
+                        MatcherError::ConfigurationError {
+                            message: format!("The ipInCidr operator in path [{node}] has an invalid network: {e}"),
+                        }
+                    })?;
+                }
+                Ok(())
+            }
+            Operator::Expression { expression } => {
+                self.validate_operator(node, &Operator::parse_expression(expression)?)
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn check_unique_name(rule_names: &mut Vec<String>, name: &str) -> Result<(), MatcherError> {
         let name_string = name.to_owned();
         debug!(
@@ -378,8 +500,12 @@ mod test {
     #[test]
     fn build_should_fail_if_wrong_filter_name() {
         // Arrange
-        let filter =
-            Filter { filter: Defaultable::Default {}, active: true, description: "".to_owned() };
+        let filter = Filter {
+            filter: Defaultable::Default {},
+            active: true,
+            description: "".to_owned(),
+            schedule: None,
+        };
 
         // Act
         let matcher = MatcherConfigValidator::new().validate_filter(
@@ -396,8 +522,12 @@ mod test {
     #[test]
     fn should_validate_filter_name() {
         // Arrange
-        let filter =
-            Filter { filter: Defaultable::Default {}, active: true, description: "".to_owned() };
+        let filter = Filter {
+            filter: Defaultable::Default {},
+            active: true,
+            description: "".to_owned(),
+            schedule: None,
+        };
 
         // Act
         let matcher = MatcherConfigValidator::new().validate_filter(
@@ -414,8 +544,12 @@ mod test {
     #[test]
     fn build_should_fail_if_wrong_node_name() {
         // Arrange
-        let filter =
-            Filter { filter: Defaultable::Default {}, active: true, description: "".to_owned() };
+        let filter = Filter {
+            filter: Defaultable::Default {},
+            active: true,
+            description: "".to_owned(),
+            schedule: None,
+        };
 
         let rules = MatcherConfig::Ruleset { name: "wrong.name!".to_owned(), rules: vec![] };
 
@@ -434,8 +568,12 @@ mod test {
     #[test]
     fn should_validate_node_name() {
         // Arrange
-        let filter =
-            Filter { filter: Defaultable::Default {}, active: true, description: "".to_owned() };
+        let filter = Filter {
+            filter: Defaultable::Default {},
+            active: true,
+            description: "".to_owned(),
+            schedule: None,
+        };
 
         let rules = MatcherConfig::Ruleset { name: "good_name".to_owned(), rules: vec![] };
 
@@ -454,8 +592,12 @@ mod test {
     #[test]
     fn should_validate_a_config_recursively() {
         // Arrange
-        let filter1 =
-            Filter { filter: Defaultable::Default {}, active: true, description: "".to_owned() };
+        let filter1 = Filter {
+            filter: Defaultable::Default {},
+            active: true,
+            description: "".to_owned(),
+            schedule: None,
+        };
 
         let filter2 = filter1.clone();
         let rule_1 = new_rule("rule_name", None);
@@ -486,8 +628,12 @@ mod test {
     #[test]
     fn should_validate_a_config_recursively_and_fail_if_wrong_inner_rule_name() {
         // Arrange
-        let filter1 =
-            Filter { filter: Defaultable::Default {}, active: true, description: "".to_owned() };
+        let filter1 = Filter {
+            filter: Defaultable::Default {},
+            active: true,
+            description: "".to_owned(),
+            schedule: None,
+        };
 
         let filter2 = filter1.clone();
         let rule_1 = new_rule("rule.name!", None);
@@ -524,6 +670,83 @@ mod test {
         );
     }
 
+    #[test]
+    fn should_fail_validation_if_wrong_ip_in_cidr_network() {
+        // Arrange
+        let valid_rule = new_rule(
+            "valid",
+            Operator::IpInCidr {
+                target: "${event.payload.source}".to_owned(),
+                networks: vec!["10.0.0.0/8".to_owned(), "2001:db8::/32".to_owned()],
+            },
+        );
+        let invalid_rule = new_rule(
+            "invalid",
+            Operator::Not {
+                operator: Box::new(Operator::IpInCidr {
+                    target: "${event.payload.source}".to_owned(),
+                    networks: vec!["10.0.0.0/8".to_owned(), "10.0.0.300/32".to_owned()],
+                }),
+            },
+        );
+        let invalid_filter = Filter {
+            filter: Defaultable::Value(Operator::IpInCidr {
+                target: "${event.payload.source}".to_owned(),
+                networks: vec!["2001:db8::/129".to_owned()],
+            }),
+            active: true,
+            description: "".to_owned(),
+            schedule: None,
+        };
+        let validator = MatcherConfigValidator::new();
+
+        // Act & Assert
+        assert!(validator.validate_ruleset("ruleset", &[valid_rule], &NodePath::Root).is_ok());
+        assert!(validator.validate_ruleset("ruleset", &[invalid_rule], &NodePath::Root).is_err());
+        assert!(validator
+            .validate_filter("filter", &invalid_filter, &[], &NodePath::Root)
+            .is_err());
+    }
+
+    #[test]
+    fn should_validate_an_enricher_recursively() {
+        // Arrange
+        let extractor = Extractor {
+            from: "${event.payload.host}".to_owned(),
+            regex: ExtractorRegex::Regex {
+                regex: ".*".to_owned(),
+                group_match_idx: Some(0),
+                all_matches: None,
+            },
+            modifiers_post: vec![],
+        };
+        let mut valid_with = HashMap::new();
+        valid_with.insert("host".to_owned(), extractor.clone());
+        let mut invalid_with = HashMap::new();
+        invalid_with.insert("host name".to_owned(), extractor);
+        let enricher =
+            |with| Enricher { description: "".to_owned(), active: true, payload_key: None, with };
+        let ruleset = MatcherConfig::Ruleset {
+            name: "ruleset".to_owned(),
+            rules: vec![new_rule("rule name", None)],
+        };
+        let validator = MatcherConfigValidator::new();
+
+        // Act & Assert
+        assert!(validator
+            .validate_enricher("enricher", &enricher(valid_with.clone()), &[], &NodePath::Root)
+            .is_ok());
+        assert!(validator
+            .validate_enricher("enricher", &enricher(invalid_with), &[], &NodePath::Root)
+            .is_err());
+        assert!(validator
+            .validate_enricher("enricher ", &enricher(valid_with.clone()), &[], &NodePath::Root)
+            .is_err());
+        assert!(validator
+            .validate_enricher("enricher", &enricher(valid_with), &[ruleset], &NodePath::Root)
+            .is_err());
+    }
+
     fn new_rule<O: Into<Option<Operator>>>(name: &str, operator: O) -> Rule {
         let constraint = Constraint { where_operator: operator.into(), with: HashMap::new() };
 
@@ -534,6 +757,9 @@ mod test {
             actions: vec![],
             description: "".to_owned(),
             constraint,
+            threshold: None,
+            suppression: None,
+            schedule: None,
         }
     }
 }
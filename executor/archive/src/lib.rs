@@ -36,24 +36,24 @@ impl std::fmt::Display for ArchiveExecutor {
 }
 
 impl ArchiveExecutor {
-    pub fn new(config: &config::ArchiveConfig) -> ArchiveExecutor {
+    pub fn new(config: &config::ArchiveConfig) -> Result<ArchiveExecutor, ExecutorError> {
         let builder = paths::PathMatcherBuilder::new();
         let paths = config
             .paths
             .iter()
-            .map(|(key, value)| (key.to_owned(), builder.build(value.to_owned())))
-            .collect::<HashMap<String, paths::PathMatcher>>();
+            .map(|(key, value)| Ok((key.to_owned(), builder.build(value.to_owned())?)))
+            .collect::<Result<HashMap<String, paths::PathMatcher>, ExecutorError>>()?;
 
         let time_to_live = ::std::time::Duration::from_secs(config.file_cache_ttl_secs);
         let file_cache =
             LruCache::with_expiry_duration_and_capacity(time_to_live, config.file_cache_size);
 
-        ArchiveExecutor {
+        Ok(ArchiveExecutor {
             base_path: config.base_path.clone(),
             default_path: config.default_path.clone(),
             paths,
             file_cache,
-        }
+        })
     }
 
     #[instrument(level = "debug", name = "Extract parameters for Executor", skip_all)]
@@ -216,7 +216,7 @@ mod test {
 
         println!("Expected file path: [{}]", &expected_path);
 
-        let mut archiver = ArchiveExecutor::new(&config);
+        let mut archiver = ArchiveExecutor::new(&config).unwrap();
 
         let event = Event::new("event-name");
         let mut action = Action::new("action");
@@ -256,7 +256,7 @@ mod test {
 
         println!("Expected file path: [{}]", &expected_path);
 
-        let mut archiver = ArchiveExecutor::new(&config);
+        let mut archiver = ArchiveExecutor::new(&config).unwrap();
 
         let attempts = 10;
         let mut sent_events = vec![];
@@ -307,7 +307,7 @@ mod test {
 
         config.paths.insert("one".to_owned(), "/one/${key_one}/${key_two}.log".to_owned());
 
-        let mut archiver = ArchiveExecutor::new(&config);
+        let mut archiver = ArchiveExecutor::new(&config).unwrap();
 
         let event = Event::new("event-name");
         let mut action = Action::new("action");
@@ -338,7 +338,7 @@ mod test {
 
         config.paths.insert("one".to_owned(), "/one/${key_one}/${key_two}.log".to_owned());
 
-        let mut archiver = ArchiveExecutor::new(&config);
+        let mut archiver = ArchiveExecutor::new(&config).unwrap();
 
         let event = Event::new("event-name");
         let mut action = Action::new("action");
@@ -367,7 +367,7 @@ mod test {
 
         config.paths.insert("one".to_owned(), "/one/${key_one}/${key_two}.log".to_owned());
 
-        let mut archiver = ArchiveExecutor::new(&config);
+        let mut archiver = ArchiveExecutor::new(&config).unwrap();
 
         let event = Event::new("event-name");
         let mut action = Action::new("action");
@@ -397,7 +397,7 @@ mod test {
         let expected_path = format!("{}/{}", &dir, "/default/file.out");
         println!("Expected file path: [{}]", &expected_path);
 
-        let mut archiver = ArchiveExecutor::new(&config);
+        let mut archiver = ArchiveExecutor::new(&config).unwrap();
 
         let event = Event::new("event-name");
         let mut action = Action::new("action");
@@ -1,6 +1,8 @@
 use log::*;
 use regex::Regex;
+use std::borrow::Cow;
 use tornado_common_api::Payload;
+use tornado_common_parser::{apply_filters, parse_filtered_expression, ValueFilter};
 use tornado_executor_common::ExecutorError;
 
 const PATH_REGEX: &str = r"\$\{[^\}]+\}";
@@ -22,20 +24,25 @@ impl PathMatcherBuilder {
         Default::default()
     }
 
-    pub fn build<S: Into<String>>(&self, source: S) -> PathMatcher {
+    pub fn build<S: Into<String>>(&self, source: S) -> Result<PathMatcher, ExecutorError> {
         let path = source.into();
         let mut variables = vec![];
         for capture in self.regex.captures_iter(&path) {
             if let Some(value) = capture.get(0) {
                 let group = value.as_str();
-                let param = Parameter {
-                    simple: group[2..group.len() - 1].to_owned(),
-                    full: group.to_owned(),
-                };
+                let (simple, filters) = parse_filtered_expression(&group[2..group.len() - 1])
+                    .map_err(|err| ExecutorError::ConfigurationError {
+                        message: format!(
+                            "Invalid path parameter [{}] for path [{}]: {}",
+                            group, path, err
+                        ),
+                    })?;
+                let param =
+                    Parameter { simple: simple.to_owned(), full: group.to_owned(), filters };
                 variables.push(param)
             }
         }
-        PathMatcher { path, parameters: variables }
+        Ok(PathMatcher { path, parameters: variables })
     }
 }
 
@@ -48,14 +55,17 @@ pub struct PathMatcher {
 pub struct Parameter {
     pub simple: String,
     pub full: String,
+    pub filters: Vec<ValueFilter>,
 }
 
 impl PathMatcher {
     pub fn build_path(&self, payload: &Payload) -> Result<String, ExecutorError> {
         let mut path = self.path.clone();
         for param in self.parameters.iter() {
-            let var_value = payload
-                .get(&param.simple)
+            let var_value =
+                apply_filters(&param.filters, payload.get(&param.simple).map(Cow::Borrowed));
+            let var_value = var_value
+                .as_deref()
                 .and_then(tornado_common_api::ValueExt::get_text)
                 .ok_or_else(|| {
                     let message = format!(
@@ -89,17 +99,21 @@ mod test {
         let empty: Vec<Parameter> = vec![];
 
         // Assert
-        assert_eq!(empty, builder.build("").parameters);
+        assert_eq!(empty, builder.build("").unwrap().parameters);
         assert_eq!(
             vec![
-                Parameter { simple: "one".to_owned(), full: "${one}".to_owned() },
-                Parameter { simple: "two".to_owned(), full: "${two}".to_owned() }
+                Parameter { simple: "one".to_owned(), full: "${one}".to_owned(), filters: vec![] },
+                Parameter { simple: "two".to_owned(), full: "${two}".to_owned(), filters: vec![] }
             ],
-            builder.build("/dir/${one}/${two}").parameters
+            builder.build("/dir/${one}/${two}").unwrap().parameters
         );
         assert_eq!(
-            vec![Parameter { simple: "one_tw.o".to_owned(), full: "${one_tw.o}".to_owned() }],
-            builder.build("/dir/${one_tw.o}").parameters
+            vec![Parameter {
+                simple: "one_tw.o".to_owned(),
+                full: "${one_tw.o}".to_owned(),
+                filters: vec![]
+            }],
+            builder.build("/dir/${one_tw.o}").unwrap().parameters
         );
     }
 
@@ -107,7 +121,7 @@ mod test {
     fn should_return_expected_path() {
         // Arrange
         let builder = PathMatcherBuilder::new();
-        let path_matcher = builder.build("/dir/${one}/${two}");
+        let path_matcher = builder.build("/dir/${one}/${two}").unwrap();
 
         let mut payload = Payload::new();
         payload.insert("one".to_owned(), Value::String("one_value".to_owned()));
@@ -124,7 +138,7 @@ mod test {
     fn should_return_error_if_missing_variables() {
         // Arrange
         let builder = PathMatcherBuilder::new();
-        let path_matcher = builder.build("/dir/${one}/${two}");
+        let path_matcher = builder.build("/dir/${one}/${two}").unwrap();
 
         let payload = Payload::new();
 
@@ -139,7 +153,7 @@ mod test {
     fn should_return_error_if_at_least_one_var_is_missing() {
         // Arrange
         let builder = PathMatcherBuilder::new();
-        let path_matcher = builder.build("/dir/${one}/${two}/${three}");
+        let path_matcher = builder.build("/dir/${one}/${two}/${three}").unwrap();
 
         let mut payload = Payload::new();
         payload.insert("one".to_owned(), Value::String("one_value".to_owned()));
@@ -156,7 +170,7 @@ mod test {
     fn should_resolve_path_with_repeated_vars() {
         // Arrange
         let builder = PathMatcherBuilder::new();
-        let path_matcher = builder.build("/dir/${one}/${two}/${one}");
+        let path_matcher = builder.build("/dir/${one}/${two}/${one}").unwrap();
 
         let mut payload = Payload::new();
         payload.insert("one".to_owned(), Value::String("one_value".to_owned()));
@@ -173,7 +187,7 @@ mod test {
     fn should_always_return_path_if_it_has_no_parameters() {
         // Arrange
         let builder = PathMatcherBuilder::new();
-        let path_matcher = builder.build("/dir/one/two");
+        let path_matcher = builder.build("/dir/one/two").unwrap();
 
         let payload = Payload::new();
 
@@ -183,4 +197,33 @@ mod test {
         // Assert
         assert_eq!("/dir/one/two", result);
     }
+
+    #[test]
+    fn should_apply_the_filters_of_the_parameters() {
+        // Arrange
+        let builder = PathMatcherBuilder::new();
+        let path_matcher =
+            builder.build(r#"/dir/${one | lower}/${two | default("none")}.log"#).unwrap();
+
+        let mut payload = Payload::new();
+        payload.insert("one".to_owned(), Value::String("ONE_Value".to_owned()));
+
+        // Act
+        let result = path_matcher.build_path(&payload).unwrap();
+
+        // Assert
+        assert_eq!("/dir/one_value/none.log", result);
+    }
+
+    #[test]
+    fn build_should_fail_with_invalid_filters() {
+        // Arrange
+        let builder = PathMatcherBuilder::new();
+
+        // Act
+        let result = builder.build("/dir/${one | capitalize}");
+
+        // Assert
+        assert!(result.is_err());
+    }
 }
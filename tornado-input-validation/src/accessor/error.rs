@@ -7,6 +7,8 @@ pub enum AccessorErrorKind {
     NotANumberError,
     InvalidCharacterError,
     EmptyAccessorError,
+    InvalidFilterError,
+    InvalidSliceError,
 }
 
 // The AccessorError struct must be "inspectable" because otherwise the
@@ -48,6 +50,11 @@ impl From<tornado_common_parser::ParserError> for AccessorError {
                 key: Some(key),
                 character: None,
             },
+            tornado_common_parser::ParserError::InvalidSliceError { key } => AccessorError {
+                kind: AccessorErrorKind::InvalidSliceError,
+                key: Some(key),
+                character: None,
+            },
             tornado_common_parser::ParserError::InvalidCharacterError { key, character } => {
                 AccessorError {
                     kind: AccessorErrorKind::InvalidCharacterError,
@@ -60,6 +67,13 @@ impl From<tornado_common_parser::ParserError> for AccessorError {
                 key: None,
                 character: None,
             },
+            tornado_common_parser::ParserError::InvalidFilterError { filter, .. } => {
+                AccessorError {
+                    kind: AccessorErrorKind::InvalidFilterError,
+                    key: Some(filter),
+                    character: None,
+                }
+            }
         }
     }
 }
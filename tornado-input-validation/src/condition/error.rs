@@ -0,0 +1,66 @@
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub enum ConditionErrorKind {
+    UnexpectedCharacter,
+    UnterminatedLiteral,
+    InvalidLiteral,
+    UnexpectedToken,
+    UnexpectedEnd,
+    UnknownFunction,
+    InvalidArguments,
+    InvalidAccessor,
+    TooDeeplyNested,
+}
+
+// The ConditionError struct must be "inspectable" because otherwise the
+// translation module in the UI cannot access its properties.
+#[wasm_bindgen(inspectable)]
+#[derive(Clone)]
+pub struct ConditionError {
+    kind: ConditionErrorKind,
+    message: String,
+    line: usize,
+    column: usize,
+}
+
+#[wasm_bindgen]
+impl ConditionError {
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> String {
+        format!("{:?}", self.kind)
+    }
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        self.message.clone()
+    }
+    /// The line of the error, starting from 1
+    #[wasm_bindgen(getter)]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+    /// The column of the error, starting from 1
+    #[wasm_bindgen(getter)]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl From<tornado_common_parser::ConditionError> for ConditionError {
+    fn from(value: tornado_common_parser::ConditionError) -> Self {
+        use tornado_common_parser::ConditionErrorKind as Kind;
+        let kind = match value.kind {
+            Kind::UnexpectedCharacter => ConditionErrorKind::UnexpectedCharacter,
+            Kind::UnterminatedLiteral => ConditionErrorKind::UnterminatedLiteral,
+            Kind::InvalidLiteral => ConditionErrorKind::InvalidLiteral,
+            Kind::UnexpectedToken => ConditionErrorKind::UnexpectedToken,
+            Kind::UnexpectedEnd => ConditionErrorKind::UnexpectedEnd,
+            Kind::UnknownFunction => ConditionErrorKind::UnknownFunction,
+            Kind::InvalidArguments => ConditionErrorKind::InvalidArguments,
+            Kind::InvalidAccessor => ConditionErrorKind::InvalidAccessor,
+            Kind::TooDeeplyNested => ConditionErrorKind::TooDeeplyNested,
+        };
+        Self { kind, message: value.message, line: value.line, column: value.column }
+    }
+}
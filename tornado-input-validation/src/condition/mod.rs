@@ -0,0 +1,29 @@
+use crate::condition::error::ConditionError;
+use tornado_common_parser::Condition;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+mod error;
+
+#[wasm_bindgen]
+pub struct ConditionValidationResult {
+    pub is_valid: bool,
+    error: Option<ConditionError>,
+}
+
+#[wasm_bindgen]
+impl ConditionValidationResult {
+    #[wasm_bindgen(getter)]
+    pub fn error(&self) -> Option<ConditionError> {
+        self.error.clone()
+    }
+}
+
+/// Validates the textual form of a Rule condition,
+/// e.g. `event.type == "syslog" && payload.severity <= 3`.
+#[wasm_bindgen]
+pub fn validate_condition(input: &str) -> ConditionValidationResult {
+    match Condition::parse(input) {
+        Ok(_) => ConditionValidationResult { is_valid: true, error: None },
+        Err(error) => ConditionValidationResult { is_valid: false, error: Some(error.into()) },
+    }
+}
@@ -0,0 +1,78 @@
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub enum GrokErrorKind {
+    UnknownPatternError,
+    RecursivePatternError,
+    InvalidReferenceError,
+    UnclosedReferenceError,
+    InvalidPatternDefinitionError,
+}
+
+// The GrokError struct must be "inspectable" because otherwise the
+// translation module in the UI cannot access its properties.
+#[wasm_bindgen(inspectable)]
+#[derive(Clone)]
+pub struct GrokError {
+    kind: GrokErrorKind,
+    name: Option<String>,
+    reference: Option<String>,
+    position: Option<usize>,
+    definition: Option<String>,
+}
+
+#[wasm_bindgen]
+impl GrokError {
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> String {
+        format!("{:?}", self.kind)
+    }
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+    #[wasm_bindgen(getter)]
+    pub fn reference(&self) -> Option<String> {
+        self.reference.clone()
+    }
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+    #[wasm_bindgen(getter)]
+    pub fn definition(&self) -> Option<String> {
+        self.definition.clone()
+    }
+}
+
+impl GrokError {
+    fn new(kind: GrokErrorKind) -> Self {
+        Self { kind, name: None, reference: None, position: None, definition: None }
+    }
+}
+
+impl From<tornado_common_grok::GrokError> for GrokError {
+    fn from(value: tornado_common_grok::GrokError) -> Self {
+        match value {
+            tornado_common_grok::GrokError::UnknownPatternError { name } => {
+                Self { name: Some(name), ..Self::new(GrokErrorKind::UnknownPatternError) }
+            }
+            tornado_common_grok::GrokError::RecursivePatternError { name } => {
+                Self { name: Some(name), ..Self::new(GrokErrorKind::RecursivePatternError) }
+            }
+            tornado_common_grok::GrokError::InvalidReferenceError { reference } => Self {
+                reference: Some(reference),
+                ..Self::new(GrokErrorKind::InvalidReferenceError)
+            },
+            tornado_common_grok::GrokError::UnclosedReferenceError { position } => Self {
+                position: Some(position),
+                ..Self::new(GrokErrorKind::UnclosedReferenceError)
+            },
+            tornado_common_grok::GrokError::InvalidPatternDefinitionError { definition } => Self {
+                definition: Some(definition),
+                ..Self::new(GrokErrorKind::InvalidPatternDefinitionError)
+            },
+        }
+    }
+}
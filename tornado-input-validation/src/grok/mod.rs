@@ -0,0 +1,50 @@
+use crate::grok::error::GrokError;
+use crate::regex::error::RegexError;
+use crate::regex::validate_regex;
+use tornado_common_grok::GrokPatterns;
+use wasm_bindgen::prelude::*;
+
+mod error;
+
+#[wasm_bindgen]
+pub struct GrokValidationResult {
+    pub is_valid: bool,
+    pub has_named_groups: bool,
+    error: Option<GrokError>,
+    /// The error of the regex obtained by expanding the grok expression.
+    /// Its span refers to the expanded regex.
+    pub regex_error: Option<RegexError>,
+}
+
+#[wasm_bindgen]
+impl GrokValidationResult {
+    #[wasm_bindgen(getter)]
+    pub fn error(&self) -> Option<GrokError> {
+        self.error.clone()
+    }
+}
+
+/// Validates a grok expression against the built-in patterns and the custom ones,
+/// which are passed with the content of a pattern file.
+#[wasm_bindgen]
+pub fn validate_grok(expression: &str, custom_patterns: &str) -> GrokValidationResult {
+    let mut patterns = GrokPatterns::default();
+    let expanded = patterns.add_patterns(custom_patterns).and_then(|_| patterns.expand(expression));
+    match expanded {
+        Ok(regex) => {
+            let result = validate_regex(&regex);
+            GrokValidationResult {
+                is_valid: result.is_valid,
+                has_named_groups: result.has_named_groups,
+                error: None,
+                regex_error: result.error,
+            }
+        }
+        Err(error) => GrokValidationResult {
+            is_valid: false,
+            has_named_groups: false,
+            error: Some(error.into()),
+            regex_error: None,
+        },
+    }
+}
@@ -1,7 +1,11 @@
 #![allow(clippy::enum_variant_names)]
 
 mod accessor;
+mod condition;
+mod grok;
 mod regex;
 
 pub use accessor::{validate_accessor, AccessorValidationResult};
+pub use condition::{validate_condition, ConditionValidationResult};
+pub use grok::{validate_grok, GrokValidationResult};
 pub use regex::{validate_regex, RegexValidationResult};
@@ -2,7 +2,7 @@ use crate::regex::error::RegexError;
 use regex_syntax::hir::{Hir, HirKind};
 use wasm_bindgen::prelude::*;
 
-mod error;
+pub(crate) mod error;
 
 #[wasm_bindgen]
 pub struct RegexValidationResult {
@@ -4,17 +4,25 @@ use actix::prelude::*;
 use log::*;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use tornado_common_api::{Value, WithEventData};
 use tornado_engine_api::event::api::ProcessType;
 use tornado_engine_matcher::config::operation::{matcher_config_filter, NodeFilter};
 use tornado_engine_matcher::config::{MatcherConfig, MatcherConfigEditor};
 use tornado_engine_matcher::error::MatcherError;
+use tornado_engine_matcher::lookup::LookupTables;
+use tornado_engine_matcher::matcher::state::MatcherState;
+use tornado_engine_matcher::matcher::thread_pool::MatcherThreadPool;
 use tornado_engine_matcher::matcher::Matcher;
 use tornado_engine_matcher::model::ProcessedEvent;
+use tornado_engine_matcher::resources::MatcherResources;
 use tornado_engine_matcher::{error, matcher};
 use tracing::{instrument, Span};
 
+/// How often the expired sequences of the Correlation nodes and the missing keys of the
+/// Heartbeat nodes are checked.
+const STATEFUL_NODES_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Message)]
 #[rtype(result = "Result<ProcessedEvent, error::MatcherError>")]
 pub struct EventMessageWithReply {
@@ -22,6 +30,7 @@ pub struct EventMessageWithReply {
     pub config_filter: HashMap<String, NodeFilter>,
     pub process_type: ProcessType,
     pub include_metadata: bool,
+    pub include_trace: bool,
     pub span: Span,
 }
 
@@ -32,6 +41,7 @@ pub struct EventMessageAndConfigWithReply {
     pub matcher_config: MatcherConfig,
     pub process_type: ProcessType,
     pub include_metadata: bool,
+    pub include_trace: bool,
 }
 
 #[derive(Message)]
@@ -49,27 +59,69 @@ pub struct ReconfigureMessage {}
 #[rtype(result = "Arc<MatcherConfig>")]
 pub struct GetCurrentConfigMessage {}
 
+#[derive(Message)]
+#[rtype(result = "Result<Arc<LookupTables>, error::MatcherError>")]
+pub struct ReloadLookupTablesMessage {
+    pub lookup_tables: LookupTables,
+}
+
 pub struct MatcherActor {
     dispatcher_addr: Recipient<ProcessedEventMessage>,
     matcher_config_manager: Arc<dyn MatcherConfigEditor>,
     matcher_config: Arc<MatcherConfig>,
     matcher: Arc<matcher::Matcher>,
+    matcher_state: MatcherState,
+    thread_pool: Option<MatcherThreadPool>,
     meter: Arc<TornadoMeter>,
 }
 
+/// Builds a Matcher that processes in parallel the independent nodes of the processing tree
+/// when a thread pool is provided.
+fn build_matcher(
+    matcher_config: &MatcherConfig,
+    matcher_state: MatcherState,
+    resources: &MatcherResources,
+    thread_pool: &Option<MatcherThreadPool>,
+) -> Result<Matcher, MatcherError> {
+    let matcher = Matcher::build_with_state(matcher_config, matcher_state, resources)?;
+    Ok(with_thread_pool(matcher, thread_pool))
+}
+
+fn with_thread_pool(matcher: Matcher, thread_pool: &Option<MatcherThreadPool>) -> Matcher {
+    match thread_pool {
+        Some(thread_pool) => matcher.with_thread_pool(thread_pool.clone()),
+        None => matcher,
+    }
+}
+
 impl MatcherActor {
     pub async fn start(
         dispatcher_addr: Recipient<ProcessedEventMessage>,
         matcher_config_manager: Arc<dyn MatcherConfigEditor>,
         message_mailbox_capacity: usize,
+        thread_pool: Option<MatcherThreadPool>,
         meter: Arc<TornadoMeter>,
     ) -> Result<Addr<MatcherActor>, MatcherError> {
         let matcher_config = Arc::new(matcher_config_manager.get_config().await?);
-        let matcher = Arc::new(Matcher::build(&matcher_config)?);
+        let matcher_state = MatcherState::new();
+        let matcher = Arc::new(build_matcher(
+            &matcher_config,
+            matcher_state.clone(),
+            &matcher_config_manager.get_resources(),
+            &thread_pool,
+        )?);
 
         Ok(actix::Supervisor::start(move |ctx: &mut Context<MatcherActor>| {
             ctx.set_mailbox_capacity(message_mailbox_capacity);
-            MatcherActor { dispatcher_addr, matcher_config_manager, matcher_config, matcher, meter }
+            MatcherActor {
+                dispatcher_addr,
+                matcher_config_manager,
+                matcher_config,
+                matcher,
+                matcher_state,
+                thread_pool,
+                meter,
+            }
         }))
     }
 
@@ -79,8 +131,9 @@ impl MatcherActor {
         event: Value,
         process_type: ProcessType,
         include_metadata: bool,
+        include_trace: bool,
     ) -> ProcessedEvent {
-        let processed_event = self.process(matcher, event, include_metadata);
+        let processed_event = self.process(matcher, event, include_metadata, include_trace);
 
         match process_type {
             ProcessType::Full => self
@@ -94,7 +147,13 @@ impl MatcherActor {
 
     #[inline]
     #[instrument(level = "info", name = "Match against Processing Tree", skip_all)]
-    fn process(&self, matcher: &Matcher, event: Value, include_metadata: bool) -> ProcessedEvent {
+    fn process(
+        &self,
+        matcher: &Matcher,
+        event: Value,
+        include_metadata: bool,
+        include_trace: bool,
+    ) -> ProcessedEvent {
         let timer = SystemTime::now();
         let labels = [EVENT_TYPE_LABEL_KEY.string(
             event
@@ -103,7 +162,11 @@ impl MatcherActor {
                 .unwrap_or_else(|| "".to_owned()),
         )];
 
-        let process = matcher.process(event, include_metadata);
+        let process = if include_trace {
+            matcher.process_with_trace(event, include_metadata)
+        } else {
+            matcher.process(event, include_metadata)
+        };
 
         self.meter.events_processed_counter.add(1, &labels);
         self.meter
@@ -112,12 +175,37 @@ impl MatcherActor {
 
         process
     }
+
+    /// Dispatches the timeout actions of the expired sequences of the Correlation nodes.
+    fn expire_correlations(&self) {
+        for processed_event in self.matcher.expire_correlations() {
+            trace!("MatcherActor - correlation sequence expired [{:?}]", &processed_event.event);
+            self.dispatcher_addr.try_send(ProcessedEventMessage { span: tracing::Span::current(), event: processed_event }).unwrap_or_else(|err| error!("MatcherActor -  Error while sending ProcessedEventMessage to DispatcherActor. Error: {}", err));
+        }
+    }
+
+    /// Processes the synthetic events generated by the Heartbeat nodes like any other
+    /// incoming Event.
+    fn check_heartbeats(&self) {
+        for event in self.matcher.check_heartbeats() {
+            let span = tracing::info_span!("Process heartbeat event");
+            let _g = span.clone().entered();
+            trace!("MatcherActor - processing heartbeat event [{:?}]", &event);
+
+            let processed_event = self.process(&self.matcher, event, false, false);
+            self.dispatcher_addr.try_send(ProcessedEventMessage { span, event: processed_event }).unwrap_or_else(|err| error!("MatcherActor -  Error while sending ProcessedEventMessage to DispatcherActor. Error: {}", err));
+        }
+    }
 }
 
 impl Actor for MatcherActor {
     type Context = Context<Self>;
-    fn started(&mut self, _ctx: &mut Self::Context) {
+    fn started(&mut self, ctx: &mut Self::Context) {
         debug!("MatcherActor started.");
+        ctx.run_interval(STATEFUL_NODES_CHECK_INTERVAL, |actor, _ctx| {
+            actor.expire_correlations();
+            actor.check_heartbeats();
+        });
     }
 }
 
@@ -134,7 +222,7 @@ impl Handler<EventMessage> for MatcherActor {
         let _g = msg.span.clone().entered();
         trace!("MatcherActor - received new EventMessage [{:?}]", &msg.event);
 
-        let processed_event = self.process(&self.matcher, msg.event, false);
+        let processed_event = self.process(&self.matcher, msg.event, false, false);
         self.dispatcher_addr.try_send(ProcessedEventMessage { span: msg.span, event: processed_event }).unwrap_or_else(|err| error!("MatcherActor -  Error while sending ProcessedEventMessage to DispatcherActor. Error: {}", err));
         Ok(())
     }
@@ -151,13 +239,28 @@ impl Handler<EventMessageWithReply> for MatcherActor {
             .ok_or_else(|| MatcherError::ConfigurationError {
                 message: "The config filter does not match any existing node".to_owned(),
             })?;
-        let matcher = Matcher::build(&filtered_config)?;
+        // Events processed without actions must not alter the state of the engine rules
+        let matcher_state = match msg.process_type {
+            ProcessType::Full => self.matcher_state.clone(),
+            ProcessType::SkipActions => self.matcher_state.snapshot(),
+        };
+        // The filtered config is a part of the processing tree: the state of the other nodes
+        // must be kept
+        let matcher = with_thread_pool(
+            Matcher::build_with_shared_state(
+                &filtered_config,
+                matcher_state,
+                &self.matcher_config_manager.get_resources(),
+            )?,
+            &self.thread_pool,
+        );
 
         Ok(self.process_event_with_reply(
             &matcher,
             msg.event,
             msg.process_type,
             msg.include_metadata,
+            msg.include_trace,
         ))
     }
 }
@@ -172,12 +275,18 @@ impl Handler<EventMessageAndConfigWithReply> for MatcherActor {
     ) -> Self::Result {
         trace!("MatcherActor - received new EventMessageAndConfigWithReply [{:?}]", msg);
 
-        let matcher = Matcher::build(&msg.matcher_config)?;
+        let matcher = build_matcher(
+            &msg.matcher_config,
+            MatcherState::new(),
+            &self.matcher_config_manager.get_resources(),
+            &self.thread_pool,
+        )?;
         Ok(self.process_event_with_reply(
             &matcher,
             msg.event,
             msg.process_type,
             msg.include_metadata,
+            msg.include_trace,
         ))
     }
 }
@@ -196,12 +305,19 @@ impl Handler<ReconfigureMessage> for MatcherActor {
 
     fn handle(&mut self, _msg: ReconfigureMessage, _ctx: &mut Context<Self>) -> Self::Result {
         let matcher_config_manager = self.matcher_config_manager.clone();
+        let matcher_state = self.matcher_state.clone();
+        let thread_pool = self.thread_pool.clone();
         info!("MatcherActor - received ReconfigureMessage.");
 
         Box::pin(
             async move {
                 let matcher_config = Arc::new(matcher_config_manager.get_config().await?);
-                let matcher = Arc::new(Matcher::build(&matcher_config)?);
+                let matcher = Arc::new(build_matcher(
+                    &matcher_config,
+                    matcher_state,
+                    &matcher_config_manager.get_resources(),
+                    &thread_pool,
+                )?);
                 Ok((matcher, matcher_config))
             }
             .into_actor(self) // converts future to ActorFuture
@@ -221,6 +337,32 @@ impl Handler<ReconfigureMessage> for MatcherActor {
     }
 }
 
+impl Handler<ReloadLookupTablesMessage> for MatcherActor {
+    type Result = Result<Arc<LookupTables>, error::MatcherError>;
+
+    fn handle(&mut self, msg: ReloadLookupTablesMessage, _: &mut Context<Self>) -> Self::Result {
+        info!("MatcherActor - received ReloadLookupTablesMessage.");
+        let resources =
+            self.matcher_config_manager.get_resources().with_lookup_tables(msg.lookup_tables);
+        // The current configuration is rebuilt with the new tables, so that they are rejected
+        // if a Rule references a table that does not exist anymore.
+        let matcher = build_matcher(
+            &self.matcher_config,
+            self.matcher_state.clone(),
+            &resources,
+            &self.thread_pool,
+        )
+        .map_err(|err| {
+            error!("MatcherActor - Cannot reload the lookup tables: {:?}", err);
+            err
+        })?;
+        self.matcher = Arc::new(matcher);
+        self.matcher_config_manager.set_resources(resources.clone());
+        info!("MatcherActor - Lookup tables reloaded successfully.");
+        Ok(resources.lookup_tables)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -230,7 +372,11 @@ mod test {
     use maplit::hashmap;
     use serde_json::json;
     use tornado_common_api::{Event, Value};
+    use tornado_engine_matcher::config::nodes::Filter;
+    use tornado_engine_matcher::config::rule::Rule;
     use tornado_engine_matcher::config::v1::fs::ROOT_NODE_NAME;
+    use tornado_engine_matcher::config::Defaultable;
+    use tornado_engine_matcher::lookup::LookupTable;
     use tornado_engine_matcher::model::ProcessedFilterStatus;
     use tornado_engine_matcher::model::ProcessedNode;
 
@@ -245,10 +391,15 @@ mod test {
         let config_manager = configs.matcher_config.clone();
         let dispatcher_addr = FakeDispatcher {}.start().recipient();
 
-        let matcher_actor =
-            MatcherActor::start(dispatcher_addr, config_manager.clone(), 10, Default::default())
-                .await
-                .unwrap();
+        let matcher_actor = MatcherActor::start(
+            dispatcher_addr,
+            config_manager.clone(),
+            10,
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
 
         let draft_id = config_manager.create_draft("user_1".to_owned()).await.unwrap();
         let draft = config_manager.get_draft(&draft_id).await.unwrap();
@@ -263,6 +414,106 @@ mod test {
         assert_eq!(config_from_response, draft.config);
     }
 
+    #[actix::test]
+    async fn should_rebuild_the_matcher_only_if_the_lookup_tables_are_valid() {
+        // Arrange
+        let tempdir = tempfile::tempdir().unwrap();
+        let (config_dir, rules_dir, drafts_dir) = prepare_temp_dirs(&tempdir);
+
+        let configs = parse_config_files(&config_dir, &rules_dir, &drafts_dir).unwrap();
+
+        let config_manager = configs.matcher_config.clone();
+        let rule: Rule = serde_json::from_value(json!({
+            "name": "lookup_team",
+            "description": "",
+            "continue": true,
+            "active": true,
+            "constraint": {
+                "WHERE": null,
+                "WITH": {
+                    "team": {
+                        "from": "${event.payload.host}",
+                        "regex": { "match": ".*", "group_match_idx": 0 },
+                        "modifiers_post": [{ "type": "Lookup", "table": "teams", "field": "team" }]
+                    }
+                }
+            },
+            "actions": []
+        }))
+        .unwrap();
+        let config = MatcherConfig::Filter {
+            name: ROOT_NODE_NAME.to_owned(),
+            filter: Filter {
+                description: "".to_owned(),
+                active: true,
+                filter: Defaultable::Default {},
+                schedule: None,
+            },
+            nodes: vec![MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![rule] }],
+        };
+        config_manager.deploy_config(&config).await.unwrap();
+
+        let dispatcher_addr = FakeDispatcher {}.start().recipient();
+        let matcher_actor = MatcherActor::start(
+            dispatcher_addr,
+            config_manager.clone(),
+            10,
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
+
+        // Act
+        let result_without_teams = matcher_actor
+            .send(ReloadLookupTablesMessage { lookup_tables: LookupTables::default() })
+            .await
+            .unwrap();
+        let lookup_tables_after_failure = config_manager.get_resources().lookup_tables;
+
+        let mut lookup_tables = LookupTables::default();
+        lookup_tables
+            .add(LookupTable::from_csv("teams", "", "host,team\nweb01,ops\n").unwrap())
+            .unwrap();
+        let result_with_teams =
+            matcher_actor.send(ReloadLookupTablesMessage { lookup_tables }).await.unwrap();
+
+        // Assert
+        assert!(result_without_teams.is_err());
+        assert_eq!(3, lookup_tables_after_failure.get("teams").unwrap().len());
+
+        let reloaded_tables = result_with_teams.unwrap();
+        assert_eq!(1, reloaded_tables.get("teams").unwrap().len());
+        assert_eq!(reloaded_tables, config_manager.get_resources().lookup_tables);
+
+        let mut event = Event::new("test");
+        event.payload.insert("host".to_owned(), json!("web01"));
+        let processed_event = matcher_actor
+            .send(EventMessageWithReply {
+                event: json!(event),
+                config_filter: hashmap![ROOT_NODE_NAME.to_owned() => NodeFilter::AllChildren],
+                process_type: ProcessType::SkipActions,
+                include_metadata: false,
+                include_trace: false,
+                span: Span::current(),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        match processed_event.result {
+            ProcessedNode::Filter { nodes, .. } => match &nodes[0] {
+                ProcessedNode::Ruleset { rules, .. } => {
+                    assert_eq!(
+                        Some(&json!("ops")),
+                        rules.extracted_vars.get("lookup_team").and_then(|vars| vars.get("team"))
+                    );
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
     #[actix::test]
     async fn should_return_the_current_config() {
         // Arrange
@@ -273,10 +524,15 @@ mod test {
 
         let config_manager = configs.matcher_config.clone();
         let dispatcher_addr = FakeDispatcher {}.start().recipient();
-        let matcher_actor =
-            MatcherActor::start(dispatcher_addr, config_manager.clone(), 10, Default::default())
-                .await
-                .unwrap();
+        let matcher_actor = MatcherActor::start(
+            dispatcher_addr,
+            config_manager.clone(),
+            10,
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
 
         // Act
         let returned_config = matcher_actor.send(GetCurrentConfigMessage {}).await.unwrap();
@@ -296,10 +552,15 @@ mod test {
 
         let config_manager = configs.matcher_config.clone();
         let dispatcher_addr = FakeDispatcher {}.start().recipient();
-        let matcher_actor =
-            MatcherActor::start(dispatcher_addr, config_manager.clone(), 10, Default::default())
-                .await
-                .unwrap();
+        let matcher_actor = MatcherActor::start(
+            dispatcher_addr,
+            config_manager.clone(),
+            10,
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
 
         let mut event: Value = json!(Event::new("test"));
         event.add_to_metadata("tenant_id".to_owned(), Value::String("alpha".to_owned())).unwrap();
@@ -312,6 +573,7 @@ mod test {
                 event,
                 config_filter,
                 include_metadata: false,
+                include_trace: false,
                 process_type: ProcessType::Full,
                 span: Span::current(),
             })
@@ -346,10 +608,15 @@ mod test {
 
         let config_manager = configs.matcher_config.clone();
         let dispatcher_addr = FakeDispatcher {}.start().recipient();
-        let matcher_actor =
-            MatcherActor::start(dispatcher_addr, config_manager.clone(), 10, Default::default())
-                .await
-                .unwrap();
+        let matcher_actor = MatcherActor::start(
+            dispatcher_addr,
+            config_manager.clone(),
+            10,
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
 
         let mut event_tenant_alpha: Value = json!(Event::new("test"));
         event_tenant_alpha
@@ -373,6 +640,7 @@ mod test {
                 event: event_tenant_alpha,
                 config_filter: config_filter.clone(),
                 include_metadata: false,
+                include_trace: false,
                 process_type: ProcessType::Full,
                 span: Span::current(),
             })
@@ -385,6 +653,7 @@ mod test {
                 event: event_tenant_beta,
                 config_filter: config_filter.clone(),
                 include_metadata: false,
+                include_trace: false,
                 process_type: ProcessType::Full,
                 span: Span::current(),
             })
@@ -433,10 +702,15 @@ mod test {
 
         let config_manager = configs.matcher_config.clone();
         let dispatcher_addr = FakeDispatcher {}.start().recipient();
-        let matcher_actor =
-            MatcherActor::start(dispatcher_addr, config_manager.clone(), 10, Default::default())
-                .await
-                .unwrap();
+        let matcher_actor = MatcherActor::start(
+            dispatcher_addr,
+            config_manager.clone(),
+            10,
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
 
         let mut event: Value = json!(Event::new("test"));
         event.add_to_metadata("tenant_id".to_owned(), Value::String("alpha".to_owned())).unwrap();
@@ -451,6 +725,7 @@ mod test {
                     ]),
                 ],
                 include_metadata: false,
+                include_trace: false,
                 process_type: ProcessType::Full,
                 span: Span::current(),
             })
@@ -0,0 +1,49 @@
+use crate::actor::matcher::{MatcherActor, ReloadLookupTablesMessage};
+use actix::Addr;
+use async_trait::async_trait;
+use log::*;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tornado_engine_api::error::ApiError;
+use tornado_engine_api::lookup_table::api::LookupTableApiHandler;
+use tornado_engine_matcher::config::MatcherConfigEditor;
+use tornado_engine_matcher::lookup::LookupTables;
+
+#[derive(Clone)]
+pub struct LookupTableApiHandlerImpl {
+    lookup_tables_dir: PathBuf,
+    matcher: Addr<MatcherActor>,
+    config_manager: Arc<dyn MatcherConfigEditor>,
+}
+
+impl LookupTableApiHandlerImpl {
+    pub fn new<P: Into<PathBuf>>(
+        lookup_tables_dir: P,
+        matcher: Addr<MatcherActor>,
+        config_manager: Arc<dyn MatcherConfigEditor>,
+    ) -> Self {
+        Self { lookup_tables_dir: lookup_tables_dir.into(), matcher, config_manager }
+    }
+}
+
+#[async_trait(?Send)]
+impl LookupTableApiHandler for LookupTableApiHandlerImpl {
+    async fn get_lookup_tables(&self) -> Result<Arc<LookupTables>, ApiError> {
+        trace!("LookupTableApiHandlerImpl - get_lookup_tables");
+        Ok(self.config_manager.get_resources().lookup_tables)
+    }
+
+    async fn reload_lookup_tables(&self) -> Result<Arc<LookupTables>, ApiError> {
+        info!(
+            "LookupTableApiHandlerImpl - reload_lookup_tables from [{}]",
+            self.lookup_tables_dir.display()
+        );
+        let lookup_tables = LookupTables::from_dir(&self.lookup_tables_dir).map_err(|err| {
+            error!("Cannot reload the lookup tables. The current ones are kept. Err: {:?}", err);
+            err
+        })?;
+        // The MatcherActor validates the tables against the current configuration and rebuilds
+        // the Matcher with them; if they are not valid, the current ones are kept.
+        Ok(self.matcher.send(ReloadLookupTablesMessage { lookup_tables }).await??)
+    }
+}
@@ -14,6 +14,7 @@ use tornado_engine_matcher::config::operation::NodeFilter;
 use tornado_engine_matcher::config::MatcherConfig;
 use tornado_engine_matcher::model::ProcessedEvent;
 
+pub mod lookup_table;
 pub mod runtime_config;
 
 #[derive(Clone)]
@@ -44,6 +45,7 @@ impl EventApiHandler for MatcherApiHandler {
                 config_filter,
                 process_type: event.process_type,
                 include_metadata: true,
+                include_trace: event.explain,
                 span: span.clone(),
             })
             .await?;
@@ -74,6 +76,7 @@ impl EventApiHandler for MatcherApiHandler {
                 process_type: event.process_type,
                 matcher_config,
                 include_metadata: true,
+                include_trace: event.explain,
             })
             .await?;
 
@@ -138,6 +141,7 @@ mod test {
             dispatcher_addr.clone().recipient(),
             config_manager,
             47,
+            None,
             Default::default(),
         )
         .await
@@ -147,6 +151,7 @@ mod test {
 
         let send_event_request = SendEventRequest {
             process_type: ProcessType::SkipActions,
+            explain: false,
             event: Event::new("test-type"),
         };
 
@@ -187,6 +192,7 @@ mod test {
             dispatcher_addr.clone().recipient(),
             config_manager.clone(),
             47,
+            None,
             Default::default(),
         )
         .await
@@ -235,6 +241,7 @@ mod test {
             dispatcher_addr.clone().recipient(),
             config_manager,
             47,
+            None,
             Default::default(),
         )
         .await
@@ -244,6 +251,7 @@ mod test {
 
         let send_event_request = SendEventRequest {
             process_type: ProcessType::SkipActions,
+            explain: false,
             event: Event::new("test-type-custom"),
         };
 
@@ -262,6 +270,9 @@ mod test {
                     }),
                     with: HashMap::new(),
                 },
+                threshold: None,
+                suppression: None,
+                schedule: None,
             }],
         };
 
@@ -281,6 +292,75 @@ mod test {
         }
     }
 
+    #[actix_rt::test]
+    async fn should_send_an_event_to_the_draft_and_return_the_trace_if_requested() {
+        // Arrange
+        let path = "./config/rules.d";
+        let config_manager = Arc::new(FsMatcherConfigManagerV2::new(path, ""));
+
+        let event_bus = Arc::new(ActixEventBus { callback: |_| {} });
+
+        let dispatcher_addr =
+            DispatcherActor::start_new(1, Dispatcher::build(event_bus.clone()).unwrap());
+
+        let matcher_addr = MatcherActor::start(
+            dispatcher_addr.clone().recipient(),
+            config_manager,
+            47,
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
+
+        let api = MatcherApiHandler { matcher: matcher_addr, meter: Default::default() };
+
+        let send_event_request = SendEventRequest {
+            process_type: ProcessType::SkipActions,
+            explain: true,
+            event: Event::new("test-type-custom"),
+        };
+
+        let config = MatcherConfig::Ruleset {
+            name: "custom_ruleset".to_owned(),
+            rules: vec![Rule {
+                name: "rule_1".to_owned(),
+                actions: vec![],
+                active: true,
+                description: "".to_owned(),
+                do_continue: true,
+                constraint: Constraint {
+                    where_operator: Some(Operator::Equals {
+                        first: Value::String("${event.type}".to_owned()),
+                        second: Value::String("another-type".to_owned()),
+                    }),
+                    with: HashMap::new(),
+                },
+                threshold: None,
+                suppression: None,
+                schedule: None,
+            }],
+        };
+
+        // Act
+        let res = api.send_event_to_config(send_event_request, config).await.unwrap();
+
+        // Assert
+        match res.result {
+            ProcessedNode::Ruleset { rules, .. } => {
+                assert_eq!(ProcessedRuleStatus::NotMatched, rules.rules[0].status);
+                let trace = rules.rules[0].trace.as_ref().unwrap();
+                assert_eq!("equals", trace.operator);
+                assert!(!trace.result);
+                assert_eq!(
+                    Some(Value::String("test-type-custom".to_owned())),
+                    trace.values[0].value
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[actix_rt::test]
     async fn send_an_event_should_include_metadata() {
         // Arrange
@@ -296,6 +376,7 @@ mod test {
             dispatcher_addr.clone().recipient(),
             config_manager,
             47,
+            None,
             Default::default(),
         )
         .await
@@ -308,7 +389,8 @@ mod test {
         event.metadata = Map::new();
         event.metadata.insert("tenant_id".to_owned(), Value::String("beta".to_owned()));
 
-        let send_event_request = SendEventRequest { process_type: ProcessType::SkipActions, event };
+        let send_event_request =
+            SendEventRequest { process_type: ProcessType::SkipActions, event, explain: false };
 
         let mut config_filter = HashMap::new();
         config_filter.insert(ROOT_NODE_NAME.to_owned(), NodeFilter::AllChildren);
@@ -1,15 +1,26 @@
 use crate::config::parse_config_files;
 use tornado_engine_matcher::matcher::Matcher;
+use tornado_engine_matcher::validator::lint::lint;
 
 pub async fn check(
     config_dir: &str,
     rules_dir: &str,
     drafts_dir: &str,
+    with_lint: bool,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
     println!("Check Tornado configuration");
     let configs = parse_config_files(config_dir, rules_dir, drafts_dir)?;
-    let _matcher =
-        configs.matcher_config.get_config().await.and_then(|config| Matcher::build(&config))?;
+    let config = configs.matcher_config.get_config().await?;
+    let resources = configs.matcher_config.get_resources();
+    let _matcher = Matcher::build_with_resources(&config, &resources)?;
     println!("The configuration is correct.");
+
+    if with_lint {
+        let warnings = lint(&config, &resources);
+        for warning in &warnings {
+            println!("WARNING {}", warning);
+        }
+        println!("Lint completed with {} warning(s).", warnings.len());
+    }
     Ok(())
 }
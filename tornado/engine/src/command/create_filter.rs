@@ -114,6 +114,7 @@ pub mod test {
             description: "my new filter".to_string(),
             active: true,
             filter: Defaultable::Default {},
+            schedule: None,
         };
 
         // Act
@@ -155,6 +156,7 @@ pub mod test {
                 first: Value::String("1".to_owned()),
                 second: Value::String("1".to_owned()),
             }),
+            schedule: None,
         };
 
         // Act
@@ -201,6 +203,7 @@ pub mod test {
                 first: Value::String("1".to_owned()),
                 second: Value::String("1".to_owned()),
             }),
+            schedule: None,
         };
 
         // Act
@@ -247,6 +250,7 @@ pub mod test {
                 first: Value::String("${event.metadata.tenant_id}".to_owned()),
                 second: Value::String("alpha".to_owned()),
             }),
+            schedule: None,
         };
 
         // Act
@@ -268,6 +272,9 @@ pub mod test {
                 active: false,
                 constraint: Constraint { where_operator: None, with: Default::default() },
                 actions: vec![],
+                threshold: None,
+                suppression: None,
+                schedule: None,
             }],
         };
 
@@ -276,6 +283,7 @@ pub mod test {
             description: "my new filter".to_string(),
             active: true,
             filter: Defaultable::Default {},
+            schedule: None,
         };
 
         // Act
@@ -293,6 +301,7 @@ pub mod test {
                 description: "".to_string(),
                 active: true,
                 filter: Defaultable::Default {},
+                schedule: None,
             },
             nodes: vec![],
         };
@@ -303,6 +312,7 @@ pub mod test {
             description: "my new filter".to_string(),
             active: true,
             filter: Defaultable::Default {},
+            schedule: None,
         };
 
         // Act
@@ -318,7 +328,8 @@ pub mod test {
                     Filter {
                         description: "".to_string(),
                         active: true,
-                        filter: Defaultable::Default {}
+                        filter: Defaultable::Default {},
+                        schedule: None,
                     }
                 );
                 assert_eq!(nodes.len(), 1);
@@ -355,6 +366,7 @@ pub mod test {
                 first: Value::String("1".to_owned()),
                 second: Value::String("1".to_owned()),
             }),
+            schedule: None,
         };
 
         // Act
@@ -1,6 +1,7 @@
 use crate::actor::dispatcher::{ActixEventBus, DispatcherActor};
 use crate::actor::foreach::{ForEachExecutorActor, ForEachExecutorActorInitMessage};
 use crate::actor::matcher::{EventMessage, MatcherActor};
+use crate::api::lookup_table::LookupTableApiHandlerImpl;
 use crate::api::runtime_config::RuntimeConfigApiHandlerImpl;
 use crate::api::MatcherApiHandler;
 use crate::config;
@@ -13,6 +14,7 @@ use actix_web::middleware::Logger;
 use actix_web::{web, App, HttpServer};
 use log::*;
 use serde_json::json;
+use std::path::Path;
 use std::rc::Rc;
 use std::sync::Arc;
 use tornado_common::actors::command::CommandExecutorActor;
@@ -35,9 +37,11 @@ use tornado_engine_api::auth::auth_v2::AuthServiceV2;
 use tornado_engine_api::auth::{roles_map_to_permissions_map, AuthService};
 use tornado_engine_api::config::api::ConfigApi;
 use tornado_engine_api::event::api_v2::EventApiV2;
+use tornado_engine_api::lookup_table::api::LookupTableApi;
 use tornado_engine_api::model::{ApiData, ApiDataV2};
 use tornado_engine_api::runtime_config::api::RuntimeConfigApi;
 use tornado_engine_matcher::dispatcher::Dispatcher;
+use tornado_engine_matcher::matcher::thread_pool::MatcherThreadPool;
 use tracing_actix_web::TracingLogger;
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
@@ -101,7 +105,8 @@ pub async fn daemon(
                 CommandMutPool::new(1, move || {
                     StatefulExecutorCommand::new(
                         archive_action_meter.clone(),
-                        tornado_executor_archive::ArchiveExecutor::new(&archive_config),
+                        tornado_executor_archive::ArchiveExecutor::new(&archive_config)
+                            .expect("Cannot start the Archive Executor"),
                     )
                 }),
             )),
@@ -292,10 +297,22 @@ pub async fn daemon(
     );
 
     // Start matcher actor
+    let matcher_thread_pool = match &daemon_config.matcher_thread_pool_config {
+        Some(matcher_thread_pool_config) => {
+            let threads = matcher_thread_pool_config.get_threads_count();
+            info!(
+                "The matcher processes the processing tree in parallel with {} threads. Thread config: {:?}",
+                threads, matcher_thread_pool_config
+            );
+            Some(MatcherThreadPool::build(threads)?)
+        }
+        None => None,
+    };
     let matcher_addr = MatcherActor::start(
         dispatcher_addr.clone().recipient(),
         configs.matcher_config.clone(),
         message_queue_size,
+        matcher_thread_pool,
         tornado_meter.clone(),
     )
     .await?;
@@ -432,6 +449,11 @@ pub async fn daemon(
     let auth_service_v2 = AuthServiceV2::new(Arc::new(roles_map_to_permissions_map(
         daemon_config.auth.role_permissions.clone(),
     )));
+    let lookup_table_api_handler = LookupTableApiHandlerImpl::new(
+        Path::new(config_dir).join(config::LOOKUP_TABLES_DIR),
+        matcher_addr.clone(),
+        configs.matcher_config.clone(),
+    );
     let api_handler = MatcherApiHandler::new(matcher_addr, tornado_meter.clone());
     let daemon_config = daemon_config.clone();
     let matcher_config = configs.matcher_config.clone();
@@ -455,6 +477,10 @@ pub async fn daemon(
                 service_logger_guard.clone(),
             )),
         };
+        let lookup_table_api = ApiData {
+            auth: auth_service.clone(),
+            api: LookupTableApi::new(lookup_table_api_handler.clone()),
+        };
         let metrics = metrics.clone();
         App::new()
             .wrap(Logger::default())
@@ -474,6 +500,9 @@ pub async fn daemon(
                             runtime_config_api,
                         ),
                     )
+                    .service(tornado_engine_api::lookup_table::web::build_lookup_table_endpoints(
+                        lookup_table_api,
+                    ))
                     .service(
                         web::scope("/v2_beta")
                             .service(tornado_engine_api::config::web::build_config_v2_endpoints(
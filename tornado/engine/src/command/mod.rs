@@ -2,4 +2,5 @@ pub mod apm_tracing;
 pub mod check;
 pub mod create_filter;
 pub mod daemon;
+pub mod run_tests;
 pub mod upgrade_rules;
@@ -0,0 +1,27 @@
+use crate::config::parse_config_files;
+use tornado_engine_matcher::suite::run_test_suites;
+
+pub async fn run_tests(
+    config_dir: &str,
+    rules_dir: &str,
+    drafts_dir: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    println!("Run the test cases of the Tornado Rulesets");
+    let configs = parse_config_files(config_dir, rules_dir, drafts_dir)?;
+    let config = configs.matcher_config.get_config().await?;
+    let suites = configs.matcher_config.get_test_suites().await?;
+
+    let resources = configs.matcher_config.get_resources();
+
+    let results = run_test_suites(&config, &suites, &resources)?;
+    for result in &results {
+        println!("{}", result);
+    }
+
+    let failed = results.iter().filter(|result| !result.is_success()).count();
+    println!("Test cases: {} passed, {} failed.", results.len() - failed, failed);
+    if failed > 0 {
+        return Err(format!("{} test cases failed", failed).into());
+    }
+    Ok(())
+}
@@ -3,14 +3,19 @@ use clap::Parser;
 use config_rs::{Config, ConfigError, File};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
 use std::sync::Arc;
 use tornado_common::{
     actors::nats_subscriber::NatsSubscriberConfig, command::retry::RetryStrategy,
 };
+use tornado_common_grok::GrokPatterns;
 use tornado_common_logger::LoggerConfig;
 use tornado_engine_api::auth::Permission;
 use tornado_engine_matcher::config::v2::FsMatcherConfigManagerV2;
 use tornado_engine_matcher::config::MatcherConfigEditor;
+use tornado_engine_matcher::lookup::LookupTables;
+use tornado_engine_matcher::resources::MatcherResources;
 use tornado_executor_archive::config::ArchiveConfig;
 use tornado_executor_director::config::DirectorClientConfig;
 use tornado_executor_elasticsearch::config::ElasticsearchConfig;
@@ -18,6 +23,15 @@ use tornado_executor_icinga2::config::Icinga2ClientConfig;
 
 pub const CONFIG_DIR_DEFAULT: Option<&'static str> = option_env!("TORNADO_CONFIG_DIR_DEFAULT");
 
+/// The folder, relative to the `config-dir`, that contains the custom grok pattern files
+pub const GROK_PATTERNS_DIR: &str = "grok_patterns.d";
+
+/// The folder, relative to the `config-dir`, that contains the lookup table files
+pub const LOOKUP_TABLES_DIR: &str = "lookup_tables.d";
+
+/// The optional file, relative to the `config-dir`, that enables the configuration history
+pub const CONFIG_HISTORY_FILE: &str = "config_history.toml";
+
 #[derive(Parser, Debug)]
 #[clap(name = "tornado")]
 pub struct Opt {
@@ -69,7 +83,12 @@ impl Opt {
 #[derive(Parser, Debug)]
 pub enum SubCommand {
     /// Checks that the configuration is valid
-    Check,
+    Check {
+        /// Reports also the Rules that can never match and the extracted variables that are
+        /// unknown or never used. The warnings do not make the check fail.
+        #[clap(long)]
+        lint: bool,
+    },
 
     /// Starts the Tornado daemon
     Daemon,
@@ -80,6 +99,9 @@ pub enum SubCommand {
     /// Creates a Filter in Tornado configuration
     FilterCreate(FilterCreateOpt),
 
+    /// Runs the test cases of the Rulesets against the configuration, without starting the daemon
+    Test,
+
     /// Enable or disable the APM logger priority configuration.
     /// When used with `enable`, it:
     /// - enables the elastic-APM logger output
@@ -162,6 +184,9 @@ pub struct DaemonCommandConfig {
     pub message_queue_size: usize,
 
     pub thread_pool_config: Option<ThreadPoolConfig>,
+    /// The thread pool used to process in parallel the independent nodes of the processing tree.
+    /// If not set, the processing tree is processed sequentially.
+    pub matcher_thread_pool_config: Option<ThreadPoolConfig>,
     #[serde(default)]
     pub retry_strategy: RetryStrategy,
 
@@ -230,6 +255,33 @@ fn build_elasticsearch_config(config_dir: &str) -> Result<ElasticsearchConfig, C
     s.try_into()
 }
 
+/// The configuration history saves a snapshot of the processing tree after each deploy.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ConfigHistoryConfig {
+    /// The folder where the snapshots are saved. This folder is relative to the `config-dir`
+    #[serde(default = "default_history_dir")]
+    pub history_dir: String,
+    /// The number of snapshots to keep; when exceeded, the oldest snapshots are deleted
+    pub max_snapshots: usize,
+}
+
+fn default_history_dir() -> String {
+    "history/".to_owned()
+}
+
+/// Returns the configuration history settings, if the CONFIG_HISTORY_FILE exists.
+fn build_config_history_config(
+    config_dir: &str,
+) -> Result<Option<ConfigHistoryConfig>, ConfigError> {
+    let config_file_path = Path::new(config_dir).join(CONFIG_HISTORY_FILE);
+    if !config_file_path.is_file() {
+        return Ok(None);
+    }
+    let mut s = Config::new();
+    s.merge(File::from(config_file_path))?;
+    s.try_into().map(Some)
+}
+
 pub struct ComponentsConfig {
     pub matcher_config: Arc<dyn MatcherConfigEditor>,
     pub archive_executor_config: ArchiveConfig,
@@ -243,7 +295,11 @@ pub fn parse_config_files(
     rules_dir: &str,
     drafts_dir: &str,
 ) -> Result<ComponentsConfig, Box<dyn std::error::Error + Send + Sync + 'static>> {
-    let matcher_config = Arc::new(build_matcher_config(config_dir, rules_dir, drafts_dir));
+    let lookup_tables = LookupTables::from_dir(&Path::new(config_dir).join(LOOKUP_TABLES_DIR))?;
+    let resources = MatcherResources::new(build_grok_patterns(config_dir)?, lookup_tables);
+    let matcher_config = Arc::new(
+        build_matcher_config(config_dir, rules_dir, drafts_dir)?.with_resources(resources),
+    );
     let archive_executor_config = build_archive_config(config_dir)?;
     let icinga2_executor_config = build_icinga2_client_config(config_dir)?;
     let director_executor_config = build_director_client_config(config_dir)?;
@@ -257,15 +313,47 @@ pub fn parse_config_files(
     })
 }
 
+/// Returns the built-in grok patterns together with the custom ones defined in the files of the
+/// GROK_PATTERNS_DIR folder, if it exists. The files are read in alphabetical order and a pattern
+/// can override the ones with the same name defined before.
+fn build_grok_patterns(
+    config_dir: &str,
+) -> Result<GrokPatterns, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let mut patterns = GrokPatterns::default();
+    let patterns_dir = Path::new(config_dir).join(GROK_PATTERNS_DIR);
+    if !patterns_dir.is_dir() {
+        return Ok(patterns);
+    }
+
+    let mut paths = fs::read_dir(&patterns_dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.retain(|path| path.is_file());
+    paths.sort();
+    for path in paths {
+        patterns.add_patterns(&fs::read_to_string(&path)?).map_err(|err| {
+            format!("Cannot read the grok patterns file [{}]: {}", path.display(), err)
+        })?;
+    }
+    Ok(patterns)
+}
+
 fn build_matcher_config(
     config_dir: &str,
     rules_dir: &str,
     drafts_dir: &str,
-) -> FsMatcherConfigManagerV2 {
-    FsMatcherConfigManagerV2::new(
+) -> Result<FsMatcherConfigManagerV2, ConfigError> {
+    let config_manager = FsMatcherConfigManagerV2::new(
         format!("{}/{}", config_dir, rules_dir),
         format!("{}/{}", config_dir, drafts_dir),
-    )
+    );
+    Ok(match build_config_history_config(config_dir)? {
+        Some(history_config) => config_manager.with_history(
+            format!("{}/{}", config_dir, history_config.history_dir),
+            history_config.max_snapshots,
+        ),
+        None => config_manager,
+    })
 }
 
 #[cfg(test)]
@@ -331,6 +419,80 @@ mod test {
         )
     }
 
+    #[test]
+    fn should_read_grok_patterns_from_file() {
+        // Arrange
+        let config_dir = "./config";
+
+        // Act
+        let patterns = build_grok_patterns(config_dir).unwrap();
+
+        // Assert
+        assert_eq!(Some("TCP|UDP|ICMP"), patterns.get("FW_PROTO"));
+        assert!(patterns.get("IPV4").is_some());
+        assert!(patterns.expand("%{FW_CONNECTION}").is_ok());
+    }
+
+    #[test]
+    fn should_return_the_built_in_grok_patterns_if_no_patterns_dir() {
+        // Arrange
+        let config_dir = tempfile::TempDir::new().unwrap();
+
+        // Act
+        let patterns = build_grok_patterns(config_dir.path().to_str().unwrap()).unwrap();
+
+        // Assert
+        assert_eq!(GrokPatterns::default(), patterns);
+    }
+
+    #[test]
+    fn should_read_the_config_history_settings_from_file() {
+        // Arrange
+        let config_dir = tempfile::TempDir::new().unwrap();
+        fs::write(config_dir.path().join(CONFIG_HISTORY_FILE), "max_snapshots = 20").unwrap();
+
+        // Act
+        let history_config =
+            build_config_history_config(config_dir.path().to_str().unwrap()).unwrap().unwrap();
+
+        // Assert
+        assert_eq!("history/", history_config.history_dir);
+        assert_eq!(20, history_config.max_snapshots);
+    }
+
+    #[test]
+    fn should_have_no_config_history_if_no_settings_file() {
+        // Arrange
+        let config_dir = tempfile::TempDir::new().unwrap();
+
+        // Act
+        let history_config =
+            build_config_history_config(config_dir.path().to_str().unwrap()).unwrap();
+
+        // Assert
+        assert!(history_config.is_none());
+    }
+
+    #[test]
+    fn should_read_lookup_tables_from_file() {
+        // Arrange
+        let config_dir = "./config";
+        let rules_dir = "/rules";
+        let drafts_dir = "/drafts";
+
+        // Act
+        let config = parse_config_files(config_dir, rules_dir, drafts_dir).unwrap();
+
+        // Assert
+        let lookup_tables = config.matcher_config.get_resources().lookup_tables;
+        let teams = lookup_tables.get("teams").unwrap();
+        assert_eq!(3, teams.len());
+        assert_eq!(
+            Some("dba-team@example.com"),
+            teams.get("db01").and_then(|row| row.get("email")).and_then(|email| email.as_str())
+        );
+    }
+
     #[test]
     fn should_read_archiver_configurations_from_file() {
         // Arrange
@@ -382,6 +544,7 @@ mod test {
             web_max_json_payload_size: None,
             message_queue_size: 0,
             thread_pool_config: None,
+            matcher_thread_pool_config: None,
             retry_strategy: Default::default(),
             auth: AuthConfig::default(),
         };
@@ -410,6 +573,7 @@ mod test {
             web_max_json_payload_size: None,
             message_queue_size: 0,
             thread_pool_config: None,
+            matcher_thread_pool_config: None,
             retry_strategy: Default::default(),
             auth: AuthConfig::default(),
         };
@@ -18,7 +18,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>
     let drafts_dir = opt.drafts_dir();
 
     match &opt.command {
-        SubCommand::Check => command::check::check(config_dir, rules_dir, drafts_dir).await,
+        SubCommand::Check { lint } => {
+            command::check::check(config_dir, rules_dir, drafts_dir, *lint).await
+        }
         SubCommand::Daemon => command::daemon::daemon(config_dir, rules_dir, drafts_dir).await,
         SubCommand::RulesUpgrade => {
             command::upgrade_rules::upgrade_rules(config_dir, rules_dir, drafts_dir).await
@@ -26,6 +28,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>
         SubCommand::FilterCreate(opts) => {
             command::create_filter::create_filter(config_dir, rules_dir, drafts_dir, opts).await
         }
+        SubCommand::Test => command::run_tests::run_tests(config_dir, rules_dir, drafts_dir).await,
         SubCommand::ApmTracing { command } => apm_tracing(config_dir, command).await,
     }
 }
@@ -88,6 +88,7 @@ mod test {
             web_max_json_payload_size: None,
             message_queue_size: 0,
             thread_pool_config: None,
+            matcher_thread_pool_config: None,
             retry_strategy: Default::default(),
             auth: AuthConfig::default(),
         };
@@ -125,6 +126,7 @@ mod test {
             web_max_json_payload_size: None,
             message_queue_size: 0,
             thread_pool_config: None,
+            matcher_thread_pool_config: None,
             retry_strategy: Default::default(),
             auth: AuthConfig::default(),
         };
@@ -162,6 +164,7 @@ mod test {
             web_max_json_payload_size: None,
             message_queue_size: 0,
             thread_pool_config: None,
+            matcher_thread_pool_config: None,
             retry_strategy: Default::default(),
             auth: AuthConfig::default(),
         };
@@ -204,6 +207,7 @@ mod test {
                 web_max_json_payload_size: None,
                 message_queue_size: 0,
                 thread_pool_config: None,
+                matcher_thread_pool_config: None,
                 retry_strategy: Default::default(),
                 auth: AuthConfig::default(),
             };
@@ -1,17 +1,25 @@
 use crate::auth::auth_v2::AuthContextV2;
 use crate::auth::{AuthContext, AuthContextTrait, Permission};
-use crate::config::convert::{dto_into_rule, rule_into_dto};
+use crate::config::convert::{
+    config_change_into_dto, config_snapshot_into_dto, dto_into_rule, lint_warning_into_dto,
+    rule_into_dto, rule_test_result_into_dto,
+};
 use crate::error::ApiError;
 use log::*;
 use std::sync::Arc;
 use tornado_engine_api_dto::common::Id;
 use tornado_engine_api_dto::config::{
-    ProcessingTreeNodeConfigDto, ProcessingTreeNodeDetailsDto, RuleDto, TreeInfoDto,
+    ConfigChangeDto, ConfigSnapshotDto, DeployDraftResultDto, ProcessingTreeNodeConfigDto,
+    ProcessingTreeNodeDetailsDto, RuleDto, RuleTestResultDto, TreeInfoDto,
 };
+use tornado_engine_matcher::config::diff::diff_configs;
 use tornado_engine_matcher::config::operation::{matcher_config_filter, NodeFilter};
+use tornado_engine_matcher::config::v1::fs::ROOT_NODE_NAME;
 use tornado_engine_matcher::config::{
     MatcherConfig, MatcherConfigDraft, MatcherConfigEditor, MatcherConfigReader,
 };
+use tornado_engine_matcher::suite::{run_test_suites, RuleTestResult, RuleTestSuite};
+use tornado_engine_matcher::validator::lint::{lint, LintWarning};
 
 const NODE_PATH_SEPARATOR: &str = ",";
 
@@ -134,6 +142,10 @@ impl<A: ConfigApiHandler, CM: MatcherConfigReader + MatcherConfigEditor + ?Sized
                     TreeInfoDto { iterators_count: 1, ..Default::default() }
                         + Self::fetch_tree_info(nodes)
                 }
+                MatcherConfig::Enricher { nodes, .. } => Self::fetch_tree_info(nodes),
+                MatcherConfig::Correlation { .. } | MatcherConfig::Heartbeat { .. } => {
+                    TreeInfoDto::default()
+                }
             })
             .sum()
     }
@@ -259,7 +271,11 @@ impl<A: ConfigApiHandler, CM: MatcherConfigReader + MatcherConfigEditor + ?Sized
         let mut draft = self.get_draft_and_check_owner(&auth, draft_id).await?;
         let absolute_node_path = self.get_absolute_path_from_relative(&auth, ruleset_path)?;
         let rule = dto_into_rule(rule_dto)?;
-        draft.config.create_rule(&absolute_node_path, rule)?;
+        draft.config.create_rule(
+            &absolute_node_path,
+            rule,
+            &self.config_manager.get_resources(),
+        )?;
         Ok(self
             .config_manager
             .update_draft(draft_id, auth.auth.user.clone(), &draft.config)
@@ -278,7 +294,12 @@ impl<A: ConfigApiHandler, CM: MatcherConfigReader + MatcherConfigEditor + ?Sized
         let mut draft = self.get_draft_and_check_owner(&auth, draft_id).await?;
         let absolute_node_path = self.get_absolute_path_from_relative(&auth, ruleset_path)?;
         let rule = dto_into_rule(rule_dto)?;
-        draft.config.edit_rule(&absolute_node_path, rule_name, rule)?;
+        draft.config.edit_rule(
+            &absolute_node_path,
+            rule_name,
+            rule,
+            &self.config_manager.get_resources(),
+        )?;
         Ok(self
             .config_manager
             .update_draft(draft_id, auth.auth.user.clone(), &draft.config)
@@ -343,6 +364,15 @@ impl<A: ConfigApiHandler, CM: MatcherConfigReader + MatcherConfigEditor + ?Sized
             MatcherConfig::Iterator { .. } => Err(ApiError::NodeNotFoundError {
                 message: format!("Found iterator instead of ruleset. Path: {:?}", ruleset_path),
             }),
+            MatcherConfig::Correlation { .. } => Err(ApiError::NodeNotFoundError {
+                message: format!("Found correlation instead of ruleset. Path: {:?}", ruleset_path),
+            }),
+            MatcherConfig::Heartbeat { .. } => Err(ApiError::NodeNotFoundError {
+                message: format!("Found heartbeat instead of ruleset. Path: {:?}", ruleset_path),
+            }),
+            MatcherConfig::Enricher { .. } => Err(ApiError::NodeNotFoundError {
+                message: format!("Found enricher instead of ruleset. Path: {:?}", ruleset_path),
+            }),
             MatcherConfig::Ruleset { name: _, rules } => {
                 let rule = rules.iter().find(|rule| rule.name == rule_name);
                 if let Some(rule) = rule.cloned() {
@@ -442,30 +472,162 @@ impl<A: ConfigApiHandler, CM: MatcherConfigReader + MatcherConfigEditor + ?Sized
         Ok(self.config_manager.update_draft(draft_id, auth.auth.user, &config).await?)
     }
 
-    /// Deploy a draft by id and reload the tornado configuration
+    /// Deploy a draft by id and reload the tornado configuration.
+    /// The comment is saved with the snapshot of the deployed configuration.
+    /// Returns the lint warnings of the deployed configuration.
     pub async fn deploy_draft(
         &self,
         auth: AuthContext<'_>,
         draft_id: &str,
-    ) -> Result<MatcherConfig, ApiError> {
+        comment: &str,
+    ) -> Result<DeployDraftResultDto, ApiError> {
         auth.has_permission(&Permission::ConfigEdit)?;
-        self.get_draft_and_check_owner(&auth, draft_id).await?;
-        self.config_manager.deploy_draft(draft_id).await?;
-        self.handler.reload_configuration().await
+        let draft = self.get_draft_and_check_owner(&auth, draft_id).await?;
+        self.deploy(draft_id, comment, &draft, &[]).await
     }
 
-    /// Deploy a draft by id and reload the tornado configuration
+    /// Deploy a draft by id and reload the tornado configuration.
+    /// The comment is saved with the snapshot of the deployed configuration.
+    /// Returns the lint warnings of the deployed configuration.
     /// TODO: implement the multitenancy https://siwuerthphoenix.atlassian.net/browse/NEPROD-1232
     pub async fn deploy_draft_for_tenant(
         &self,
         auth: &AuthContextV2<'_>,
         draft_id: &str,
-    ) -> Result<MatcherConfig, ApiError> {
+        comment: &str,
+    ) -> Result<DeployDraftResultDto, ApiError> {
         auth.has_permission(&Permission::ConfigEdit)?;
         let draft = self.config_manager.get_draft(draft_id).await?;
         auth.is_owner(&draft)?;
-        self.config_manager.deploy_draft(draft_id).await?;
-        self.handler.reload_configuration().await
+        self.deploy(draft_id, comment, &draft, &auth.auth.authorization.path).await
+    }
+
+    /// Deploys a draft after checking the test cases of the Rulesets in the authorized path;
+    /// an empty path authorizes all the Rulesets.
+    async fn deploy(
+        &self,
+        draft_id: &str,
+        comment: &str,
+        draft: &MatcherConfigDraft,
+        authorized_path: &[String],
+    ) -> Result<DeployDraftResultDto, ApiError> {
+        let suites = self.config_manager.get_draft_test_suites(&draft.data.draft_id).await?;
+        self.check_authorized_test_suites(&draft.config, suites, authorized_path).await?;
+        let warnings = self.lint_authorized_config(&draft.config, authorized_path);
+        for warning in &warnings {
+            warn!("ConfigApi - Deploy of draft [{}]: {}", draft_id, warning);
+        }
+        self.config_manager.deploy_draft(draft_id, comment).await?;
+        self.handler.reload_configuration().await?;
+        Ok(DeployDraftResultDto {
+            warnings: warnings.into_iter().map(lint_warning_into_dto).collect(),
+        })
+    }
+
+    /// Runs the test cases of the Rulesets of a draft and returns their results
+    pub async fn test_draft_for_tenant(
+        &self,
+        auth: &AuthContextV2<'_>,
+        draft_id: &str,
+    ) -> Result<Vec<RuleTestResultDto>, ApiError> {
+        auth.has_permission(&Permission::ConfigView)?;
+        let draft = self.config_manager.get_draft(draft_id).await?;
+        auth.is_owner(&draft)?;
+        let suites = self.config_manager.get_draft_test_suites(draft_id).await?;
+        let results = self
+            .run_authorized_test_suites(&draft.config, suites, &auth.auth.authorization.path)
+            .await?;
+        Ok(results.into_iter().map(rule_test_result_into_dto).collect())
+    }
+
+    /// Returns the changes of a draft with respect to the deployed configuration.
+    /// Only the nodes in the authorized path are compared, and the paths of the changes start
+    /// from the authorized node.
+    pub async fn get_draft_diff(
+        &self,
+        auth: &AuthContextV2<'_>,
+        draft_id: &str,
+    ) -> Result<Vec<ConfigChangeDto>, ApiError> {
+        auth.has_permission(&Permission::ConfigView)?;
+        let draft = self.config_manager.get_draft(draft_id).await?;
+        auth.is_owner(&draft)?;
+        let current_config =
+            get_filtered_matcher(&self.config_manager.get_config().await?, auth).await?;
+        let draft_config = get_filtered_matcher(&draft.config, auth).await?;
+
+        let authorized_path =
+            auth.auth.authorization.path.iter().map(|s| s as &str).collect::<Vec<_>>();
+        match (
+            current_config.get_node_by_path(&authorized_path),
+            draft_config.get_node_by_path(&authorized_path),
+        ) {
+            (Some(current_node), Some(draft_node)) => Ok(diff_configs(current_node, draft_node)
+                .into_iter()
+                .map(config_change_into_dto)
+                .collect()),
+            _ => Err(self.get_unauthorized_path_error()),
+        }
+    }
+
+    /// Runs the test cases of the Rulesets of a configuration in the authorized path;
+    /// an empty path authorizes all the Rulesets.
+    /// The cases are run on the blocking thread pool because each of them builds a Matcher.
+    async fn run_authorized_test_suites(
+        &self,
+        config: &MatcherConfig,
+        suites: Vec<RuleTestSuite>,
+        authorized_path: &[String],
+    ) -> Result<Vec<RuleTestResult>, ApiError> {
+        // The authorized path starts from the root node, while the paths of the suites do not.
+        let authorized_ruleset_path = authorized_path.get(1..).unwrap_or_default();
+        let suites = suites
+            .into_iter()
+            .filter(|suite| suite.ruleset_path.starts_with(authorized_ruleset_path))
+            .collect::<Vec<_>>();
+        let config = config.clone();
+        let resources = self.config_manager.get_resources();
+        let results = actix_web::web::block(move || run_test_suites(&config, &suites, &resources))
+            .await
+            .map_err(|err| ApiError::InternalServerError { cause: err.to_string() })?;
+        Ok(results?)
+    }
+
+    /// Fails if any test case of the configuration in the authorized path does not pass
+    async fn check_authorized_test_suites(
+        &self,
+        config: &MatcherConfig,
+        suites: Vec<RuleTestSuite>,
+        authorized_path: &[String],
+    ) -> Result<(), ApiError> {
+        let failures = self
+            .run_authorized_test_suites(config, suites, authorized_path)
+            .await?
+            .into_iter()
+            .filter(|result| !result.is_success())
+            .map(|result| result.to_string())
+            .collect::<Vec<_>>();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(ApiError::RuleTestFailedError { message: failures.join("\n") })
+        }
+    }
+
+    /// Returns the lint warnings of the authorized subtree of a configuration;
+    /// an empty path authorizes the whole configuration. A configuration that does not
+    /// contain the authorized path has nothing to lint.
+    fn lint_authorized_config(
+        &self,
+        config: &MatcherConfig,
+        authorized_path: &[String],
+    ) -> Vec<LintWarning> {
+        let resources = self.config_manager.get_resources();
+        match authorized_path {
+            [] => lint(config, &resources),
+            path => matcher_config_filter(config, &NodeFilter::map_from(&[path.to_vec()]))
+                .map(|config| lint(&config, &resources))
+                .unwrap_or_default(),
+        }
     }
 
     /// Deletes a draft by id
@@ -511,6 +673,93 @@ impl<A: ConfigApiHandler, CM: MatcherConfigReader + MatcherConfigEditor + ?Sized
         Ok(self.config_manager.draft_take_over(draft_id, auth.clone().auth.user).await?)
     }
 
+    /// Returns the snapshots of the deployed configurations, from the most recent one.
+    /// The users authorized on a subtree only get the snapshots saved by their own deploys,
+    /// as the other ones can come from the deploys of other tenants.
+    pub async fn get_snapshots(
+        &self,
+        auth: &AuthContextV2<'_>,
+    ) -> Result<Vec<ConfigSnapshotDto>, ApiError> {
+        auth.has_permission(&Permission::ConfigView)?;
+        let is_root_authorized = is_root_path(&auth.auth.authorization.path);
+        let snapshots = self.config_manager.get_snapshots().await?;
+        Ok(snapshots
+            .into_iter()
+            .filter(|snapshot| is_root_authorized || snapshot.user == auth.auth.user)
+            .map(config_snapshot_into_dto)
+            .collect())
+    }
+
+    /// Returns child processing tree nodes of a node found by a path
+    /// of the configuration of a snapshot
+    pub async fn get_snapshot_config_processing_tree_nodes_by_path(
+        &self,
+        auth: AuthContextV2<'_>,
+        snapshot_id: &str,
+        node_path: Option<&str>,
+    ) -> Result<Vec<ProcessingTreeNodeConfigDto>, ApiError> {
+        auth.has_permission(&Permission::ConfigView)?;
+        let relative_node_path: Vec<_> = node_path
+            .map(|node_path| node_path.split(NODE_PATH_SEPARATOR).collect())
+            .unwrap_or_default();
+
+        let snapshot = self.config_manager.get_snapshot(snapshot_id).await?;
+        let filtered_matcher = get_filtered_matcher(&snapshot.config, &auth).await?;
+        self.get_authorized_child_nodes(&auth, relative_node_path, filtered_matcher).await
+    }
+
+    /// Returns the changes from the configuration of a snapshot to the one of another snapshot.
+    /// Only the nodes in the authorized path are compared, and the paths of the changes start
+    /// from the authorized node.
+    pub async fn diff_snapshots(
+        &self,
+        auth: &AuthContextV2<'_>,
+        snapshot_id: &str,
+        other_snapshot_id: &str,
+    ) -> Result<Vec<ConfigChangeDto>, ApiError> {
+        auth.has_permission(&Permission::ConfigView)?;
+        let snapshot = self.config_manager.get_snapshot(snapshot_id).await?;
+        let other_snapshot = self.config_manager.get_snapshot(other_snapshot_id).await?;
+        let config = get_filtered_matcher(&snapshot.config, auth).await?;
+        let other_config = get_filtered_matcher(&other_snapshot.config, auth).await?;
+
+        let authorized_path =
+            auth.auth.authorization.path.iter().map(|s| s as &str).collect::<Vec<_>>();
+        match (
+            config.get_node_by_path(&authorized_path),
+            other_config.get_node_by_path(&authorized_path),
+        ) {
+            (Some(node), Some(other_node)) => {
+                Ok(diff_configs(node, other_node).into_iter().map(config_change_into_dto).collect())
+            }
+            _ => Err(self.get_unauthorized_path_error()),
+        }
+    }
+
+    /// Deploys the configuration of a snapshot and reloads the tornado configuration,
+    /// after checking the test cases of its Rulesets.
+    /// The rollback replaces the whole processing tree, so it requires the authorization
+    /// on the root node.
+    pub async fn rollback_to_snapshot(
+        &self,
+        auth: &AuthContextV2<'_>,
+        snapshot_id: &str,
+    ) -> Result<(), ApiError> {
+        auth.has_permission(&Permission::ConfigEdit)?;
+        if !is_root_path(&auth.auth.authorization.path) {
+            return Err(self.get_unauthorized_path_error());
+        }
+        let snapshot = self.config_manager.get_snapshot(snapshot_id).await?;
+        let suites = self.config_manager.get_snapshot_test_suites(snapshot_id).await?;
+        self.check_authorized_test_suites(&snapshot.config, suites, &[]).await?;
+        for warning in self.lint_authorized_config(&snapshot.config, &[]) {
+            warn!("ConfigApi - Rollback to snapshot [{}]: {}", snapshot_id, warning);
+        }
+        self.config_manager.rollback_to_snapshot(snapshot_id, auth.auth.user.clone()).await?;
+        self.handler.reload_configuration().await?;
+        Ok(())
+    }
+
     async fn get_draft_and_check_owner<T: AuthContextTrait>(
         &self,
         auth: &T,
@@ -532,7 +781,11 @@ impl<A: ConfigApiHandler, CM: MatcherConfigReader + MatcherConfigEditor + ?Sized
         let mut draft = self.get_draft_and_check_owner(&auth, draft_id).await?;
         let absolute_node_path = self.get_absolute_path_from_relative(&auth, node_path)?;
 
-        draft.config.create_node_in_path(&absolute_node_path, config)?;
+        draft.config.create_node_in_path(
+            &absolute_node_path,
+            config,
+            &self.config_manager.get_resources(),
+        )?;
         Ok(self.config_manager.update_draft(draft_id, auth.auth.user, &draft.config).await?)
     }
 
@@ -547,7 +800,11 @@ impl<A: ConfigApiHandler, CM: MatcherConfigReader + MatcherConfigEditor + ?Sized
         let mut draft = self.get_draft_and_check_owner(&auth, draft_id).await?;
         let absolute_node_path = self.get_absolute_path_from_relative(&auth, node_path)?;
 
-        draft.config.edit_node_in_path(&absolute_node_path, config)?;
+        draft.config.edit_node_in_path(
+            &absolute_node_path,
+            config,
+            &self.config_manager.get_resources(),
+        )?;
         Ok(self.config_manager.update_draft(draft_id, auth.auth.user, &draft.config).await?)
     }
 
@@ -592,6 +849,11 @@ pub async fn get_filtered_matcher(
     })
 }
 
+/// Returns whether an authorized path is the root node, i.e. it grants the whole configuration
+fn is_root_path(authorized_path: &[String]) -> bool {
+    matches!(authorized_path, [root] if root == ROOT_NODE_NAME)
+}
+
 fn pop_authorized_path_and_append_relative_path<'a>(
     mut base_path: Vec<&'a str>,
     mut relative_path: Vec<&'a str>,
@@ -622,19 +884,31 @@ mod test {
     use crate::auth::Permission;
     use crate::error::ApiError;
     use async_trait::async_trait;
+    use serde_json::json;
     use std::collections::BTreeMap;
     use std::sync::Arc;
     use tornado_engine_api_dto::auth::Auth;
     use tornado_engine_api_dto::auth_v2::{AuthV2, Authorization};
-    use tornado_engine_api_dto::config::{ConstraintDto, RuleDetailsDto};
+    use tornado_engine_api_dto::config::{
+        ConfigChangeKindDto, ConfigChangeTargetDto, ConstraintDto, LintWarningKindDto,
+        RuleDetailsDto,
+    };
     use tornado_engine_matcher::config::nodes::Filter;
     use tornado_engine_matcher::config::rule::{Constraint, Rule};
     use tornado_engine_matcher::config::{
         Defaultable, MatcherConfig, MatcherConfigDraft, MatcherConfigDraftData,
+        MatcherConfigSnapshot, MatcherConfigSnapshotData,
     };
     use tornado_engine_matcher::error::MatcherError;
+    use tornado_engine_matcher::model::ProcessedRuleStatus;
+    use tornado_engine_matcher::suite::{RuleTestCase, RuleTestExpectation, RuleTestSuite};
 
     const DRAFT_OWNER_ID: &str = "OWNER";
+    const FAILING_TESTS_DRAFT_ID: &str = "failing_tests";
+    const FAILING_TESTS_SNAPSHOT_ID: &str = "1";
+    const OTHER_TENANT_USER_ID: &str = "other_tenant_user";
+    const LINT_WARNINGS_DRAFT_ID: &str = "lint_warnings";
+    const DIFF_DRAFT_ID: &str = "diff";
 
     struct TestConfigManager {}
 
@@ -647,6 +921,7 @@ mod test {
                     description: "".to_string(),
                     active: false,
                     filter: Defaultable::Default {},
+                    schedule: None,
                 },
                 nodes: vec![
                     MatcherConfig::Filter {
@@ -655,6 +930,7 @@ mod test {
                             description: "".to_string(),
                             active: false,
                             filter: Defaultable::Default {},
+                            schedule: None,
                         },
                         nodes: vec![
                             MatcherConfig::Filter {
@@ -663,6 +939,7 @@ mod test {
                                     description: "".to_string(),
                                     active: false,
                                     filter: Defaultable::Default {},
+                                    schedule: None,
                                 },
                                 nodes: vec![],
                             },
@@ -678,6 +955,9 @@ mod test {
                                         with: Default::default(),
                                     },
                                     actions: vec![],
+                                    threshold: None,
+                                    suppression: None,
+                                    schedule: None,
                                 }],
                             },
                         ],
@@ -688,6 +968,7 @@ mod test {
                             description: "".to_string(),
                             active: false,
                             filter: Defaultable::Default {},
+                            schedule: None,
                         },
                         nodes: vec![
                             MatcherConfig::Ruleset {
@@ -702,6 +983,9 @@ mod test {
                                         with: Default::default(),
                                     },
                                     actions: vec![],
+                                    threshold: None,
+                                    suppression: None,
+                                    schedule: None,
                                 }],
                             },
                             MatcherConfig::Ruleset { name: "root_2_2".to_string(), rules: vec![] },
@@ -719,13 +1003,39 @@ mod test {
         }
 
         async fn get_draft(&self, draft_id: &str) -> Result<MatcherConfigDraft, MatcherError> {
+            let data = MatcherConfigDraftData {
+                user: DRAFT_OWNER_ID.to_owned(),
+                draft_id: draft_id.to_owned(),
+                created_ts_ms: 0,
+                updated_ts_ms: 0,
+            };
+            if draft_id == DIFF_DRAFT_ID {
+                let mut config = self.get_config().await?;
+                config.delete_node_in_path(&["root", "root_2"])?;
+                let rule = Rule { name: "root_1_2_1".to_owned(), ..Default::default() };
+                config.edit_rule(
+                    &["root", "root_1", "root_1_2"],
+                    "root_1_2_1",
+                    rule,
+                    &Default::default(),
+                )?;
+                return Ok(MatcherConfigDraft { data, config });
+            }
+            if draft_id == LINT_WARNINGS_DRAFT_ID {
+                let mut config = self.get_config().await?;
+                if let MatcherConfig::Filter { nodes, .. } = &mut config {
+                    nodes.push(MatcherConfig::Ruleset {
+                        name: "ruleset".to_owned(),
+                        rules: vec![
+                            Rule { name: "all".to_owned(), active: true, ..Default::default() },
+                            Rule { name: "never".to_owned(), active: true, ..Default::default() },
+                        ],
+                    });
+                }
+                return Ok(MatcherConfigDraft { data, config });
+            }
             Ok(MatcherConfigDraft {
-                data: MatcherConfigDraftData {
-                    user: DRAFT_OWNER_ID.to_owned(),
-                    draft_id: draft_id.to_owned(),
-                    created_ts_ms: 0,
-                    updated_ts_ms: 0,
-                },
+                data,
                 config: MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![] },
             })
         }
@@ -743,7 +1053,11 @@ mod test {
             Ok(())
         }
 
-        async fn deploy_draft(&self, _draft_id: &str) -> Result<MatcherConfig, MatcherError> {
+        async fn deploy_draft(
+            &self,
+            _draft_id: &str,
+            _comment: &str,
+        ) -> Result<MatcherConfig, MatcherError> {
             Ok(MatcherConfig::Ruleset { name: "ruleset_new".to_owned(), rules: vec![] })
         }
 
@@ -765,6 +1079,86 @@ mod test {
         ) -> Result<MatcherConfig, MatcherError> {
             unimplemented!()
         }
+
+        async fn get_snapshots(&self) -> Result<Vec<MatcherConfigSnapshotData>, MatcherError> {
+            Ok(vec![self.get_snapshot("2").await?.data, self.get_snapshot("1").await?.data])
+        }
+
+        async fn get_snapshot(
+            &self,
+            snapshot_id: &str,
+        ) -> Result<MatcherConfigSnapshot, MatcherError> {
+            let config = match snapshot_id {
+                "1" => self.get_config().await?,
+                "2" => MatcherConfig::Filter {
+                    name: "root".to_owned(),
+                    filter: Filter { active: true, ..Default::default() },
+                    nodes: vec![],
+                },
+                _ => {
+                    return Err(MatcherError::SnapshotNotFoundError {
+                        snapshot_id: snapshot_id.to_owned(),
+                    })
+                }
+            };
+            Ok(MatcherConfigSnapshot {
+                data: MatcherConfigSnapshotData {
+                    snapshot_id: snapshot_id.to_owned(),
+                    created_ts_ms: 0,
+                    user: if snapshot_id == "1" { DRAFT_OWNER_ID } else { OTHER_TENANT_USER_ID }
+                        .to_owned(),
+                    comment: "".to_owned(),
+                },
+                config,
+            })
+        }
+
+        async fn rollback_to_snapshot(
+            &self,
+            snapshot_id: &str,
+            _user: String,
+        ) -> Result<MatcherConfig, MatcherError> {
+            Ok(self.get_snapshot(snapshot_id).await?.config)
+        }
+
+        async fn get_draft_test_suites(
+            &self,
+            draft_id: &str,
+        ) -> Result<Vec<RuleTestSuite>, MatcherError> {
+            if draft_id != FAILING_TESTS_DRAFT_ID {
+                return Ok(vec![]);
+            }
+            Ok(failing_test_suites(vec![]))
+        }
+
+        async fn get_snapshot_test_suites(
+            &self,
+            snapshot_id: &str,
+        ) -> Result<Vec<RuleTestSuite>, MatcherError> {
+            if snapshot_id != FAILING_TESTS_SNAPSHOT_ID {
+                return Ok(vec![]);
+            }
+            Ok(failing_test_suites(vec!["root_1".to_owned(), "root_1_2".to_owned()]))
+        }
+    }
+
+    fn failing_test_suites(ruleset_path: Vec<String>) -> Vec<RuleTestSuite> {
+        let mut rules = BTreeMap::new();
+        rules.insert(
+            "missing_rule".to_owned(),
+            RuleTestExpectation { status: Some(ProcessedRuleStatus::Matched), actions: None },
+        );
+        let mut cases = BTreeMap::new();
+        cases.insert(
+            "case_01".to_owned(),
+            RuleTestCase {
+                description: "".to_owned(),
+                event: json!({ "type": "email", "created_ms": 0 }),
+                matched_rules: None,
+                rules,
+            },
+        );
+        vec![RuleTestSuite { ruleset_path, cases }]
     }
 
     struct TestApiHandler {}
@@ -1006,10 +1400,322 @@ mod test {
             create_users(&permissions_map);
 
         // Act & Assert
-        assert!(api.deploy_draft(not_owner_edit_and_view, "id").await.is_err());
-        assert!(api.deploy_draft(owner_view, "id").await.is_err());
-        assert!(api.deploy_draft(owner_edit, "id").await.is_ok());
-        assert!(api.deploy_draft(owner_edit_and_view, "id").await.is_ok());
+        assert!(api.deploy_draft(not_owner_edit_and_view, "id", "").await.is_err());
+        assert!(api.deploy_draft(owner_view, "id", "").await.is_err());
+        assert!(api.deploy_draft(owner_edit, "id", "").await.is_ok());
+        assert!(api.deploy_draft(owner_edit_and_view, "id", "").await.is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn deploy_draft_should_return_the_lint_warnings() {
+        // Arrange
+        let api = ConfigApi::new(TestApiHandler {}, Arc::new(TestConfigManager {}));
+        let permissions_map = auth_permissions();
+        let (_, _, _, owner_edit_and_view) = create_users_v2(&permissions_map);
+
+        // Act
+        let result = api
+            .deploy_draft_for_tenant(&owner_edit_and_view, LINT_WARNINGS_DRAFT_ID, "")
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(1, result.warnings.len());
+        assert_eq!(
+            vec!["root".to_owned(), "ruleset".to_owned(), "never".to_owned()],
+            result.warnings[0].path
+        );
+        assert_eq!(LintWarningKindDto::UnreachableRule, result.warnings[0].kind);
+    }
+
+    #[actix_rt::test]
+    async fn deploy_draft_should_return_only_the_lint_warnings_in_the_authorized_path() {
+        // Arrange
+        let api = ConfigApi::new(TestApiHandler {}, Arc::new(TestConfigManager {}));
+        let permissions_map = auth_permissions();
+        let owner_edit_and_view_root_1 = AuthContextV2::new(
+            AuthV2 {
+                user: DRAFT_OWNER_ID.to_owned(),
+                authorization: Authorization {
+                    path: vec!["root".to_owned(), "root_1".to_owned()],
+                    roles: vec!["edit".to_owned(), "view".to_owned()],
+                },
+                preferences: None,
+            },
+            &permissions_map,
+        );
+
+        // Act
+        let result = api
+            .deploy_draft_for_tenant(&owner_edit_and_view_root_1, LINT_WARNINGS_DRAFT_ID, "")
+            .await
+            .unwrap();
+
+        // Assert
+        assert!(result.warnings.is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn get_draft_diff_should_return_the_changes_in_the_authorized_path() {
+        // Arrange
+        let api = ConfigApi::new(TestApiHandler {}, Arc::new(TestConfigManager {}));
+        let permissions_map = auth_permissions();
+        let (not_owner_edit_and_view, owner_view, _, _) = create_users_v2(&permissions_map);
+        let owner_view_root_1 = AuthContextV2::new(
+            AuthV2 {
+                user: DRAFT_OWNER_ID.to_owned(),
+                authorization: Authorization {
+                    path: vec!["root".to_owned(), "root_1".to_owned()],
+                    roles: vec!["view".to_owned()],
+                },
+                preferences: None,
+            },
+            &permissions_map,
+        );
+
+        // Act
+        let changes = api.get_draft_diff(&owner_view, DIFF_DRAFT_ID).await.unwrap();
+        let authorized_changes =
+            api.get_draft_diff(&owner_view_root_1, DIFF_DRAFT_ID).await.unwrap();
+
+        // Assert
+        assert_eq!(2, changes.len());
+        assert_eq!(vec!["root", "root_2"], changes[0].path);
+        assert_eq!(ConfigChangeKindDto::Removed {}, changes[0].change);
+        assert_eq!(vec!["root", "root_1", "root_1_2", "root_1_2_1"], changes[1].path);
+        assert_eq!(ConfigChangeTargetDto::Rule, changes[1].target);
+        match &changes[1].change {
+            ConfigChangeKindDto::Modified { fields } => {
+                assert!(fields.iter().any(|field| field.field == vec!["active".to_owned()]))
+            }
+            change => panic!("{:?}", change),
+        }
+
+        assert_eq!(1, authorized_changes.len());
+        assert_eq!(vec!["root_1", "root_1_2", "root_1_2_1"], authorized_changes[0].path);
+
+        assert!(api.get_draft_diff(&not_owner_edit_and_view, DIFF_DRAFT_ID).await.is_err());
+    }
+
+    #[actix_rt::test]
+    async fn get_snapshots_should_require_view_permission() {
+        // Arrange
+        let api = ConfigApi::new(TestApiHandler {}, Arc::new(TestConfigManager {}));
+        let permissions_map = auth_permissions();
+        let (not_owner_edit_and_view, owner_view, owner_edit, _) =
+            create_users_v2(&permissions_map);
+
+        // Act & Assert
+        let snapshots = api.get_snapshots(&owner_view).await.unwrap();
+        assert_eq!(
+            vec!["2".to_owned(), "1".to_owned()],
+            snapshots.into_iter().map(|snapshot| snapshot.snapshot_id).collect::<Vec<_>>()
+        );
+        assert!(api.get_snapshots(&not_owner_edit_and_view).await.is_ok());
+        assert!(api.get_snapshots(&owner_edit).await.is_err());
+    }
+
+    #[actix_rt::test]
+    async fn get_snapshots_should_return_only_the_own_snapshots_to_a_tenant() {
+        // Arrange
+        let api = ConfigApi::new(TestApiHandler {}, Arc::new(TestConfigManager {}));
+        let permissions_map = auth_permissions();
+        let owner_view_root_1 = AuthContextV2::new(
+            AuthV2 {
+                user: DRAFT_OWNER_ID.to_owned(),
+                authorization: Authorization {
+                    path: vec!["root".to_owned(), "root_1".to_owned()],
+                    roles: vec!["view".to_owned()],
+                },
+                preferences: None,
+            },
+            &permissions_map,
+        );
+
+        // Act
+        let snapshots = api.get_snapshots(&owner_view_root_1).await.unwrap();
+
+        // Assert
+        assert_eq!(1, snapshots.len());
+        assert_eq!("1", snapshots[0].snapshot_id);
+        assert_eq!(DRAFT_OWNER_ID, snapshots[0].user);
+    }
+
+    #[actix_rt::test]
+    async fn get_snapshot_tree_should_return_the_authorized_nodes() {
+        // Arrange
+        let api = ConfigApi::new(TestApiHandler {}, Arc::new(TestConfigManager {}));
+        let permissions_map = auth_permissions();
+        let (_, owner_view, _, _) = create_users_v2(&permissions_map);
+
+        // Act
+        let nodes = api
+            .get_snapshot_config_processing_tree_nodes_by_path(owner_view.clone(), "1", None)
+            .await
+            .unwrap();
+        let missing =
+            api.get_snapshot_config_processing_tree_nodes_by_path(owner_view, "100", None).await;
+
+        // Assert
+        assert_eq!(1, nodes.len());
+        assert!(
+            matches!(&nodes[0], ProcessingTreeNodeConfigDto::Filter { name, .. } if name == "root")
+        );
+        match missing {
+            Err(ApiError::MatcherError { cause: MatcherError::SnapshotNotFoundError { .. } }) => {}
+            result => panic!("{:?}", result),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn diff_snapshots_should_return_the_changes() {
+        // Arrange
+        let api = ConfigApi::new(TestApiHandler {}, Arc::new(TestConfigManager {}));
+        let permissions_map = auth_permissions();
+        let (_, owner_view, _, _) = create_users_v2(&permissions_map);
+
+        // Act
+        let changes = api.diff_snapshots(&owner_view, "1", "2").await.unwrap();
+        let no_changes = api.diff_snapshots(&owner_view, "1", "1").await.unwrap();
+
+        // Assert
+        assert_eq!(3, changes.len());
+        assert_eq!(vec!["root"], changes[0].path);
+        assert!(matches!(changes[0].change, ConfigChangeKindDto::Modified { .. }));
+        assert_eq!(vec!["root", "root_1"], changes[1].path);
+        assert_eq!(ConfigChangeKindDto::Removed {}, changes[1].change);
+        assert_eq!(vec!["root", "root_2"], changes[2].path);
+        assert_eq!(ConfigChangeKindDto::Removed {}, changes[2].change);
+        assert!(no_changes.is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn rollback_to_snapshot_should_require_edit_permission() {
+        // Arrange
+        let api = ConfigApi::new(TestApiHandler {}, Arc::new(TestConfigManager {}));
+        let permissions_map = auth_permissions();
+        let (_, owner_view, owner_edit, _) = create_users_v2(&permissions_map);
+
+        // Act & Assert
+        assert!(api.rollback_to_snapshot(&owner_view, "2").await.is_err());
+        assert!(api.rollback_to_snapshot(&owner_edit, "2").await.is_ok());
+        assert!(api.rollback_to_snapshot(&owner_edit, "100").await.is_err());
+    }
+
+    #[actix_rt::test]
+    async fn rollback_to_snapshot_should_require_the_root_authorization() {
+        // Arrange
+        let api = ConfigApi::new(TestApiHandler {}, Arc::new(TestConfigManager {}));
+        let permissions_map = auth_permissions();
+        let owner_edit_root_1 = AuthContextV2::new(
+            AuthV2 {
+                user: DRAFT_OWNER_ID.to_owned(),
+                authorization: Authorization {
+                    path: vec!["root".to_owned(), "root_1".to_owned()],
+                    roles: vec!["edit".to_owned()],
+                },
+                preferences: None,
+            },
+            &permissions_map,
+        );
+
+        // Act
+        let result = api.rollback_to_snapshot(&owner_edit_root_1, "2").await;
+
+        // Assert
+        assert!(matches!(result, Err(ApiError::ForbiddenError { .. })));
+    }
+
+    #[actix_rt::test]
+    async fn rollback_to_snapshot_should_fail_if_the_snapshot_tests_fail() {
+        // Arrange
+        let api = ConfigApi::new(TestApiHandler {}, Arc::new(TestConfigManager {}));
+        let permissions_map = auth_permissions();
+        let (_, _, owner_edit, _) = create_users_v2(&permissions_map);
+
+        // Act
+        let result = api.rollback_to_snapshot(&owner_edit, FAILING_TESTS_SNAPSHOT_ID).await;
+
+        // Assert
+        match result {
+            Err(ApiError::RuleTestFailedError { message }) => {
+                assert!(message.contains("[FAIL] root_1/root_1_2/case_01"));
+            }
+            result => panic!("{:?}", result),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn deploy_draft_should_fail_if_the_draft_tests_fail() {
+        // Arrange
+        let api = ConfigApi::new(TestApiHandler {}, Arc::new(TestConfigManager {}));
+        let permissions_map = auth_permissions();
+        let (_, _, _, owner_edit_and_view) = create_users_v2(&permissions_map);
+
+        // Act
+        let result =
+            api.deploy_draft_for_tenant(&owner_edit_and_view, FAILING_TESTS_DRAFT_ID, "").await;
+
+        // Assert
+        match result {
+            Err(ApiError::RuleTestFailedError { message }) => {
+                assert!(message.contains("[FAIL] /case_01"));
+                assert!(message.contains("rule [missing_rule]"));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_draft_should_return_the_results_of_the_draft_tests() {
+        // Arrange
+        let api = ConfigApi::new(TestApiHandler {}, Arc::new(TestConfigManager {}));
+        let permissions_map = auth_permissions();
+        let (not_owner_edit_and_view, owner_view, owner_edit, _) =
+            create_users_v2(&permissions_map);
+
+        // Act
+        let results = api.test_draft_for_tenant(&owner_view, FAILING_TESTS_DRAFT_ID).await.unwrap();
+
+        // Assert
+        assert_eq!(1, results.len());
+        assert_eq!("case_01", results[0].case);
+        assert!(!results[0].success);
+        assert_eq!(1, results[0].differences.len());
+        assert!(api.test_draft_for_tenant(&not_owner_edit_and_view, "id").await.is_err());
+        assert!(api.test_draft_for_tenant(&owner_edit, "id").await.is_err());
+        assert!(api.test_draft_for_tenant(&owner_view, "id").await.unwrap().is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn test_draft_should_run_only_the_tests_in_the_authorized_path() {
+        // Arrange
+        let api = ConfigApi::new(TestApiHandler {}, Arc::new(TestConfigManager {}));
+        let permissions_map = auth_permissions();
+        let owner_edit_and_view_root_1 = AuthContextV2::new(
+            AuthV2 {
+                user: DRAFT_OWNER_ID.to_owned(),
+                authorization: Authorization {
+                    path: vec!["root".to_owned(), "root_1".to_owned()],
+                    roles: vec!["edit".to_owned(), "view".to_owned()],
+                },
+                preferences: None,
+            },
+            &permissions_map,
+        );
+
+        // Act
+        let results = api
+            .test_draft_for_tenant(&owner_edit_and_view_root_1, FAILING_TESTS_DRAFT_ID)
+            .await
+            .unwrap();
+        let deploy_result = api
+            .deploy_draft_for_tenant(&owner_edit_and_view_root_1, FAILING_TESTS_DRAFT_ID, "")
+            .await;
+
+        // Assert
+        assert!(results.is_empty());
+        assert!(deploy_result.is_ok());
     }
 
     #[actix_rt::test]
@@ -1356,6 +2062,9 @@ mod test {
             active: true,
             constraint: ConstraintDto { where_operator: None, with: Default::default() },
             actions: vec![],
+            threshold: None,
+            suppression: None,
+            schedule: None,
         };
         assert_eq!(res_get_rule_details, expected_res);
     }
@@ -1510,6 +2219,7 @@ mod test {
                 description: "".to_string(),
                 active: false,
                 filter: Defaultable::Default {},
+                schedule: None,
             },
             nodes: vec![],
         };
@@ -1537,6 +2247,9 @@ mod test {
                 active: false,
                 constraint: Constraint { where_operator: None, with: Default::default() },
                 actions: vec![],
+                threshold: None,
+                suppression: None,
+                schedule: None,
             }],
         };
 
@@ -1,13 +1,24 @@
 use serde_json::Error;
 use tornado_engine_api_dto::config::{
-    ActionDto, ConstraintDto, ExtractorDto, ExtractorRegexDto, ModifierDto, OperatorDto,
-    ProcessingTreeNodeEditDto, RuleDto,
+    ActionDto, ConfigChangeDto, ConfigChangeKindDto, ConfigChangeTargetDto, ConfigSnapshotDto,
+    ConstraintDto, CorrelationDto, EnricherDto, ExtractorDto, ExtractorRegexDto, FieldChangeDto,
+    HeartbeatDto, LintWarningDto, LintWarningKindDto, ModifierDto, OperatorDto,
+    ProcessingTreeNodeEditDto, RuleDto, RuleTestDifferenceDto, RuleTestResultDto, ScheduleDto,
+    ScheduleWindowDto, SuppressionDto, ThresholdDto, ValueTypeDto,
+};
+use tornado_engine_matcher::config::diff::{
+    ConfigChange, ConfigChangeKind, ConfigChangeTarget, FieldChange,
+};
+use tornado_engine_matcher::config::nodes::{
+    Correlation, Enricher, Filter, Heartbeat, MatcherIterator,
 };
-use tornado_engine_matcher::config::nodes::{Filter, MatcherIterator};
 use tornado_engine_matcher::config::rule::{
-    ConfigAction, Constraint, Extractor, ExtractorRegex, Modifier, Operator, Rule,
+    ConfigAction, Constraint, Extractor, ExtractorRegex, KeyValueFormat, Modifier, Operator, Rule,
+    Schedule, ScheduleWindow, Suppression, Threshold, ValueType,
 };
-use tornado_engine_matcher::config::{Defaultable, MatcherConfig};
+use tornado_engine_matcher::config::{Defaultable, MatcherConfig, MatcherConfigSnapshotData};
+use tornado_engine_matcher::suite::{RuleTestDifference, RuleTestResult};
+use tornado_engine_matcher::validator::lint::{LintWarning, LintWarningKind};
 
 pub fn rule_into_dto(rule: Rule) -> Result<RuleDto, Error> {
     Ok(RuleDto {
@@ -17,9 +28,24 @@ pub fn rule_into_dto(rule: Rule) -> Result<RuleDto, Error> {
         description: rule.description,
         do_continue: rule.do_continue,
         name: rule.name,
+        threshold: rule.threshold.map(threshold_into_dto),
+        suppression: rule.suppression.map(suppression_into_dto),
+        schedule: rule.schedule.as_ref().map(ScheduleDto::from),
     })
 }
 
+fn suppression_into_dto(suppression: Suppression) -> SuppressionDto {
+    SuppressionDto { fingerprint: suppression.fingerprint, ttl_seconds: suppression.ttl_seconds }
+}
+
+fn threshold_into_dto(threshold: Threshold) -> ThresholdDto {
+    ThresholdDto {
+        count: threshold.count,
+        window_seconds: threshold.window_seconds,
+        group_by: threshold.group_by,
+    }
+}
+
 fn action_into_dto(action: ConfigAction) -> Result<ActionDto, Error> {
     Ok(ActionDto { id: action.id, payload: serde_json::to_value(action.payload)? })
 }
@@ -38,38 +64,7 @@ fn constraint_into_dto(constraint: Constraint) -> Result<ConstraintDto, Error> {
 }
 
 fn extractor_into_dto(extractor: Extractor) -> ExtractorDto {
-    ExtractorDto {
-        from: extractor.from,
-        regex: extractor_regex_into_dto(extractor.regex),
-        modifiers_post: extractor
-            .modifiers_post
-            .into_iter()
-            .map(|modifier| match modifier {
-                Modifier::Lowercase {} => ModifierDto::Lowercase {},
-                Modifier::Map { mapping, default_value } => {
-                    ModifierDto::Map { mapping, default_value }
-                }
-                Modifier::ReplaceAll { find, replace, is_regex } => {
-                    ModifierDto::ReplaceAll { find, replace, is_regex }
-                }
-                Modifier::ToNumber {} => ModifierDto::ToNumber {},
-                Modifier::Trim {} => ModifierDto::Trim {},
-                Modifier::DateAndTime { timezone } => ModifierDto::DateAndTime { timezone },
-            })
-            .collect(),
-    }
-}
-
-fn extractor_regex_into_dto(extractor_regex: ExtractorRegex) -> ExtractorRegexDto {
-    match extractor_regex {
-        ExtractorRegex::Regex { regex, all_matches, group_match_idx } => {
-            ExtractorRegexDto::Regex { regex, all_matches, group_match_idx }
-        }
-        ExtractorRegex::RegexNamedGroups { regex, all_matches } => {
-            ExtractorRegexDto::RegexNamedGroups { regex, all_matches }
-        }
-        ExtractorRegex::SingleKeyRegex { regex } => ExtractorRegexDto::KeyRegex { regex },
-    }
+    ExtractorDto::from(&extractor)
 }
 
 pub fn processing_tree_node_details_dto_into_matcher_config(
@@ -79,7 +74,7 @@ pub fn processing_tree_node_details_dto_into_matcher_config(
         ProcessingTreeNodeEditDto::Ruleset { name } => {
             MatcherConfig::Ruleset { name, rules: vec![] }
         }
-        ProcessingTreeNodeEditDto::Filter { name, description, active, filter } => {
+        ProcessingTreeNodeEditDto::Filter { name, description, active, filter, schedule } => {
             let filter_matcher_config = if let Some(filter_inner) = filter {
                 Defaultable::from(Option::Some(dto_into_operator(filter_inner)?))
             } else {
@@ -87,7 +82,12 @@ pub fn processing_tree_node_details_dto_into_matcher_config(
             };
             MatcherConfig::Filter {
                 name,
-                filter: Filter { description, filter: filter_matcher_config, active },
+                filter: Filter {
+                    description,
+                    filter: filter_matcher_config,
+                    active,
+                    schedule: schedule.map(dto_into_schedule),
+                },
                 nodes: vec![],
             }
         }
@@ -98,9 +98,132 @@ pub fn processing_tree_node_details_dto_into_matcher_config(
                 nodes: vec![],
             }
         }
+        ProcessingTreeNodeEditDto::Correlation { name, correlation } => {
+            MatcherConfig::Correlation { name, correlation: dto_into_correlation(correlation)? }
+        }
+        ProcessingTreeNodeEditDto::Heartbeat { name, heartbeat } => {
+            MatcherConfig::Heartbeat { name, heartbeat: dto_into_heartbeat(heartbeat)? }
+        }
+        ProcessingTreeNodeEditDto::Enricher { name, enricher } => {
+            MatcherConfig::Enricher { name, enricher: dto_into_enricher(enricher), nodes: vec![] }
+        }
+    })
+}
+
+fn dto_into_enricher(enricher: EnricherDto) -> Enricher {
+    Enricher {
+        description: enricher.description,
+        active: enricher.active,
+        payload_key: enricher.payload_key,
+        with: enricher
+            .with
+            .into_iter()
+            .map(|(key, extractor)| (key, dto_into_extractor(extractor)))
+            .collect(),
+    }
+}
+
+fn dto_into_heartbeat(heartbeat: HeartbeatDto) -> Result<Heartbeat, Error> {
+    Ok(Heartbeat {
+        description: heartbeat.description,
+        active: heartbeat.active,
+        filter: match heartbeat.filter {
+            Some(filter) => Defaultable::Value(dto_into_operator(filter)?),
+            None => Defaultable::Default {},
+        },
+        key: heartbeat.key,
+        max_interval_seconds: heartbeat.max_interval_seconds,
     })
 }
 
+fn dto_into_correlation(correlation: CorrelationDto) -> Result<Correlation, Error> {
+    Ok(Correlation {
+        description: correlation.description,
+        active: correlation.active,
+        correlate_by: correlation.correlate_by,
+        steps: correlation
+            .steps
+            .into_iter()
+            .map(dto_into_operator)
+            .collect::<Result<Vec<_>, _>>()?,
+        timeout_seconds: correlation.timeout_seconds,
+        on_complete: correlation
+            .on_complete
+            .into_iter()
+            .map(dto_into_action)
+            .collect::<Result<Vec<_>, _>>()?,
+        on_timeout: correlation
+            .on_timeout
+            .into_iter()
+            .map(dto_into_action)
+            .collect::<Result<Vec<_>, _>>()?,
+    })
+}
+
+pub fn rule_test_result_into_dto(result: RuleTestResult) -> RuleTestResultDto {
+    RuleTestResultDto {
+        success: result.is_success(),
+        ruleset_path: result.ruleset_path,
+        case: result.case,
+        differences: result.differences.into_iter().map(rule_test_difference_into_dto).collect(),
+        processed_ruleset: result.processed_ruleset,
+    }
+}
+
+fn rule_test_difference_into_dto(difference: RuleTestDifference) -> RuleTestDifferenceDto {
+    RuleTestDifferenceDto {
+        diff: difference.diff(),
+        subject: difference.subject,
+        expected: difference.expected,
+        actual: difference.actual,
+    }
+}
+
+pub fn config_snapshot_into_dto(snapshot: MatcherConfigSnapshotData) -> ConfigSnapshotDto {
+    ConfigSnapshotDto {
+        snapshot_id: snapshot.snapshot_id,
+        created_ts_ms: snapshot.created_ts_ms,
+        user: snapshot.user,
+        comment: snapshot.comment,
+    }
+}
+
+pub fn lint_warning_into_dto(warning: LintWarning) -> LintWarningDto {
+    LintWarningDto {
+        path: warning.path,
+        kind: match warning.kind {
+            LintWarningKind::UnreachableRule => LintWarningKindDto::UnreachableRule,
+            LintWarningKind::UnknownVariable => LintWarningKindDto::UnknownVariable,
+            LintWarningKind::UnusedVariable => LintWarningKindDto::UnusedVariable,
+            LintWarningKind::UnknownRegexGroup => LintWarningKindDto::UnknownRegexGroup,
+        },
+        message: warning.message,
+    }
+}
+
+pub fn config_change_into_dto(change: ConfigChange) -> ConfigChangeDto {
+    let field_change_into_dto = |field_change: FieldChange| FieldChangeDto {
+        field: field_change.field,
+        old_value: field_change.old_value,
+        new_value: field_change.new_value,
+    };
+    ConfigChangeDto {
+        path: change.path,
+        target: match change.target {
+            ConfigChangeTarget::Node => ConfigChangeTargetDto::Node,
+            ConfigChangeTarget::Rule => ConfigChangeTargetDto::Rule,
+        },
+        change: match change.kind {
+            ConfigChangeKind::Added => ConfigChangeKindDto::Added {},
+            ConfigChangeKind::Removed => ConfigChangeKindDto::Removed {},
+            ConfigChangeKind::Moved { from } => ConfigChangeKindDto::Moved { from },
+            ConfigChangeKind::Modified { fields } => ConfigChangeKindDto::Modified {
+                fields: fields.into_iter().map(field_change_into_dto).collect(),
+            },
+        },
+    }
+}
+
 pub fn dto_into_rule(rule: RuleDto) -> Result<Rule, Error> {
     Ok(Rule {
         active: rule.active,
@@ -109,9 +232,38 @@ pub fn dto_into_rule(rule: RuleDto) -> Result<Rule, Error> {
         description: rule.description,
         do_continue: rule.do_continue,
         name: rule.name,
+        threshold: rule.threshold.map(dto_into_threshold),
+        suppression: rule.suppression.map(dto_into_suppression),
+        schedule: rule.schedule.map(dto_into_schedule),
     })
 }
 
+fn dto_into_schedule(schedule: ScheduleDto) -> Schedule {
+    let into_window = |window: ScheduleWindowDto| match window {
+        ScheduleWindowDto::Recurring { cron, duration_seconds } => {
+            ScheduleWindow::Recurring { cron, duration_seconds }
+        }
+        ScheduleWindowDto::Range { from, to } => ScheduleWindow::Range { from, to },
+    };
+    Schedule {
+        timezone: schedule.timezone,
+        active_windows: schedule.active_windows.into_iter().map(into_window).collect(),
+        inactive_windows: schedule.inactive_windows.into_iter().map(into_window).collect(),
+    }
+}
+
+fn dto_into_suppression(suppression: SuppressionDto) -> Suppression {
+    Suppression { fingerprint: suppression.fingerprint, ttl_seconds: suppression.ttl_seconds }
+}
+
+fn dto_into_threshold(threshold: ThresholdDto) -> Threshold {
+    Threshold {
+        count: threshold.count,
+        window_seconds: threshold.window_seconds,
+        group_by: threshold.group_by,
+    }
+}
+
 fn dto_into_action(action: ActionDto) -> Result<ConfigAction, Error> {
     Ok(ConfigAction { id: action.id, payload: serde_json::from_value(action.payload)? })
 }
@@ -180,11 +332,48 @@ fn dto_into_operator(operator: OperatorDto) -> Result<Operator, Error> {
             first: serde_json::from_value(first)?,
             second: serde_json::from_value(second)?,
         },
-        OperatorDto::Regex { regex, target } => Operator::Regex { regex, target },
+        OperatorDto::Regex { regex, target, grok } => Operator::Regex { regex, target, grok },
+        OperatorDto::In { first, values } => Operator::In {
+            first: serde_json::from_value(first)?,
+            values: values
+                .into_iter()
+                .map(serde_json::from_value)
+                .collect::<Result<Vec<_>, _>>()?,
+        },
+        OperatorDto::StartsWith { first, second } => Operator::StartsWith {
+            first: serde_json::from_value(first)?,
+            second: serde_json::from_value(second)?,
+        },
+        OperatorDto::EndsWith { first, second } => Operator::EndsWith {
+            first: serde_json::from_value(first)?,
+            second: serde_json::from_value(second)?,
+        },
+        OperatorDto::Exists { target } => Operator::Exists { target },
+        OperatorDto::NotExists { target } => Operator::NotExists { target },
+        OperatorDto::IsType { target, value_type } => {
+            Operator::IsType { target, value_type: dto_into_value_type(value_type) }
+        }
+        OperatorDto::IpInCidr { target, networks } => Operator::IpInCidr { target, networks },
+        OperatorDto::IpEquals { first, second } => Operator::IpEquals {
+            first: serde_json::from_value(first)?,
+            second: serde_json::from_value(second)?,
+        },
+        OperatorDto::Expression { expression } => Operator::Expression { expression },
     };
     Ok(result)
 }
 
+fn dto_into_value_type(value_type: ValueTypeDto) -> ValueType {
+    match value_type {
+        ValueTypeDto::String => ValueType::String,
+        ValueTypeDto::Number => ValueType::Number,
+        ValueTypeDto::Bool => ValueType::Bool,
+        ValueTypeDto::Array => ValueType::Array,
+        ValueTypeDto::Object => ValueType::Object,
+        ValueTypeDto::Null => ValueType::Null,
+    }
+}
+
 fn dto_into_extractor(extractor: ExtractorDto) -> Extractor {
     Extractor {
         from: extractor.from,
@@ -203,6 +392,29 @@ fn dto_into_extractor(extractor: ExtractorDto) -> Extractor {
                 ModifierDto::ToNumber {} => Modifier::ToNumber {},
                 ModifierDto::Trim {} => Modifier::Trim {},
                 ModifierDto::DateAndTime { timezone } => Modifier::DateAndTime { timezone },
+                ModifierDto::Uppercase {} => Modifier::Uppercase {},
+                ModifierDto::Substring { start, end } => Modifier::Substring { start, end },
+                ModifierDto::Split { separator, is_regex } => {
+                    Modifier::Split { separator, is_regex }
+                }
+                ModifierDto::Join { separator } => Modifier::Join { separator },
+                ModifierDto::PadLeft { length, padding } => Modifier::PadLeft { length, padding },
+                ModifierDto::PadRight { length, padding } => Modifier::PadRight { length, padding },
+                ModifierDto::Default { value } => Modifier::Default { value },
+                ModifierDto::ParseJson {} => Modifier::ParseJson {},
+                ModifierDto::Base64Decode {} => Modifier::Base64Decode {},
+                ModifierDto::Base64Encode {} => Modifier::Base64Encode {},
+                ModifierDto::UrlDecode {} => Modifier::UrlDecode {},
+                ModifierDto::HexDecode {} => Modifier::HexDecode {},
+                ModifierDto::ParseDate { formats, timezone } => {
+                    Modifier::ParseDate { formats, timezone }
+                }
+                ModifierDto::FormatDate { format, timezone } => {
+                    Modifier::FormatDate { format, timezone }
+                }
+                ModifierDto::Lookup { table, field, default_value } => {
+                    Modifier::Lookup { table, field, default_value }
+                }
             })
             .collect(),
     }
@@ -216,17 +428,36 @@ fn dto_into_extractor_regex(extractor_regex: ExtractorRegexDto) -> ExtractorRege
         ExtractorRegexDto::RegexNamedGroups { regex, all_matches } => {
             ExtractorRegex::RegexNamedGroups { regex, all_matches }
         }
+        ExtractorRegexDto::Grok { expression, all_matches } => {
+            ExtractorRegex::Grok { expression, all_matches }
+        }
         ExtractorRegexDto::KeyRegex { regex } => ExtractorRegex::SingleKeyRegex { regex },
+        ExtractorRegexDto::KeyValue { format } => ExtractorRegex::KeyValue {
+            format: KeyValueFormat {
+                pair_separator: format.pair_separator,
+                key_value_separator: format.key_value_separator,
+                quote: format.quote,
+            },
+        },
+        ExtractorRegexDto::JMESPath { expression } => ExtractorRegex::JMESPath { expression },
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::config::convert::processing_tree_node_details_dto_into_matcher_config;
+    use crate::config::convert::{
+        dto_into_extractor, dto_into_operator, extractor_into_dto,
+        processing_tree_node_details_dto_into_matcher_config,
+    };
     use serde_json::json;
-    use tornado_engine_api_dto::config::{OperatorDto, ProcessingTreeNodeEditDto};
-    use tornado_engine_matcher::config::nodes::Filter;
-    use tornado_engine_matcher::config::rule::Operator;
+    use std::collections::HashMap;
+    use tornado_engine_api_dto::config::{
+        CorrelationDto, EnricherDto, HeartbeatDto, OperatorDto, ProcessingTreeNodeEditDto,
+    };
+    use tornado_engine_matcher::config::nodes::{Correlation, Enricher, Filter, Heartbeat};
+    use tornado_engine_matcher::config::rule::{
+        ConfigAction, Extractor, ExtractorRegex, KeyValueFormat, Modifier, Operator, ValueType,
+    };
     use tornado_engine_matcher::config::{Defaultable, MatcherConfig};
 
     #[actix_rt::test]
@@ -239,6 +470,7 @@ mod test {
                 description: "test_filter description".to_string(),
                 active: false,
                 filter: Defaultable::Default {},
+                schedule: None,
             },
             nodes: vec![],
         };
@@ -250,6 +482,7 @@ mod test {
                 filter: Defaultable::from(Option::Some(Operator::And {
                     operators: vec![Operator::Equals { first: json!(12), second: json!(15) }],
                 })),
+                schedule: None,
             },
             nodes: vec![],
         };
@@ -259,6 +492,7 @@ mod test {
                 description: "test_filter description".to_string(),
                 active: false,
                 filter: None,
+                schedule: None,
             };
         let processing_tree_node_details_dto = ProcessingTreeNodeEditDto::Filter {
             name: "test_filter".to_string(),
@@ -267,6 +501,7 @@ mod test {
             filter: Option::Some(OperatorDto::And {
                 operators: vec![OperatorDto::Equals { first: json!(12), second: json!(15) }],
             }),
+            schedule: None,
         };
 
         // Act
@@ -285,6 +520,192 @@ mod test {
         assert_eq!(actual_maatcher_config_filter.unwrap(), expected_maatcher_config_filter);
     }
 
+    #[actix_rt::test]
+    async fn operators_should_be_converted_to_and_from_dto() {
+        // Arrange
+        let operator = Operator::Or {
+            operators: vec![
+                Operator::In {
+                    first: json!("${event.type}"),
+                    values: vec![json!("email"), json!(12)],
+                },
+                Operator::StartsWith {
+                    first: json!("${event.payload.host}"),
+                    second: json!("web"),
+                },
+                Operator::EndsWith { first: json!("${event.payload.host}"), second: json!(".com") },
+                Operator::Exists { target: "${event.payload.severity}".to_string() },
+                Operator::NotExists { target: "${event.payload.ack}".to_string() },
+                Operator::IsType {
+                    target: "${event.payload.severity}".to_string(),
+                    value_type: ValueType::Number,
+                },
+                Operator::IpInCidr {
+                    target: "${event.payload.source}".to_string(),
+                    networks: vec!["10.0.0.0/8".to_string()],
+                },
+                Operator::IpEquals {
+                    first: json!("${event.payload.source}"),
+                    second: json!("::1"),
+                },
+            ],
+        };
+        let expected_matcher_config_filter = MatcherConfig::Filter {
+            name: "test_filter".to_string(),
+            filter: Filter {
+                description: "".to_string(),
+                active: true,
+                filter: Defaultable::from(Some(operator.clone())),
+                schedule: None,
+            },
+            nodes: vec![],
+        };
+        let processing_tree_node_details_dto = ProcessingTreeNodeEditDto::Filter {
+            name: "test_filter".to_string(),
+            description: "".to_string(),
+            active: true,
+            filter: Some(OperatorDto::from(&operator)),
+            schedule: None,
+        };
+
+        // Act
+        let actual_matcher_config_filter =
+            processing_tree_node_details_dto_into_matcher_config(processing_tree_node_details_dto);
+
+        // Assert
+        assert_eq!(actual_matcher_config_filter.unwrap(), expected_matcher_config_filter);
+    }
+
+    #[actix_rt::test]
+    async fn extractors_should_be_converted_to_and_from_dto() {
+        // Arrange
+        let extractor = Extractor {
+            from: "${event.payload.hosts}".to_string(),
+            regex: ExtractorRegex::Regex {
+                regex: ".*".to_string(),
+                group_match_idx: Some(0),
+                all_matches: None,
+            },
+            modifiers_post: vec![
+                Modifier::Uppercase {},
+                Modifier::Substring { start: -10, end: Some(-1) },
+                Modifier::Split { separator: ";".to_string(), is_regex: false },
+                Modifier::Join { separator: ",".to_string() },
+                Modifier::PadLeft { length: 12, padding: Some("0".to_string()) },
+                Modifier::PadRight { length: 14, padding: None },
+                Modifier::Default { value: json!(["unknown"]) },
+                Modifier::ParseJson {},
+                Modifier::Base64Decode {},
+                Modifier::Base64Encode {},
+                Modifier::UrlDecode {},
+                Modifier::HexDecode {},
+                Modifier::ParseDate {
+                    formats: vec!["rfc3339".to_string(), "%d/%m/%Y".to_string()],
+                    timezone: Some("Europe/Rome".to_string()),
+                },
+                Modifier::FormatDate { format: "%Y-%m-%d".to_string(), timezone: None },
+                Modifier::Lookup {
+                    table: "teams".to_string(),
+                    field: Some("email".to_string()),
+                    default_value: Some(json!("ops@example.com")),
+                },
+            ],
+        };
+
+        // Act
+        let converted_extractor = dto_into_extractor(extractor_into_dto(extractor.clone()));
+
+        // Assert
+        assert_eq!(extractor, converted_extractor);
+    }
+
+    #[actix_rt::test]
+    async fn key_value_extractors_should_be_converted_to_and_from_dto() {
+        // Arrange
+        let extractor = Extractor {
+            from: "${event.payload.line}".to_string(),
+            regex: ExtractorRegex::KeyValue {
+                format: KeyValueFormat {
+                    pair_separator: Some(";".to_string()),
+                    key_value_separator: None,
+                    quote: Some("'".to_string()),
+                },
+            },
+            modifiers_post: vec![],
+        };
+
+        // Act
+        let converted_extractor = dto_into_extractor(extractor_into_dto(extractor.clone()));
+
+        // Assert
+        assert_eq!(extractor, converted_extractor);
+    }
+
+    #[actix_rt::test]
+    async fn jmespath_extractors_should_be_converted_to_and_from_dto() {
+        // Arrange
+        let extractor = Extractor {
+            from: "${event.payload}".to_string(),
+            regex: ExtractorRegex::JMESPath {
+                expression: "alerts[?status=='firing'].labels.host".to_string(),
+            },
+            modifiers_post: vec![],
+        };
+
+        // Act
+        let converted_extractor = dto_into_extractor(extractor_into_dto(extractor.clone()));
+
+        // Assert
+        assert_eq!(extractor, converted_extractor);
+    }
+
+    #[actix_rt::test]
+    async fn grok_extractors_and_operators_should_be_converted_to_and_from_dto() {
+        // Arrange
+        let extractor = Extractor {
+            from: "${event.payload.line}".to_string(),
+            regex: ExtractorRegex::Grok {
+                expression: "%{IP:src}:%{POSINT:port}".to_string(),
+                all_matches: Some(true),
+            },
+            modifiers_post: vec![],
+        };
+        let operator = Operator::Regex {
+            regex: "^%{SYSLOGBASE}".to_string(),
+            target: "${event.payload.line}".to_string(),
+            grok: true,
+        };
+
+        // Act
+        let converted_extractor = dto_into_extractor(extractor_into_dto(extractor.clone()));
+        let converted_operator = dto_into_operator(OperatorDto::from(&operator)).unwrap();
+
+        // Assert
+        assert_eq!(extractor, converted_extractor);
+        assert_eq!(operator, converted_operator);
+    }
+
+    #[actix_rt::test]
+    async fn expression_operators_should_be_converted_to_and_from_dto() {
+        // Arrange
+        let operator = Operator::And {
+            operators: vec![Operator::Expression {
+                expression: r#"event.type == "syslog" && payload.severity <= 3"#.to_string(),
+            }],
+        };
+
+        // Act
+        let dto = OperatorDto::from(&operator);
+        let converted_operator = dto_into_operator(dto.clone()).unwrap();
+
+        // Assert
+        assert_eq!(
+            json!({ "type": "AND", "operators": [{ "type": "expression", "expression": r#"event.type == "syslog" && payload.severity <= 3"# }] }),
+            serde_json::to_value(&dto).unwrap()
+        );
+        assert_eq!(operator, converted_operator);
+    }
+
     #[actix_rt::test]
     async fn processing_tree_node_details_dto_ruleset_into_matcher_config_should_return_a_matcher_config_ruleset(
     ) {
@@ -302,4 +723,120 @@ mod test {
         // Assert
         assert_eq!(actual_maatcher_config_ruleset.unwrap(), expected_maatcher_config_ruleset);
     }
+
+    #[actix_rt::test]
+    async fn processing_tree_node_details_dto_correlation_into_matcher_config_should_return_a_matcher_config_correlation(
+    ) {
+        // Arrange
+        let correlation = Correlation {
+            description: "problem not followed by recovery".to_string(),
+            active: true,
+            correlate_by: "${event.payload.host}".to_string(),
+            steps: vec![Operator::Equals {
+                first: json!("${event.type}"),
+                second: json!("problem"),
+            }],
+            timeout_seconds: 600,
+            on_complete: vec![],
+            on_timeout: vec![ConfigAction {
+                id: "logger".to_string(),
+                payload: Default::default(),
+            }],
+        };
+        let expected_matcher_config_correlation = MatcherConfig::Correlation {
+            name: "correlation_test".to_string(),
+            correlation: correlation.clone(),
+        };
+        let processing_tree_node_details_dto_correlation = ProcessingTreeNodeEditDto::Correlation {
+            name: "correlation_test".to_string(),
+            correlation: CorrelationDto::from(&correlation),
+        };
+
+        // Act
+        let actual_matcher_config_correlation =
+            processing_tree_node_details_dto_into_matcher_config(
+                processing_tree_node_details_dto_correlation,
+            );
+
+        // Assert
+        assert_eq!(actual_matcher_config_correlation.unwrap(), expected_matcher_config_correlation);
+    }
+
+    #[actix_rt::test]
+    async fn processing_tree_node_details_dto_heartbeat_into_matcher_config_should_return_a_matcher_config_heartbeat(
+    ) {
+        // Arrange
+        let heartbeat = Heartbeat {
+            description: "hosts sending check results".to_string(),
+            active: true,
+            filter: Defaultable::Value(Operator::Equals {
+                first: json!("${event.type}"),
+                second: json!("check_result"),
+            }),
+            key: "${event.payload.host}".to_string(),
+            max_interval_seconds: 300,
+        };
+        let expected_matcher_config_heartbeat = MatcherConfig::Heartbeat {
+            name: "heartbeat_test".to_string(),
+            heartbeat: heartbeat.clone(),
+        };
+        let processing_tree_node_details_dto_heartbeat = ProcessingTreeNodeEditDto::Heartbeat {
+            name: "heartbeat_test".to_string(),
+            heartbeat: HeartbeatDto::from(&heartbeat),
+        };
+
+        // Act
+        let actual_matcher_config_heartbeat = processing_tree_node_details_dto_into_matcher_config(
+            processing_tree_node_details_dto_heartbeat,
+        );
+
+        // Assert
+        assert_eq!(actual_matcher_config_heartbeat.unwrap(), expected_matcher_config_heartbeat);
+    }
+
+    #[actix_rt::test]
+    async fn processing_tree_node_details_dto_enricher_into_matcher_config_should_return_a_matcher_config_enricher(
+    ) {
+        // Arrange
+        let mut with = HashMap::new();
+        with.insert(
+            "team".to_owned(),
+            Extractor {
+                from: "${event.payload.host}".to_owned(),
+                regex: ExtractorRegex::Regex {
+                    regex: ".*".to_owned(),
+                    group_match_idx: Some(0),
+                    all_matches: None,
+                },
+                modifiers_post: vec![Modifier::Lookup {
+                    table: "teams".to_owned(),
+                    field: Some("team".to_owned()),
+                    default_value: None,
+                }],
+            },
+        );
+        let enricher = Enricher {
+            description: "adds the team of the host".to_string(),
+            active: true,
+            payload_key: Some("enrichment".to_owned()),
+            with,
+        };
+        let expected_matcher_config_enricher = MatcherConfig::Enricher {
+            name: "enricher_test".to_string(),
+            enricher: enricher.clone(),
+            nodes: vec![],
+        };
+        let processing_tree_node_details_dto_enricher = ProcessingTreeNodeEditDto::Enricher {
+            name: "enricher_test".to_string(),
+            enricher: EnricherDto::from(&enricher),
+        };
+
+        // Act
+        let actual_matcher_config_enricher = processing_tree_node_details_dto_into_matcher_config(
+            processing_tree_node_details_dto_enricher,
+        );
+
+        // Assert
+        assert_eq!(actual_matcher_config_enricher.unwrap(), expected_matcher_config_enricher);
+    }
 }
@@ -4,7 +4,7 @@ use crate::error::ApiError;
 use crate::model::{ApiData, ApiDataV2, ExportVersionedMatcherConfig};
 use actix_multipart::Multipart;
 use actix_web::http::header;
-use actix_web::web::{Data, Json, Path};
+use actix_web::web::{Data, Json, Path, Query};
 use actix_web::{web, HttpRequest, HttpResponse, Scope};
 use chrono::{Local, SecondsFormat};
 use futures_util::TryStreamExt as _;
@@ -15,8 +15,9 @@ use serde::Deserialize;
 use std::os::unix::ffi::OsStrExt;
 use tornado_engine_api_dto::common::Id;
 use tornado_engine_api_dto::config::{
-    ProcessingTreeNodeConfigDto, ProcessingTreeNodeDetailsDto, ProcessingTreeNodeEditDto, RuleDto,
-    RulePositionDto, TreeInfoDto,
+    ConfigChangeDto, ConfigSnapshotDto, DeployDraftResultDto, ProcessingTreeNodeConfigDto,
+    ProcessingTreeNodeDetailsDto, ProcessingTreeNodeEditDto, RuleDto, RulePositionDto,
+    RuleTestResultDto, TreeInfoDto,
 };
 use tornado_engine_matcher::config::MatcherConfigEditor;
 
@@ -94,6 +95,28 @@ pub fn build_config_v2_endpoints<
                         .route(web::put().to(draft_move_rule::<A, CM>)),
                 ),
         )
+        .service(
+            web::scope("/snapshot")
+                .service(
+                    web::resource("/tree/children/{param_auth}/{snapshot_id}")
+                        .route(web::get().to(get_snapshot_tree_node::<A, CM>)),
+                )
+                .service(
+                    web::resource("/tree/children/{param_auth}/{snapshot_id}/{node_path}")
+                        .route(web::get().to(get_snapshot_tree_node_with_node_path::<A, CM>)),
+                ),
+        )
+        .service(
+            web::resource("/snapshots/{param_auth}").route(web::get().to(get_snapshots::<A, CM>)),
+        )
+        .service(
+            web::resource("/snapshots/{param_auth}/{snapshot_id}/diff/{other_snapshot_id}")
+                .route(web::get().to(diff_snapshots::<A, CM>)),
+        )
+        .service(
+            web::resource("/snapshots/{param_auth}/{snapshot_id}/rollback")
+                .route(web::post().to(rollback_to_snapshot::<A, CM>)),
+        )
         .service(
             web::resource("/drafts/{param_auth}")
                 .route(web::get().to(get_drafts_by_tenant::<A, CM>))
@@ -107,6 +130,14 @@ pub fn build_config_v2_endpoints<
             web::resource("/drafts/{param_auth}/{draft_id}/deploy")
                 .route(web::post().to(deploy_draft_for_tenant::<A, CM>)),
         )
+        .service(
+            web::resource("/drafts/{param_auth}/{draft_id}/test")
+                .route(web::post().to(test_draft_for_tenant::<A, CM>)),
+        )
+        .service(
+            web::resource("/drafts/{param_auth}/{draft_id}/diff")
+                .route(web::get().to(get_draft_diff::<A, CM>)),
+        )
         .service(
             web::resource("/drafts/{param_auth}/{draft_id}/takeover")
                 .route(web::post().to(draft_take_over_for_tenant::<A, CM>)),
@@ -154,6 +185,31 @@ struct DraftPathWithNode {
     node_path: String,
 }
 
+#[derive(Deserialize)]
+struct DeployDraftQuery {
+    comment: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SnapshotPath {
+    param_auth: String,
+    snapshot_id: String,
+}
+
+#[derive(Deserialize)]
+struct SnapshotPathWithNode {
+    param_auth: String,
+    snapshot_id: String,
+    node_path: String,
+}
+
+#[derive(Deserialize)]
+struct SnapshotDiffPath {
+    param_auth: String,
+    snapshot_id: String,
+    other_snapshot_id: String,
+}
+
 async fn get_current_tree_node<
     A: ConfigApiHandler + 'static,
     CM: MatcherConfigEditor + ?Sized + 'static,
@@ -634,12 +690,42 @@ async fn deploy_draft_for_tenant<
 >(
     req: HttpRequest,
     path: Path<DraftPath>,
+    query: Query<DeployDraftQuery>,
     data: Data<ApiDataV2<ConfigApi<A, CM>>>,
-) -> actix_web::Result<Json<()>> {
+) -> actix_web::Result<Json<DeployDraftResultDto>> {
     debug!("HttpRequest method [{}] path [{}]", req.method(), req.path());
     let auth_ctx = data.auth.auth_from_request(&req, &path.param_auth)?;
-    data.api.deploy_draft_for_tenant(&auth_ctx, &path.draft_id).await?;
-    Ok(Json(()))
+    let comment = query.comment.as_deref().unwrap_or_default();
+    let result = data.api.deploy_draft_for_tenant(&auth_ctx, &path.draft_id, comment).await?;
+    Ok(Json(result))
+}
+
+async fn test_draft_for_tenant<
+    A: ConfigApiHandler + 'static,
+    CM: MatcherConfigEditor + ?Sized + 'static,
+>(
+    req: HttpRequest,
+    path: Path<DraftPath>,
+    data: Data<ApiDataV2<ConfigApi<A, CM>>>,
+) -> actix_web::Result<Json<Vec<RuleTestResultDto>>> {
+    debug!("HttpRequest method [{}] path [{}]", req.method(), req.path());
+    let auth_ctx = data.auth.auth_from_request(&req, &path.param_auth)?;
+    let result = data.api.test_draft_for_tenant(&auth_ctx, &path.draft_id).await?;
+    Ok(Json(result))
+}
+
+async fn get_draft_diff<
+    A: ConfigApiHandler + 'static,
+    CM: MatcherConfigEditor + ?Sized + 'static,
+>(
+    req: HttpRequest,
+    path: Path<DraftPath>,
+    data: Data<ApiDataV2<ConfigApi<A, CM>>>,
+) -> actix_web::Result<Json<Vec<ConfigChangeDto>>> {
+    debug!("HttpRequest method [{}] path [{}]", req.method(), req.path());
+    let auth_ctx = data.auth.auth_from_request(&req, &path.param_auth)?;
+    let result = data.api.get_draft_diff(&auth_ctx, &path.draft_id).await?;
+    Ok(Json(result))
 }
 
 async fn draft_take_over_for_tenant<
@@ -656,6 +742,87 @@ async fn draft_take_over_for_tenant<
     Ok(Json(()))
 }
 
+async fn get_snapshots<
+    A: ConfigApiHandler + 'static,
+    CM: MatcherConfigEditor + ?Sized + 'static,
+>(
+    req: HttpRequest,
+    param_auth: Path<String>,
+    data: Data<ApiDataV2<ConfigApi<A, CM>>>,
+) -> actix_web::Result<Json<Vec<ConfigSnapshotDto>>> {
+    debug!("HttpRequest method [{}] path [{}]", req.method(), req.path());
+    let auth_ctx = data.auth.auth_from_request(&req, &param_auth)?;
+    let result = data.api.get_snapshots(&auth_ctx).await?;
+    Ok(Json(result))
+}
+
+async fn get_snapshot_tree_node<
+    A: ConfigApiHandler + 'static,
+    CM: MatcherConfigEditor + ?Sized + 'static,
+>(
+    req: HttpRequest,
+    path: Path<SnapshotPath>,
+    data: Data<ApiDataV2<ConfigApi<A, CM>>>,
+) -> actix_web::Result<Json<Vec<ProcessingTreeNodeConfigDto>>> {
+    debug!("HttpRequest method [{}] path [{}]", req.method(), req.path());
+    let auth_ctx = data.auth.auth_from_request(&req, &path.param_auth)?;
+    let result = data
+        .api
+        .get_snapshot_config_processing_tree_nodes_by_path(auth_ctx, &path.snapshot_id, None)
+        .await?;
+    Ok(Json(result))
+}
+
+async fn get_snapshot_tree_node_with_node_path<
+    A: ConfigApiHandler + 'static,
+    CM: MatcherConfigEditor + ?Sized + 'static,
+>(
+    req: HttpRequest,
+    path: Path<SnapshotPathWithNode>,
+    data: Data<ApiDataV2<ConfigApi<A, CM>>>,
+) -> actix_web::Result<Json<Vec<ProcessingTreeNodeConfigDto>>> {
+    debug!("HttpRequest method [{}] path [{}]", req.method(), req.path());
+    let auth_ctx = data.auth.auth_from_request(&req, &path.param_auth)?;
+    let result = data
+        .api
+        .get_snapshot_config_processing_tree_nodes_by_path(
+            auth_ctx,
+            &path.snapshot_id,
+            Some(&path.node_path),
+        )
+        .await?;
+    Ok(Json(result))
+}
+
+async fn diff_snapshots<
+    A: ConfigApiHandler + 'static,
+    CM: MatcherConfigEditor + ?Sized + 'static,
+>(
+    req: HttpRequest,
+    path: Path<SnapshotDiffPath>,
+    data: Data<ApiDataV2<ConfigApi<A, CM>>>,
+) -> actix_web::Result<Json<Vec<ConfigChangeDto>>> {
+    debug!("HttpRequest method [{}] path [{}]", req.method(), req.path());
+    let auth_ctx = data.auth.auth_from_request(&req, &path.param_auth)?;
+    let result =
+        data.api.diff_snapshots(&auth_ctx, &path.snapshot_id, &path.other_snapshot_id).await?;
+    Ok(Json(result))
+}
+
+async fn rollback_to_snapshot<
+    A: ConfigApiHandler + 'static,
+    CM: MatcherConfigEditor + ?Sized + 'static,
+>(
+    req: HttpRequest,
+    path: Path<SnapshotPath>,
+    data: Data<ApiDataV2<ConfigApi<A, CM>>>,
+) -> actix_web::Result<Json<()>> {
+    debug!("HttpRequest method [{}] path [{}]", req.method(), req.path());
+    let auth_ctx = data.auth.auth_from_request(&req, &path.param_auth)?;
+    data.api.rollback_to_snapshot(&auth_ctx, &path.snapshot_id).await?;
+    Ok(Json(()))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -690,6 +857,7 @@ mod test {
                     description: "".to_string(),
                     filter: Defaultable::Default {},
                     active: false,
+                    schedule: None,
                 },
                 nodes: vec![
                     MatcherConfig::Filter {
@@ -698,6 +866,7 @@ mod test {
                             description: "".to_string(),
                             filter: Defaultable::Default {},
                             active: false,
+                            schedule: None,
                         },
                         nodes: vec![],
                     },
@@ -713,6 +882,9 @@ mod test {
                                 with: Default::default(),
                             },
                             actions: vec![],
+                            threshold: None,
+                            suppression: None,
+                            schedule: None,
                         }],
                     },
                 ],
@@ -740,6 +912,7 @@ mod test {
                         description: "".to_string(),
                         filter: Defaultable::Default {},
                         active: false,
+                        schedule: None,
                     },
                     nodes: vec![
                         MatcherConfig::Filter {
@@ -748,6 +921,7 @@ mod test {
                                 description: "".to_string(),
                                 filter: Defaultable::Default {},
                                 active: false,
+                                schedule: None,
                             },
                             nodes: vec![MatcherConfig::Filter {
                                 name: "child_1_1".to_owned(),
@@ -755,6 +929,7 @@ mod test {
                                     description: "".to_string(),
                                     filter: Defaultable::Default {},
                                     active: false,
+                                    schedule: None,
                                 },
                                 nodes: vec![],
                             }],
@@ -771,6 +946,9 @@ mod test {
                                     with: Default::default(),
                                 },
                                 actions: vec![],
+                                threshold: None,
+                                suppression: None,
+                                schedule: None,
                             }],
                         },
                     ],
@@ -791,7 +969,11 @@ mod test {
             Ok(())
         }
 
-        async fn deploy_draft(&self, _draft_id: &str) -> Result<MatcherConfig, MatcherError> {
+        async fn deploy_draft(
+            &self,
+            _draft_id: &str,
+            _comment: &str,
+        ) -> Result<MatcherConfig, MatcherError> {
             Ok(MatcherConfig::Ruleset { name: "ruleset_new".to_owned(), rules: vec![] })
         }
 
@@ -1012,6 +1194,95 @@ mod test {
         Ok(())
     }
 
+    #[actix_rt::test]
+    async fn v2_endpoint_should_accept_a_comment_on_deploy() -> Result<(), ApiError> {
+        // Arrange
+        let srv = test::init_service(App::new().service(build_config_v2_endpoints(ApiDataV2 {
+            auth: test_auth_service_v2(),
+            api: ConfigApi::new(TestApiHandler {}, Arc::new(ConfigManager {})),
+        })))
+        .await;
+
+        // Act
+        let request = test::TestRequest::post()
+            .insert_header(test_auth_root_edit())
+            .uri("/config/drafts/auth1/draft123/deploy?comment=New%20email%20rules")
+            .to_request();
+
+        let response = test::call_service(&srv, request).await;
+
+        // Assert
+        assert_eq!(StatusCode::OK, response.status());
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    async fn v2_endpoint_should_have_a_draft_diff_get_endpoint() -> Result<(), ApiError> {
+        // Arrange
+        let srv = test::init_service(App::new().service(build_config_v2_endpoints(ApiDataV2 {
+            auth: test_auth_service_v2(),
+            api: ConfigApi::new(TestApiHandler {}, Arc::new(ConfigManager {})),
+        })))
+        .await;
+
+        // Act
+        let request = test::TestRequest::get()
+            .insert_header(test_auth_root_edit())
+            .uri("/config/drafts/auth1/draft123/diff")
+            .to_request();
+
+        let response = test::call_service(&srv, request).await;
+
+        // Assert
+        assert_eq!(StatusCode::OK, response.status());
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    async fn v2_endpoint_should_have_a_snapshots_get_endpoint() -> Result<(), ApiError> {
+        // Arrange
+        let srv = test::init_service(App::new().service(build_config_v2_endpoints(ApiDataV2 {
+            auth: test_auth_service_v2(),
+            api: ConfigApi::new(TestApiHandler {}, Arc::new(ConfigManager {})),
+        })))
+        .await;
+
+        // Act
+        let request = test::TestRequest::get()
+            .insert_header(test_auth_root_edit())
+            .uri("/config/snapshots/auth1")
+            .to_request();
+
+        let response: Vec<ConfigSnapshotDto> = test::call_and_read_body_json(&srv, request).await;
+
+        // Assert
+        assert!(response.is_empty());
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    async fn v2_endpoint_should_return_not_found_on_rollback_to_a_missing_snapshot(
+    ) -> Result<(), ApiError> {
+        // Arrange
+        let srv = test::init_service(App::new().service(build_config_v2_endpoints(ApiDataV2 {
+            auth: test_auth_service_v2(),
+            api: ConfigApi::new(TestApiHandler {}, Arc::new(ConfigManager {})),
+        })))
+        .await;
+
+        // Act
+        let request = test::TestRequest::post()
+            .insert_header(test_auth_root_edit())
+            .uri("/config/snapshots/auth1/1/rollback")
+            .to_request();
+
+        let response = test::call_service(&srv, request).await;
+
+        // Assert
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+        Ok(())
+    }
+
     #[actix_rt::test]
     async fn v2_endpoint_should_have_a_draft_take_over_for_tenant_post_endpoint(
     ) -> Result<(), ApiError> {
@@ -1303,6 +1574,7 @@ mod test {
                 description: "".to_string(),
                 active: false,
                 filter: None,
+                schedule: None,
             })
             .to_request();
 
@@ -1333,6 +1605,9 @@ mod test {
                 active: true,
                 constraint: ConstraintDto { where_operator: None, with: Default::default() },
                 actions: vec![],
+                threshold: None,
+                suppression: None,
+                schedule: None,
             })
             .to_request();
 
@@ -1363,6 +1638,9 @@ mod test {
                 active: true,
                 constraint: ConstraintDto { where_operator: None, with: Default::default() },
                 actions: vec![],
+                threshold: None,
+                suppression: None,
+                schedule: None,
             })
             .to_request();
 
@@ -1460,6 +1738,7 @@ mod test {
                 description: "".to_string(),
                 active: false,
                 filter: None,
+                schedule: None,
             })
             .to_request();
 
@@ -41,6 +41,9 @@ pub enum ApiError {
 
     #[error("NodeNotFoundError [{message}]")]
     NodeNotFoundError { message: String },
+
+    #[error("RuleTestFailedError [{message}]")]
+    RuleTestFailedError { message: String },
 }
 
 impl From<MatcherError> for ApiError {
@@ -63,6 +66,7 @@ impl From<serde_json::Error> for ApiError {
 
 const VALIDATION_ERROR: &str = "VALIDATION_ERROR";
 const NESTED_ITERATOR_ERROR: &str = "NESTED_ITERATOR_ERROR";
+const RULE_TEST_ERROR: &str = "RULE_TEST_ERROR";
 
 // Use default implementation for `error_response()` method.
 impl actix_web::error::ResponseError for ApiError {
@@ -92,6 +96,7 @@ impl actix_web::error::ResponseError for ApiError {
                         params: HashMap::new(),
                     })
                 }
+                MatcherError::SnapshotNotFoundError { .. } => HttpResponse::NotFound().finish(),
                 _ => HttpResponse::BadRequest().finish(),
             },
             ApiError::ActixMailboxError { .. }
@@ -100,6 +105,13 @@ impl actix_web::error::ResponseError for ApiError {
             ApiError::BadRequestError { .. } => HttpResponse::BadRequest().finish(),
             ApiError::PayloadToLarge => HttpResponse::PayloadTooLarge().finish(),
             ApiError::NodeNotFoundError { .. } => HttpResponse::NotFound().finish(),
+            ApiError::RuleTestFailedError { message } => {
+                HttpResponseBuilder::new(http::StatusCode::CONFLICT).json(WebError {
+                    code: RULE_TEST_ERROR.to_owned(),
+                    message: Some(message.to_owned()),
+                    params: HashMap::new(),
+                })
+            }
             ApiError::InvalidTokenError { .. }
             | ApiError::ExpiredTokenError { .. }
             | ApiError::MissingAuthTokenError { .. }
@@ -31,6 +31,8 @@ pub trait EventApiHandler: Send + Sync {
 pub struct SendEventRequest {
     pub event: Event,
     pub process_type: ProcessType,
+    /// Whether the processed Rules contain the trace of the evaluation of their WHERE clause
+    pub explain: bool,
 }
 
 impl SendEventRequest {
@@ -112,6 +114,7 @@ pub mod test {
                     description: "".to_string(),
                     active: true,
                     filter: Defaultable::Default {},
+                    schedule: None,
                 },
                 nodes: vec![MatcherConfig::Ruleset { name: "ruleset".to_owned(), rules: vec![] }],
             })
@@ -138,6 +141,7 @@ pub mod test {
                         description: "".to_string(),
                         active: true,
                         filter: Defaultable::Default {},
+                        schedule: None,
                     },
                     nodes: vec![MatcherConfig::Ruleset {
                         name: "ruleset".to_owned(),
@@ -160,7 +164,11 @@ pub mod test {
             Ok(())
         }
 
-        async fn deploy_draft(&self, _draft_id: &str) -> Result<MatcherConfig, MatcherError> {
+        async fn deploy_draft(
+            &self,
+            _draft_id: &str,
+            _comment: &str,
+        ) -> Result<MatcherConfig, MatcherError> {
             Ok(MatcherConfig::Ruleset { name: "ruleset_new".to_owned(), rules: vec![] })
         }
 
@@ -231,8 +231,11 @@ pub mod test {
             &permissions_map,
         );
 
-        let request =
-            SendEventRequest { event: Event::new("event"), process_type: ProcessType::SkipActions };
+        let request = SendEventRequest {
+            event: Event::new("event"),
+            process_type: ProcessType::SkipActions,
+            explain: false,
+        };
 
         // Act & Assert
         assert!(api.send_event_to_current_config(user_edit, request.clone()).await.is_ok());
@@ -277,8 +280,11 @@ pub mod test {
             &permissions,
         );
 
-        let request =
-            SendEventRequest { event: Event::new("event"), process_type: ProcessType::Full };
+        let request = SendEventRequest {
+            event: Event::new("event"),
+            process_type: ProcessType::Full,
+            explain: false,
+        };
 
         // Act & Assert
         assert!(api.send_event_to_current_config(user_edit, request.clone()).await.is_err());
@@ -311,7 +317,8 @@ pub mod test {
             .insert("something".to_owned(), Value::String(format!("{}", rand::random::<usize>())));
         event.metadata = metadata.clone();
 
-        let request = SendEventRequest { event, process_type: ProcessType::SkipActions };
+        let request =
+            SendEventRequest { event, process_type: ProcessType::SkipActions, explain: false };
 
         // Act
         let result = api.send_event_to_current_config(user_edit, request.clone()).await.unwrap();
@@ -335,6 +342,7 @@ pub mod test {
         let request = SendEventRequest {
             event: Event::new("event_for_draft"),
             process_type: ProcessType::SkipActions,
+            explain: false,
         };
 
         // Act & Assert
@@ -382,7 +390,8 @@ pub mod test {
             .insert("something".to_owned(), Value::String(format!("{}", rand::random::<usize>())));
         event.metadata = metadata.clone();
 
-        let request = SendEventRequest { event, process_type: ProcessType::SkipActions };
+        let request =
+            SendEventRequest { event, process_type: ProcessType::SkipActions, explain: false };
 
         // Act
         let result = api.send_event_to_draft(user_edit, "id", request.clone()).await.unwrap();
@@ -3,13 +3,16 @@ use serde_json::Error;
 use tornado_common_api::Action;
 use tornado_engine_api_dto::config::ActionDto;
 use tornado_engine_api_dto::event::{
-    ProcessType, ProcessedEventDto, ProcessedFilterDto, ProcessedFilterStatusDto,
-    ProcessedIteratorDto, ProcessedNodeDto, ProcessedRuleDto, ProcessedRuleStatusDto,
-    ProcessedRulesDto, SendEventRequestDto,
+    ProcessType, ProcessedCorrelationDto, ProcessedCorrelationStatusDto, ProcessedEnricherDto,
+    ProcessedEnricherStatusDto, ProcessedEventDto, ProcessedFilterDto, ProcessedFilterStatusDto,
+    ProcessedHeartbeatDto, ProcessedHeartbeatStatusDto, ProcessedIteratorDto, ProcessedNodeDto,
+    ProcessedRuleDto, ProcessedRuleStatusDto, ProcessedRulesDto, SendEventRequestDto,
 };
 use tornado_engine_matcher::model::{
-    ProcessedEvent, ProcessedFilter, ProcessedFilterStatus, ProcessedIterator, ProcessedNode,
-    ProcessedRule, ProcessedRuleStatus, ProcessedRules,
+    ProcessedCorrelation, ProcessedCorrelationStatus, ProcessedEnricher, ProcessedEnricherStatus,
+    ProcessedEvent, ProcessedFilter, ProcessedFilterStatus, ProcessedHeartbeat,
+    ProcessedHeartbeatStatus, ProcessedIterator, ProcessedNode, ProcessedRule, ProcessedRuleStatus,
+    ProcessedRules,
 };
 
 pub fn dto_into_send_event_request(dto: SendEventRequestDto) -> Result<SendEventRequest, Error> {
@@ -19,6 +22,7 @@ pub fn dto_into_send_event_request(dto: SendEventRequestDto) -> Result<SendEvent
             ProcessType::SkipActions => crate::event::api::ProcessType::SkipActions,
         },
         event: serde_json::from_value(serde_json::to_value(dto.event)?)?,
+        explain: dto.explain,
     })
 }
 
@@ -51,9 +55,85 @@ pub fn processed_node_into_dto(node: ProcessedNode) -> Result<ProcessedNodeDto,
                 .map(processed_node_into_dto)
                 .collect::<Result<Vec<_>, _>>()?,
         },
+        ProcessedNode::Correlation { name, correlation } => ProcessedNodeDto::Correlation {
+            name,
+            correlation: processed_correlation_into_dto(correlation)?,
+        },
+        ProcessedNode::Heartbeat { name, heartbeat } => {
+            ProcessedNodeDto::Heartbeat { name, heartbeat: processed_heartbeat_into_dto(heartbeat) }
+        }
+        ProcessedNode::Enricher { name, enricher, nodes } => ProcessedNodeDto::Enricher {
+            name,
+            enricher: processed_enricher_into_dto(enricher),
+            nodes: nodes.into_iter().map(processed_node_into_dto).collect::<Result<Vec<_>, _>>()?,
+        },
     })
 }
 
+pub fn processed_enricher_into_dto(node: ProcessedEnricher) -> ProcessedEnricherDto {
+    ProcessedEnricherDto {
+        status: processed_enricher_status_into_dto(node.status),
+        values: node.values,
+        message: node.message,
+    }
+}
+
+pub fn processed_enricher_status_into_dto(
+    node: ProcessedEnricherStatus,
+) -> ProcessedEnricherStatusDto {
+    match node {
+        ProcessedEnricherStatus::Enriched => ProcessedEnricherStatusDto::Enriched,
+        ProcessedEnricherStatus::PartiallyEnriched => ProcessedEnricherStatusDto::PartiallyEnriched,
+        ProcessedEnricherStatus::Inactive => ProcessedEnricherStatusDto::Inactive,
+    }
+}
+
+pub fn processed_heartbeat_into_dto(node: ProcessedHeartbeat) -> ProcessedHeartbeatDto {
+    ProcessedHeartbeatDto {
+        status: processed_heartbeat_status_into_dto(node.status),
+        key: node.key,
+    }
+}
+
+pub fn processed_heartbeat_status_into_dto(
+    node: ProcessedHeartbeatStatus,
+) -> ProcessedHeartbeatStatusDto {
+    match node {
+        ProcessedHeartbeatStatus::Seen => ProcessedHeartbeatStatusDto::Seen,
+        ProcessedHeartbeatStatus::Recovered => ProcessedHeartbeatStatusDto::Recovered,
+        ProcessedHeartbeatStatus::NotMatched => ProcessedHeartbeatStatusDto::NotMatched,
+        ProcessedHeartbeatStatus::Inactive => ProcessedHeartbeatStatusDto::Inactive,
+    }
+}
+
+pub fn processed_correlation_into_dto(
+    node: ProcessedCorrelation,
+) -> Result<ProcessedCorrelationDto, Error> {
+    Ok(ProcessedCorrelationDto {
+        status: processed_correlation_status_into_dto(node.status),
+        key: node.key,
+        matched_steps: node.matched_steps,
+        actions: node.actions.into_iter().map(action_into_dto).collect::<Result<Vec<_>, _>>()?,
+        message: node.message,
+    })
+}
+
+pub fn processed_correlation_status_into_dto(
+    node: ProcessedCorrelationStatus,
+) -> ProcessedCorrelationStatusDto {
+    match node {
+        ProcessedCorrelationStatus::Started => ProcessedCorrelationStatusDto::Started,
+        ProcessedCorrelationStatus::Progressed => ProcessedCorrelationStatusDto::Progressed,
+        ProcessedCorrelationStatus::Completed => ProcessedCorrelationStatusDto::Completed,
+        ProcessedCorrelationStatus::Expired => ProcessedCorrelationStatusDto::Expired,
+        ProcessedCorrelationStatus::PartiallyMatched => {
+            ProcessedCorrelationStatusDto::PartiallyMatched
+        }
+        ProcessedCorrelationStatus::NotMatched => ProcessedCorrelationStatusDto::NotMatched,
+        ProcessedCorrelationStatus::Inactive => ProcessedCorrelationStatusDto::Inactive,
+    }
+}
+
 pub fn processed_rules_into_dto(node: ProcessedRules) -> Result<ProcessedRulesDto, Error> {
     Ok(ProcessedRulesDto {
         extracted_vars: serde_json::to_value(node.extracted_vars)?,
@@ -72,6 +152,7 @@ pub fn processed_rule_into_dto(node: ProcessedRule) -> Result<ProcessedRuleDto,
         actions: node.actions.into_iter().map(action_into_dto).collect::<Result<Vec<_>, _>>()?,
         status: processed_rule_status_into_dto(node.status),
         meta: node.meta,
+        trace: node.trace,
     })
 }
 
@@ -81,6 +162,9 @@ pub fn processed_rule_status_into_dto(node: ProcessedRuleStatus) -> ProcessedRul
         ProcessedRuleStatus::NotMatched => ProcessedRuleStatusDto::NotMatched,
         ProcessedRuleStatus::Matched => ProcessedRuleStatusDto::Matched,
         ProcessedRuleStatus::PartiallyMatched => ProcessedRuleStatusDto::PartiallyMatched,
+        ProcessedRuleStatus::Duplicate => ProcessedRuleStatusDto::Duplicate,
+        ProcessedRuleStatus::OutOfSchedule => ProcessedRuleStatusDto::OutOfSchedule,
+        ProcessedRuleStatus::ThresholdNotReached => ProcessedRuleStatusDto::ThresholdNotReached,
     }
 }
 
@@ -105,5 +189,6 @@ pub fn processed_filter_status_into_dto(node: ProcessedFilterStatus) -> Processe
         ProcessedFilterStatus::NotMatched => ProcessedFilterStatusDto::NotMatched,
         ProcessedFilterStatus::Matched => ProcessedFilterStatusDto::Matched,
         ProcessedFilterStatus::Inactive => ProcessedFilterStatusDto::Inactive,
+        ProcessedFilterStatus::OutOfSchedule => ProcessedFilterStatusDto::OutOfSchedule,
     }
 }
@@ -137,6 +137,7 @@ mod test {
                 created_ms: 0,
             },
             process_type: ProcessType::SkipActions,
+            explain: false,
         };
 
         // Act
@@ -191,6 +192,7 @@ mod test {
                 created_ms: 0,
             },
             process_type: ProcessType::SkipActions,
+            explain: false,
         };
 
         // Act
@@ -239,6 +241,7 @@ mod test {
                 created_ms: 0,
             },
             process_type: ProcessType::SkipActions,
+            explain: false,
         };
 
         // Act
@@ -2,6 +2,7 @@ pub mod auth;
 pub mod config;
 pub mod error;
 pub mod event;
+pub mod lookup_table;
 pub mod model;
 pub mod runtime_config;
 
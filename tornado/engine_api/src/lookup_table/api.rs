@@ -0,0 +1,166 @@
+use crate::auth::{AuthContext, Permission};
+use crate::error::ApiError;
+use std::sync::Arc;
+use tornado_engine_api_dto::lookup_table::{LookupTableDto, LookupTableFormatDto};
+use tornado_engine_matcher::lookup::{LookupTable, LookupTableFormat, LookupTables};
+
+/// The LookupTableApiHandler trait defines the contract that a struct has to respect to
+/// be used by the backend.
+/// It permits to decouple the backend from a specific implementation.
+#[async_trait::async_trait(?Send)]
+pub trait LookupTableApiHandler: Send + Sync {
+    /// Returns the lookup tables currently used by the Rules
+    async fn get_lookup_tables(&self) -> Result<Arc<LookupTables>, ApiError>;
+
+    /// Reloads the lookup tables from their files and returns them.
+    /// If a table cannot be loaded, the current tables are kept.
+    async fn reload_lookup_tables(&self) -> Result<Arc<LookupTables>, ApiError>;
+}
+
+pub struct LookupTableApi<A: LookupTableApiHandler> {
+    handler: A,
+}
+
+impl<A: LookupTableApiHandler> LookupTableApi<A> {
+    pub fn new(handler: A) -> Self {
+        Self { handler }
+    }
+
+    /// Returns the lookup tables available to the Rules
+    pub async fn get_lookup_tables(
+        &self,
+        auth: AuthContext<'_>,
+    ) -> Result<Vec<LookupTableDto>, ApiError> {
+        auth.has_permission(&Permission::ConfigView)?;
+        let tables = self.handler.get_lookup_tables().await?;
+        Ok(tables.iter().map(lookup_table_into_dto).collect())
+    }
+
+    /// Reloads the lookup tables from their files
+    pub async fn reload_lookup_tables(
+        &self,
+        auth: AuthContext<'_>,
+    ) -> Result<Vec<LookupTableDto>, ApiError> {
+        auth.has_permission(&Permission::ConfigEdit)?;
+        let tables = self.handler.reload_lookup_tables().await?;
+        Ok(tables.iter().map(lookup_table_into_dto).collect())
+    }
+}
+
+fn lookup_table_into_dto(table: &LookupTable) -> LookupTableDto {
+    LookupTableDto {
+        name: table.name().to_owned(),
+        format: match table.format() {
+            LookupTableFormat::Csv => LookupTableFormatDto::Csv,
+            LookupTableFormat::Json => LookupTableFormatDto::Json,
+        },
+        source: table.source().to_owned(),
+        rows_count: table.len(),
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use std::collections::BTreeMap;
+    use tornado_engine_api_dto::auth::Auth;
+
+    pub struct TestLookupTableApiHandler {}
+
+    #[async_trait::async_trait(?Send)]
+    impl LookupTableApiHandler for TestLookupTableApiHandler {
+        async fn get_lookup_tables(&self) -> Result<Arc<LookupTables>, ApiError> {
+            let mut tables = LookupTables::default();
+            tables
+                .add(
+                    LookupTable::from_csv(
+                        "teams",
+                        "/etc/tornado/lookup_tables.d/teams.csv",
+                        "host,team\nweb01,ops\ndb01,dba\n",
+                    )
+                    .unwrap(),
+                )
+                .unwrap();
+            Ok(Arc::new(tables))
+        }
+
+        async fn reload_lookup_tables(&self) -> Result<Arc<LookupTables>, ApiError> {
+            self.get_lookup_tables().await
+        }
+    }
+
+    fn auth_permissions() -> BTreeMap<Permission, Vec<String>> {
+        let mut permission_roles_map = BTreeMap::new();
+        permission_roles_map.insert(Permission::ConfigEdit, vec!["edit".to_owned()]);
+        permission_roles_map.insert(Permission::ConfigView, vec!["view".to_owned()]);
+        permission_roles_map
+    }
+
+    #[actix_rt::test]
+    async fn get_lookup_tables_should_return_the_tables() {
+        // Arrange
+        let api = LookupTableApi::new(TestLookupTableApiHandler {});
+        let permissions_map = &auth_permissions();
+
+        let auth_view = AuthContext::new(
+            Auth { user: "1".to_owned(), roles: vec!["view".to_owned()], preferences: None },
+            permissions_map,
+        );
+
+        // Act
+        let tables = api.get_lookup_tables(auth_view).await.unwrap();
+
+        // Assert
+        assert_eq!(
+            vec![LookupTableDto {
+                name: "teams".to_owned(),
+                format: LookupTableFormatDto::Csv,
+                source: "/etc/tornado/lookup_tables.d/teams.csv".to_owned(),
+                rows_count: 2,
+            }],
+            tables
+        );
+    }
+
+    #[actix_rt::test]
+    async fn get_lookup_tables_should_require_view_permission() {
+        // Arrange
+        let api = LookupTableApi::new(TestLookupTableApiHandler {});
+        let permissions_map = &auth_permissions();
+
+        let auth_view = AuthContext::new(
+            Auth { user: "1".to_owned(), roles: vec!["view".to_owned()], preferences: None },
+            permissions_map,
+        );
+
+        let auth_edit = AuthContext::new(
+            Auth { user: "1".to_owned(), roles: vec!["edit".to_owned()], preferences: None },
+            permissions_map,
+        );
+
+        // Act & Assert
+        assert!(api.get_lookup_tables(auth_view).await.is_ok());
+        assert!(api.get_lookup_tables(auth_edit).await.is_err());
+    }
+
+    #[actix_rt::test]
+    async fn reload_lookup_tables_should_require_edit_permission() {
+        // Arrange
+        let api = LookupTableApi::new(TestLookupTableApiHandler {});
+        let permissions_map = &auth_permissions();
+
+        let auth_view = AuthContext::new(
+            Auth { user: "1".to_owned(), roles: vec!["view".to_owned()], preferences: None },
+            permissions_map,
+        );
+
+        let auth_edit = AuthContext::new(
+            Auth { user: "1".to_owned(), roles: vec!["edit".to_owned()], preferences: None },
+            permissions_map,
+        );
+
+        // Act & Assert
+        assert!(api.reload_lookup_tables(auth_view).await.is_err());
+        assert!(api.reload_lookup_tables(auth_edit).await.is_ok());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::lookup_table::api::{LookupTableApi, LookupTableApiHandler};
+use crate::model::ApiData;
+use actix_web::web::{Data, Json};
+use actix_web::{web, HttpRequest, Scope};
+use log::*;
+use tornado_engine_api_dto::lookup_table::LookupTableDto;
+
+pub const LOOKUP_TABLE_ENDPOINT_V1_BASE: &str = "/v1_beta/lookup_tables";
+
+pub fn build_lookup_table_endpoints<A: LookupTableApiHandler + 'static>(
+    data: ApiData<LookupTableApi<A>>,
+) -> Scope {
+    web::scope(LOOKUP_TABLE_ENDPOINT_V1_BASE)
+        .app_data(Data::new(data))
+        .service(web::resource("").route(web::get().to(get_lookup_tables::<A>)))
+        .service(web::resource("/reload").route(web::post().to(reload_lookup_tables::<A>)))
+}
+
+async fn get_lookup_tables<A: LookupTableApiHandler + 'static>(
+    req: HttpRequest,
+    data: Data<ApiData<LookupTableApi<A>>>,
+) -> actix_web::Result<Json<Vec<LookupTableDto>>> {
+    debug!("HttpRequest method [{}] path [{}]", req.method(), req.path());
+    let auth_ctx = data.auth.auth_from_request(&req)?;
+    let result = data.api.get_lookup_tables(auth_ctx).await?;
+    Ok(Json(result))
+}
+
+async fn reload_lookup_tables<A: LookupTableApiHandler + 'static>(
+    req: HttpRequest,
+    data: Data<ApiData<LookupTableApi<A>>>,
+) -> actix_web::Result<Json<Vec<LookupTableDto>>> {
+    debug!("HttpRequest method [{}] path [{}]", req.method(), req.path());
+    let auth_ctx = data.auth.auth_from_request(&req)?;
+    let result = data.api.reload_lookup_tables(auth_ctx).await?;
+    Ok(Json(result))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::auth::{AuthService, Permission};
+    use crate::error::ApiError;
+    use crate::lookup_table::api::test::TestLookupTableApiHandler;
+    use crate::lookup_table::api::LookupTableApi;
+    use crate::lookup_table::web::build_lookup_table_endpoints;
+    use crate::model::ApiData;
+    use actix_web::{http::header, http::StatusCode, test, App};
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+    use tornado_engine_api_dto::auth::Auth;
+    use tornado_engine_api_dto::lookup_table::LookupTableDto;
+
+    pub fn test_auth_service() -> AuthService {
+        let mut permission_roles_map = BTreeMap::new();
+        permission_roles_map.insert(Permission::ConfigEdit, vec!["edit".to_owned()]);
+        permission_roles_map.insert(Permission::ConfigView, vec!["view".to_owned()]);
+        AuthService::new(Arc::new(permission_roles_map))
+    }
+
+    #[actix_rt::test]
+    async fn get_lookup_tables_should_return_the_tables() -> Result<(), ApiError> {
+        // Arrange
+        let srv = test::init_service(App::new().service(build_lookup_table_endpoints(ApiData {
+            auth: test_auth_service(),
+            api: LookupTableApi::new(TestLookupTableApiHandler {}),
+        })))
+        .await;
+
+        // Act
+        let request = test::TestRequest::get()
+            .insert_header((header::CONTENT_TYPE, "application/json"))
+            .insert_header((
+                header::AUTHORIZATION,
+                AuthService::auth_to_token_header(&Auth::new("user", vec!["view"])).unwrap(),
+            ))
+            .uri("/v1_beta/lookup_tables")
+            .to_request();
+
+        let dto: Vec<LookupTableDto> = test::call_and_read_body_json(&srv, request).await;
+
+        // Assert
+        assert_eq!(1, dto.len());
+        assert_eq!("teams", dto[0].name);
+        assert_eq!(2, dto[0].rows_count);
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    async fn reload_lookup_tables_should_return_status_code_forbidden_if_no_edit_permission(
+    ) -> Result<(), ApiError> {
+        // Arrange
+        let srv = test::init_service(App::new().service(build_lookup_table_endpoints(ApiData {
+            auth: test_auth_service(),
+            api: LookupTableApi::new(TestLookupTableApiHandler {}),
+        })))
+        .await;
+
+        // Act
+        let request = test::TestRequest::post()
+            .insert_header((header::CONTENT_TYPE, "application/json"))
+            .insert_header((
+                header::AUTHORIZATION,
+                AuthService::auth_to_token_header(&Auth::new("user", vec!["view"])).unwrap(),
+            ))
+            .uri("/v1_beta/lookup_tables/reload")
+            .to_request();
+
+        let response = test::call_service(&srv, request).await;
+
+        // Assert
+        assert_eq!(StatusCode::FORBIDDEN, response.status());
+        Ok(())
+    }
+}
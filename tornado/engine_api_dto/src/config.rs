@@ -3,8 +3,11 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::iter::Sum;
 use std::ops::Add;
-use tornado_engine_matcher::config::nodes::Filter;
-use tornado_engine_matcher::config::rule::{Operator, Rule};
+use tornado_engine_matcher::config::nodes::{Correlation, Enricher, Filter, Heartbeat};
+use tornado_engine_matcher::config::rule::{
+    ConfigAction, Extractor, ExtractorRegex, Modifier, Operator, Rule, Schedule, ScheduleWindow,
+    ValueType,
+};
 use tornado_engine_matcher::config::{Defaultable, MatcherConfig};
 use typescript_definitions::TypeScriptify;
 
@@ -41,6 +44,60 @@ pub struct RuleDto {
     pub active: bool,
     pub constraint: ConstraintDto,
     pub actions: Vec<ActionDto>,
+    #[serde(default)]
+    pub threshold: Option<ThresholdDto>,
+    #[serde(default)]
+    pub suppression: Option<SuppressionDto>,
+    #[serde(default)]
+    pub schedule: Option<ScheduleDto>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct ThresholdDto {
+    pub count: usize,
+    pub window_seconds: u64,
+    pub group_by: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct SuppressionDto {
+    pub fingerprint: Vec<String>,
+    pub ttl_seconds: u64,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct ScheduleDto {
+    pub timezone: Option<String>,
+    pub active_windows: Vec<ScheduleWindowDto>,
+    pub inactive_windows: Vec<ScheduleWindowDto>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+#[serde(tag = "type")]
+pub enum ScheduleWindowDto {
+    #[serde(rename = "recurring")]
+    Recurring { cron: String, duration_seconds: u64 },
+    #[serde(rename = "range")]
+    Range { from: String, to: String },
+}
+
+impl From<&Schedule> for ScheduleDto {
+    fn from(schedule: &Schedule) -> Self {
+        let into_window_dto = |window: &ScheduleWindow| match window {
+            ScheduleWindow::Recurring { cron, duration_seconds } => ScheduleWindowDto::Recurring {
+                cron: cron.to_owned(),
+                duration_seconds: *duration_seconds,
+            },
+            ScheduleWindow::Range { from, to } => {
+                ScheduleWindowDto::Range { from: from.to_owned(), to: to.to_owned() }
+            }
+        };
+        ScheduleDto {
+            timezone: schedule.timezone.to_owned(),
+            active_windows: schedule.active_windows.iter().map(into_window_dto).collect(),
+            inactive_windows: schedule.inactive_windows.iter().map(into_window_dto).collect(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
@@ -83,6 +140,50 @@ pub enum ModifierDto {
     DateAndTime {
         timezone: String,
     },
+    Uppercase {},
+    Substring {
+        #[serde(default)]
+        start: i64,
+        end: Option<i64>,
+    },
+    Split {
+        separator: String,
+        #[serde(default)]
+        is_regex: bool,
+    },
+    Join {
+        separator: String,
+    },
+    PadLeft {
+        length: usize,
+        padding: Option<String>,
+    },
+    PadRight {
+        length: usize,
+        padding: Option<String>,
+    },
+    Default {
+        value: Value,
+    },
+    ParseJson {},
+    Base64Decode {},
+    Base64Encode {},
+    UrlDecode {},
+    HexDecode {},
+    ParseDate {
+        #[serde(default)]
+        formats: Vec<String>,
+        timezone: Option<String>,
+    },
+    FormatDate {
+        format: String,
+        timezone: Option<String>,
+    },
+    Lookup {
+        table: String,
+        field: Option<String>,
+        default_value: Option<Value>,
+    },
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
@@ -99,10 +200,102 @@ pub enum ExtractorRegexDto {
         regex: String,
         all_matches: Option<bool>,
     },
+    Grok {
+        #[serde(rename = "grok_match")]
+        expression: String,
+        all_matches: Option<bool>,
+    },
     KeyRegex {
         #[serde(rename = "single_key_match")]
         regex: String,
     },
+    KeyValue {
+        #[serde(rename = "key_value_match")]
+        format: KeyValueFormatDto,
+    },
+    JMESPath {
+        #[serde(rename = "jmespath_match")]
+        expression: String,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+#[serde(deny_unknown_fields)]
+pub struct KeyValueFormatDto {
+    pub pair_separator: Option<String>,
+    pub key_value_separator: Option<String>,
+    pub quote: Option<String>,
+}
+
+impl From<&Extractor> for ExtractorDto {
+    fn from(extractor: &Extractor) -> Self {
+        ExtractorDto {
+            from: extractor.from.to_owned(),
+            regex: ExtractorRegexDto::from(&extractor.regex),
+            modifiers_post: extractor.modifiers_post.iter().map(ModifierDto::from).collect(),
+        }
+    }
+}
+
+impl From<&Modifier> for ModifierDto {
+    fn from(modifier: &Modifier) -> Self {
+        match modifier.clone() {
+            Modifier::Lowercase {} => ModifierDto::Lowercase {},
+            Modifier::Map { mapping, default_value } => ModifierDto::Map { mapping, default_value },
+            Modifier::ReplaceAll { find, replace, is_regex } => {
+                ModifierDto::ReplaceAll { find, replace, is_regex }
+            }
+            Modifier::ToNumber {} => ModifierDto::ToNumber {},
+            Modifier::Trim {} => ModifierDto::Trim {},
+            Modifier::DateAndTime { timezone } => ModifierDto::DateAndTime { timezone },
+            Modifier::Uppercase {} => ModifierDto::Uppercase {},
+            Modifier::Substring { start, end } => ModifierDto::Substring { start, end },
+            Modifier::Split { separator, is_regex } => ModifierDto::Split { separator, is_regex },
+            Modifier::Join { separator } => ModifierDto::Join { separator },
+            Modifier::PadLeft { length, padding } => ModifierDto::PadLeft { length, padding },
+            Modifier::PadRight { length, padding } => ModifierDto::PadRight { length, padding },
+            Modifier::Default { value } => ModifierDto::Default { value },
+            Modifier::ParseJson {} => ModifierDto::ParseJson {},
+            Modifier::Base64Decode {} => ModifierDto::Base64Decode {},
+            Modifier::Base64Encode {} => ModifierDto::Base64Encode {},
+            Modifier::UrlDecode {} => ModifierDto::UrlDecode {},
+            Modifier::HexDecode {} => ModifierDto::HexDecode {},
+            Modifier::ParseDate { formats, timezone } => {
+                ModifierDto::ParseDate { formats, timezone }
+            }
+            Modifier::FormatDate { format, timezone } => {
+                ModifierDto::FormatDate { format, timezone }
+            }
+            Modifier::Lookup { table, field, default_value } => {
+                ModifierDto::Lookup { table, field, default_value }
+            }
+        }
+    }
+}
+
+impl From<&ExtractorRegex> for ExtractorRegexDto {
+    fn from(extractor_regex: &ExtractorRegex) -> Self {
+        match extractor_regex.clone() {
+            ExtractorRegex::Regex { regex, all_matches, group_match_idx } => {
+                ExtractorRegexDto::Regex { regex, all_matches, group_match_idx }
+            }
+            ExtractorRegex::RegexNamedGroups { regex, all_matches } => {
+                ExtractorRegexDto::RegexNamedGroups { regex, all_matches }
+            }
+            ExtractorRegex::Grok { expression, all_matches } => {
+                ExtractorRegexDto::Grok { expression, all_matches }
+            }
+            ExtractorRegex::SingleKeyRegex { regex } => ExtractorRegexDto::KeyRegex { regex },
+            ExtractorRegex::KeyValue { format } => ExtractorRegexDto::KeyValue {
+                format: KeyValueFormatDto {
+                    pair_separator: format.pair_separator,
+                    key_value_separator: format.key_value_separator,
+                    quote: format.quote,
+                },
+            },
+            ExtractorRegex::JMESPath { expression } => ExtractorRegexDto::JMESPath { expression },
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
@@ -133,7 +326,59 @@ pub enum OperatorDto {
     #[serde(rename = "ne")]
     NotEquals { first: Value, second: Value },
     #[serde(rename = "regex")]
-    Regex { regex: String, target: String },
+    Regex {
+        regex: String,
+        target: String,
+        #[serde(default)]
+        grok: bool,
+    },
+    #[serde(rename = "in")]
+    In { first: Value, values: Vec<Value> },
+    #[serde(rename = "startsWith")]
+    StartsWith { first: Value, second: Value },
+    #[serde(rename = "endsWith")]
+    EndsWith { first: Value, second: Value },
+    #[serde(rename = "exists")]
+    Exists { target: String },
+    #[serde(rename = "notExists")]
+    NotExists { target: String },
+    #[serde(rename = "isType")]
+    IsType { target: String, value_type: ValueTypeDto },
+    #[serde(rename = "ipInCidr")]
+    IpInCidr { target: String, networks: Vec<String> },
+    #[serde(rename = "ipEquals")]
+    IpEquals { first: Value, second: Value },
+    #[serde(rename = "expression")]
+    Expression { expression: String },
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub enum ValueTypeDto {
+    #[serde(rename = "string")]
+    String,
+    #[serde(rename = "number")]
+    Number,
+    #[serde(rename = "bool")]
+    Bool,
+    #[serde(rename = "array")]
+    Array,
+    #[serde(rename = "object")]
+    Object,
+    #[serde(rename = "null")]
+    Null,
+}
+
+impl From<&ValueType> for ValueTypeDto {
+    fn from(value_type: &ValueType) -> Self {
+        match value_type {
+            ValueType::String => ValueTypeDto::String,
+            ValueType::Number => ValueTypeDto::Number,
+            ValueType::Bool => ValueTypeDto::Bool,
+            ValueType::Array => ValueTypeDto::Array,
+            ValueType::Object => ValueTypeDto::Object,
+            ValueType::Null => ValueTypeDto::Null,
+        }
+    }
 }
 
 impl From<&Operator> for OperatorDto {
@@ -184,8 +429,41 @@ impl From<&Operator> for OperatorDto {
                 first: serde_json::to_value(first).unwrap_or(serde_json::Value::Null),
                 second: serde_json::to_value(second).unwrap_or(serde_json::Value::Null),
             },
-            Operator::Regex { regex, target } => {
-                OperatorDto::Regex { regex: regex.to_owned(), target: target.to_owned() }
+            Operator::Regex { regex, target, grok } => OperatorDto::Regex {
+                regex: regex.to_owned(),
+                target: target.to_owned(),
+                grok: *grok,
+            },
+            Operator::In { first, values } => OperatorDto::In {
+                first: serde_json::to_value(first).unwrap_or(serde_json::Value::Null),
+                values: values
+                    .iter()
+                    .map(|value| serde_json::to_value(value).unwrap_or(serde_json::Value::Null))
+                    .collect(),
+            },
+            Operator::StartsWith { first, second } => OperatorDto::StartsWith {
+                first: serde_json::to_value(first).unwrap_or(serde_json::Value::Null),
+                second: serde_json::to_value(second).unwrap_or(serde_json::Value::Null),
+            },
+            Operator::EndsWith { first, second } => OperatorDto::EndsWith {
+                first: serde_json::to_value(first).unwrap_or(serde_json::Value::Null),
+                second: serde_json::to_value(second).unwrap_or(serde_json::Value::Null),
+            },
+            Operator::Exists { target } => OperatorDto::Exists { target: target.to_owned() },
+            Operator::NotExists { target } => OperatorDto::NotExists { target: target.to_owned() },
+            Operator::IsType { target, value_type } => OperatorDto::IsType {
+                target: target.to_owned(),
+                value_type: ValueTypeDto::from(value_type),
+            },
+            Operator::IpInCidr { target, networks } => {
+                OperatorDto::IpInCidr { target: target.to_owned(), networks: networks.to_owned() }
+            }
+            Operator::IpEquals { first, second } => OperatorDto::IpEquals {
+                first: serde_json::to_value(first).unwrap_or(serde_json::Value::Null),
+                second: serde_json::to_value(second).unwrap_or(serde_json::Value::Null),
+            },
+            Operator::Expression { expression } => {
+                OperatorDto::Expression { expression: expression.to_owned() }
             }
         }
     }
@@ -202,6 +480,7 @@ pub struct FilterDto {
     pub description: String,
     pub active: bool,
     pub filter: Option<OperatorDto>,
+    pub schedule: Option<ScheduleDto>,
 }
 
 impl From<Filter> for FilterDto {
@@ -213,6 +492,84 @@ impl From<Filter> for FilterDto {
                 Defaultable::Default { .. } => None,
             },
             active: filter.active,
+            schedule: filter.schedule.as_ref().map(ScheduleDto::from),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct CorrelationDto {
+    pub description: String,
+    pub active: bool,
+    pub correlate_by: String,
+    pub steps: Vec<OperatorDto>,
+    pub timeout_seconds: u64,
+    pub on_complete: Vec<ActionDto>,
+    pub on_timeout: Vec<ActionDto>,
+}
+
+impl From<&Correlation> for CorrelationDto {
+    fn from(correlation: &Correlation) -> Self {
+        let into_action_dto = |action: &ConfigAction| ActionDto {
+            id: action.id.to_owned(),
+            payload: Value::Object(action.payload.clone()),
+        };
+        CorrelationDto {
+            description: correlation.description.to_owned(),
+            active: correlation.active,
+            correlate_by: correlation.correlate_by.to_owned(),
+            steps: correlation.steps.iter().map(OperatorDto::from).collect(),
+            timeout_seconds: correlation.timeout_seconds,
+            on_complete: correlation.on_complete.iter().map(into_action_dto).collect(),
+            on_timeout: correlation.on_timeout.iter().map(into_action_dto).collect(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct HeartbeatDto {
+    pub description: String,
+    pub active: bool,
+    pub filter: Option<OperatorDto>,
+    pub key: String,
+    pub max_interval_seconds: u64,
+}
+
+impl From<&Heartbeat> for HeartbeatDto {
+    fn from(heartbeat: &Heartbeat) -> Self {
+        HeartbeatDto {
+            description: heartbeat.description.to_owned(),
+            active: heartbeat.active,
+            filter: match &heartbeat.filter {
+                Defaultable::Value(operator) => Some(operator.into()),
+                Defaultable::Default { .. } => None,
+            },
+            key: heartbeat.key.to_owned(),
+            max_interval_seconds: heartbeat.max_interval_seconds,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct EnricherDto {
+    pub description: String,
+    pub active: bool,
+    #[serde(default)]
+    pub payload_key: Option<String>,
+    pub with: HashMap<String, ExtractorDto>,
+}
+
+impl From<&Enricher> for EnricherDto {
+    fn from(enricher: &Enricher) -> Self {
+        EnricherDto {
+            description: enricher.description.to_owned(),
+            active: enricher.active,
+            payload_key: enricher.payload_key.to_owned(),
+            with: enricher
+                .with
+                .iter()
+                .map(|(key, extractor)| (key.to_owned(), ExtractorDto::from(extractor)))
+                .collect(),
         }
     }
 }
@@ -247,6 +604,23 @@ pub enum ProcessingTreeNodeConfigDto {
         name: String,
         rules_count: usize,
     },
+    Correlation {
+        name: String,
+        description: String,
+        active: bool,
+    },
+    Heartbeat {
+        name: String,
+        description: String,
+        active: bool,
+    },
+    Enricher {
+        name: String,
+        rules_count: usize,
+        children_count: usize,
+        description: String,
+        active: bool,
+    },
 }
 
 impl ProcessingTreeNodeConfigDto {
@@ -274,6 +648,29 @@ impl ProcessingTreeNodeConfigDto {
                     active: iterator.is_active(),
                 }
             }
+            MatcherConfig::Correlation { name, correlation } => {
+                ProcessingTreeNodeConfigDto::Correlation {
+                    name: name.to_owned(),
+                    description: correlation.description.to_owned(),
+                    active: correlation.active,
+                }
+            }
+            MatcherConfig::Heartbeat { name, heartbeat } => {
+                ProcessingTreeNodeConfigDto::Heartbeat {
+                    name: name.to_owned(),
+                    description: heartbeat.description.to_owned(),
+                    active: heartbeat.active,
+                }
+            }
+            MatcherConfig::Enricher { name, enricher, nodes } => {
+                ProcessingTreeNodeConfigDto::Enricher {
+                    name: name.to_owned(),
+                    rules_count: matcher_config_node.get_all_rules_count(),
+                    children_count: nodes.len(),
+                    description: enricher.description.to_owned(),
+                    active: enricher.active,
+                }
+            }
         }
     }
 }
@@ -281,17 +678,70 @@ impl ProcessingTreeNodeConfigDto {
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
 #[serde(tag = "type")]
 pub enum ProcessingTreeNodeDetailsDto {
-    Filter { name: String, description: String, active: bool, filter: Option<OperatorDto> },
-    Iterator { name: String, description: String, active: bool, target: String },
-    Ruleset { name: String, rules: Vec<RuleDetailsDto> },
+    Filter {
+        name: String,
+        description: String,
+        active: bool,
+        filter: Option<OperatorDto>,
+        #[serde(default)]
+        schedule: Option<ScheduleDto>,
+    },
+    Iterator {
+        name: String,
+        description: String,
+        active: bool,
+        target: String,
+    },
+    Ruleset {
+        name: String,
+        rules: Vec<RuleDetailsDto>,
+    },
+    Correlation {
+        name: String,
+        correlation: CorrelationDto,
+    },
+    Heartbeat {
+        name: String,
+        heartbeat: HeartbeatDto,
+    },
+    Enricher {
+        name: String,
+        enricher: EnricherDto,
+    },
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
 #[serde(tag = "type")]
 pub enum ProcessingTreeNodeEditDto {
-    Filter { name: String, description: String, active: bool, filter: Option<OperatorDto> },
-    Iterator { name: String, description: String, target: String, active: bool },
-    Ruleset { name: String },
+    Filter {
+        name: String,
+        description: String,
+        active: bool,
+        filter: Option<OperatorDto>,
+        #[serde(default)]
+        schedule: Option<ScheduleDto>,
+    },
+    Iterator {
+        name: String,
+        description: String,
+        target: String,
+        active: bool,
+    },
+    Ruleset {
+        name: String,
+    },
+    Correlation {
+        name: String,
+        correlation: CorrelationDto,
+    },
+    Heartbeat {
+        name: String,
+        heartbeat: HeartbeatDto,
+    },
+    Enricher {
+        name: String,
+        enricher: EnricherDto,
+    },
 }
 
 impl From<&MatcherConfig> for ProcessingTreeNodeDetailsDto {
@@ -305,6 +755,7 @@ impl From<&MatcherConfig> for ProcessingTreeNodeDetailsDto {
                     Defaultable::Value(operator) => Some(operator.into()),
                     Defaultable::Default { .. } => None,
                 },
+                schedule: filter.schedule.as_ref().map(ScheduleDto::from),
             },
             MatcherConfig::Iterator { name, iterator, .. } => {
                 ProcessingTreeNodeDetailsDto::Iterator {
@@ -321,6 +772,24 @@ impl From<&MatcherConfig> for ProcessingTreeNodeDetailsDto {
                     rules: rules_details_dto,
                 }
             }
+            MatcherConfig::Correlation { name, correlation } => {
+                ProcessingTreeNodeDetailsDto::Correlation {
+                    name: name.to_owned(),
+                    correlation: correlation.into(),
+                }
+            }
+            MatcherConfig::Heartbeat { name, heartbeat } => {
+                ProcessingTreeNodeDetailsDto::Heartbeat {
+                    name: name.to_owned(),
+                    heartbeat: heartbeat.into(),
+                }
+            }
+            MatcherConfig::Enricher { name, enricher, .. } => {
+                ProcessingTreeNodeDetailsDto::Enricher {
+                    name: name.to_owned(),
+                    enricher: enricher.into(),
+                }
+            }
         }
     }
 }
@@ -332,6 +801,94 @@ pub struct TreeInfoDto {
     pub iterators_count: usize,
 }
 
+/// The result of a test case of a Ruleset.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, TypeScriptify)]
+pub struct RuleTestResultDto {
+    pub ruleset_path: Vec<String>,
+    pub case: String,
+    pub success: bool,
+    pub differences: Vec<RuleTestDifferenceDto>,
+    /// The pretty printed JSON of the processed Ruleset, or None if the event did not reach it.
+    pub processed_ruleset: Option<String>,
+}
+
+/// A difference between the expected and the actual result of a test case.
+/// The `diff` contains the lines of `expected` and `actual` prefixed by
+/// `-` if they are only expected and by `+` if they are only in the actual value.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, TypeScriptify)]
+pub struct RuleTestDifferenceDto {
+    pub subject: String,
+    pub expected: String,
+    pub actual: String,
+    pub diff: Vec<String>,
+}
+
+/// A snapshot of the configuration saved when it was deployed.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, TypeScriptify)]
+pub struct ConfigSnapshotDto {
+    pub snapshot_id: String,
+    pub created_ts_ms: i64,
+    pub user: String,
+    pub comment: String,
+}
+
+/// The result of the deploy of a draft.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, TypeScriptify)]
+pub struct DeployDraftResultDto {
+    pub warnings: Vec<LintWarningDto>,
+}
+
+/// A warning about a Rule of the deployed configuration that probably does not behave
+/// as expected. The `path` contains the names of the nodes from the root to the Rule.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, TypeScriptify)]
+pub struct LintWarningDto {
+    pub path: Vec<String>,
+    pub kind: LintWarningKindDto,
+    pub message: String,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, TypeScriptify)]
+pub enum LintWarningKindDto {
+    UnreachableRule,
+    UnknownVariable,
+    UnusedVariable,
+    UnknownRegexGroup,
+}
+
+/// A change of a node or of a Rule of a draft with respect to the deployed configuration.
+/// The `path` contains the names of the nodes from the authorized node to the changed
+/// node or Rule.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, TypeScriptify)]
+pub struct ConfigChangeDto {
+    pub path: Vec<String>,
+    pub target: ConfigChangeTargetDto,
+    pub change: ConfigChangeKindDto,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, TypeScriptify)]
+pub enum ConfigChangeTargetDto {
+    Node,
+    Rule,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, TypeScriptify)]
+#[serde(tag = "type")]
+pub enum ConfigChangeKindDto {
+    Added {},
+    Removed {},
+    Moved { from: Vec<String> },
+    Modified { fields: Vec<FieldChangeDto> },
+}
+
+/// A changed field of a node or of a Rule. The `field` contains the keys of the JSON
+/// representation of the node, or of the Rule, to the changed value.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, TypeScriptify)]
+pub struct FieldChangeDto {
+    pub field: Vec<String>,
+    pub old_value: Option<Value>,
+    pub new_value: Option<Value>,
+}
+
 impl Add for TreeInfoDto {
     type Output = TreeInfoDto;
 
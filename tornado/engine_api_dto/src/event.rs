@@ -2,13 +2,16 @@ use crate::config::ActionDto;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
-use tornado_engine_matcher::model::ProcessedRuleMetaData;
+use tornado_engine_matcher::model::{OperatorTrace, ProcessedRuleMetaData};
 use typescript_definitions::TypeScriptify;
 
 #[derive(Clone, Serialize, Deserialize, TypeScriptify)]
 pub struct SendEventRequestDto {
     pub process_type: ProcessType,
     pub event: EventDto,
+    /// Whether the processed Rules contain the trace of the evaluation of their WHERE clause
+    #[serde(default)]
+    pub explain: bool,
 }
 
 #[derive(Clone, Serialize, Deserialize, TypeScriptify)]
@@ -39,6 +42,9 @@ pub enum ProcessedNodeDto {
     Filter { name: String, filter: ProcessedFilterDto, nodes: Vec<ProcessedNodeDto> },
     Iterator { name: String, iterator: ProcessedIteratorDto, nodes: Vec<ProcessedNodeDto> },
     Ruleset { name: String, rules: ProcessedRulesDto },
+    Correlation { name: String, correlation: ProcessedCorrelationDto },
+    Heartbeat { name: String, heartbeat: ProcessedHeartbeatDto },
+    Enricher { name: String, enricher: ProcessedEnricherDto, nodes: Vec<ProcessedNodeDto> },
 }
 
 #[derive(Clone, Serialize, Deserialize, TypeScriptify)]
@@ -58,6 +64,55 @@ pub enum ProcessedFilterStatusDto {
     Matched,
     NotMatched,
     Inactive,
+    OutOfSchedule,
+}
+
+#[derive(Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct ProcessedCorrelationDto {
+    pub status: ProcessedCorrelationStatusDto,
+    pub key: Option<String>,
+    pub matched_steps: usize,
+    pub actions: Vec<ActionDto>,
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, TypeScriptify)]
+pub enum ProcessedCorrelationStatusDto {
+    Started,
+    Progressed,
+    Completed,
+    Expired,
+    PartiallyMatched,
+    NotMatched,
+    Inactive,
+}
+
+#[derive(Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct ProcessedHeartbeatDto {
+    pub status: ProcessedHeartbeatStatusDto,
+    pub key: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, TypeScriptify)]
+pub enum ProcessedHeartbeatStatusDto {
+    Seen,
+    Recovered,
+    NotMatched,
+    Inactive,
+}
+
+#[derive(Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct ProcessedEnricherDto {
+    pub status: ProcessedEnricherStatusDto,
+    pub values: Value,
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, TypeScriptify)]
+pub enum ProcessedEnricherStatusDto {
+    Enriched,
+    PartiallyEnriched,
+    Inactive,
 }
 
 #[derive(Clone, Serialize, Deserialize, TypeScriptify)]
@@ -73,6 +128,7 @@ pub struct ProcessedRuleDto {
     pub actions: Vec<ActionDto>,
     pub message: Option<String>,
     pub meta: Option<ProcessedRuleMetaData>,
+    pub trace: Option<OperatorTrace>,
 }
 
 #[derive(Clone, Serialize, Deserialize, TypeScriptify)]
@@ -81,4 +137,7 @@ pub enum ProcessedRuleStatusDto {
     PartiallyMatched,
     NotMatched,
     NotProcessed,
+    Duplicate,
+    OutOfSchedule,
+    ThresholdNotReached,
 }
@@ -3,4 +3,5 @@ pub mod auth_v2;
 pub mod common;
 pub mod config;
 pub mod event;
+pub mod lookup_table;
 pub mod runtime_config;
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use typescript_definitions::TypeScriptify;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub struct LookupTableDto {
+    pub name: String,
+    pub format: LookupTableFormatDto,
+    pub source: String,
+    pub rows_count: usize,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, TypeScriptify)]
+pub enum LookupTableFormatDto {
+    #[serde(rename = "csv")]
+    Csv,
+    #[serde(rename = "json")]
+    Json,
+}
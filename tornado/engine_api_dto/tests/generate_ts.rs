@@ -119,19 +119,38 @@ export type Value = any;"#;
     );
     push_ts(&mut ts_code, &config::ActionDto::type_script_ify());
     push_ts(&mut ts_code, &config::ConstraintDto::type_script_ify());
+    push_ts(&mut ts_code, &config::CorrelationDto::type_script_ify());
+    push_ts(&mut ts_code, &config::HeartbeatDto::type_script_ify());
+    push_ts(&mut ts_code, &config::EnricherDto::type_script_ify());
     push_ts(&mut ts_code, &config::ExtractorDto::type_script_ify());
     push_ts(&mut ts_code, &config::ExtractorRegexDto::type_script_ify());
+    push_ts(&mut ts_code, &config::KeyValueFormatDto::type_script_ify());
     push_ts(&mut ts_code, &config::FilterDto::type_script_ify());
     push_ts(&mut ts_code, &config::MatcherConfigDraftDataDto::type_script_ify());
     push_ts(&mut ts_code, &config::ModifierDto::type_script_ify());
     push_ts(&mut ts_code, &config::OperatorDto::type_script_ify());
+    push_ts(&mut ts_code, &config::ValueTypeDto::type_script_ify());
     push_ts(&mut ts_code, &config::RuleDto::type_script_ify());
     push_ts(&mut ts_code, &config::ProcessingTreeNodeConfigDto::type_script_ify());
     push_ts(&mut ts_code, &config::ProcessingTreeNodeEditDto::type_script_ify());
     push_ts(&mut ts_code, &config::ProcessingTreeNodeDetailsDto::type_script_ify());
     push_ts(&mut ts_code, &config::RuleDetailsDto::type_script_ify());
     push_ts(&mut ts_code, &config::TreeInfoDto::type_script_ify());
+    push_ts(&mut ts_code, &config::RuleTestResultDto::type_script_ify());
+    push_ts(&mut ts_code, &config::RuleTestDifferenceDto::type_script_ify());
+    push_ts(&mut ts_code, &config::DeployDraftResultDto::type_script_ify());
+    push_ts(&mut ts_code, &config::LintWarningDto::type_script_ify());
+    push_ts(&mut ts_code, &config::LintWarningKindDto::type_script_ify());
+    push_ts(&mut ts_code, &config::ConfigSnapshotDto::type_script_ify());
+    push_ts(&mut ts_code, &config::ConfigChangeDto::type_script_ify());
+    push_ts(&mut ts_code, &config::ConfigChangeTargetDto::type_script_ify());
+    push_ts(&mut ts_code, &config::ConfigChangeKindDto::type_script_ify());
+    push_ts(&mut ts_code, &config::FieldChangeDto::type_script_ify());
     push_ts(&mut ts_code, &config::RulePositionDto::type_script_ify());
+    push_ts(&mut ts_code, &config::ThresholdDto::type_script_ify());
+    push_ts(&mut ts_code, &config::SuppressionDto::type_script_ify());
+    push_ts(&mut ts_code, &config::ScheduleDto::type_script_ify());
+    push_ts(&mut ts_code, &config::ScheduleWindowDto::type_script_ify());
 
     // Push 'event' ts types
     push_ts(
@@ -143,6 +162,12 @@ export type Value = any;"#;
     );
     push_ts(&mut ts_code, &event::EventDto::type_script_ify());
     push_ts(&mut ts_code, &event::ProcessType::type_script_ify());
+    push_ts(&mut ts_code, &event::ProcessedCorrelationDto::type_script_ify());
+    push_ts(&mut ts_code, &event::ProcessedCorrelationStatusDto::type_script_ify());
+    push_ts(&mut ts_code, &event::ProcessedHeartbeatDto::type_script_ify());
+    push_ts(&mut ts_code, &event::ProcessedHeartbeatStatusDto::type_script_ify());
+    push_ts(&mut ts_code, &event::ProcessedEnricherDto::type_script_ify());
+    push_ts(&mut ts_code, &event::ProcessedEnricherStatusDto::type_script_ify());
     push_ts(&mut ts_code, &event::ProcessedEventDto::type_script_ify());
     push_ts(&mut ts_code, &event::ProcessedFilterDto::type_script_ify());
     push_ts(&mut ts_code, &event::ProcessedFilterStatusDto::type_script_ify());
@@ -164,7 +189,11 @@ export type Value = any;"#;
     push_ts(&mut ts_code, &matcher::model::ActionMetaData::type_script_ify());
     push_ts(&mut ts_code, &matcher::model::EnrichedValue::type_script_ify());
     push_ts(&mut ts_code, &matcher::model::EnrichedValueContent::type_script_ify());
+    push_ts(&mut ts_code, &matcher::model::OperatorTrace::type_script_ify());
+    push_ts(&mut ts_code, &matcher::model::OperatorTraceValue::type_script_ify());
     push_ts(&mut ts_code, &matcher::model::ProcessedRuleMetaData::type_script_ify());
+    push_ts(&mut ts_code, &matcher::model::ThresholdMetaData::type_script_ify());
+    push_ts(&mut ts_code, &matcher::model::SuppressionMetaData::type_script_ify());
     push_ts(&mut ts_code, &matcher::model::ValueMetaData::type_script_ify());
 
     // Push 'runtime_config' ts types
@@ -188,6 +217,17 @@ export type Value = any;"#;
         &runtime_config::SetStdoutPriorityConfigurationRequestDto::type_script_ify(),
     );
 
+    // Push 'lookup_table' ts types
+    push_ts(
+        &mut ts_code,
+        r#"
+/* -------------- */
+/* 'lookup_table' types */
+/* -------------- */"#,
+    );
+    push_ts(&mut ts_code, &lookup_table::LookupTableDto::type_script_ify());
+    push_ts(&mut ts_code, &lookup_table::LookupTableFormatDto::type_script_ify());
+
     ts_code
 }
 
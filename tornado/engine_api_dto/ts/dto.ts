@@ -43,24 +43,50 @@ export type ActionDto = { id: string; payload: Value };
 
 export type ConstraintDto = { WHERE: OperatorDto | null; WITH: { [key: string]: ExtractorDto } };
 
+export type CorrelationDto = {     description: string; active: boolean; correlate_by: string; steps:     OperatorDto []; timeout_seconds: number; on_complete: ActionDto [];     on_timeout: ActionDto [] };
+
+export type HeartbeatDto = {     description: string; active: boolean; filter: OperatorDto | null; key:     string; max_interval_seconds: number };
+
+export type EnricherDto = {     description: string; active: boolean; payload_key: string | null; with: { [key: string]: ExtractorDto } };
+
 export type ExtractorDto = {     from: string; regex: ExtractorRegexDto; modifiers_post: ModifierDto     [] };
 
 export type ExtractorRegexDto = 
  | {     type: "Regex"; match: string; group_match_idx: number | null;     all_matches: boolean | null } 
  | {     type: "RegexNamedGroups"; named_match: string; all_matches: boolean |     null } 
- | { type: "KeyRegex"; single_key_match: string };
+ | { type: "Grok"; grok_match: string; all_matches: boolean | null } 
+ | { type: "KeyRegex"; single_key_match: string } 
+ | { type: "KeyValue"; key_value_match: KeyValueFormatDto } 
+ | { type: "JMESPath"; jmespath_match: string };
 
-export type FilterDto = { description: string; active: boolean; filter: OperatorDto | null };
+export type KeyValueFormatDto = {     pair_separator: string | null; key_value_separator: string | null; quote: string | null };
+
+export type FilterDto = {     description: string; active: boolean; filter: OperatorDto | null;     schedule: ScheduleDto | null };
 
 export type MatcherConfigDraftDataDto = {     user: string; created_ts_ms: number; updated_ts_ms: number; draft_id: string };
 
 export type ModifierDto = 
  | { type: "Lowercase" } 
  | {     type: "Map"; mapping: { [key: string]: string }; default_value:     string | null } 
- | {     type: "ReplaceAll"; find: string; replace: string; is_regex:     boolean } 
+ | { type: "ReplaceAll"; find: string; replace: string; is_regex: boolean } 
  | { type: "ToNumber" } 
  | { type: "Trim" } 
- | { type: "DateAndTime"; timezone: string };
+ | { type: "DateAndTime"; timezone: string } 
+ | { type: "Uppercase" } 
+ | { type: "Substring"; start: number; end: number | null } 
+ | { type: "Split"; separator: string; is_regex: boolean } 
+ | { type: "Join"; separator: string } 
+ | { type: "PadLeft"; length: number; padding: string | null } 
+ | { type: "PadRight"; length: number; padding: string | null } 
+ | { type: "Default"; value: Value } 
+ | { type: "ParseJson" } 
+ | { type: "Base64Decode" } 
+ | { type: "Base64Encode" } 
+ | { type: "UrlDecode" } 
+ | { type: "HexDecode" } 
+ | { type: "ParseDate"; formats: string []; timezone: string | null } 
+ | { type: "FormatDate"; format: string; timezone: string | null } 
+ | { type: "Lookup"; table: string; field: string | null; default_value: Value | null };
 
 export type OperatorDto = 
  | { type: "AND"; operators: OperatorDto [] } 
@@ -75,31 +101,98 @@ export type OperatorDto =
  | { type: "le"; first: Value; second: Value } 
  | { type: "lt"; first: Value; second: Value } 
  | { type: "ne"; first: Value; second: Value } 
- | { type: "regex"; regex: string; target: string };
+ | { type: "regex"; regex: string; target: string; grok: boolean } 
+ | { type: "in"; first: Value; values: Value [] } 
+ | { type: "startsWith"; first: Value; second: Value } 
+ | { type: "endsWith"; first: Value; second: Value } 
+ | { type: "exists"; target: string } 
+ | { type: "notExists"; target: string } 
+ | { type: "isType"; target: string; value_type: ValueTypeDto } 
+ | { type: "ipInCidr"; target: string; networks: string [] } 
+ | { type: "ipEquals"; first: Value; second: Value } 
+ | { type: "expression"; expression: string };
+
+export enum ValueTypeDto {     string = "string", number = "number", bool = "bool", array = "array",     object = "object", null = "null" };
 
-export type RuleDto = {     name: string; description: string; continue: boolean; active:     boolean; constraint: ConstraintDto; actions: ActionDto [] };
+export type RuleDto = {     name: string; description: string; continue: boolean; active: boolean;     constraint: ConstraintDto; actions: ActionDto []; threshold:     ThresholdDto | null; suppression: SuppressionDto | null; schedule:     ScheduleDto | null };
 
 export type ProcessingTreeNodeConfigDto = 
  | {     type: "Filter"; name: string; rules_count: number; children_count:     number; description: string; has_iterator_ancestor: boolean; active:     boolean } 
- | {     type: "Iterator"; name: string; rules_count: number; children_count: number; description: string; active: boolean } 
- | { type: "Ruleset"; name: string; rules_count: number };
+ | {     type: "Iterator"; name: string; rules_count: number; children_count:     number; description: string; active: boolean } 
+ | { type: "Ruleset"; name: string; rules_count: number } 
+ | {     type: "Correlation"; name: string; description: string; active:     boolean } 
+ | { type: "Heartbeat"; name: string; description: string; active: boolean } 
+ | {     type: "Enricher"; name: string; rules_count: number; children_count:     number; description: string; active: boolean };
 
 export type ProcessingTreeNodeEditDto = 
- | {     type: "Filter"; name: string; description: string; active: boolean; filter: OperatorDto | null } 
- | {     type: "Iterator"; name: string; description: string; target: string; active: boolean } 
- | { type: "Ruleset"; name: string };
+ | {     type: "Filter"; name: string; description: string; active: boolean;     filter: OperatorDto | null; schedule: ScheduleDto | null } 
+ | {     type: "Iterator"; name: string; description: string; target: string;     active: boolean } 
+ | { type: "Ruleset"; name: string } 
+ | { type: "Correlation"; name: string; correlation: CorrelationDto } 
+ | { type: "Heartbeat"; name: string; heartbeat: HeartbeatDto } 
+ | { type: "Enricher"; name: string; enricher: EnricherDto };
 
 export type ProcessingTreeNodeDetailsDto = 
- | {     type: "Filter"; name: string; description: string; active: boolean; filter: OperatorDto | null } 
- | {     type: "Iterator"; name: string; description: string; active:     boolean; target: string } 
- | { type: "Ruleset"; name: string; rules: RuleDetailsDto [] };
+ | {     type: "Filter"; name: string; description: string; active: boolean;     filter: OperatorDto | null; schedule: ScheduleDto | null } 
+ | {     type: "Iterator"; name: string; description: string; active: boolean;     target: string } 
+ | { type: "Ruleset"; name: string; rules: RuleDetailsDto [] } 
+ | { type: "Correlation"; name: string; correlation: CorrelationDto } 
+ | { type: "Heartbeat"; name: string; heartbeat: HeartbeatDto } 
+ | { type: "Enricher"; name: string; enricher: EnricherDto };
 
 export type RuleDetailsDto = {     name: string; description: string; continue: boolean; active:     boolean; actions: string [] };
 
 export type TreeInfoDto = { rules_count: number; filters_count: number; iterators_count: number };
 
+// The result of a test case of a Ruleset.
+export type RuleTestResultDto = {     ruleset_path: string []; case: string; success: boolean; differences:     RuleTestDifferenceDto []; processed_ruleset: string | null };
+
+// A difference between the expected and the actual result of a test case.
+// The `diff` contains the lines of `expected` and `actual` prefixed by
+// `-` if they are only expected and by `+` if they are only in the actual value.
+export type RuleTestDifferenceDto = { subject: string; expected: string; actual: string; diff: string [] };
+
+// The result of the deploy of a draft.
+export type DeployDraftResultDto = { warnings: LintWarningDto [] };
+
+// A warning about a Rule of the deployed configuration that probably does not behave
+// as expected. The `path` contains the names of the nodes from the root to the Rule.
+export type LintWarningDto = { path: string []; kind: LintWarningKindDto; message: string };
+
+export enum LintWarningKindDto {     UnreachableRule = "UnreachableRule", UnknownVariable = "UnknownVariable",     UnusedVariable = "UnusedVariable", UnknownRegexGroup = "UnknownRegexGroup" };
+
+// A snapshot of the configuration saved when it was deployed.
+export type ConfigSnapshotDto = {     snapshot_id: string; created_ts_ms: number; user: string; comment:     string };
+
+// A change of a node or of a Rule of a draft with respect to the deployed configuration.
+// The `path` contains the names of the nodes from the authorized node to the changed
+// node or Rule.
+export type ConfigChangeDto = {     path: string []; target: ConfigChangeTargetDto; change:     ConfigChangeKindDto };
+
+export enum ConfigChangeTargetDto { Node = "Node", Rule = "Rule" };
+
+export type ConfigChangeKindDto = 
+ | { type: "Added" } 
+ | { type: "Removed" } 
+ | { type: "Moved"; from: string [] } 
+ | { type: "Modified"; fields: FieldChangeDto [] };
+
+// A changed field of a node or of a Rule. The `field` contains the keys of the JSON
+// representation of the node, or of the Rule, to the changed value.
+export type FieldChangeDto = { field: string []; old_value: Value | null; new_value: Value | null };
+
 export type RulePositionDto = { position: number };
 
+export type ThresholdDto = { count: number; window_seconds: number; group_by: string | null };
+
+export type SuppressionDto = { fingerprint: string []; ttl_seconds: number };
+
+export type ScheduleDto = {     timezone: string | null; active_windows: ScheduleWindowDto [];     inactive_windows: ScheduleWindowDto [] };
+
+export type ScheduleWindowDto = 
+ | { type: "recurring"; cron: string; duration_seconds: number } 
+ | { type: "range"; from: string; to: string };
+
 
 /* ------------- */
 /* 'event' types */
@@ -109,26 +202,41 @@ export type EventDto = {     type: string; created_ms: number; metadata:     { [
 
 export enum ProcessType { Full = "Full", SkipActions = "SkipActions" };
 
+export type ProcessedCorrelationDto = {     status: ProcessedCorrelationStatusDto; key: string | null; matched_steps: number; actions: ActionDto []; message: string | null };
+
+export enum ProcessedCorrelationStatusDto {     Started = "Started", Progressed = "Progressed", Completed = "Completed",     Expired = "Expired", PartiallyMatched = "PartiallyMatched", NotMatched =     "NotMatched", Inactive = "Inactive" };
+
+export type ProcessedHeartbeatDto = { status: ProcessedHeartbeatStatusDto; key: string | null };
+
+export enum ProcessedHeartbeatStatusDto {     Seen = "Seen", Recovered = "Recovered", NotMatched = "NotMatched",     Inactive = "Inactive" };
+
+export type ProcessedEnricherDto = {     status: ProcessedEnricherStatusDto; values: Value; message: string |     null };
+
+export enum ProcessedEnricherStatusDto {     Enriched = "Enriched", PartiallyEnriched = "PartiallyEnriched", Inactive =     "Inactive" };
+
 export type ProcessedEventDto = { event: EventDto; result: ProcessedNodeDto };
 
 export type ProcessedFilterDto = { status: ProcessedFilterStatusDto };
 
-export enum ProcessedFilterStatusDto { Matched = "Matched", NotMatched = "NotMatched", Inactive = "Inactive" };
+export enum ProcessedFilterStatusDto {     Matched = "Matched", NotMatched = "NotMatched", Inactive = "Inactive",     OutOfSchedule = "OutOfSchedule" };
 
 export enum ProcessedIteratorDto {     Matched = "Matched", AccessorError = "AccessorError", TypeError =     "TypeError" };
 
 export type ProcessedNodeDto = 
  | {     type: "Filter"; name: string; filter: ProcessedFilterDto; nodes:     ProcessedNodeDto [] } 
- | {     type: "Iterator"; name: string; iterator: ProcessedIteratorDto;     nodes: ProcessedNodeDto [] } 
- | { type: "Ruleset"; name: string; rules: ProcessedRulesDto };
+ | {     type: "Iterator"; name: string; iterator: ProcessedIteratorDto; nodes:     ProcessedNodeDto [] } 
+ | { type: "Ruleset"; name: string; rules: ProcessedRulesDto } 
+ | { type: "Correlation"; name: string; correlation: ProcessedCorrelationDto } 
+ | { type: "Heartbeat"; name: string; heartbeat: ProcessedHeartbeatDto } 
+ | {     type: "Enricher"; name: string; enricher: ProcessedEnricherDto; nodes:     ProcessedNodeDto [] };
 
-export type ProcessedRuleDto = {     name: string; status: ProcessedRuleStatusDto; actions: ActionDto [];     message: string | null; meta: ProcessedRuleMetaData | null };
+export type ProcessedRuleDto = {     name: string; status: ProcessedRuleStatusDto; actions: ActionDto [];     message: string | null; meta: ProcessedRuleMetaData | null; trace:     OperatorTrace | null };
 
 export type ProcessedRulesDto = { rules: ProcessedRuleDto []; extracted_vars: Value };
 
-export enum ProcessedRuleStatusDto {     Matched = "Matched", PartiallyMatched = "PartiallyMatched", NotMatched =     "NotMatched", NotProcessed = "NotProcessed" };
+export enum ProcessedRuleStatusDto {     Matched = "Matched", PartiallyMatched = "PartiallyMatched", NotMatched =     "NotMatched", NotProcessed = "NotProcessed", Duplicate = "Duplicate",     OutOfSchedule = "OutOfSchedule", ThresholdNotReached =     "ThresholdNotReached" };
 
-export type SendEventRequestDto = { process_type: ProcessType; event: EventDto };
+export type SendEventRequestDto = { process_type: ProcessType; event: EventDto; explain: boolean };
 
 
 /* ---------------- */
@@ -144,7 +252,20 @@ export type EnrichedValueContent =
  | { type: "Map"; content: { [key: string]: EnrichedValue } } 
  | { type: "Array"; content: EnrichedValue [] };
 
-export type ProcessedRuleMetaData = { actions: ActionMetaData [] };
+// The trace of the evaluation of an operator of the WHERE clause of a Rule.
+export type OperatorTrace = {     operator: string; result: boolean; values: OperatorTraceValue [];     operators: OperatorTrace [] };
+
+// The value resolved by the accessor of an operator argument; it is `None` if the accessor
+// cannot be resolved.
+export type OperatorTraceValue = { argument: string; value: Value | null };
+
+export type ProcessedRuleMetaData = {     actions: ActionMetaData []; threshold: ThresholdMetaData | null;     suppression: SuppressionMetaData | null };
+
+// The state of the sliding window of a threshold Rule after processing an event.
+export type ThresholdMetaData = {     group: string; count: number; threshold: number; window_seconds:     number };
+
+// The state of the deduplication window of a Rule after processing an event.
+export type SuppressionMetaData = { fingerprint: string; duplicates: number; ttl_seconds: number };
 
 export type ValueMetaData = { modified: boolean; is_leaf: boolean };
 
@@ -163,4 +284,13 @@ export type SetLoggerStdoutRequestDto = { enabled: boolean };
 
 export type SetApmPriorityConfigurationRequestDto = { logger_level: string | null };
 
-export type SetStdoutPriorityConfigurationRequestDto = {};
\ No newline at end of file
+export type SetStdoutPriorityConfigurationRequestDto = {};
+
+
+/* -------------- */
+/* 'lookup_table' types */
+/* -------------- */
+
+export type LookupTableDto = { name: string; format: LookupTableFormatDto; source: string; rows_count: number };
+
+export enum LookupTableFormatDto { csv = "csv", json = "json" };
\ No newline at end of file